@@ -0,0 +1,429 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    Expr, ExprArray, ExprLit, ExprRange, GenericArgument, Ident, Lit, LitChar, LitInt, LitStr,
+    PathArguments, Type,
+};
+
+const NUMERIC_TYPES: [&str; 14] = [
+    "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize", "f32",
+    "f64",
+];
+
+/// A field of the struct with the arguments of `#[form(...)]`.
+pub struct Field {
+    ident: Ident,
+    /// The type parsed from the input, i.e. `T` of `Option<T>` or the field type.
+    value_ty: Type,
+    /// Whether the field is `Option<T>`, which is `None` for an empty input.
+    optional: bool,
+    /// The text shown before the input, defaults to the field name.
+    label: String,
+    /// The minimum number of characters.
+    min_len: Option<usize>,
+    /// The range the parsed value must be in, along with its source text.
+    validate_range: Option<(ExprRange, String)>,
+    /// The character masking the input, e.g. for passwords.
+    mask: Option<char>,
+    /// The value pre-filling an empty input, and assigned if the input is left empty.
+    default: Option<String>,
+    /// The options to choose from, instead of a free text.
+    select: Option<Vec<String>>,
+}
+
+impl Field {
+    pub fn parse(field: &syn::Field) -> syn::Result<Self> {
+        let Some(ident) = field.ident.clone() else {
+            return Err(syn::Error::new_spanned(field, "expected a named field"));
+        };
+        let (value_ty, optional) = match option_inner(&field.ty) {
+            Some(inner) => (inner.clone(), true),
+            None => (field.ty.clone(), false),
+        };
+        let mut ret = Self {
+            label: format!("{}:", ident),
+            ident,
+            value_ty,
+            optional,
+            min_len: None,
+            validate_range: None,
+            mask: None,
+            default: None,
+            select: None,
+        };
+
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("form"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("label") {
+                    ret.label = meta.value()?.parse::<LitStr>()?.value();
+                } else if meta.path.is_ident("min_len") {
+                    ret.min_len = Some(meta.value()?.parse::<LitInt>()?.base10_parse()?);
+                } else if meta.path.is_ident("mask") {
+                    ret.mask = Some(meta.value()?.parse::<LitChar>()?.value());
+                } else if meta.path.is_ident("default") {
+                    ret.default = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("select") {
+                    let array = meta.value()?.parse::<ExprArray>()?;
+                    let options = array
+                        .elems
+                        .iter()
+                        .map(|elem| match elem {
+                            Expr::Lit(ExprLit {
+                                lit: Lit::Str(lit), ..
+                            }) => Ok(lit.value()),
+                            _ => Err(syn::Error::new_spanned(
+                                elem,
+                                "expected string literals like [\"dev\", \"prod\"]",
+                            )),
+                        })
+                        .collect::<syn::Result<Vec<_>>>()?;
+                    if options.is_empty() {
+                        return Err(syn::Error::new_spanned(
+                            array,
+                            "`select` requires at least one option",
+                        ));
+                    }
+                    ret.select = Some(options);
+                } else if meta.path.is_ident("validate_range") {
+                    let lit = meta.value()?.parse::<LitStr>()?;
+                    let range = lit.parse::<ExprRange>().map_err(|_| {
+                        syn::Error::new(lit.span(), "expected a range like \"18..=120\"")
+                    })?;
+                    let ty = &ret.value_ty;
+                    if !is_numeric(ty) {
+                        return Err(syn::Error::new_spanned(
+                            ty,
+                            format!(
+                                "`validate_range` requires a numeric field, found `{}`",
+                                quote!(#ty)
+                            ),
+                        ));
+                    }
+                    ret.validate_range = Some((range, lit.value()));
+                } else {
+                    return Err(meta.error(format!(
+                        "unknown form attribute `{}`",
+                        meta.path
+                            .get_ident()
+                            .map(Ident::to_string)
+                            .unwrap_or_default()
+                    )));
+                }
+                Ok(())
+            })?;
+        }
+
+        if let Some(options) = &ret.select {
+            let conflict = [
+                ("mask", ret.mask.is_some()),
+                ("min_len", ret.min_len.is_some()),
+            ]
+            .into_iter()
+            .find(|(_, set)| *set);
+            if let Some((name, _)) = conflict {
+                return Err(syn::Error::new_spanned(
+                    field,
+                    format!("`{}` cannot be used with `select`", name),
+                ));
+            }
+            if let Some(default) = ret.default.as_ref().filter(|d| !options.contains(d)) {
+                return Err(syn::Error::new_spanned(
+                    field,
+                    format!("default `{}` is not one of the options", default),
+                ));
+            }
+        }
+        Ok(ret)
+    }
+
+    /// Returns the form field, a text editor or a select,
+    /// starting from the current value of the field, or the default if it is empty.
+    pub fn state(&self) -> TokenStream {
+        let prefix = format!("{} ", self.label);
+        let current = self.current();
+        if let Some(options) = &self.select {
+            let chosen = self
+                .default
+                .as_ref()
+                .map(|default| quote!(.chosen(#default)));
+            return quote! {
+                ::promkit::preset::form::Field::from(
+                    ::promkit::preset::form::Select::new(#prefix, [#(#options),*])
+                        #chosen
+                        .chosen(#current)
+                )
+            };
+        }
+        let mask = match self.mask {
+            Some(mask) => quote!(::std::option::Option::Some(
+                ::promkit::text_editor::MaskPolicy::All(#mask)
+            )),
+            None => quote!(::std::option::Option::None),
+        };
+        let hint = self
+            .default
+            .as_ref()
+            .map(|default| format!("(default: {})", default))
+            .unwrap_or_default();
+        let initial = match &self.default {
+            Some(default) => quote! {
+                match #current {
+                    current if current.is_empty() => ::std::string::String::from(#default),
+                    current => current,
+                }
+            },
+            None => current,
+        };
+        quote! {
+            ::promkit::preset::form::Field::from(::promkit::text_editor::State {
+                texteditor: ::promkit::text_editor::TextEditor::new(#initial),
+                prefix: ::std::string::String::from(#prefix),
+                mask: #mask,
+                right_prompt: ::std::string::String::from(#hint),
+                right_prompt_style: ::promkit::crossterm::style::ContentStyle {
+                    foreground_color: ::std::option::Option::Some(
+                        ::promkit::crossterm::style::Color::DarkGrey,
+                    ),
+                    ..::std::default::Default::default()
+                },
+                ..::std::default::Default::default()
+            })
+        }
+    }
+
+    /// Returns the text of the current value of the field, which is empty for `None`.
+    fn current(&self) -> TokenStream {
+        let ident = &self.ident;
+        match self.optional {
+            true => quote! {
+                self.#ident
+                    .as_ref()
+                    .map(::std::string::ToString::to_string)
+                    .unwrap_or_default()
+            },
+            false => quote!(::std::string::ToString::to_string(&self.#ident)),
+        }
+    }
+
+    /// Whether an empty input is accepted, i.e. the field has a default or is optional.
+    fn allows_empty(&self) -> bool {
+        self.default.is_some() || self.optional
+    }
+
+    /// Returns the checks of the input `text`, as pairs of the condition and the error message.
+    fn checks(&self) -> Vec<(TokenStream, String)> {
+        let ty = &self.value_ty;
+        let optional = self.optional;
+        let mut checks = vec![];
+        // The options of a select are checked when they are assigned,
+        // and an optional field is reported as failing to parse instead.
+        if !is_string(ty) && self.select.is_none() && !optional {
+            checks.push((
+                quote!(text.parse::<#ty>().is_ok()),
+                format!("expected a value of type {}", quote!(#ty)),
+            ));
+        }
+        if let Some(min_len) = self.min_len {
+            checks.push((
+                quote!(text.chars().count() >= #min_len),
+                format!("must be at least {} characters", min_len),
+            ));
+        }
+        if let Some((range, source)) = &self.validate_range {
+            checks.push((
+                quote!(text.parse::<#ty>().map_or(#optional, |value| (#range).contains(&value))),
+                format!("must be in {}", source),
+            ));
+        }
+        checks
+    }
+
+    /// Returns the call of `Form::validator` for the field at the index, if it has any checks.
+    pub fn validator(&self, index: usize) -> Option<TokenStream> {
+        let (conditions, messages): (Vec<_>, Vec<_>) = self.checks().into_iter().unzip();
+        if conditions.is_empty() {
+            return None;
+        }
+        let allows_empty = self.allows_empty();
+        Some(quote! {
+            .validator(
+                #index,
+                |text: &str| (#allows_empty && text.is_empty()) || #((#conditions))&&*,
+                |text: &str| {
+                    if #allows_empty && text.is_empty() {
+                        return ::std::string::String::new();
+                    }
+                    #(
+                        if !(#conditions) {
+                            return ::std::string::String::from(#messages);
+                        }
+                    )*
+                    ::std::string::String::new()
+                },
+            )
+        })
+    }
+
+    /// Returns the statements setting the field from the result at the index,
+    /// and pushing its outcome to `report`.
+    /// An empty input takes the default, or `None` for an optional field,
+    /// and an optional field is `None` for an input failing to parse.
+    pub fn assign(&self, index: usize) -> TokenStream {
+        let ident = &self.ident;
+        let name = ident.to_string();
+        let outcome = quote!(::promkit::preset::form::FieldOutcome);
+        let answered = match (self.optional, &self.select) {
+            (true, None) => {
+                let ty = &self.value_ty;
+                quote! {
+                    match text.parse::<#ty>() {
+                        ::std::result::Result::Ok(value) => {
+                            self.#ident = ::std::option::Option::Some(value);
+                            report.push(#name, #outcome::Answered);
+                        }
+                        ::std::result::Result::Err(_) => {
+                            self.#ident = ::std::option::Option::None;
+                            report.push(#name, #outcome::ParseFailed {
+                                raw: ::std::string::String::from(text),
+                            });
+                        }
+                    }
+                }
+            }
+            _ => {
+                let parsed = self.parse_value(quote!(text));
+                quote! {
+                    self.#ident = #parsed;
+                    report.push(
+                        #name,
+                        if text.is_empty() { #outcome::Empty } else { #outcome::Answered },
+                    );
+                }
+            }
+        };
+        match self.empty() {
+            Some(empty) => quote! {
+                match results[#index].as_str() {
+                    "" => {
+                        self.#ident = #empty;
+                        report.push(#name, #outcome::Empty);
+                    }
+                    text => {
+                        #answered
+                    }
+                }
+            },
+            None => quote! {
+                {
+                    let text = results[#index].as_str();
+                    #answered
+                }
+            },
+        }
+    }
+
+    /// Returns the initializer of the field from its default alone,
+    /// which is `None` for an optional field and `Default::default()` without a default.
+    pub fn initialize(&self) -> TokenStream {
+        let ident = &self.ident;
+        let value = self
+            .empty()
+            .unwrap_or_else(|| quote!(::std::default::Default::default()));
+        quote!(#ident: #value)
+    }
+
+    /// Returns the value of the field for an empty input, if it is accepted.
+    /// A select without a default takes its first option, as chosen initially in the form.
+    fn empty(&self) -> Option<TokenStream> {
+        let default = self
+            .default
+            .as_ref()
+            .or(self.select.as_ref().and_then(|options| options.first()));
+        match (default, self.optional) {
+            (Some(default), _) => Some(self.parse_value(quote!(#default))),
+            (None, true) => Some(quote!(::std::option::Option::None)),
+            (None, false) => None,
+        }
+    }
+
+    /// Returns the expression parsing the text into the field value.
+    fn parse_value(&self, text: TokenStream) -> TokenStream {
+        let ty = &self.value_ty;
+        let name = self.ident.to_string();
+        let parsed = match &self.select {
+            Some(options) => {
+                let expected = options.join(", ");
+                quote! {
+                    match #text {
+                        text if [#(#options),*].contains(&text) => text.parse::<#ty>().map_err(|e| {
+                            ::promkit::anyhow::anyhow!(
+                                "{}: option `{}` is not a valid {}: {}",
+                                #name,
+                                text,
+                                ::std::stringify!(#ty),
+                                e
+                            )
+                        })?,
+                        text => ::promkit::anyhow::bail!(
+                            "{}: unknown option `{}`, expected one of {}",
+                            #name,
+                            text,
+                            #expected
+                        ),
+                    }
+                }
+            }
+            None => quote! {
+                #text
+                    .parse::<#ty>()
+                    .map_err(|e| ::promkit::anyhow::anyhow!("{}: {}", #name, e))?
+            },
+        };
+        match self.optional {
+            true => quote!(::std::option::Option::Some(#parsed)),
+            false => parsed,
+        }
+    }
+}
+
+fn last_segment(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => path
+            .path
+            .segments
+            .last()
+            .filter(|segment| segment.arguments.is_none())
+            .map(|segment| segment.ident.to_string()),
+        _ => None,
+    }
+}
+
+/// Returns `T` if the type is `Option<T>`.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if path.qself.is_some() || segment.ident != "Option" {
+        return None;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            GenericArgument::Type(inner) => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn is_string(ty: &Type) -> bool {
+    last_segment(ty).is_some_and(|name| name == "String")
+}
+
+fn is_numeric(ty: &Type) -> bool {
+    last_segment(ty).is_some_and(|name| NUMERIC_TYPES.contains(&name.as_str()))
+}
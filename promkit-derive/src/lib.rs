@@ -0,0 +1,261 @@
+//! A derive macro building a [promkit](https://docs.rs/promkit) form from a struct.
+//!
+//! Each named field becomes a field of `promkit::preset::form::Form`
+//! starting from the current value of the field (with `Display`, and empty for `None`),
+//! and the input is parsed into the field type with `FromStr`.
+//! Fields are configured with `#[form(...)]`:
+//!
+//! | Argument                      | Description
+//! | :---------------------------- | :-------------------------------------------
+//! | `label = "Name:"`             | Text shown before the input (default: the field name)
+//! | `min_len = 3`                 | Minimum number of characters
+//! | `validate_range = "18..=120"` | Range the parsed value must be in (numeric fields only)
+//! | `mask = '*'`                  | Character masking the input, e.g. for passwords
+//! | `default = "8080"`            | Value pre-filling an empty input and assigned if it is left empty, shown as a hint
+//! | `select = ["dev", "prod"]`    | Options to choose from instead of a free text
+//!
+//! An `Option<T>` field is parsed into `T`, and is `None` for an empty input without a default
+//! or for an input failing to parse.
+//!
+//! A `select` field is chosen from its options with ↑ and ↓, starting from the `default`
+//! or the first option, and the chosen option is parsed like a text.
+//! It fits an enum implementing `FromStr` with the options as the accepted strings:
+//! an option the enum does not accept fails `apply` with a message naming the option,
+//! and so does a result that is none of the options.
+//!
+//! The input of a field is validated before the cursor moves down to the next one,
+//! and the form is not submitted until all fields are valid,
+//! moving the cursor back to the first invalid one, e.g. a number failing to parse.
+//!
+//! `build` and `apply` return a `FormReport` with the outcome of each field:
+//! whether it is answered, left empty, or `None` for an optional input failing to parse.
+//!
+//! ```no_run
+//! use promkit_derive::Promkit;
+//!
+//! #[derive(Default, Promkit)]
+//! struct Profile {
+//!     #[form(label = "Name:", min_len = 3)]
+//!     name: String,
+//!     #[form(label = "Age:", validate_range = "18..=120")]
+//!     age: u32,
+//! }
+//!
+//! fn main() -> promkit::anyhow::Result<()> {
+//!     let mut profile = Profile::default();
+//!     let report = profile.build()?;
+//!     println!("{} ({})", profile.name, profile.age);
+//!     println!("{:?}", report.get("age"));
+//!     Ok(())
+//! }
+//! ```
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DataStruct, DeriveInput, Fields};
+
+mod field;
+use field::Field;
+
+/// Derives `form`, `apply`, `prompt_defaults` and `build` to fill the struct in with a form.
+#[proc_macro_derive(Promkit, attributes(form))]
+pub fn derive_promkit(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let named = match &input.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(fields),
+            ..
+        }) => &fields.named,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "`Promkit` can only be derived for structs with named fields",
+            ))
+        }
+    };
+    let fields = named
+        .iter()
+        .map(Field::parse)
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let states = fields.iter().map(Field::state);
+    let validators = fields
+        .iter()
+        .enumerate()
+        .filter_map(|(i, field)| field.validator(i));
+    let assignments = fields.iter().enumerate().map(|(i, field)| field.assign(i));
+    let initializers = fields.iter().map(Field::initialize);
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Returns the form with a field per field of the struct, filled in with its value.
+            pub fn form(&self) -> ::promkit::preset::form::Form {
+                ::promkit::preset::form::Form::new([#(#states),*])
+                    #(#validators)*
+            }
+
+            /// Sets the fields from the result of the form, and reports how each was filled in.
+            pub fn apply(
+                &mut self,
+                results: ::std::vec::Vec<::std::string::String>,
+            ) -> ::promkit::anyhow::Result<::promkit::preset::form::FormReport> {
+                let mut report = ::promkit::preset::form::FormReport::default();
+                #(#assignments)*
+                Ok(report)
+            }
+
+            /// Returns the struct filled in with the defaults of the fields, without any interaction.
+            /// The fields without a default are `None` or `Default::default()`.
+            pub fn prompt_defaults() -> ::promkit::anyhow::Result<Self> {
+                Ok(Self {
+                    #(#initializers),*
+                })
+            }
+
+            /// Runs the form and sets the fields from its result, and reports how each was filled in.
+            pub fn build(
+                &mut self,
+            ) -> ::promkit::anyhow::Result<::promkit::preset::form::FormReport> {
+                let results = self.form().prompt()?.run()?;
+                self.apply(results)
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn error(input: DeriveInput) -> String {
+        expand(&input).err().unwrap().to_string()
+    }
+
+    mod expand {
+        use super::*;
+
+        #[test]
+        fn test_unknown_attribute() {
+            assert_eq!(
+                "unknown form attribute `max_len`",
+                error(syn::parse_quote! {
+                    struct Profile {
+                        #[form(max_len = 3)]
+                        name: String,
+                    }
+                })
+            );
+        }
+
+        #[test]
+        fn test_validate_range_on_non_numeric_field() {
+            assert_eq!(
+                "`validate_range` requires a numeric field, found `String`",
+                error(syn::parse_quote! {
+                    struct Profile {
+                        #[form(validate_range = "1..10")]
+                        name: String,
+                    }
+                })
+            );
+        }
+
+        #[test]
+        fn test_invalid_range() {
+            assert_eq!(
+                "expected a range like \"18..=120\"",
+                error(syn::parse_quote! {
+                    struct Profile {
+                        #[form(validate_range = "18")]
+                        age: u32,
+                    }
+                })
+            );
+        }
+
+        #[test]
+        fn test_invalid_min_len() {
+            assert_eq!(
+                "expected integer literal",
+                error(syn::parse_quote! {
+                    struct Profile {
+                        #[form(min_len = "3")]
+                        name: String,
+                    }
+                })
+            );
+        }
+
+        #[test]
+        fn test_invalid_mask() {
+            assert_eq!(
+                "expected character literal",
+                error(syn::parse_quote! {
+                    struct Credentials {
+                        #[form(mask = "*")]
+                        password: String,
+                    }
+                })
+            );
+        }
+
+        #[test]
+        fn test_select_with_mask() {
+            assert_eq!(
+                "`mask` cannot be used with `select`",
+                error(syn::parse_quote! {
+                    struct Deploy {
+                        #[form(select = ["dev", "prod"], mask = '*')]
+                        env: String,
+                    }
+                })
+            );
+        }
+
+        #[test]
+        fn test_select_default_not_in_options() {
+            assert_eq!(
+                "default `qa` is not one of the options",
+                error(syn::parse_quote! {
+                    struct Deploy {
+                        #[form(select = ["dev", "prod"], default = "qa")]
+                        env: String,
+                    }
+                })
+            );
+        }
+
+        #[test]
+        fn test_select_non_string_option() {
+            assert_eq!(
+                "expected string literals like [\"dev\", \"prod\"]",
+                error(syn::parse_quote! {
+                    struct Deploy {
+                        #[form(select = ["dev", 1])]
+                        env: String,
+                    }
+                })
+            );
+        }
+
+        #[test]
+        fn test_enum() {
+            assert_eq!(
+                "`Promkit` can only be derived for structs with named fields",
+                error(syn::parse_quote! {
+                    enum Profile {
+                        Name(String),
+                    }
+                })
+            );
+        }
+    }
+}
@@ -0,0 +1,472 @@
+#[cfg(test)]
+mod derive {
+    use promkit::{
+        crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers},
+        preset::form::{FieldOutcome, FormReport},
+        terminal::TestTerminal,
+    };
+    use promkit_derive::Promkit;
+
+    #[derive(Default, Promkit)]
+    struct Profile {
+        #[form(label = "Name:", min_len = 3)]
+        name: String,
+        #[form(label = "Age:", validate_range = "18..=120")]
+        age: u32,
+        nickname: String,
+    }
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn chars(s: &str) -> Vec<Event> {
+        s.chars().map(|ch| key(KeyCode::Char(ch))).collect()
+    }
+
+    fn erase_all() -> Event {
+        Event::Key(KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL))
+    }
+
+    fn shows(terminal: &TestTerminal, message: &str) -> bool {
+        terminal
+            .frames()
+            .iter()
+            .any(|frame| frame.iter().any(|row| row.to_string() == message))
+    }
+
+    #[test]
+    fn test_build() {
+        let mut profile = Profile::default();
+        let mut terminal = TestTerminal::new(40, 10);
+        let results = profile
+            .form()
+            .prompt()
+            .unwrap()
+            .run_with_events(
+                chars("Alice")
+                    .into_iter()
+                    // The age starts from "0" of the default value.
+                    .chain([key(KeyCode::Down), erase_all()])
+                    .chain(chars("42"))
+                    .chain([key(KeyCode::Down)])
+                    .chain(chars("Al"))
+                    .chain([key(KeyCode::Enter)]),
+                &mut terminal,
+            )
+            .unwrap();
+        profile.apply(results).unwrap();
+
+        assert_eq!("Alice", profile.name);
+        assert_eq!(42, profile.age);
+        assert_eq!("Al", profile.nickname);
+        assert_eq!(
+            vec!["Name: Alice ", "Age: 42 ", "nickname: Al "],
+            terminal.last_frame()
+        );
+    }
+
+    #[test]
+    fn test_errors_before_advancing() {
+        let mut profile = Profile::default();
+        let mut terminal = TestTerminal::new(40, 10);
+        let results = profile
+            .form()
+            .prompt()
+            .unwrap()
+            .run_with_events(
+                chars("Al")
+                    .into_iter()
+                    // Too short to move down.
+                    .chain([key(KeyCode::Down)])
+                    .chain(chars("ice"))
+                    .chain([key(KeyCode::Down)])
+                    .chain(chars("17"))
+                    // Out of range to submit.
+                    .chain([key(KeyCode::Enter)])
+                    .chain([key(KeyCode::Backspace), key(KeyCode::Backspace)])
+                    .chain(chars("x"))
+                    // Not a number.
+                    .chain([key(KeyCode::Enter)])
+                    .chain([key(KeyCode::Backspace)])
+                    .chain(chars("18"))
+                    .chain([key(KeyCode::Enter)]),
+                &mut terminal,
+            )
+            .unwrap();
+        profile.apply(results).unwrap();
+
+        assert_eq!("Alice", profile.name);
+        assert_eq!(18, profile.age);
+        assert!(shows(&terminal, "must be at least 3 characters"));
+        assert!(shows(&terminal, "must be in 18..=120"));
+        assert!(shows(&terminal, "expected a value of type u32"));
+    }
+
+    #[derive(Default, Promkit)]
+    struct Credentials {
+        #[form(label = "User:")]
+        user: String,
+        #[form(label = "Password:", mask = '*', min_len = 4)]
+        password: String,
+        #[form(label = "PIN:", mask = '•')]
+        pin: u16,
+    }
+
+    #[test]
+    fn test_mask() {
+        let mut credentials = Credentials::default();
+        let mut terminal = TestTerminal::new(40, 10);
+        let results = credentials
+            .form()
+            .prompt()
+            .unwrap()
+            .run_with_events(
+                chars("alice")
+                    .into_iter()
+                    .chain([key(KeyCode::Down)])
+                    .chain(chars("s3cret"))
+                    .chain([key(KeyCode::Down), erase_all()])
+                    .chain(chars("1234"))
+                    .chain([key(KeyCode::Enter)]),
+                &mut terminal,
+            )
+            .unwrap();
+        credentials.apply(results).unwrap();
+
+        assert_eq!("alice", credentials.user);
+        assert_eq!("s3cret", credentials.password);
+        assert_eq!(1234, credentials.pin);
+        assert_eq!(
+            vec!["User: alice ", "Password: ****** ", "PIN: •••• "],
+            terminal.last_frame()
+        );
+    }
+
+    #[derive(Debug, PartialEq, Promkit)]
+    struct Server {
+        #[form(label = "Host:", default = "localhost")]
+        host: String,
+        #[form(label = "Port:", default = "8080", validate_range = "1..=65535")]
+        port: u16,
+        #[form(label = "Workers:")]
+        workers: Option<usize>,
+    }
+
+    #[test]
+    fn test_defaults_for_empty_input() {
+        let mut server = Server::prompt_defaults().unwrap();
+        let mut terminal = TestTerminal::new(40, 10);
+        let results = server
+            .form()
+            .prompt()
+            .unwrap()
+            .run_with_events(
+                [key(KeyCode::Down), key(KeyCode::Down)]
+                    .into_iter()
+                    .chain([key(KeyCode::Enter)]),
+                &mut terminal,
+            )
+            .unwrap();
+        server.apply(results).unwrap();
+
+        assert_eq!(
+            Server {
+                host: String::from("localhost"),
+                port: 8080,
+                workers: None,
+            },
+            server
+        );
+        assert!(terminal
+            .frame(0)
+            .iter()
+            .any(|row| row.ends_with("(default: 8080)")));
+    }
+
+    #[test]
+    fn test_override_defaults() {
+        let mut server = Server::prompt_defaults().unwrap();
+        let results = server
+            .form()
+            .prompt()
+            .unwrap()
+            .run_with_events(
+                // The fields start from the defaults.
+                [erase_all()]
+                    .into_iter()
+                    .chain(chars("example.com"))
+                    .chain([key(KeyCode::Down), erase_all()])
+                    .chain(chars("0"))
+                    // Out of range to move down.
+                    .chain([key(KeyCode::Down), key(KeyCode::Backspace)])
+                    .chain(chars("443"))
+                    .chain([key(KeyCode::Down)])
+                    .chain(chars("4"))
+                    .chain([key(KeyCode::Enter)]),
+                &mut TestTerminal::new(40, 10),
+            )
+            .unwrap();
+        server.apply(results).unwrap();
+
+        assert_eq!(
+            Server {
+                host: String::from("example.com"),
+                port: 443,
+                workers: Some(4),
+            },
+            server
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let mut server = Server::prompt_defaults().unwrap();
+        let report = server
+            .apply(vec![
+                String::new(),
+                String::from("443"),
+                String::from("many"),
+            ])
+            .unwrap();
+
+        assert_eq!(
+            FormReport {
+                fields: vec![
+                    (String::from("host"), FieldOutcome::Empty),
+                    (String::from("port"), FieldOutcome::Answered),
+                    (
+                        String::from("workers"),
+                        FieldOutcome::ParseFailed {
+                            raw: String::from("many")
+                        }
+                    ),
+                ],
+            },
+            report
+        );
+        assert_eq!(String::from("localhost"), server.host);
+        assert_eq!(None, server.workers);
+
+        let mut profile = Profile::default();
+        let report = profile
+            .apply(vec![
+                String::from("Alice"),
+                String::from("42"),
+                String::new(),
+            ])
+            .unwrap();
+        assert_eq!(Some(&FieldOutcome::Answered), report.get("name"));
+        assert_eq!(Some(&FieldOutcome::Empty), report.get("nickname"));
+        assert_eq!(None, report.get("email"));
+        assert!(profile
+            .apply(vec![
+                String::from("Alice"),
+                String::from("x"),
+                String::new()
+            ])
+            .is_err());
+    }
+
+    #[test]
+    fn test_prefill_defaults_and_loop_back() {
+        let server = Server {
+            host: String::new(),
+            port: 8080,
+            workers: None,
+        };
+        let mut terminal = TestTerminal::new(40, 10);
+        let results = server
+            .form()
+            .prompt()
+            .unwrap()
+            .run_with_events(
+                [key(KeyCode::Down), key(KeyCode::Down)]
+                    .into_iter()
+                    .chain(chars("some"))
+                    .chain([key(KeyCode::Up), erase_all()])
+                    .chain(chars("http"))
+                    // The port fails to parse, moving the cursor back to it from the host.
+                    .chain([key(KeyCode::Up), key(KeyCode::Enter), erase_all()])
+                    .chain(chars("80"))
+                    .chain([key(KeyCode::Enter)]),
+                &mut terminal,
+            )
+            .unwrap();
+
+        assert!(terminal.frame(0)[0].starts_with("Host: localhost "));
+        assert!(shows(&terminal, "expected a value of type u16"));
+        // The optional field is submitted as is, to be reported.
+        assert_eq!(vec!["localhost", "80", "some"], results);
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Env {
+        Dev,
+        Staging,
+        Prod,
+    }
+
+    impl std::fmt::Display for Env {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str(match self {
+                Self::Dev => "dev",
+                Self::Staging => "staging",
+                Self::Prod => "prod",
+            })
+        }
+    }
+
+    impl std::str::FromStr for Env {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "dev" => Ok(Self::Dev),
+                "staging" => Ok(Self::Staging),
+                "prod" => Ok(Self::Prod),
+                _ => Err(format!("no environment named {}", s)),
+            }
+        }
+    }
+
+    #[derive(Debug, PartialEq, Promkit)]
+    struct Deploy {
+        #[form(label = "Env:", select = ["dev", "staging", "prod"], default = "staging")]
+        env: Env,
+        #[form(label = "Tag:")]
+        tag: String,
+        #[form(label = "Region:", select = ["us", "eu", "mars"])]
+        region: String,
+    }
+
+    #[test]
+    fn test_select() {
+        let mut deploy = Deploy::prompt_defaults().unwrap();
+        assert_eq!(Env::Staging, deploy.env);
+        assert_eq!("us", deploy.region);
+
+        let mut terminal = TestTerminal::new(40, 10);
+        let results = deploy
+            .form()
+            .prompt()
+            .unwrap()
+            .run_with_events(
+                [
+                    // From staging to prod, then past the last option to the next field.
+                    key(KeyCode::Down),
+                    key(KeyCode::Down),
+                ]
+                .into_iter()
+                .chain(chars("v1"))
+                .chain([key(KeyCode::Down), key(KeyCode::Down)])
+                .chain([key(KeyCode::Enter)]),
+                &mut terminal,
+            )
+            .unwrap();
+        assert_eq!(
+            vec!["Env: prod", "Tag: v1 ", "Region: ", "❯ eu", "  mars"],
+            terminal.last_frame()
+        );
+        deploy.apply(results).unwrap();
+
+        assert_eq!(
+            Deploy {
+                env: Env::Prod,
+                tag: String::from("v1"),
+                region: String::from("eu"),
+            },
+            deploy
+        );
+    }
+
+    #[test]
+    fn test_select_errors() {
+        let mut deploy = Deploy::prompt_defaults().unwrap();
+        assert_eq!(
+            "env: unknown option `qa`, expected one of dev, staging, prod",
+            deploy
+                .apply(vec![String::from("qa"), String::new(), String::from("us")])
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[derive(Debug, Promkit)]
+    struct Misconfigured {
+        #[form(select = ["dev", "test"])]
+        env: Env,
+    }
+
+    #[test]
+    fn test_select_option_not_accepted() {
+        let mut misconfigured = Misconfigured::prompt_defaults().unwrap();
+        assert_eq!(
+            "env: option `test` is not a valid Env: no environment named test",
+            misconfigured
+                .apply(vec![String::from("test")])
+                .unwrap_err()
+                .to_string()
+        );
+        assert_eq!(Env::Dev, misconfigured.env);
+    }
+
+    #[test]
+    fn test_start_from_current_values() {
+        let mut profile = Profile {
+            name: String::from("Alice"),
+            age: 42,
+            nickname: String::new(),
+        };
+        let mut terminal = TestTerminal::new(40, 10);
+        let results = profile
+            .form()
+            .prompt()
+            .unwrap()
+            .run_with_events(
+                [key(KeyCode::Down), key(KeyCode::Backspace)]
+                    .into_iter()
+                    .chain(chars("3"))
+                    .chain([key(KeyCode::Enter)]),
+                &mut terminal,
+            )
+            .unwrap();
+        assert_eq!(
+            vec!["Name: Alice ", "Age: 42 ", "nickname:  "],
+            terminal.frame(0)
+        );
+        profile.apply(results).unwrap();
+
+        assert_eq!("Alice", profile.name);
+        assert_eq!(43, profile.age);
+        assert_eq!("", profile.nickname);
+
+        // `None` starts empty, and the chosen option follows the current value.
+        let server = Server {
+            host: String::from("example.com"),
+            port: 443,
+            workers: None,
+        };
+        let results = server
+            .form()
+            .prompt()
+            .unwrap()
+            .run_with_events([key(KeyCode::Enter)], &mut TestTerminal::new(40, 10))
+            .unwrap();
+        assert_eq!(vec!["example.com", "443", ""], results);
+        let mut deploy = Deploy {
+            env: Env::Prod,
+            tag: String::from("v1"),
+            region: String::from("eu"),
+        };
+        let results = deploy
+            .form()
+            .prompt()
+            .unwrap()
+            .run_with_events([key(KeyCode::Enter)], &mut TestTerminal::new(40, 10))
+            .unwrap();
+        assert_eq!(vec!["prod", "v1", "eu"], results);
+        deploy.apply(results).unwrap();
+        assert_eq!(Env::Prod, deploy.env);
+    }
+}
@@ -0,0 +1,9 @@
+use promkit_derive::Promkit;
+
+#[derive(Promkit)]
+struct Server {
+    #[form(label = "Port:", default = 8080)]
+    port: u16,
+}
+
+fn main() {}
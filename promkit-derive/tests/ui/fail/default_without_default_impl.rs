@@ -0,0 +1,25 @@
+use promkit_derive::Promkit;
+
+struct Address(String);
+
+impl std::fmt::Display for Address {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::str::FromStr for Address {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.to_string()))
+    }
+}
+
+#[derive(Promkit)]
+struct Server {
+    #[form(label = "Address:")]
+    address: Address,
+}
+
+fn main() {}
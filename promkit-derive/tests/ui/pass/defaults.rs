@@ -0,0 +1,18 @@
+use promkit_derive::Promkit;
+
+#[derive(Promkit)]
+struct Server {
+    #[form(label = "Host:", default = "localhost")]
+    host: String,
+    #[form(label = "Port:", default = "8080", validate_range = "1..=65535")]
+    port: u16,
+    #[form(label = "Workers:")]
+    workers: Option<usize>,
+}
+
+fn main() {
+    let server = Server::prompt_defaults().unwrap();
+    assert_eq!("localhost", server.host);
+    assert_eq!(8080, server.port);
+    assert_eq!(None, server.workers);
+}
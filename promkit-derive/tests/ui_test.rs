@@ -0,0 +1,12 @@
+#[cfg(test)]
+mod ui {
+    #[test]
+    fn test_pass() {
+        trybuild::TestCases::new().pass("tests/ui/pass/*.rs");
+    }
+
+    #[test]
+    fn test_compile_fail() {
+        trybuild::TestCases::new().compile_fail("tests/ui/fail/*.rs");
+    }
+}
@@ -1,4 +1,4 @@
-use promkit::preset::checkbox::Checkbox;
+use promkit::prelude::*;
 
 fn main() -> anyhow::Result<()> {
     let mut p = Checkbox::new(vec![
@@ -1,4 +1,4 @@
-use promkit::preset::confirm::Confirm;
+use promkit::prelude::*;
 
 fn main() -> anyhow::Result<()> {
     let mut p = Confirm::new("Do you have a pet?").prompt()?;
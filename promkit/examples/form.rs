@@ -1,4 +1,4 @@
-use promkit::{crossterm::style::Color, preset::form::Form, style::StyleBuilder, text_editor};
+use promkit::{prelude::*, text_editor};
 
 fn main() -> anyhow::Result<()> {
     let mut p = Form::new([
@@ -12,7 +12,13 @@ fn main() -> anyhow::Result<()> {
             inactive_char_style: StyleBuilder::new().build(),
             edit_mode: Default::default(),
             word_break_chars: Default::default(),
+            unicode_words: false,
+            right_prompt: Default::default(),
+            right_prompt_style: Default::default(),
             lines: Default::default(),
+            scroll_top: Default::default(),
+            horizontal_scroll: false,
+            scroll_left: Default::default(),
         },
         text_editor::State {
             texteditor: Default::default(),
@@ -24,7 +30,13 @@ fn main() -> anyhow::Result<()> {
             inactive_char_style: StyleBuilder::new().build(),
             edit_mode: Default::default(),
             word_break_chars: Default::default(),
+            unicode_words: false,
+            right_prompt: Default::default(),
+            right_prompt_style: Default::default(),
             lines: Default::default(),
+            scroll_top: Default::default(),
+            horizontal_scroll: false,
+            scroll_left: Default::default(),
         },
         text_editor::State {
             texteditor: Default::default(),
@@ -36,7 +48,13 @@ fn main() -> anyhow::Result<()> {
             inactive_char_style: StyleBuilder::new().build(),
             edit_mode: Default::default(),
             word_break_chars: Default::default(),
+            unicode_words: false,
+            right_prompt: Default::default(),
+            right_prompt_style: Default::default(),
             lines: Default::default(),
+            scroll_top: Default::default(),
+            horizontal_scroll: false,
+            scroll_left: Default::default(),
         },
     ])
     .prompt()?;
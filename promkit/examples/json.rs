@@ -1,4 +1,4 @@
-use promkit::{jsonstream::JsonStream, preset::json::Json};
+use promkit::prelude::*;
 use serde_json::Deserializer;
 
 fn main() -> anyhow::Result<()> {
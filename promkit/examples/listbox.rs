@@ -1,4 +1,4 @@
-use promkit::preset::listbox::Listbox;
+use promkit::prelude::*;
 
 fn main() -> anyhow::Result<()> {
     let mut p = Listbox::new(0..100)
@@ -0,0 +1,11 @@
+use promkit::prelude::*;
+
+fn main() -> anyhow::Result<()> {
+    let mut p = Number::default()
+        .title("How many replicas?")
+        .min(1.0)
+        .max(10.0)
+        .prompt()?;
+    println!("result: {:?}", p.run()?);
+    Ok(())
+}
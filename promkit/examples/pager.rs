@@ -0,0 +1,11 @@
+use promkit::prelude::*;
+
+fn main() -> anyhow::Result<()> {
+    let help = (1..=100)
+        .map(|i| format!("{:>3}: the help output goes on", i))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut p = Pager::new(help).title("Help").prompt()?;
+    p.run()?;
+    Ok(())
+}
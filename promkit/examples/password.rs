@@ -1,4 +1,4 @@
-use promkit::preset::password::Password;
+use promkit::prelude::*;
 
 fn main() -> anyhow::Result<()> {
     let mut p = Password::default()
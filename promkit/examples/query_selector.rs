@@ -1,4 +1,4 @@
-use promkit::preset::query_selector::QuerySelector;
+use promkit::prelude::*;
 
 fn main() -> anyhow::Result<()> {
     let mut p = QuerySelector::new(0..100, |text, items| -> Vec<String> {
@@ -0,0 +1,31 @@
+use std::time::Duration;
+
+use promkit::prelude::*;
+
+/// Pretends to be a slow web API searching the languages.
+async fn search(query: String) -> Vec<String> {
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    [
+        "Rust",
+        "Ruby",
+        "Python",
+        "Go",
+        "TypeScript",
+        "Haskell",
+        "OCaml",
+    ]
+    .iter()
+    .filter(|lang| lang.to_lowercase().contains(&query.to_lowercase()))
+    .map(|lang| lang.to_string())
+    .collect()
+}
+
+fn main() -> anyhow::Result<()> {
+    let mut p = QuerySelector::with_async_filter(|query| search(query.to_string()))
+        .title("Which language do you like?")
+        .debounce(Duration::from_millis(300))
+        .listbox_lines(5)
+        .prompt()?;
+    println!("result: {:?}", p.run()?);
+    Ok(())
+}
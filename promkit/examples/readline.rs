@@ -1,9 +1,9 @@
-use promkit::{preset::readline::Readline, suggest::Suggest};
+use promkit::prelude::*;
 
 fn main() -> anyhow::Result<()> {
     let mut p = Readline::default()
         .title("Hi!")
-        .enable_suggest(Suggest::from_iter([
+        .suggest(Suggest::from_iter([
             "apple",
             "applet",
             "application",
@@ -1,4 +1,4 @@
-use promkit::preset::readline::Readline;
+use promkit::prelude::*;
 
 fn main() -> anyhow::Result<()> {
     let mut p = Readline::default().prompt()?;
@@ -0,0 +1,22 @@
+use std::{thread, time::Duration};
+
+use promkit::{prelude::*, read_only::ReadOnly};
+
+/// Rejects the input (except Ctrl+C) while a task is loading in the background.
+fn main() -> anyhow::Result<()> {
+    let read_only = ReadOnly::default();
+    let mut p = Readline::default()
+        .title("Type once the index is loaded (in 3 seconds)")
+        .read_only(read_only.clone())
+        .prompt()?;
+
+    read_only.set(true);
+    let task = thread::spawn(move || {
+        thread::sleep(Duration::from_secs(3));
+        read_only.set(false);
+    });
+
+    println!("result: {:?}", p.run()?);
+    task.join().ok();
+    Ok(())
+}
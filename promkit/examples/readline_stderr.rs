@@ -0,0 +1,17 @@
+use std::io;
+
+use promkit::prelude::*;
+
+/// Draws the prompt on stderr (on the alternate screen),
+/// so that only the result is written to stdout, e.g. `cargo run --example readline_stderr | cat`.
+fn main() -> anyhow::Result<()> {
+    let mut p = Readline::default()
+        .title("Hi!")
+        .prompt()?
+        .writer(io::stderr())
+        .alternate_screen(true);
+    let result = p.run()?;
+    drop(p);
+    println!("{}", result);
+    Ok(())
+}
@@ -0,0 +1,15 @@
+use std::time::Duration;
+
+use promkit::prelude::*;
+
+fn main() -> anyhow::Result<()> {
+    let mut p = Readline::default()
+        .title("What is your name?")
+        .prompt()?
+        .timeout(Duration::from_secs(10))
+        .timeout_default(String::from("anonymous"))
+        .show_countdown(true);
+    let result = p.run()?;
+    println!("result: {:?}", result);
+    Ok(())
+}
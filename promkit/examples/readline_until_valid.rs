@@ -0,0 +1,14 @@
+use promkit::prelude::*;
+
+fn main() -> anyhow::Result<()> {
+    let mut p = Readline::default()
+        .title("Which port?")
+        .finalize_validator(
+            |text| !text.starts_with('0'),
+            |_| String::from("Leading zeros are not allowed"),
+        )
+        .prompt()?;
+    let port = p.run_parsed_until_valid::<u16>(Some(3))?;
+    println!("result: {:?}", port);
+    Ok(())
+}
@@ -0,0 +1,11 @@
+use promkit::prelude::*;
+
+fn main() -> anyhow::Result<()> {
+    let mut session = Session::new()?.between_steps(BetweenSteps::KeepResults);
+    let name = session.run(&mut Readline::default().title("Name the volume").prompt()?)?;
+    let class = session.run(&mut Listbox::new(["standard", "ssd", "archive"]).prompt()?)?;
+    let create = session.run(&mut Confirm::new("Create the volume?").prompt()?)?;
+    session.finish()?;
+    println!("result: {:?}", (name, class, create));
+    Ok(())
+}
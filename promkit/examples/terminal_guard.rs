@@ -0,0 +1,47 @@
+use std::io::{self, Write};
+
+use promkit::{
+    crossterm::{
+        cursor,
+        event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
+        queue,
+        terminal::{self, ClearType},
+    },
+    install_panic_hook, TerminalGuard,
+};
+
+/// Counts the keys pressed in a hand-written loop until Enter, panicking on `p`.
+/// Either way, the guard (and the panic hook) leaves the terminal as it was.
+fn main() -> anyhow::Result<()> {
+    install_panic_hook();
+    let mut guard = TerminalGuard::new(io::stdout());
+    guard.enable_raw_mode()?;
+    guard.hide_cursor()?;
+
+    let mut count = 0;
+    loop {
+        queue!(
+            guard,
+            cursor::MoveToColumn(0),
+            terminal::Clear(ClearType::CurrentLine)
+        )?;
+        write!(guard, "{} keys pressed (Enter to quit, p to panic)", count)?;
+        guard.flush()?;
+        if let Event::Key(KeyEvent {
+            code,
+            kind: KeyEventKind::Press,
+            ..
+        }) = event::read()?
+        {
+            match code {
+                KeyCode::Enter => break,
+                KeyCode::Char('p') => panic!("pressed p"),
+                _ => count += 1,
+            }
+        }
+    }
+
+    guard.restore()?;
+    println!();
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use promkit::{preset::tree::Tree, tree::Node};
+use promkit::prelude::*;
 
 fn main() -> anyhow::Result<()> {
     let mut p = Tree::new(Node::try_from(&std::env::current_dir()?.join("src"))?)
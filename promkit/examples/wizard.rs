@@ -0,0 +1,14 @@
+use promkit::prelude::*;
+
+fn main() -> anyhow::Result<()> {
+    let mut p = Wizard::default()
+        .step("Name the volume", Readline::default().renderer())
+        .step(
+            "Configure storage",
+            Listbox::new(["standard", "ssd", "archive"]).renderer(),
+        )
+        .step("Confirm", Confirm::new("Create the volume?").renderer())
+        .prompt()?;
+    println!("result: {:?}", p.run()?);
+    Ok(())
+}
@@ -0,0 +1,386 @@
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    PromptSignal,
+};
+
+/// A key along with its modifiers, e.g. `Ctrl+X`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct KeyCombo {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyCombo {
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        Self { code, modifiers }
+    }
+
+    /// Returns the combo of a key press, or `None` for the other events.
+    pub fn from_event(event: &Event) -> Option<Self> {
+        match event {
+            Event::Key(KeyEvent {
+                code,
+                modifiers,
+                kind: KeyEventKind::Press,
+                ..
+            }) => Some(Self::new(*code, *modifiers)),
+            _ => None,
+        }
+    }
+}
+
+impl From<KeyCode> for KeyCombo {
+    fn from(code: KeyCode) -> Self {
+        Self::new(code, KeyModifiers::NONE)
+    }
+}
+
+impl From<char> for KeyCombo {
+    fn from(ch: char) -> Self {
+        Self::new(KeyCode::Char(ch), KeyModifiers::NONE)
+    }
+}
+
+impl fmt::Display for KeyCombo {
+    /// Formats the combo in the short notation like `C-x`, `M-f` or `g`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "C-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "M-")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(ch) => write!(f, "{}", ch),
+            code => write!(f, "{}", code),
+        }
+    }
+}
+
+/// The handler run when a chord is completed.
+pub type ChordHandler<T> = fn(&mut T) -> anyhow::Result<PromptSignal>;
+
+/// Tells whether a chord is recognized in the current state, e.g. only in the vi normal mode.
+pub type ChordCondition<T> = fn(&T) -> bool;
+
+struct Binding<T> {
+    sequence: Vec<KeyCombo>,
+    handler: ChordHandler<T>,
+    condition: Option<ChordCondition<T>>,
+}
+
+impl<T> Binding<T> {
+    fn is_active(&self, target: &T) -> bool {
+        self.condition.is_none_or(|condition| condition(target))
+    }
+}
+
+/// What the prompt does with an event fed to `Chords`.
+pub enum Step<T> {
+    /// Evaluates the event with the renderer as usual.
+    Event(Event),
+    /// Runs the handler of the completed chord.
+    Handler(ChordHandler<T>),
+}
+
+/// Recognizes multi-key sequences (chords) like `g g` or `Ctrl+X Ctrl+E`.
+///
+/// Keys matching the beginning of a chord are held back as the pending prefix.
+/// When the chord is completed, its handler runs instead of the renderer.
+/// When a key that does not continue any chord arrives, or no key arrives within
+/// the timeout, the pending prefix is flushed to the renderer as ordinary events.
+pub struct Chords<T> {
+    bindings: Vec<Binding<T>>,
+    timeout: Duration,
+    pending: Vec<Event>,
+    last_pushed_at: Option<Instant>,
+}
+
+impl<T> Default for Chords<T> {
+    fn default() -> Self {
+        Self {
+            bindings: Default::default(),
+            timeout: Duration::from_secs(1),
+            pending: Default::default(),
+            last_pushed_at: None,
+        }
+    }
+}
+
+impl<T> Chords<T> {
+    /// Binds the sequence of keys to the handler.
+    /// A later binding to the same sequence replaces the earlier one.
+    pub fn bind<I: IntoIterator<Item = KeyCombo>>(
+        self,
+        sequence: I,
+        handler: ChordHandler<T>,
+    ) -> Self {
+        self.insert(sequence, handler, None)
+    }
+
+    /// Binds the sequence of keys to the handler, recognized only while the condition holds.
+    /// Otherwise the keys are evaluated by the renderer as usual.
+    pub fn bind_when<I: IntoIterator<Item = KeyCombo>>(
+        self,
+        sequence: I,
+        condition: ChordCondition<T>,
+        handler: ChordHandler<T>,
+    ) -> Self {
+        self.insert(sequence, handler, Some(condition))
+    }
+
+    fn insert<I: IntoIterator<Item = KeyCombo>>(
+        mut self,
+        sequence: I,
+        handler: ChordHandler<T>,
+        condition: Option<ChordCondition<T>>,
+    ) -> Self {
+        let sequence: Vec<KeyCombo> = sequence.into_iter().collect();
+        if !sequence.is_empty() {
+            self.bindings.retain(|binding| binding.sequence != sequence);
+            self.bindings.push(Binding {
+                sequence,
+                handler,
+                condition,
+            });
+        }
+        self
+    }
+
+    /// Sets the maximum wait between the keys of a chord.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Returns the pending prefix, e.g. `C-x`, or an empty string if there is none.
+    pub fn pending_text(&self) -> String {
+        self.pending
+            .iter()
+            .filter_map(KeyCombo::from_event)
+            .map(|combo| combo.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Returns the instant the pending prefix expires, if any.
+    pub fn deadline(&self) -> Option<Instant> {
+        match (self.pending.is_empty(), self.last_pushed_at) {
+            (false, Some(at)) => Some(at + self.timeout),
+            _ => None,
+        }
+    }
+
+    /// Flushes the pending prefix if it has expired at `now`.
+    pub fn expire(&mut self, now: Instant) -> Vec<Step<T>> {
+        match self.deadline() {
+            Some(deadline) if deadline <= now => self.flush(),
+            _ => vec![],
+        }
+    }
+
+    /// Feeds the event received at `now`, and returns what to do in order.
+    /// The conditions of the bindings are checked against `target`.
+    /// An empty result means the event is held as a part of the pending prefix.
+    pub fn feed(&mut self, event: Event, now: Instant, target: &T) -> Vec<Step<T>> {
+        let mut steps = self.expire(now);
+        if self.bindings.is_empty() {
+            steps.push(Step::Event(event));
+            return steps;
+        }
+        let Some(combo) = KeyCombo::from_event(&event) else {
+            // Ignore key releases and repeats, which never take part in chords.
+            if matches!(event, Event::Key(_)) {
+                steps.push(Step::Event(event));
+            } else {
+                steps.extend(self.flush());
+                steps.push(Step::Event(event));
+            }
+            return steps;
+        };
+
+        let mut sequence: Vec<KeyCombo> = self
+            .pending
+            .iter()
+            .filter_map(KeyCombo::from_event)
+            .collect();
+        sequence.push(combo);
+
+        if let Some(handler) = self.find(&sequence, target) {
+            self.pending.clear();
+            self.last_pushed_at = None;
+            steps.push(Step::Handler(handler));
+        } else if self.is_prefix(&sequence, target) {
+            self.pending.push(event);
+            self.last_pushed_at = Some(now);
+        } else if self.pending.is_empty() {
+            steps.push(Step::Event(event));
+        } else {
+            // The key may start another chord on its own.
+            steps.extend(self.flush());
+            steps.extend(self.feed(event, now, target));
+        }
+        steps
+    }
+
+    fn find(&self, sequence: &[KeyCombo], target: &T) -> Option<ChordHandler<T>> {
+        self.bindings
+            .iter()
+            .find(|binding| binding.sequence == sequence && binding.is_active(target))
+            .map(|binding| binding.handler)
+    }
+
+    fn is_prefix(&self, sequence: &[KeyCombo], target: &T) -> bool {
+        self.bindings.iter().any(|binding| {
+            binding.sequence.len() > sequence.len()
+                && binding.sequence.starts_with(sequence)
+                && binding.is_active(target)
+        })
+    }
+
+    fn flush(&mut self) -> Vec<Step<T>> {
+        self.last_pushed_at = None;
+        self.pending.drain(..).map(Step::Event).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn key(ch: char) -> Event {
+        Event::Key(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE))
+    }
+
+    fn describe(steps: &[Step<Vec<&'static str>>]) -> Vec<String> {
+        let mut log = vec![];
+        for step in steps {
+            match step {
+                Step::Event(ev) => log.push(KeyCombo::from_event(ev).unwrap().to_string()),
+                Step::Handler(handler) => {
+                    let mut calls = vec![];
+                    handler(&mut calls).unwrap();
+                    log.extend(calls.into_iter().map(String::from));
+                }
+            }
+        }
+        log
+    }
+
+    fn chords() -> Chords<Vec<&'static str>> {
+        Chords::default()
+            .bind(['g'.into(), 'g'.into()], |calls: &mut Vec<&'static str>| {
+                calls.push("head");
+                Ok(PromptSignal::Continue)
+            })
+            .bind(['d'.into(), 'd'.into()], |calls: &mut Vec<&'static str>| {
+                calls.push("delete");
+                Ok(PromptSignal::Continue)
+            })
+    }
+
+    mod feed {
+        use super::*;
+
+        #[test]
+        fn test_complete_chord() {
+            let mut chords = chords();
+            let now = Instant::now();
+            assert!(chords.feed(key('g'), now, &vec![]).is_empty());
+            assert_eq!("g", chords.pending_text());
+            assert_eq!(
+                vec!["head"],
+                describe(&chords.feed(key('g'), now + Duration::from_millis(10), &vec![]))
+            );
+            assert_eq!("", chords.pending_text());
+        }
+
+        #[test]
+        fn test_non_matching_key_flushes_prefix() {
+            let mut chords = chords();
+            let now = Instant::now();
+            assert!(chords.feed(key('g'), now, &vec![]).is_empty());
+            assert_eq!(
+                vec!["g", "x"],
+                describe(&chords.feed(key('x'), now, &vec![]))
+            );
+            assert_eq!(None, chords.deadline());
+        }
+
+        #[test]
+        fn test_non_matching_key_starts_another_chord() {
+            let mut chords = chords();
+            let now = Instant::now();
+            assert!(chords.feed(key('g'), now, &vec![]).is_empty());
+            assert_eq!(vec!["g"], describe(&chords.feed(key('d'), now, &vec![])));
+            assert_eq!("d", chords.pending_text());
+            assert_eq!(
+                vec!["delete"],
+                describe(&chords.feed(key('d'), now, &vec![]))
+            );
+        }
+
+        #[test]
+        fn test_timeout_flushes_prefix() {
+            let mut chords = chords().timeout(Duration::from_millis(100));
+            let now = Instant::now();
+            assert!(chords.feed(key('g'), now, &vec![]).is_empty());
+            assert_eq!(Some(now + Duration::from_millis(100)), chords.deadline());
+            assert!(chords.expire(now + Duration::from_millis(50)).is_empty());
+            assert_eq!(
+                vec!["g", "g"],
+                describe(&chords.feed(key('g'), now + Duration::from_millis(200), &vec![]))
+                    .into_iter()
+                    .chain(describe(&chords.expire(now + Duration::from_millis(400))))
+                    .collect::<Vec<_>>()
+            );
+        }
+
+        #[test]
+        fn test_condition() {
+            let mut chords = chords().bind_when(
+                ['g'.into(), 'g'.into()],
+                |calls: &Vec<&'static str>| calls.is_empty(),
+                |calls: &mut Vec<&'static str>| {
+                    calls.push("head");
+                    Ok(PromptSignal::Continue)
+                },
+            );
+            let now = Instant::now();
+            // The keys pass through while the condition does not hold.
+            let busy = vec!["busy"];
+            assert_eq!(vec!["g"], describe(&chords.feed(key('g'), now, &busy)));
+            assert!(chords.feed(key('g'), now, &vec![]).is_empty());
+            assert_eq!(vec!["head"], describe(&chords.feed(key('g'), now, &vec![])));
+        }
+
+        #[test]
+        fn test_without_bindings() {
+            let mut chords = Chords::<Vec<&'static str>>::default();
+            assert_eq!(
+                vec!["g"],
+                describe(&chords.feed(key('g'), Instant::now(), &vec![]))
+            );
+        }
+    }
+
+    mod fmt {
+        use super::*;
+
+        #[test]
+        fn test() {
+            assert_eq!(
+                "C-x",
+                KeyCombo::new(KeyCode::Char('x'), KeyModifiers::CONTROL).to_string()
+            );
+            assert_eq!("g", KeyCombo::from('g').to_string());
+            assert_eq!("Enter", KeyCombo::from(KeyCode::Enter).to_string());
+        }
+    }
+}
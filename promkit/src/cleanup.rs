@@ -0,0 +1,246 @@
+//! Best-effort restoration of the terminal when the process is terminated
+//! while a prompt is running (e.g. by SIGTERM), where `Drop` never runs.
+//!
+//! `arm` records the terminal state before `Prompt::run` changes it,
+//! and `disarm` is called once `Drop` has restored it. A signal (or console control)
+//! handler, or the panic hook, restores the recorded state only while armed,
+//! so restoring is never done twice.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether the terminal is in the state set up by a running prompt.
+static ARMED: AtomicBool = AtomicBool::new(false);
+/// Whether the running prompt is on the alternate screen.
+static ALTERNATE_SCREEN: AtomicBool = AtomicBool::new(false);
+
+/// The sequences to show the cursor, reset its shape and blinking to the user's default,
+/// and disable the mouse capture and the bracketed paste.
+const RESET_SEQUENCE: &[u8] =
+    b"\x1b[?25h\x1b[0 q\x1b[?1006l\x1b[?1015l\x1b[?1003l\x1b[?1002l\x1b[?1000l\x1b[?2004l";
+const LEAVE_ALTERNATE_SCREEN: &[u8] = b"\x1b[?1049l";
+
+/// Records the current terminal state to be restored on termination.
+/// Must be called before entering the raw mode.
+pub(crate) fn arm(alternate_screen: bool) {
+    ALTERNATE_SCREEN.store(alternate_screen, Ordering::SeqCst);
+    platform::install();
+    ARMED.store(true, Ordering::SeqCst);
+}
+
+/// Updates whether the terminal is on the alternate screen while armed,
+/// e.g. as the prompts of a `Session` enter and leave it.
+pub(crate) fn set_alternate_screen(alternate_screen: bool) {
+    ALTERNATE_SCREEN.store(alternate_screen, Ordering::SeqCst);
+}
+
+/// Marks the terminal as restored, so that the handlers do nothing.
+/// Returns whether it was armed.
+pub(crate) fn disarm() -> bool {
+    ARMED.swap(false, Ordering::SeqCst)
+}
+
+/// Restores the recorded state if armed, e.g. from the panic hook.
+pub(crate) fn restore() {
+    if disarm() {
+        platform::restore();
+    }
+}
+
+#[cfg(unix)]
+mod platform {
+    use std::{mem::MaybeUninit, sync::atomic::Ordering};
+
+    use super::{ALTERNATE_SCREEN, ARMED, LEAVE_ALTERNATE_SCREEN, RESET_SEQUENCE};
+
+    const SIGNALS: [libc::c_int; 4] = [libc::SIGTERM, libc::SIGINT, libc::SIGHUP, libc::SIGQUIT];
+
+    /// The terminal attributes before the raw mode, and the handlers replaced by ours.
+    /// They are written only while not armed, i.e. before the handlers may read them.
+    static mut ORIGINAL_TERMIOS: Option<libc::termios> = None;
+    static mut PREVIOUS_ACTIONS: [Option<libc::sigaction>; 4] = [None; 4];
+
+    /// Returns the terminal crossterm enters the raw mode on:
+    /// stdin if it is a tty, otherwise `/dev/tty`. The latter must be closed by the caller.
+    fn tty_fd() -> Option<(libc::c_int, bool)> {
+        unsafe {
+            if libc::isatty(libc::STDIN_FILENO) == 1 {
+                return Some((libc::STDIN_FILENO, false));
+            }
+            let fd = libc::open(c"/dev/tty".as_ptr(), libc::O_RDWR);
+            (fd >= 0).then_some((fd, true))
+        }
+    }
+
+    pub(super) fn install() {
+        unsafe {
+            if let Some((fd, owned)) = tty_fd() {
+                let mut termios = MaybeUninit::<libc::termios>::uninit();
+                if libc::tcgetattr(fd, termios.as_mut_ptr()) == 0 {
+                    *std::ptr::addr_of_mut!(ORIGINAL_TERMIOS) = Some(termios.assume_init());
+                }
+                if owned {
+                    libc::close(fd);
+                }
+            }
+        }
+
+        // The handler puts back the previous one once it fires,
+        // so it is installed again on every arm unless it is still in place.
+        unsafe {
+            for (i, signal) in SIGNALS.iter().enumerate() {
+                if current_handler(*signal) == Some(handler()) {
+                    continue;
+                }
+                let mut action: libc::sigaction = std::mem::zeroed();
+                action.sa_sigaction = handler();
+                libc::sigemptyset(&mut action.sa_mask);
+                let mut previous = MaybeUninit::<libc::sigaction>::uninit();
+                if libc::sigaction(*signal, &action, previous.as_mut_ptr()) == 0 {
+                    (*std::ptr::addr_of_mut!(PREVIOUS_ACTIONS))[i] = Some(previous.assume_init());
+                }
+            }
+        }
+    }
+
+    fn handler() -> libc::sighandler_t {
+        handle as *const () as libc::sighandler_t
+    }
+
+    /// Returns the handler currently installed for the signal.
+    fn current_handler(signal: libc::c_int) -> Option<libc::sighandler_t> {
+        unsafe {
+            let mut current = MaybeUninit::<libc::sigaction>::uninit();
+            (libc::sigaction(signal, std::ptr::null(), current.as_mut_ptr()) == 0)
+                .then(|| current.assume_init().sa_sigaction)
+        }
+    }
+
+    /// Restores the recorded state. Only async-signal-safe functions are called.
+    pub(super) fn restore() {
+        unsafe {
+            if let Some((fd, owned)) = tty_fd() {
+                if ALTERNATE_SCREEN.load(Ordering::SeqCst) {
+                    write_all(fd, LEAVE_ALTERNATE_SCREEN);
+                }
+                write_all(fd, RESET_SEQUENCE);
+                if let Some(termios) = *std::ptr::addr_of!(ORIGINAL_TERMIOS) {
+                    libc::tcsetattr(fd, libc::TCSANOW, &termios);
+                }
+                if owned {
+                    libc::close(fd);
+                }
+            }
+        }
+    }
+
+    /// Restores the terminal if armed, then re-raises the signal with the previous handler.
+    extern "C" fn handle(signal: libc::c_int) {
+        unsafe {
+            if ARMED.swap(false, Ordering::SeqCst) {
+                restore();
+            }
+
+            let index = SIGNALS.iter().position(|s| *s == signal);
+            match index.and_then(|i| (*std::ptr::addr_of!(PREVIOUS_ACTIONS))[i]) {
+                Some(previous) => libc::sigaction(signal, &previous, std::ptr::null_mut()),
+                None => {
+                    let mut action: libc::sigaction = std::mem::zeroed();
+                    action.sa_sigaction = libc::SIG_DFL;
+                    libc::sigaction(signal, &action, std::ptr::null_mut())
+                }
+            };
+            libc::raise(signal);
+        }
+    }
+
+    unsafe fn write_all(fd: libc::c_int, mut bytes: &[u8]) {
+        while !bytes.is_empty() {
+            let written = libc::write(fd, bytes.as_ptr() as *const libc::c_void, bytes.len());
+            if written <= 0 {
+                return;
+            }
+            bytes = &bytes[written as usize..];
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        mod install {
+            use super::*;
+            use crate::cleanup::{arm, disarm};
+
+            #[test]
+            fn test_reinstalls_after_firing() {
+                unsafe {
+                    libc::signal(libc::SIGHUP, libc::SIG_IGN);
+
+                    arm(false);
+                    assert_eq!(Some(handler()), current_handler(libc::SIGHUP));
+
+                    // Fires once, putting back the ignoring handler.
+                    libc::raise(libc::SIGHUP);
+                    assert!(!ARMED.load(Ordering::SeqCst));
+                    assert_eq!(Some(libc::SIG_IGN), current_handler(libc::SIGHUP));
+
+                    arm(false);
+                    assert_eq!(Some(handler()), current_handler(libc::SIGHUP));
+                    disarm();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use std::{
+        io::Write,
+        sync::{atomic::Ordering, Once},
+    };
+
+    use super::{ALTERNATE_SCREEN, ARMED, LEAVE_ALTERNATE_SCREEN, RESET_SEQUENCE};
+
+    type HandlerRoutine = unsafe extern "system" fn(u32) -> i32;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn SetConsoleCtrlHandler(handler: Option<HandlerRoutine>, add: i32) -> i32;
+    }
+
+    static INSTALL: Once = Once::new();
+
+    pub(super) fn install() {
+        INSTALL.call_once(|| unsafe {
+            SetConsoleCtrlHandler(Some(handle), 1);
+        });
+    }
+
+    /// Restores the recorded state.
+    pub(super) fn restore() {
+        let mut stdout = std::io::stdout();
+        if ALTERNATE_SCREEN.load(Ordering::SeqCst) {
+            stdout.write_all(LEAVE_ALTERNATE_SCREEN).ok();
+        }
+        stdout.write_all(RESET_SEQUENCE).ok();
+        stdout.flush().ok();
+        crossterm::terminal::disable_raw_mode().ok();
+    }
+
+    /// Restores the terminal if armed, and lets the next handler terminate the process.
+    /// Runs on a separate thread, so the ordinary restoration can be used.
+    unsafe extern "system" fn handle(_ctrl_type: u32) -> i32 {
+        if ARMED.swap(false, Ordering::SeqCst) {
+            restore();
+        }
+        0
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+mod platform {
+    pub(super) fn install() {}
+
+    pub(super) fn restore() {}
+}
@@ -0,0 +1,56 @@
+//! Copying to the system clipboard with the OSC 52 escape sequence,
+//! which the terminal emulator handles itself, so it works over SSH as well.
+//! Note that some terminals (or multiplexers like tmux) need to be configured to allow it.
+
+const BASE64_TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Returns the OSC 52 sequence setting the clipboard to the text.
+pub fn osc52_sequence(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", base64(text.as_bytes()))
+}
+
+fn base64(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, byte)| n | ((*byte as u32) << (16 - 8 * i)));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(BASE64_TABLE[((n >> (18 - 6 * i)) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod base64 {
+        use super::*;
+
+        #[test]
+        fn test() {
+            assert_eq!("", base64(b""));
+            assert_eq!("Zg==", base64(b"f"));
+            assert_eq!("Zm8=", base64(b"fo"));
+            assert_eq!("Zm9v", base64(b"foo"));
+            assert_eq!("Zm9vYmFy", base64(b"foobar"));
+            assert_eq!("5pel5pys", base64("日本".as_bytes()));
+        }
+    }
+
+    mod osc52_sequence {
+        use super::*;
+
+        #[test]
+        fn test() {
+            assert_eq!("\x1b]52;c;LmE=\x07", osc52_sequence(".a"));
+        }
+    }
+}
@@ -0,0 +1,56 @@
+//! The source of the current time for the timers of the renderers,
+//! which tests replace with a manual clock to avoid waiting.
+
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// Tells the current time: the system time by default,
+/// or a manual time only moved by `advance`, shared among the clones.
+#[derive(Clone, Debug, Default)]
+pub struct Clock {
+    manual: Option<Arc<Mutex<Instant>>>,
+}
+
+impl Clock {
+    /// Creates a clock standing still at the current time until `advance` is called.
+    pub fn manual() -> Self {
+        Self {
+            manual: Some(Arc::new(Mutex::new(Instant::now()))),
+        }
+    }
+
+    /// Returns the current time of the clock.
+    pub fn now(&self) -> Instant {
+        match &self.manual {
+            Some(now) => *now.lock().unwrap(),
+            None => Instant::now(),
+        }
+    }
+
+    /// Moves a manual clock forward by the duration. It does nothing to the system clock.
+    pub fn advance(&self, duration: Duration) {
+        if let Some(now) = &self.manual {
+            *now.lock().unwrap() += duration;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod advance {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let clock = Clock::manual();
+            let start = clock.now();
+            let shared = clock.clone();
+            shared.advance(Duration::from_secs(3));
+            assert_eq!(start + Duration::from_secs(3), clock.now());
+        }
+    }
+}
@@ -1,15 +1,39 @@
 pub mod checkbox;
 mod cursor;
 pub use cursor::Cursor;
+pub mod indented_rows;
 pub mod jsonstream;
 pub mod listbox;
+pub mod progress;
 pub mod snapshot;
+pub mod spinner;
 pub mod text;
 pub mod text_editor;
 pub mod tree;
+pub mod viewport;
 
 use crate::Pane;
 
+/// Determines how items that exceed the pane width are rendered.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Overflow {
+    /// Wraps the item onto multiple rows.
+    #[default]
+    Wrap,
+    /// Clips the item to a single row with a trailing `…`.
+    TruncateWithEllipsis,
+}
+
+/// Determines how an item not fitting at the bottom of the viewport is rendered.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum PartialItem {
+    /// Shows the rows of the item that fit.
+    #[default]
+    Clip,
+    /// Omits the item, so that only whole items are shown.
+    Skip,
+}
+
 pub trait PaneFactory {
     /// Creates pane with the given width.
     fn create_pane(&self, width: u16, height: u16) -> Pane;
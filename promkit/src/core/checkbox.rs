@@ -1,4 +1,6 @@
-use std::{collections::HashSet, fmt};
+use std::{cmp::Ordering, collections::HashSet, fmt, ops::RangeInclusive};
+
+use indexmap::IndexSet;
 
 use crate::{core::listbox::Listbox, grapheme::StyledGraphemes};
 
@@ -6,30 +8,45 @@ mod state;
 pub use state::State;
 
 /// A `Checkbox` struct that encapsulates a listbox
-/// for item selection and a set of picked (selected) indices.
+/// for item selection and a set of picked (selected) items.
 /// It allows for multiple selections,
 /// toggling the selection state of items,
 /// and navigating through the items.
+///
+/// Each item is given a stable identity (its index at construction),
+/// and the picked state is tracked by the identities,
+/// so that items with the same label are never confused
+/// even after they are reordered or filtered.
 #[derive(Clone)]
 pub struct Checkbox {
     listbox: Listbox,
-    picked: HashSet<usize>,
+    /// Identities of the items, in the same order as the items in the listbox.
+    ids: Vec<usize>,
+    /// Identities of the picked items, in the order they were picked.
+    picked: IndexSet<usize>,
+    /// Identity of the item toggled last, the anchor of `toggle_range`.
+    last_toggled: Option<usize>,
 }
 
 impl Checkbox {
     /// Creates a new `Checkbox` from a vector of `fmt::Display`.
     pub fn from_displayable<E: fmt::Display, I: IntoIterator<Item = E>>(items: I) -> Self {
+        let listbox = Listbox::from_displayable(items);
         Self {
-            listbox: Listbox::from_displayable(items),
-            picked: HashSet::new(),
+            ids: (0..listbox.len()).collect(),
+            listbox,
+            picked: IndexSet::new(),
+            last_toggled: None,
         }
     }
 
     /// Creates a new `Checkbox` from a vector of `StyledGraphemes`.
     pub fn from_styled_graphemes(items: Vec<StyledGraphemes>) -> Self {
         Self {
+            ids: (0..items.len()).collect(),
             listbox: Listbox::from_styled_graphemes(items),
-            picked: HashSet::new(),
+            picked: IndexSet::new(),
+            last_toggled: None,
         }
     }
 
@@ -61,11 +78,13 @@ impl Checkbox {
                     }
                 },
             )
-            .collect::<HashSet<usize>>();
+            .collect::<IndexSet<usize>>();
 
         Self {
+            ids: (0..listbox_items.len()).collect(),
             listbox: Listbox::from_displayable(listbox_items),
             picked: picked_indices,
+            last_toggled: None,
         }
     }
 
@@ -79,28 +98,205 @@ impl Checkbox {
         self.listbox.position()
     }
 
-    /// Returns a reference to the set of picked (selected) indices.
-    pub fn picked_indexes(&self) -> &HashSet<usize> {
+    /// Returns a reference to the set of picked (selected) items,
+    /// identified by their indices at construction, in the order they were picked.
+    pub fn picked_indexes(&self) -> &IndexSet<usize> {
         &self.picked
     }
 
-    /// Retrieves the items at the picked (selected) indices as a vector of strings.
+    /// Returns whether the item at the given position is picked.
+    pub fn is_picked(&self, position: usize) -> bool {
+        self.ids
+            .get(position)
+            .is_some_and(|id| self.picked.contains(id))
+    }
+
+    /// Returns the identities of the picked (selected) items in the displayed order.
+    pub fn picked_ids(&self) -> Vec<usize> {
+        self.ids
+            .iter()
+            .filter(|id| self.picked.contains(*id))
+            .copied()
+            .collect()
+    }
+
+    /// Retrieves the picked (selected) items in the displayed order.
     pub fn get(&self) -> Vec<StyledGraphemes> {
-        self.picked
+        self.listbox
+            .items()
             .iter()
-            .fold(Vec::<StyledGraphemes>::new(), |mut ret, idx| {
-                ret.push(self.listbox.items().get(*idx).unwrap().to_owned());
-                ret
-            })
+            .enumerate()
+            .filter(|(position, _)| self.is_picked(*position))
+            .map(|(_, item)| item.to_owned())
+            .collect()
+    }
+
+    /// Picks the items with the given identities (i.e. the indices at construction),
+    /// e.g. the current configuration being edited, in the given order.
+    /// Identities out of range are ignored, with a debug assertion.
+    pub fn preselect<I: IntoIterator<Item = usize>>(&mut self, ids: I) {
+        for id in ids {
+            debug_assert!(self.ids.contains(&id), "no item with the identity {id}");
+            if self.ids.contains(&id) {
+                self.picked.insert(id);
+            }
+        }
+    }
+
+    /// Picks the items with the given labels, in the given order.
+    /// A label listed multiple times picks as many of the items with it, from the head,
+    /// and the labels not listed are ignored.
+    pub fn preselect_labels<S: AsRef<str>, I: IntoIterator<Item = S>>(&mut self, labels: I) {
+        let items: Vec<String> = self.items().iter().map(|item| item.to_string()).collect();
+        for label in labels {
+            if let Some(id) = items
+                .iter()
+                .zip(&self.ids)
+                .find(|(item, id)| *item == label.as_ref() && !self.picked.contains(*id))
+                .map(|(_, id)| *id)
+            {
+                self.picked.insert(id);
+            }
+        }
     }
 
     /// Toggles the selection state of the item at the current cursor position within the listbox.
     pub fn toggle(&mut self) {
-        if self.picked.contains(&self.listbox.position()) {
-            self.picked.remove(&self.listbox.position());
+        if let Some(id) = self.ids.get(self.listbox.position()) {
+            if !self.picked.shift_remove(id) {
+                self.picked.insert(*id);
+            }
+            self.last_toggled = Some(*id);
+        }
+    }
+
+    /// Returns the position of the item toggled last, if it is still listed.
+    pub fn last_toggled(&self) -> Option<usize> {
+        self.last_toggled
+            .and_then(|last| self.ids.iter().position(|id| *id == last))
+    }
+
+    /// Picks all items.
+    pub fn check_all(&mut self) {
+        self.picked.extend(self.ids.iter().copied());
+    }
+
+    /// Unpicks all items.
+    pub fn uncheck_all(&mut self) {
+        self.picked.clear();
+    }
+
+    /// Picks all items if any of them is unpicked, otherwise unpicks all items.
+    pub fn toggle_all(&mut self) {
+        if (0..self.ids.len()).all(|position| self.is_picked(position)) {
+            self.uncheck_all();
+        } else {
+            self.check_all();
+        }
+    }
+
+    /// Toggles the items at the positions in the range as a whole:
+    /// picks them all if any of them is unpicked, otherwise unpicks them all.
+    /// The range may be given in either order, and is clamped to the items.
+    pub fn toggle_range(&mut self, range: RangeInclusive<usize>) {
+        let (start, end) = (
+            *range.start().min(range.end()),
+            *range.start().max(range.end()),
+        );
+        let Some(ids) = self
+            .ids
+            .get(start..=end.min(self.ids.len().saturating_sub(1)))
+        else {
+            return;
+        };
+        if ids.iter().all(|id| self.picked.contains(id)) {
+            ids.iter().for_each(|id| {
+                self.picked.shift_remove(id);
+            });
         } else {
-            self.picked.insert(self.listbox.position());
+            self.picked.extend(ids.iter().copied());
+        }
+        self.last_toggled = self.ids.get(self.listbox.position()).copied();
+    }
+
+    /// Toggles the items between the item toggled last and the cursor,
+    /// or only the item at the cursor if none has been toggled yet.
+    pub fn toggle_range_from_last(&mut self) {
+        let position = self.listbox.position();
+        match self.last_toggled() {
+            Some(last) => self.toggle_range(last..=position),
+            None => self.toggle(),
+        }
+    }
+
+    /// Inverts the picked state of every item.
+    pub fn invert(&mut self) {
+        self.picked = self
+            .ids
+            .iter()
+            .filter(|id| !self.picked.contains(*id))
+            .copied()
+            .collect();
+    }
+
+    /// Returns the labels appearing more than once, in the order of their first occurrence.
+    pub fn duplicates(&self) -> Vec<String> {
+        let mut seen = HashSet::new();
+        let mut duplicates = Vec::new();
+        for label in self.listbox.items().iter().map(|item| item.to_string()) {
+            if !seen.insert(label.clone()) && !duplicates.contains(&label) {
+                duplicates.push(label);
+            }
         }
+        duplicates
+    }
+
+    /// Removes the items whose labels have already appeared,
+    /// preserving the first occurrence (and its selection state).
+    pub fn dedup(&mut self) {
+        let mut seen = HashSet::new();
+        self.retain(|item| seen.insert(item.to_string()));
+    }
+
+    /// Keeps only the items for which the predicate returns `true`.
+    /// The cursor stays on the same item if it is kept, otherwise it moves to the head.
+    pub fn retain<F: FnMut(&StyledGraphemes) -> bool>(&mut self, mut f: F) {
+        let items = self.listbox.items();
+        let order: Vec<usize> = (0..items.len()).filter(|i| f(&items[*i])).collect();
+        self.rebuild(order);
+        self.picked.retain(|id| self.ids.contains(id));
+    }
+
+    /// Sorts the items with the comparator, keeping the cursor on the same item.
+    pub fn sort_by<F: FnMut(&StyledGraphemes, &StyledGraphemes) -> Ordering>(&mut self, mut f: F) {
+        let items = self.listbox.items();
+        let mut order: Vec<usize> = (0..items.len()).collect();
+        order.sort_by(|a, b| f(&items[*a], &items[*b]));
+        self.rebuild(order);
+    }
+
+    /// Rearranges the items (with their descriptions and identities)
+    /// into the given order of their current positions, dropping the others.
+    fn rebuild(&mut self, order: Vec<usize>) {
+        let items = order
+            .iter()
+            .map(|i| self.listbox.items()[*i].clone())
+            .collect();
+        let descriptions = order
+            .iter()
+            .map(|i| self.listbox.description(*i).map(String::from))
+            .collect();
+        let ids: Vec<usize> = order.iter().map(|i| self.ids[*i]).collect();
+        let current = self.ids.get(self.listbox.position()).copied();
+        let cyclic = self.listbox.is_cyclic();
+        let interactive = self.listbox.is_interactive();
+        self.listbox = Listbox::from_styled_graphemes_with_descriptions(items, descriptions);
+        self.listbox.set_cyclic(cyclic);
+        if let Some(position) = current.and_then(|id| ids.iter().position(|i| *i == id)) {
+            self.listbox.move_to(position);
+        }
+        self.listbox.set_interactive(interactive);
+        self.ids = ids;
     }
 
     /// Moves the cursor backward in the listbox, if possible.
@@ -124,6 +320,118 @@ impl Checkbox {
     pub fn move_to_tail(&mut self) {
         self.listbox.move_to_tail()
     }
+
+    /// Moves the cursor backward by a page of the given number of items,
+    /// stopping at the head. Returns `true` if the cursor moved.
+    pub fn page_backward(&mut self, page: usize) -> bool {
+        self.listbox.page_backward(page)
+    }
+
+    /// Moves the cursor forward by a page of the given number of items,
+    /// stopping at the tail. Returns `true` if the cursor moved.
+    pub fn page_forward(&mut self, page: usize) -> bool {
+        self.listbox.page_forward(page)
+    }
+
+    /// Returns whether `forward` from the tail goes to the head and vice versa.
+    pub fn is_cyclic(&self) -> bool {
+        self.listbox.is_cyclic()
+    }
+
+    /// Sets whether `forward` from the tail goes to the head and vice versa.
+    /// It is off by default.
+    pub fn set_cyclic(&mut self, cyclic: bool) {
+        self.listbox.set_cyclic(cyclic)
+    }
+
+    /// Returns whether the cursor can be moved and is rendered.
+    pub fn is_interactive(&self) -> bool {
+        self.listbox.is_interactive()
+    }
+
+    /// Sets whether the cursor can be moved and is rendered. It is on by default.
+    /// Making it display-only moves the cursor back to the head,
+    /// and the moves become no-ops. The picked items are still rendered.
+    pub fn set_interactive(&mut self, interactive: bool) {
+        self.listbox.set_interactive(interactive)
+    }
+
+    /// Exports the picked items and the cursor, to be restored by `import_state`,
+    /// e.g. in the next session. The items are identified by their labels,
+    /// so that the state applies to a changed list of items.
+    /// The scroll offset needs no saving, since the list is shown from the cursor.
+    pub fn export_state(&self) -> serde_json::Value {
+        serde_json::json!({
+            "picked": self.get().iter().map(|item| item.to_string()).collect::<Vec<_>>(),
+            "cursor": self.listbox.get().to_string(),
+            "position": self.position(),
+        })
+    }
+
+    /// Restores the picked items and the cursor exported by `export_state`,
+    /// replacing the current picks.
+    ///
+    /// Labels no longer listed are ignored, and a label listed multiple times
+    /// picks as many of the items with it, from the head.
+    /// The cursor moves to the item with its label nearest to the exported position,
+    /// or to the exported position if the label is no longer listed.
+    /// Returns an error if the state is malformed.
+    pub fn import_state(&mut self, state: &serde_json::Value) -> anyhow::Result<()> {
+        let picked = match state.get("picked") {
+            Some(picked) => picked
+                .as_array()
+                .ok_or_else(|| anyhow::anyhow!("\"picked\" must be an array"))?
+                .iter()
+                .map(|label| {
+                    label
+                        .as_str()
+                        .ok_or_else(|| anyhow::anyhow!("\"picked\" must contain strings"))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?,
+            None => Vec::new(),
+        };
+        let cursor = match state.get("cursor") {
+            Some(cursor) => Some(
+                cursor
+                    .as_str()
+                    .ok_or_else(|| anyhow::anyhow!("\"cursor\" must be a string"))?,
+            ),
+            None => None,
+        };
+        let position = match state.get("position") {
+            Some(position) => position
+                .as_u64()
+                .ok_or_else(|| anyhow::anyhow!("\"position\" must be an unsigned integer"))?
+                as usize,
+            None => 0,
+        };
+
+        let labels: Vec<String> = self.items().iter().map(|item| item.to_string()).collect();
+        self.picked.clear();
+        for label in picked {
+            if let Some(id) = labels
+                .iter()
+                .zip(&self.ids)
+                .find(|(l, id)| *l == label && !self.picked.contains(*id))
+                .map(|(_, id)| *id)
+            {
+                self.picked.insert(id);
+            }
+        }
+
+        let target = cursor
+            .and_then(|cursor| {
+                labels
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, label)| *label == cursor)
+                    .map(|(i, _)| i)
+                    .min_by_key(|i| i.abs_diff(position))
+            })
+            .unwrap_or(position.min(labels.len().saturating_sub(1)));
+        self.listbox.move_to(target);
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -156,8 +464,268 @@ mod tests {
             );
 
             // Verify the picked (selected) indices
-            let expected_picked_indexes: HashSet<usize> = [0, 2].iter().cloned().collect();
+            let expected_picked_indexes: IndexSet<usize> = [0, 2].iter().cloned().collect();
             assert_eq!(checkbox.picked_indexes(), &expected_picked_indexes);
         }
     }
+
+    mod duplicates {
+        use super::*;
+
+        fn label(checkbox: &Checkbox) -> String {
+            checkbox.listbox.get().to_string()
+        }
+
+        #[test]
+        fn test_toggle_one_of_identical_labels() {
+            let mut checkbox = Checkbox::from_displayable(["b", "a", "c", "a"]);
+            assert_eq!(checkbox.duplicates(), vec![String::from("a")]);
+
+            // Toggle the second "a".
+            checkbox.move_to_tail();
+            checkbox.toggle();
+            assert!(!checkbox.is_picked(1));
+            assert!(checkbox.is_picked(3));
+
+            // Only that row stays picked after sorting.
+            checkbox.sort_by(|a, b| a.to_string().cmp(&b.to_string()));
+            assert_eq!(
+                checkbox.items(),
+                &vec![
+                    StyledGraphemes::from("a"),
+                    StyledGraphemes::from("a"),
+                    StyledGraphemes::from("b"),
+                    StyledGraphemes::from("c"),
+                ]
+            );
+            assert_eq!(checkbox.position(), 1);
+            assert!(!checkbox.is_picked(0));
+            assert!(checkbox.is_picked(1));
+
+            // And after filtering out the other rows.
+            checkbox.retain(|item| item.to_string() != "b");
+            assert!(!checkbox.is_picked(0));
+            assert!(checkbox.is_picked(1));
+            assert_eq!(label(&checkbox), "a");
+            assert_eq!(checkbox.get(), vec![StyledGraphemes::from("a")]);
+
+            // Untoggling affects only that row too.
+            checkbox.toggle();
+            assert!(checkbox.picked_indexes().is_empty());
+        }
+
+        #[test]
+        fn test_descriptions_follow_items() {
+            let mut checkbox = Checkbox::from_displayable(["c", "a", "b"]);
+            checkbox.listbox = Listbox::from_displayable_with_descriptions([
+                ("c", Some("third")),
+                ("a", None),
+                ("b", Some("second")),
+            ]);
+            checkbox.sort_by(|a, b| a.to_string().cmp(&b.to_string()));
+            assert_eq!(None, checkbox.listbox.description(0));
+            assert_eq!(Some("second"), checkbox.listbox.description(1));
+            assert_eq!(Some("third"), checkbox.listbox.description(2));
+
+            checkbox.retain(|item| item.to_string() != "b");
+            assert_eq!(None, checkbox.listbox.description(0));
+            assert_eq!(Some("third"), checkbox.listbox.description(1));
+        }
+
+        #[test]
+        fn test_toggle_after_sort() {
+            let mut checkbox = Checkbox::from_displayable(["c", "a", "b"]);
+            checkbox.toggle();
+            checkbox.sort_by(|a, b| a.to_string().cmp(&b.to_string()));
+            assert_eq!(checkbox.last_toggled(), Some(2));
+        }
+
+        #[test]
+        fn test_dedup() {
+            let mut checkbox =
+                Checkbox::new_with_checked([("a", true), ("b", false), ("a", false), ("b", true)]);
+            checkbox.dedup();
+            assert_eq!(
+                checkbox.items(),
+                &vec![StyledGraphemes::from("a"), StyledGraphemes::from("b")]
+            );
+            assert!(checkbox.is_picked(0));
+            assert!(!checkbox.is_picked(1));
+            assert!(checkbox.duplicates().is_empty());
+        }
+    }
+
+    fn picked(checkbox: &Checkbox) -> Vec<usize> {
+        let mut ret: Vec<_> = checkbox.picked_indexes().iter().copied().collect();
+        ret.sort();
+        ret
+    }
+
+    mod import_state {
+        use super::*;
+
+        #[test]
+        fn test_round_trip() {
+            let mut checkbox = Checkbox::from_displayable(["a", "b", "c", "d"]);
+            checkbox.toggle();
+            checkbox.forward();
+            checkbox.forward();
+            checkbox.toggle();
+            let state = checkbox.export_state();
+            assert_eq!(
+                serde_json::json!({"picked": ["a", "c"], "cursor": "c", "position": 2}),
+                state
+            );
+
+            let mut restored = Checkbox::from_displayable(["a", "b", "c", "d"]);
+            restored.import_state(&state).unwrap();
+            assert_eq!(picked(&restored), vec![0, 2]);
+            assert_eq!(2, restored.position());
+        }
+
+        #[test]
+        fn test_changed_items() {
+            let state =
+                serde_json::json!({"picked": ["a", "c", "x"], "cursor": "c", "position": 2});
+            // "a" is removed, "x" never existed, and "c" is moved.
+            let mut checkbox =
+                Checkbox::new_with_checked([("c", false), ("b", true), ("d", false)]);
+            checkbox.import_state(&state).unwrap();
+            assert_eq!(picked(&checkbox), vec![0]);
+            assert_eq!(0, checkbox.position());
+
+            // The removed cursor item falls back to the position.
+            let mut checkbox = Checkbox::from_displayable(["a", "b"]);
+            checkbox.import_state(&state).unwrap();
+            assert_eq!(picked(&checkbox), vec![0]);
+            assert_eq!(1, checkbox.position());
+        }
+
+        #[test]
+        fn test_duplicates() {
+            let state = serde_json::json!({"picked": ["a", "a"], "cursor": "a", "position": 3});
+            let mut checkbox = Checkbox::from_displayable(["a", "b", "a", "a"]);
+            checkbox.import_state(&state).unwrap();
+            assert_eq!(picked(&checkbox), vec![0, 2]);
+            assert_eq!(3, checkbox.position());
+        }
+
+        #[test]
+        fn test_malformed() {
+            let mut checkbox = Checkbox::from_displayable(["a"]);
+            assert!(checkbox
+                .import_state(&serde_json::json!({"picked": "a"}))
+                .is_err());
+            assert!(checkbox
+                .import_state(&serde_json::json!({"position": -1}))
+                .is_err());
+            assert!(checkbox.import_state(&serde_json::json!({})).is_ok());
+        }
+    }
+
+    mod preselect {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let mut checkbox = Checkbox::from_displayable(["a", "b", "c"]);
+            checkbox.preselect([2, 0]);
+            assert_eq!(vec![2, 0], picked_in_order(&checkbox));
+            assert_eq!(vec![0, 2], checkbox.picked_ids());
+        }
+
+        #[test]
+        fn test_labels() {
+            let mut checkbox = Checkbox::from_displayable(["a", "b", "a"]);
+            checkbox.preselect_labels(["b", "a", "a", "a", "x"]);
+            assert_eq!(vec![1, 0, 2], picked_in_order(&checkbox));
+        }
+
+        #[test]
+        fn test_toggle_order() {
+            let mut checkbox = Checkbox::from_displayable(["a", "b", "c"]);
+            checkbox.preselect([1]);
+            checkbox.move_to_tail();
+            checkbox.toggle();
+            checkbox.move_to_head();
+            checkbox.toggle();
+            assert_eq!(vec![1, 2, 0], picked_in_order(&checkbox));
+
+            // Unpicking and picking again moves the item to the end.
+            checkbox.forward();
+            checkbox.toggle();
+            checkbox.toggle();
+            assert_eq!(vec![2, 0, 1], picked_in_order(&checkbox));
+        }
+
+        fn picked_in_order(checkbox: &Checkbox) -> Vec<usize> {
+            checkbox.picked_indexes().iter().copied().collect()
+        }
+    }
+
+    mod toggle_all {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let mut checkbox = Checkbox::new_with_checked([("a", true), ("b", false), ("c", true)]);
+            checkbox.toggle_all();
+            assert_eq!(picked(&checkbox), vec![0, 1, 2]);
+            checkbox.toggle_all();
+            assert!(picked(&checkbox).is_empty());
+        }
+    }
+
+    mod toggle_range {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let mut checkbox = Checkbox::from_displayable(["a", "b", "c", "d", "e"]);
+            checkbox.toggle_range(1..=3);
+            assert_eq!(picked(&checkbox), vec![1, 2, 3]);
+            checkbox.toggle_range(2..=10);
+            assert_eq!(picked(&checkbox), vec![1, 2, 3, 4]);
+            checkbox.toggle_range(1..=4);
+            assert!(picked(&checkbox).is_empty());
+            checkbox.toggle_range(5..=7);
+            assert!(picked(&checkbox).is_empty());
+        }
+
+        #[test]
+        fn test_from_last() {
+            let mut checkbox = Checkbox::from_displayable(["a", "b", "c", "d", "e"]);
+            checkbox.forward();
+            checkbox.toggle();
+            checkbox.forward();
+            checkbox.forward();
+            checkbox.toggle_range_from_last();
+            assert_eq!(picked(&checkbox), vec![1, 2, 3]);
+
+            // The cursor becomes the anchor of the next range.
+            checkbox.move_to_head();
+            checkbox.toggle_range_from_last();
+            assert_eq!(picked(&checkbox), vec![0, 1, 2, 3]);
+        }
+
+        #[test]
+        fn test_from_last_without_toggle() {
+            let mut checkbox = Checkbox::from_displayable(["a", "b"]);
+            checkbox.forward();
+            checkbox.toggle_range_from_last();
+            assert_eq!(picked(&checkbox), vec![1]);
+        }
+    }
+
+    mod invert {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let mut checkbox = Checkbox::new_with_checked([("a", true), ("b", false), ("c", true)]);
+            checkbox.retain(|item| item.to_string() != "c");
+            checkbox.invert();
+            assert_eq!(picked(&checkbox), vec![1]);
+        }
+    }
 }
@@ -1,4 +1,7 @@
-use crate::{crossterm::style::ContentStyle, grapheme::StyledGraphemes, pane::Pane, PaneFactory};
+use crate::{
+    crossterm::style::ContentStyle, grapheme::StyledGraphemes, pane::Pane, viewport, Overflow,
+    PaneFactory, PartialItem,
+};
 
 use super::Checkbox;
 
@@ -14,11 +17,14 @@ pub struct State {
     pub checkbox: Checkbox,
 
     /// Symbol for the selected line.
+    /// It is not rendered, nor is the active style, if the checkbox is not interactive.
     pub cursor: String,
 
     /// Symbol used to indicate an active (selected) checkbox item.
     pub active_mark: char,
     /// Symbol used to indicate an inactive (unselected) checkbox item.
+    /// If the marks have different display widths (e.g. `✅` and `-`),
+    /// the narrower one is padded to the width of the other.
     pub inactive_mark: char,
 
     /// Style for the selected line.
@@ -26,56 +32,211 @@ pub struct State {
     /// Style for unselected lines.
     pub inactive_item_style: ContentStyle,
 
+    /// How items exceeding the pane width are rendered.
+    pub overflow: Overflow,
+    /// Maximum number of rows of an item when wrapped, or `None` for no limit.
+    pub item_lines: Option<usize>,
+    /// How an item not fitting at the bottom of the pane is rendered.
+    pub partial_item: PartialItem,
+
     /// Number of lines available for rendering.
     pub lines: Option<usize>,
 }
 
+impl State {
+    fn height(&self, height: u16) -> usize {
+        match self.lines {
+            Some(lines) => lines.min(height as usize),
+            None => height as usize,
+        }
+    }
+
+    /// Returns how many items PageUp and PageDown move over
+    /// in the pane created for the height, i.e. the rows available for the list.
+    pub fn page_size(&self, height: u16) -> usize {
+        self.height(height).max(1)
+    }
+}
+
 impl PaneFactory for State {
     fn create_pane(&self, width: u16, height: u16) -> Pane {
+        // Pad the narrower mark so that items are aligned
+        // even if the marks have different display widths.
+        let mark_width = StyledGraphemes::from(self.active_mark.to_string())
+            .widths()
+            .max(StyledGraphemes::from(self.inactive_mark.to_string()).widths());
         let f = |idx: usize| -> StyledGraphemes {
-            if self.checkbox.picked_indexes().contains(&idx) {
-                StyledGraphemes::from(format!("{} ", self.active_mark))
+            let mark = if self.checkbox.is_picked(idx) {
+                StyledGraphemes::from(self.active_mark.to_string())
             } else {
-                StyledGraphemes::from(format!("{} ", self.inactive_mark))
-            }
+                StyledGraphemes::from(self.inactive_mark.to_string())
+            };
+            let padding = " ".repeat(mark_width - mark.widths() + 1);
+            StyledGraphemes::from_iter([mark, StyledGraphemes::from(padding)])
         };
 
-        let height = match self.lines {
-            Some(lines) => lines.min(height as usize),
-            None => height as usize,
-        };
+        let height = self.height(height);
 
-        let matrix = self
+        let items = self
             .checkbox
             .items()
             .iter()
             .enumerate()
-            .filter(|(i, _)| {
-                *i >= self.checkbox.position() && *i < self.checkbox.position() + height
-            })
+            // Only the visible items are styled, so that huge lists stay responsive.
+            .skip(self.checkbox.position())
+            .take(height)
             .map(|(i, item)| {
-                if i == self.checkbox.position() {
-                    StyledGraphemes::from_iter([&StyledGraphemes::from(&self.cursor), &f(i), item])
-                        .apply_style(self.active_item_style)
+                let (cursor, style) = if !self.checkbox.is_interactive() {
+                    (StyledGraphemes::default(), self.inactive_item_style)
+                } else if i == self.checkbox.position() {
+                    (StyledGraphemes::from(&self.cursor), self.active_item_style)
                 } else {
-                    StyledGraphemes::from_iter([
-                        &StyledGraphemes::from(
+                    (
+                        StyledGraphemes::from(
                             " ".repeat(StyledGraphemes::from(&self.cursor).widths()),
                         ),
-                        &f(i),
-                        item,
-                    ])
-                    .apply_style(self.inactive_item_style)
-                }
-            })
-            .fold((vec![], 0), |(mut acc, pos), item| {
-                let rows = item.matrixify(width as usize, height, 0).0;
-                if pos < self.checkbox.position() + height {
-                    acc.extend(rows);
-                }
-                (acc, pos + 1)
+                        self.inactive_item_style,
+                    )
+                };
+                viewport::item_rows(
+                    &StyledGraphemes::from_iter([cursor, f(i)]).apply_style(style),
+                    &item.clone().apply_style(style),
+                    width as usize,
+                    self.overflow,
+                    self.item_lines,
+                )
             });
+        let matrix = viewport::stack(items, height, self.partial_item);
+
+        Pane::new(matrix, 0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod create_pane {
+        use super::*;
 
-        Pane::new(matrix.0, 0)
+        #[test]
+        fn test_with_emoji_cursor() {
+            let state = State {
+                checkbox: Checkbox::from_displayable(["a", "b"]),
+                cursor: String::from("👉 "),
+                active_mark: '☒',
+                inactive_mark: '☐',
+                active_item_style: ContentStyle::default(),
+                inactive_item_style: ContentStyle::default(),
+                overflow: Overflow::Wrap,
+                item_lines: None,
+                partial_item: Default::default(),
+                lines: None,
+            };
+            let rows = state.create_pane(20, 10).extract(10);
+            assert_eq!("👉 ☐ a", rows[0].to_string());
+            assert_eq!("   ☐ b", rows[1].to_string());
+        }
+
+        #[test]
+        fn test_multi_row_items() {
+            let state = State {
+                checkbox: Checkbox::from_displayable(["fix parser\nhandle empty input", "b"]),
+                cursor: String::from("❯ "),
+                active_mark: '☒',
+                inactive_mark: '☐',
+                active_item_style: ContentStyle::default(),
+                inactive_item_style: ContentStyle::default(),
+                overflow: Overflow::Wrap,
+                item_lines: None,
+                partial_item: Default::default(),
+                lines: None,
+            };
+            let rows = state.create_pane(16, 10).extract(10);
+            assert_eq!(
+                vec!["❯ ☐ fix parser", "    handle empty", "     input", "  ☐ b"],
+                rows.iter().map(|row| row.to_string()).collect::<Vec<_>>()
+            );
+        }
+
+        #[test]
+        fn test_with_marks_of_different_widths() {
+            let mut state = State {
+                checkbox: Checkbox::from_displayable(["a", "b"]),
+                cursor: String::from("❯ "),
+                active_mark: '✅',
+                inactive_mark: '-',
+                active_item_style: ContentStyle::default(),
+                inactive_item_style: ContentStyle::default(),
+                overflow: Overflow::Wrap,
+                item_lines: None,
+                partial_item: Default::default(),
+                lines: None,
+            };
+            state.checkbox.toggle();
+            let rows = state.create_pane(20, 10).extract(10);
+            assert_eq!("❯ ✅ a", rows[0].to_string());
+            assert_eq!("  -  b", rows[1].to_string());
+        }
+
+        #[test]
+        fn test_not_interactive() {
+            let mut state = State {
+                checkbox: Checkbox::from_displayable(["a", "b"]),
+                cursor: String::from("❯ "),
+                active_mark: '☒',
+                inactive_mark: '☐',
+                active_item_style: ContentStyle::default(),
+                inactive_item_style: ContentStyle::default(),
+                overflow: Overflow::Wrap,
+                item_lines: None,
+                partial_item: Default::default(),
+                lines: None,
+            };
+            state.checkbox.toggle();
+            state.checkbox.set_interactive(false);
+            let rows = state.create_pane(20, 10).extract(10);
+            assert_eq!(
+                vec!["☒ a", "☐ b"],
+                rows.iter().map(|row| row.to_string()).collect::<Vec<_>>()
+            );
+        }
+
+        #[test]
+        fn test_with_emoji_marks_and_wide_chars() {
+            let mut state = State {
+                checkbox: Checkbox::from_displayable(["日本", "b", "中文字"]),
+                cursor: String::from("❯ "),
+                active_mark: '✅',
+                inactive_mark: '⬜',
+                active_item_style: ContentStyle::default(),
+                inactive_item_style: ContentStyle::default(),
+                overflow: Overflow::Wrap,
+                item_lines: None,
+                partial_item: Default::default(),
+                lines: None,
+            };
+            state.checkbox.toggle();
+            state.checkbox.forward();
+            state.checkbox.forward();
+            state.checkbox.toggle();
+            state.checkbox.backward();
+            state.checkbox.backward();
+            let rows = state.create_pane(20, 10).extract(10);
+            assert_eq!(
+                vec!["❯ ✅ 日本", "  ⬜ b", "  ✅ 中文字"],
+                rows.iter().map(|row| row.to_string()).collect::<Vec<_>>()
+            );
+            // The labels start at the same column on every row.
+            for row in &rows {
+                assert_eq!(
+                    5,
+                    row.iter()
+                        .take_while(|g| !matches!(g.as_str(), "日" | "b" | "中"))
+                        .map(|g| g.width())
+                        .sum::<usize>()
+                );
+            }
+        }
     }
 }
@@ -48,9 +48,19 @@ impl<C: Len> Cursor<C> {
         self.position
     }
 
+    /// Returns whether the cursor cycles through the collection.
+    pub fn is_cyclic(&self) -> bool {
+        self.cyclic
+    }
+
+    /// Sets whether the cursor cycles through the collection.
+    pub fn set_cyclic(&mut self, cyclic: bool) {
+        self.cyclic = cyclic;
+    }
+
     pub fn shift(&mut self, backward: usize, forward: usize) -> bool {
         let len = self.contents.len();
-        if self.cyclic {
+        if self.cyclic && len > 0 {
             let total_move = forward as isize - backward as isize;
             let new_position =
                 (self.position as isize + total_move).rem_euclid(len as isize) as usize;
@@ -0,0 +1,253 @@
+//! Rendering of indented rows with a cursor, shared by the tree and the JSON views.
+//!
+//! The views only turn their items into `Row`s; the indentation, the cursor highlighting,
+//! the search highlighting, the gutter and the overflow are applied here.
+
+use crate::{
+    crossterm::style::{Attribute, ContentStyle},
+    grapheme::StyledGraphemes,
+    pane::Pane,
+    Overflow,
+};
+
+/// A row of an indented view, e.g. a node of a tree or a line of a JSON document.
+#[derive(Clone, Debug, Default)]
+pub struct Row {
+    /// The nesting level, indented by `Layout::indent_unit` per level.
+    pub depth: usize,
+    /// Shown before the indentation, e.g. the fold marker of the node under the cursor.
+    pub marker: StyledGraphemes,
+    /// The content after the indentation.
+    pub body: StyledGraphemes,
+    /// The column of the body the wrapped rows are indented to, e.g. after the key of a JSON row.
+    /// If it leaves no room, they are indented to the depth of the row instead, or not at all.
+    /// If `None`, the wrapped rows start at the left edge.
+    pub hanging: Option<usize>,
+    /// Whether the row is under the cursor.
+    pub active: bool,
+    /// Whether the search query is highlighted in the row.
+    pub matched: bool,
+    /// The index of the row in the whole view, shown in the gutter.
+    pub number: usize,
+}
+
+/// How the row under the cursor is told apart from the others.
+#[derive(Clone, Copy, Debug)]
+pub enum Highlight {
+    /// Replaces the styles of the active and the inactive rows.
+    Style {
+        active: ContentStyle,
+        inactive: ContentStyle,
+    },
+    /// Adds the attributes to the styles of the active and the inactive rows,
+    /// keeping e.g. the colors of JSON values.
+    Attribute {
+        active: Attribute,
+        inactive: Attribute,
+    },
+}
+
+/// Lays `Row`s out into a pane.
+#[derive(Clone, Copy, Debug)]
+pub struct Layout<'a> {
+    /// The string repeated for each level of indentation, e.g. `"  "` or `"│ "`.
+    pub indent_unit: &'a str,
+    /// How the row under the cursor is highlighted.
+    pub highlight: Highlight,
+    /// How rows wider than the pane are rendered.
+    /// With `Overflow::Wrap`, a row is continued on the following rows.
+    pub overflow: Overflow,
+    /// The query highlighted in the matched rows, if not empty, and its style.
+    pub search: (&'a str, ContentStyle),
+    /// The style of the line numbers in the gutter, or `None` for no gutter.
+    pub line_numbers: Option<ContentStyle>,
+}
+
+impl Layout<'_> {
+    /// Creates a pane from the rows starting at the top of the viewport,
+    /// filling at most `height` rows. The rows are consumed lazily,
+    /// and each takes at least one row, so at most `height` rows are laid out.
+    pub fn create_pane<I: IntoIterator<Item = Row>>(
+        &self,
+        rows: I,
+        width: u16,
+        height: usize,
+    ) -> Pane {
+        let rows: Vec<Row> = rows.into_iter().take(height).collect();
+        let gutter_width = match self.line_numbers {
+            Some(_) => rows
+                .iter()
+                .map(|row| (row.number + 1).to_string().len() + 1)
+                .max()
+                .unwrap_or_default(),
+            None => 0,
+        };
+        let body_width = (width as usize).saturating_sub(gutter_width) as u16;
+
+        let mut matrix = Vec::with_capacity(height);
+        for row in rows {
+            if matrix.len() >= height {
+                break;
+            }
+            let number = row.number;
+            let lines = self.lay_out(row, body_width, height);
+            let room = height - matrix.len();
+            for (i, line) in lines.into_iter().take(room).enumerate() {
+                matrix.push(match self.line_numbers {
+                    Some(style) => {
+                        let label = match i {
+                            0 => format!("{:>1$} ", number + 1, gutter_width - 1),
+                            _ => " ".repeat(gutter_width),
+                        };
+                        StyledGraphemes::from_iter([StyledGraphemes::from_str(label, style), line])
+                    }
+                    None => line,
+                });
+            }
+        }
+        Pane::new(matrix, 0)
+    }
+
+    /// Lays a row out into the visual rows of `width`.
+    fn lay_out(&self, row: Row, width: u16, height: usize) -> Vec<StyledGraphemes> {
+        let indentation = StyledGraphemes::from(self.indent_unit.repeat(row.depth));
+        let prefix_width = row.marker.widths() + indentation.widths();
+        let line = StyledGraphemes::from_iter([row.marker, indentation, row.body]);
+        let line = match (self.highlight, row.active) {
+            (Highlight::Style { active, .. }, true) => line.apply_style(active),
+            (Highlight::Style { inactive, .. }, false) => line.apply_style(inactive),
+            (Highlight::Attribute { active, .. }, true) => line.apply_attribute(active),
+            (Highlight::Attribute { inactive, .. }, false) => line.apply_attribute(inactive),
+        };
+        let (query, style) = self.search;
+        let line = if row.matched && !query.is_empty() {
+            line.clone().highlight(query, style).unwrap_or(line)
+        } else {
+            line
+        };
+        match (self.overflow, row.hanging) {
+            (Overflow::Wrap, Some(hanging)) => {
+                wrap_with_hanging_indent(line, &[prefix_width + hanging, prefix_width], width)
+            }
+            (Overflow::Wrap, None) => line.matrixify(width as usize, height, 0).0,
+            (Overflow::TruncateWithEllipsis, _) => {
+                vec![line.truncate_with_ellipsis(width as usize)]
+            }
+        }
+    }
+}
+
+/// Wraps a line exceeding the width onto continuation rows,
+/// indented to the first of the `hanging` columns leaving room for the rest, or not at all.
+/// The indentation takes the attributes of the start of the line, e.g. underlined if active.
+pub fn wrap_with_hanging_indent(
+    line: StyledGraphemes,
+    hanging: &[usize],
+    width: u16,
+) -> Vec<StyledGraphemes> {
+    let width = width as usize;
+    if line.widths() <= width {
+        return vec![line];
+    }
+
+    let hanging = hanging
+        .iter()
+        .copied()
+        .find(|hanging| *hanging < width)
+        .unwrap_or_default();
+
+    let padding_style = ContentStyle {
+        attributes: line
+            .front()
+            .map(|styled| styled.style().attributes)
+            .unwrap_or_default(),
+        ..Default::default()
+    };
+
+    let mut rest = line;
+    let mut head = StyledGraphemes::default();
+    let mut head_width = 0;
+    while let Some(styled) = rest.front() {
+        if head_width + styled.width() > width {
+            break;
+        }
+        head_width += styled.width();
+        head.push_back(rest.pop_front().unwrap());
+    }
+    if head.is_empty() {
+        // Not even the first grapheme fits (e.g. a wide char at width 1).
+        return rest.matrixify(width, usize::MAX, 0).0;
+    }
+
+    let padding = StyledGraphemes::from_str(" ".repeat(hanging), padding_style);
+    let mut wrapped = vec![head];
+    wrapped.extend(
+        rest.matrixify(width - hanging, usize::MAX, 0)
+            .0
+            .into_iter()
+            .map(|tail| StyledGraphemes::from_iter([padding.clone(), tail])),
+    );
+    wrapped
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn layout(line_numbers: Option<ContentStyle>) -> Layout<'static> {
+        Layout {
+            indent_unit: "  ",
+            highlight: Highlight::Attribute {
+                active: Attribute::Underlined,
+                inactive: Attribute::Dim,
+            },
+            overflow: Overflow::Wrap,
+            search: ("", ContentStyle::default()),
+            line_numbers,
+        }
+    }
+
+    fn rows() -> Vec<Row> {
+        (0..10)
+            .map(|number| Row {
+                depth: number % 2,
+                body: StyledGraphemes::from(format!("item{}", number)),
+                hanging: Some(0),
+                active: number == 0,
+                number,
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    mod create_pane {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let rows = layout(None).create_pane(rows(), 7, 3).extract(10);
+            assert_eq!(
+                vec!["item0", "  item1", "item2"],
+                rows.iter().map(|row| row.to_string()).collect::<Vec<_>>(),
+            );
+            assert!(rows[0]
+                .iter()
+                .all(|styled| styled.style().attributes.has(Attribute::Underlined)));
+            assert!(rows[1]
+                .iter()
+                .all(|styled| styled.style().attributes.has(Attribute::Dim)));
+        }
+
+        #[test]
+        fn test_line_numbers() {
+            let rows = layout(Some(ContentStyle::default()))
+                .create_pane(rows().into_iter().skip(8), 9, 3)
+                .extract(10);
+            // The continuation row of the wrapped item leaves the gutter blank.
+            assert_eq!(
+                vec![" 9 item8", "10   item", "     9"],
+                rows.iter().map(|row| row.to_string()).collect::<Vec<_>>(),
+            );
+        }
+    }
+}
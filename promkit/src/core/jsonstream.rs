@@ -1,7 +1,7 @@
 mod state;
 pub use state::State;
 
-use crate::jsonz::{self, Row, RowOperation};
+use crate::jsonz::{self, Row, RowOperation, Value};
 
 /// Represents a stream of JSON data, allowing for efficient navigation and manipulation.
 #[derive(Clone)]
@@ -25,11 +25,106 @@ impl JsonStream {
         &self.rows
     }
 
+    /// Replaces the value of the leaf row at the given index with the scalar value.
+    /// Returns `false` if it cannot be replaced (see `jsonz::set_value`).
+    pub fn set_value(&mut self, index: usize, value: serde_json::Value) -> bool {
+        jsonz::set_value(&mut self.rows, index, value)
+    }
+
+    /// Reconstructs the documents, including the edited values.
+    pub fn to_value(&self) -> Vec<serde_json::Value> {
+        jsonz::to_value(&self.rows)
+    }
+
     /// Extracts a specified number of rows from the current position in JSON stream.
     pub fn extract_rows_from_current(&self, n: usize) -> Vec<Row> {
         self.rows.extract(self.position, n)
     }
 
+    /// Returns the indices of the rows extracted by `extract_rows_from_current`.
+    pub fn extract_indices_from_current(&self, n: usize) -> Vec<usize> {
+        let mut indices = Vec::with_capacity(n);
+        let mut i = self.position;
+        while i < self.rows.len() && indices.len() < n {
+            indices.push(i);
+            i = match self.rows[i].v {
+                Value::Open {
+                    collapsed: true,
+                    close_index,
+                    ..
+                } => close_index + 1,
+                _ => i + 1,
+            };
+        }
+        indices
+    }
+
+    /// Returns the current position of the cursor.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Moves the cursor to the row at the specified index,
+    /// unfolding any collapsed containers that enclose it.
+    /// Returns `false` if the index is out of range.
+    pub fn move_to(&mut self, index: usize) -> bool {
+        if index >= self.rows.len() {
+            return false;
+        }
+        for i in 0..index {
+            if let Value::Open {
+                collapsed: true,
+                close_index,
+                ..
+            } = self.rows[i].v
+            {
+                if close_index > index {
+                    self.rows.toggle(i);
+                }
+            }
+        }
+        self.position = index;
+        true
+    }
+
+    /// Replaces the JSON values, e.g. when the document is updated.
+    /// The containers stay folded on the paths that still exist.
+    /// The cursor stays on the same path if it still exists, otherwise it stays
+    /// at the same index as far as possible. Returns whether the path was found.
+    pub fn replace<'a, I: IntoIterator<Item = &'a serde_json::Value>>(&mut self, iter: I) -> bool {
+        let path = jsonz::path_of(&self.rows, self.position);
+        let folded = (0..self.rows.len())
+            .filter(|i| {
+                matches!(
+                    self.rows[*i].v,
+                    Value::Open {
+                        collapsed: true,
+                        ..
+                    }
+                )
+            })
+            .map(|i| jsonz::path_of(&self.rows, i))
+            .collect::<Vec<String>>();
+        self.rows = jsonz::create_rows(iter);
+        for folded in folded {
+            if let Some(index) = jsonz::find_by_path(&self.rows, &folded) {
+                if let Value::Open {
+                    collapsed: false, ..
+                } = self.rows[index].v
+                {
+                    self.rows.toggle(index);
+                }
+            }
+        }
+        match jsonz::find_by_path(&self.rows, &path) {
+            Some(index) => self.move_to(index),
+            None => {
+                self.position = self.position.min(self.rows.len().saturating_sub(1));
+                false
+            }
+        }
+    }
+
     /// Toggles the visibility of a node at the cursor's current position.
     pub fn toggle(&mut self) {
         let index = self.rows.toggle(self.position);
@@ -70,3 +165,45 @@ impl JsonStream {
         true
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod replace {
+        use super::*;
+
+        #[test]
+        fn test_keeps_path() {
+            let mut stream = JsonStream::new([&serde_json::json!({"a": 1, "b": 2})]);
+            stream.move_to(2);
+
+            assert!(stream.replace([&serde_json::json!({"new": 0, "a": 1, "b": 3})]));
+            assert_eq!(3, stream.position());
+        }
+
+        #[test]
+        fn test_path_disappeared() {
+            let mut stream = JsonStream::new([&serde_json::json!({"a": 1, "b": 2})]);
+            stream.move_to(2);
+
+            assert!(!stream.replace([&serde_json::json!({"a": 1})]));
+            assert_eq!(2, stream.position());
+        }
+
+        #[test]
+        fn test_keeps_folds() {
+            let mut stream = JsonStream::new([&serde_json::json!({"a": [1, 2], "b": {"c": 3}})]);
+            stream.move_to(1);
+            stream.toggle();
+            stream.move_to(0);
+
+            assert!(stream.replace([&serde_json::json!({"a": [1, 2, 3], "b": {"c": 4}})]));
+            // `a` stays folded, while `b` is unfolded as before.
+            assert_eq!(
+                vec![0, 1, 6, 7, 8, 9],
+                stream.extract_indices_from_current(10)
+            );
+        }
+    }
+}
@@ -1,4 +1,11 @@
-use crate::{jsonz::format::RowFormatter, pane::Pane, PaneFactory};
+use crate::{
+    crossterm::style::ContentStyle,
+    grapheme::StyledGraphemes,
+    indented_rows::{self, Highlight, Layout},
+    jsonz::{self, format::RowFormatter},
+    pane::Pane,
+    Overflow, PaneFactory,
+};
 
 use super::JsonStream;
 
@@ -14,10 +21,34 @@ pub struct State {
 
     pub formatter: RowFormatter,
 
+    /// Query to highlight in the keys and string values, or empty if not searching.
+    pub search_query: String,
+    /// Style for the substrings matching the search query.
+    pub search_highlight_style: ContentStyle,
+
+    /// The row the visual selection started at, extending to the cursor, if selecting.
+    /// The selection is balanced by `jsonz::balance_range`.
+    pub visual_anchor: Option<usize>,
+    /// Style for the selected rows.
+    pub visual_style: ContentStyle,
+
+    /// How rows wider than the pane are rendered.
+    /// With `Overflow::Wrap`, a row is continued on the following rows,
+    /// which are still a part of the same row for the cursor.
+    pub overflow: Overflow,
+
     /// Number of lines available for rendering.
     pub lines: Option<usize>,
 }
 
+impl State {
+    /// Returns the rows of the visual selection, balanced to make valid JSON, if selecting.
+    pub fn selection(&self) -> Option<std::ops::RangeInclusive<usize>> {
+        self.visual_anchor
+            .map(|anchor| jsonz::balance_range(self.stream.rows(), anchor, self.stream.position()))
+    }
+}
+
 impl PaneFactory for State {
     fn create_pane(&self, width: u16, height: u16) -> Pane {
         let height = match self.lines {
@@ -26,8 +57,133 @@ impl PaneFactory for State {
         };
 
         let rows = self.stream.extract_rows_from_current(height);
-        let formatted_rows = self.formatter.format_for_terminal_display(&rows, width);
+        let contents = self.formatter.format_contents(&rows);
+        let indices = self.stream.extract_indices_from_current(height);
+        let selection = self.selection();
+        let indent_unit = self.formatter.indentation(1);
+        Layout {
+            indent_unit: &indent_unit,
+            highlight: Highlight::Attribute {
+                active: self.formatter.active_item_attribute,
+                inactive: self.formatter.inactive_item_attribute,
+            },
+            overflow: self.overflow,
+            search: (&self.search_query, self.search_highlight_style),
+            line_numbers: None,
+        }
+        .create_pane(
+            contents
+                .into_iter()
+                .zip(&rows)
+                .zip(indices)
+                .enumerate()
+                .map(|(i, ((body, row), index))| {
+                    let body = match &selection {
+                        Some(selection) if selection.contains(&index) => {
+                            body.apply_style(self.visual_style)
+                        }
+                        _ => body,
+                    };
+                    indented_rows::Row {
+                        depth: row.depth,
+                        body,
+                        // The wrapped rows of a value start after the key.
+                        hanging: Some(
+                            row.k
+                                .as_ref()
+                                .map(|key| StyledGraphemes::from(format!("\"{}\": ", key)).widths())
+                                .unwrap_or_default(),
+                        ),
+                        active: i == 0,
+                        matched: row.contains(&self.search_query),
+                        number: self.stream.position() + i,
+                        ..Default::default()
+                    }
+                }),
+            width,
+            height,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::crossterm::style::Attribute;
+
+    fn state(overflow: Overflow, lines: Option<usize>) -> State {
+        State {
+            stream: JsonStream::new([&serde_json::json!({"k": "日本語テキスト", "n": 1})]),
+            formatter: RowFormatter {
+                active_item_attribute: Attribute::Underlined,
+                inactive_item_attribute: Attribute::Dim,
+                indent: 2,
+                ..Default::default()
+            },
+            search_query: Default::default(),
+            search_highlight_style: Default::default(),
+            visual_anchor: None,
+            visual_style: Default::default(),
+            overflow,
+            lines,
+        }
+    }
+
+    mod create_pane {
+        use super::*;
+
+        #[test]
+        fn test_wrap() {
+            let rows = state(Overflow::Wrap, None).create_pane(12, 10).extract(10);
+            assert_eq!(
+                vec![
+                    "{",
+                    "  \"k\": \"日本",
+                    "       語テ ",
+                    "       キス ",
+                    "       ト\",",
+                    "  \"n\": 1",
+                    "}",
+                ],
+                rows.iter().map(|row| row.to_string()).collect::<Vec<_>>(),
+            );
+        }
+
+        #[test]
+        fn test_wrap_counts_visual_rows() {
+            let rows = state(Overflow::Wrap, Some(3))
+                .create_pane(12, 10)
+                .extract(10);
+            assert_eq!(
+                vec!["{", "  \"k\": \"日本", "       語テ "],
+                rows.iter().map(|row| row.to_string()).collect::<Vec<_>>(),
+            );
+        }
+
+        #[test]
+        fn test_wrap_highlights_whole_active_row() {
+            let mut state = state(Overflow::Wrap, None);
+            assert!(state.stream.move_to(1));
+            let rows = state.create_pane(12, 10).extract(10);
+            // The continuation rows, including their indentation, belong to the active row.
+            for row in &rows[..4] {
+                assert!(row[0].style().attributes.has(Attribute::Underlined));
+                assert!(row
+                    .iter()
+                    .filter(|styled| styled.as_str() != " ")
+                    .all(|styled| styled.style().attributes.has(Attribute::Underlined)));
+            }
+            assert!(rows[4][0].style().attributes.has(Attribute::Dim));
+        }
 
-        Pane::new(formatted_rows, 0)
+        #[test]
+        fn test_truncate_with_ellipsis() {
+            let rows = state(Overflow::TruncateWithEllipsis, None)
+                .create_pane(12, 10)
+                .extract(10);
+            assert_eq!(4, rows.len());
+            assert_eq!("  \"k\": \"日 …", rows[1].to_string());
+        }
     }
 }
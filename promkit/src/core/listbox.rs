@@ -1,59 +1,155 @@
-use std::fmt;
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
 
 use crate::{core::cursor::Cursor, grapheme::StyledGraphemes};
 
 mod state;
 pub use state::State;
 
+/// Tells double clicks on the items of a listbox apart from single clicks.
+///
+/// The clicks are compared by row rather than by item,
+/// since the list scrolls to the item selected by the first click.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Clicks {
+    last: Option<(Instant, u16)>,
+}
+
+impl Clicks {
+    /// The maximum interval between the clicks of a double click.
+    pub const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+
+    /// Records a click on the row, and returns whether it completes a double click,
+    /// i.e. follows a click on an item at the same row.
+    /// Only the clicks on items (`on_item`) may start a double click.
+    pub fn click(&mut self, row: u16, on_item: bool) -> bool {
+        let now = Instant::now();
+        let double = self.last.is_some_and(|(at, last)| {
+            last == row && now.duration_since(at) <= Self::DOUBLE_CLICK_INTERVAL
+        });
+        // A third click starts over instead of making another double click.
+        self.last = (!double && on_item).then_some((now, row));
+        double
+    }
+}
+
+/// An item chosen from a list along with its index in the full list,
+/// which tells apart items with the same label.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IndexedItem {
+    pub index: usize,
+    pub item: String,
+}
+
 /// A `Listbox` struct that encapsulates a list of strings,
 /// allowing for navigation and manipulation through a cursor.
 /// It supports basic operations
 /// such as moving the cursor forward and backward,
 /// retrieving the current item,
 /// and initializing from an iterator of displayable items.
+/// Each item may optionally carry a description,
+/// which is rendered next to the item but never returned as part of it.
+///
+/// A listbox may be made display-only with `set_interactive(false)`,
+/// e.g. for a summary shown in a larger prompt:
+/// the cursor then stays at the head and is not rendered.
 #[derive(Clone)]
-pub struct Listbox(Cursor<Vec<StyledGraphemes>>);
+pub struct Listbox {
+    cursor: Cursor<Vec<StyledGraphemes>>,
+    descriptions: Vec<Option<String>>,
+    interactive: bool,
+}
 
 impl Default for Listbox {
     fn default() -> Self {
-        Self(Cursor::new(vec![StyledGraphemes::default()], 0, false))
+        Self::from_styled_graphemes(vec![StyledGraphemes::default()])
     }
 }
 
 impl Listbox {
     /// Creates a new `Listbox` from a vector of `fmt::Display`.
     pub fn from_displayable<E: fmt::Display, I: IntoIterator<Item = E>>(items: I) -> Self {
-        Self(Cursor::new(
+        Self::from_styled_graphemes(
             items
                 .into_iter()
                 .map(|e| StyledGraphemes::from(format!("{}", e)))
                 .collect(),
-            0,
-            false,
-        ))
+        )
+    }
+
+    /// Creates a new `Listbox` from pairs of `fmt::Display` items
+    /// and their optional descriptions.
+    pub fn from_displayable_with_descriptions<E, D, I>(items: I) -> Self
+    where
+        E: fmt::Display,
+        D: fmt::Display,
+        I: IntoIterator<Item = (E, Option<D>)>,
+    {
+        let (items, descriptions): (Vec<_>, Vec<_>) = items
+            .into_iter()
+            .map(|(e, d)| {
+                (
+                    StyledGraphemes::from(format!("{}", e)),
+                    d.map(|d| format!("{}", d)),
+                )
+            })
+            .unzip();
+        Self {
+            cursor: Cursor::new(items, 0, false),
+            descriptions,
+            interactive: true,
+        }
     }
 
     pub fn len(&self) -> usize {
-        self.0.contents().len()
+        self.cursor.contents().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cursor.contents().is_empty()
     }
 
     pub fn push_string(&mut self, item: String) {
-        self.0.contents_mut().push(StyledGraphemes::from(item));
+        self.cursor.contents_mut().push(StyledGraphemes::from(item));
+        self.descriptions.push(None);
     }
 
     /// Creates a new `Listbox` from a vector of `StyledGraphemes`.
     pub fn from_styled_graphemes(items: Vec<StyledGraphemes>) -> Self {
-        Self(Cursor::new(items, 0, false))
+        let descriptions = vec![None; items.len()];
+        Self::from_styled_graphemes_with_descriptions(items, descriptions)
+    }
+
+    /// Creates a new `Listbox` from a vector of `StyledGraphemes`
+    /// and their optional descriptions, in the same order.
+    /// Missing descriptions are `None`, and extra ones are ignored.
+    pub fn from_styled_graphemes_with_descriptions(
+        items: Vec<StyledGraphemes>,
+        mut descriptions: Vec<Option<String>>,
+    ) -> Self {
+        descriptions.resize(items.len(), None);
+        Self {
+            cursor: Cursor::new(items, 0, false),
+            descriptions,
+            interactive: true,
+        }
     }
 
     /// Returns a reference to the vector of items in the listbox.
     pub fn items(&self) -> &Vec<StyledGraphemes> {
-        self.0.contents()
+        self.cursor.contents()
+    }
+
+    /// Returns the description of the item at the specified index, if any.
+    pub fn description(&self, idx: usize) -> Option<&str> {
+        self.descriptions.get(idx).and_then(|d| d.as_deref())
     }
 
     /// Returns the current position of the cursor within the listbox.
     pub fn position(&self) -> usize {
-        self.0.position()
+        self.cursor.position()
     }
 
     /// Retrieves the item at the current cursor position as a `String`.
@@ -69,26 +165,163 @@ impl Listbox {
     /// Moves the cursor backward in the listbox, if possible.
     /// Returns `true` if the cursor was successfully moved backward, `false` otherwise.
     pub fn backward(&mut self) -> bool {
-        self.0.backward()
+        self.interactive && self.cursor.backward()
     }
 
     /// Moves the cursor forward in the listbox, if possible.
     /// Returns `true` if the cursor was successfully moved forward, `false` otherwise.
     pub fn forward(&mut self) -> bool {
-        self.0.forward()
+        self.interactive && self.cursor.forward()
     }
 
     /// Moves the cursor to the head (beginning) of the listbox.
     pub fn move_to_head(&mut self) {
-        self.0.move_to_head()
+        if self.interactive {
+            self.cursor.move_to_head()
+        }
     }
 
     /// Moves the cursor to the tail of the listbox.
     pub fn move_to_tail(&mut self) {
-        self.0.move_to_tail()
+        if self.interactive {
+            self.cursor.move_to_tail()
+        }
     }
 
     pub fn is_tail(&self) -> bool {
-        self.0.is_tail()
+        self.cursor.is_tail()
+    }
+
+    /// Moves the cursor to the specified position.
+    /// Returns `true` if the position is within the listbox, `false` otherwise.
+    pub fn move_to(&mut self, position: usize) -> bool {
+        self.interactive && self.cursor.move_to(position)
+    }
+
+    /// Moves the cursor backward by a page of the given number of items,
+    /// stopping at the head. Returns `true` if the cursor moved.
+    pub fn page_backward(&mut self, page: usize) -> bool {
+        let position = self.position();
+        self.move_to(position.saturating_sub(page.max(1))) && self.position() != position
+    }
+
+    /// Moves the cursor forward by a page of the given number of items,
+    /// stopping at the tail. Returns `true` if the cursor moved.
+    pub fn page_forward(&mut self, page: usize) -> bool {
+        let position = self.position();
+        let tail = self.len().saturating_sub(1);
+        self.move_to((position + page.max(1)).min(tail)) && self.position() != position
+    }
+
+    /// Returns whether `forward` from the tail goes to the head and vice versa.
+    pub fn is_cyclic(&self) -> bool {
+        self.cursor.is_cyclic()
+    }
+
+    /// Sets whether `forward` from the tail goes to the head and vice versa.
+    /// It is off by default.
+    pub fn set_cyclic(&mut self, cyclic: bool) {
+        self.cursor.set_cyclic(cyclic)
+    }
+
+    /// Returns whether the cursor can be moved and is rendered.
+    pub fn is_interactive(&self) -> bool {
+        self.interactive
+    }
+
+    /// Sets whether the cursor can be moved and is rendered. It is on by default.
+    /// Making it display-only moves the cursor back to the head,
+    /// and the moves become no-ops.
+    pub fn set_interactive(&mut self, interactive: bool) {
+        if !interactive {
+            self.cursor.move_to_head();
+        }
+        self.interactive = interactive;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod from_displayable_with_descriptions {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let listbox = Listbox::from_displayable_with_descriptions([
+                ("--all", Some("show all entries")),
+                ("--long", None),
+            ]);
+            assert_eq!(
+                &vec![
+                    StyledGraphemes::from("--all"),
+                    StyledGraphemes::from("--long")
+                ],
+                listbox.items()
+            );
+            assert_eq!(Some("show all entries"), listbox.description(0));
+            assert_eq!(None, listbox.description(1));
+            assert_eq!("--all", listbox.get().to_string());
+        }
+    }
+
+    mod page_forward {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let mut listbox = Listbox::from_displayable(0..5);
+            assert!(listbox.page_forward(3));
+            assert_eq!(3, listbox.position());
+            // Stops at the tail, even if cyclic.
+            listbox.set_cyclic(true);
+            assert!(listbox.page_forward(3));
+            assert_eq!(4, listbox.position());
+            assert!(!listbox.page_forward(3));
+            assert!(listbox.page_backward(10));
+            assert_eq!(0, listbox.position());
+            assert!(!listbox.page_backward(1));
+        }
+    }
+
+    mod set_interactive {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let mut listbox = Listbox::from_displayable(["a", "b", "c"]);
+            listbox.forward();
+            listbox.set_interactive(false);
+            assert_eq!(0, listbox.position());
+            assert!(!listbox.forward());
+            assert!(!listbox.move_to(2));
+            assert!(!listbox.page_forward(2));
+            listbox.move_to_tail();
+            assert_eq!(0, listbox.position());
+
+            listbox.set_interactive(true);
+            assert!(listbox.forward());
+            assert_eq!(1, listbox.position());
+        }
+    }
+
+    mod set_cyclic {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let mut listbox = Listbox::from_displayable(["a", "b"]);
+            assert!(!listbox.backward());
+            listbox.set_cyclic(true);
+            assert!(listbox.backward());
+            assert_eq!(1, listbox.position());
+            assert!(listbox.forward());
+            assert_eq!(0, listbox.position());
+
+            let mut empty = Listbox::from_displayable(Vec::<String>::new());
+            empty.set_cyclic(true);
+            assert!(!empty.forward());
+        }
     }
 }
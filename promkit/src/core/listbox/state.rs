@@ -1,4 +1,7 @@
-use crate::{crossterm::style::ContentStyle, grapheme::StyledGraphemes, pane::Pane, PaneFactory};
+use crate::{
+    crossterm::style::ContentStyle, grapheme::StyledGraphemes, pane::Pane, viewport, Overflow,
+    PaneFactory, PartialItem,
+};
 
 use super::Listbox;
 
@@ -11,61 +14,334 @@ pub struct State {
     pub listbox: Listbox,
 
     /// Symbol for the selected line.
+    /// It is not rendered, nor is the active style, if the listbox is not interactive.
     pub cursor: String,
 
     /// Style for the selected line.
     pub active_item_style: Option<ContentStyle>,
     /// Style for un-selected lines.
     pub inactive_item_style: Option<ContentStyle>,
+    /// Style for item descriptions, rendered after the item.
+    pub description_style: ContentStyle,
+    /// How items exceeding the pane width are rendered.
+    pub overflow: Overflow,
+    /// Maximum number of rows of an item when wrapped, or `None` for no limit.
+    pub item_lines: Option<usize>,
+    /// How an item not fitting at the bottom of the pane is rendered.
+    pub partial_item: PartialItem,
 
     /// Number of lines available for rendering.
     pub lines: Option<usize>,
 }
 
-impl PaneFactory for State {
-    fn create_pane(&self, width: u16, height: u16) -> Pane {
-        let height = match self.lines {
+impl State {
+    fn height(&self, height: u16) -> usize {
+        match self.lines {
             Some(lines) => lines.min(height as usize),
             None => height as usize,
+        }
+    }
+
+    /// Lays out the rows of the item at the index.
+    fn item_rows(&self, i: usize, item: &StyledGraphemes, width: u16) -> Vec<StyledGraphemes> {
+        let (prefix, style) = if !self.listbox.is_interactive() {
+            (StyledGraphemes::default(), self.inactive_item_style)
+        } else if i == self.listbox.position() {
+            (StyledGraphemes::from(&self.cursor), self.active_item_style)
+        } else {
+            (
+                StyledGraphemes::from(" ".repeat(StyledGraphemes::from(&self.cursor).widths())),
+                self.inactive_item_style,
+            )
+        };
+        let (prefix, item) = match style {
+            Some(style) => (prefix.apply_style(style), apply_base_style(item, style)),
+            None => (prefix, item.clone()),
         };
+        let body = match self.listbox.description(i) {
+            Some(description) => StyledGraphemes::from_iter([
+                item,
+                StyledGraphemes::from_str(format!("  {}", description), self.description_style),
+            ]),
+            None => item,
+        };
+        viewport::item_rows(
+            &prefix,
+            &body,
+            width as usize,
+            self.overflow,
+            self.item_lines,
+        )
+    }
 
-        let matrix = self
-            .listbox
+    /// Returns the visible items with their rows, from the top of the pane.
+    fn visible_items(
+        &self,
+        width: u16,
+        height: usize,
+    ) -> impl Iterator<Item = (usize, Vec<StyledGraphemes>)> + '_ {
+        self.listbox
             .items()
             .iter()
             .enumerate()
-            .filter(|(i, _)| *i >= self.listbox.position() && *i < self.listbox.position() + height)
-            .map(|(i, item)| {
-                if i == self.listbox.position() {
-                    let init =
-                        StyledGraphemes::from_iter([&StyledGraphemes::from(&self.cursor), item]);
-                    if let Some(style) = &self.active_item_style {
-                        init.apply_style(*style)
-                    } else {
-                        init
-                    }
-                } else {
-                    let init = StyledGraphemes::from_iter([
-                        &StyledGraphemes::from(
-                            " ".repeat(StyledGraphemes::from(&self.cursor).widths()),
-                        ),
-                        item,
-                    ]);
-                    if let Some(style) = &self.inactive_item_style {
-                        init.apply_style(*style)
-                    } else {
-                        init
-                    }
+            // Only the visible items are styled, so that huge lists stay responsive.
+            .skip(self.listbox.position())
+            .take(height)
+            .map(move |(i, item)| (i, self.item_rows(i, item, width)))
+    }
+
+    /// Returns how many items PageUp and PageDown move over
+    /// in the pane created for the height, i.e. the rows available for the list.
+    pub fn page_size(&self, height: u16) -> usize {
+        self.height(height).max(1)
+    }
+
+    /// Returns the index of the item shown at the row of the pane
+    /// created for the width and the height, e.g. to select the item clicked with the mouse.
+    /// Returns `None` for the rows below the list.
+    pub fn item_at(&self, width: u16, height: u16, row: u16) -> Option<usize> {
+        let height = self.height(height);
+        let owners = viewport::stack(
+            self.visible_items(width, height)
+                .map(|(i, rows)| vec![i; rows.len()]),
+            height,
+            self.partial_item,
+        );
+        owners.get(row as usize).copied()
+    }
+}
+
+impl PaneFactory for State {
+    fn create_pane(&self, width: u16, height: u16) -> Pane {
+        let height = self.height(height);
+        let items = self.visible_items(width, height).map(|(_, rows)| rows);
+        let matrix = viewport::stack(items, height, self.partial_item);
+
+        Pane::new(matrix, 0)
+    }
+}
+
+/// Applies the style to the graphemes of the item without their own style,
+/// so that e.g. the highlighted matches of `QuerySelector` stay visible.
+fn apply_base_style(item: &StyledGraphemes, style: ContentStyle) -> StyledGraphemes {
+    item.iter()
+        .map(|g| {
+            let mut g = g.clone();
+            if *g.style() == ContentStyle::default() {
+                g.apply_style(style);
+            }
+            g
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod create_pane {
+        use super::*;
+
+        use crate::crossterm::style::Attribute;
+
+        #[test]
+        fn test_with_emoji_cursor() {
+            let state = State {
+                listbox: Listbox::from_displayable(["a", "b"]),
+                cursor: String::from("👨‍👩‍👧 "),
+                active_item_style: None,
+                inactive_item_style: None,
+                description_style: ContentStyle::default(),
+                overflow: Overflow::Wrap,
+                item_lines: None,
+                partial_item: Default::default(),
+                lines: None,
+            };
+            let rows = state.create_pane(20, 10).extract(10);
+            assert_eq!("👨‍👩‍👧 a", rows[0].to_string());
+            assert_eq!("   b", rows[1].to_string());
+            assert_eq!(rows[0].widths(), rows[1].widths());
+        }
+
+        #[test]
+        fn test_with_truncate_with_ellipsis() {
+            let state = State {
+                listbox: Listbox::from_displayable(["日本語テキスト", "b"]),
+                cursor: String::from("❯ "),
+                active_item_style: None,
+                inactive_item_style: None,
+                description_style: ContentStyle::default(),
+                overflow: Overflow::TruncateWithEllipsis,
+                item_lines: None,
+                partial_item: Default::default(),
+                lines: None,
+            };
+            let rows = state.create_pane(9, 10).extract(10);
+            assert_eq!(2, rows.len());
+            assert_eq!("❯ 日本語…", rows[0].to_string());
+            assert_eq!("  b", rows[1].to_string());
+        }
+
+        #[test]
+        fn test_not_interactive() {
+            let mut state = State {
+                listbox: Listbox::from_displayable(["a", "b"]),
+                cursor: String::from("❯ "),
+                active_item_style: Some(
+                    crate::style::StyleBuilder::new()
+                        .attrs(Attribute::Bold.into())
+                        .build(),
+                ),
+                inactive_item_style: None,
+                description_style: ContentStyle::default(),
+                overflow: Overflow::Wrap,
+                item_lines: None,
+                partial_item: Default::default(),
+                lines: None,
+            };
+            state.listbox.set_interactive(false);
+            let rows = state.create_pane(20, 10).extract(10);
+            assert_eq!(
+                vec!["a", "b"],
+                rows.iter().map(|row| row.to_string()).collect::<Vec<_>>()
+            );
+            assert!(rows[0]
+                .iter()
+                .all(|g| *g.style() == ContentStyle::default()));
+        }
+
+        #[test]
+        fn test_multi_row_items() {
+            let mut state = State {
+                listbox: Listbox::from_displayable([
+                    "one",
+                    "fix parser\nhandle empty input",
+                    "two",
+                ]),
+                cursor: String::from("❯ "),
+                active_item_style: Some(
+                    crate::style::StyleBuilder::new()
+                        .attrs(Attribute::Bold.into())
+                        .build(),
+                ),
+                inactive_item_style: None,
+                description_style: ContentStyle::default(),
+                overflow: Overflow::Wrap,
+                item_lines: Some(2),
+                partial_item: PartialItem::Clip,
+                lines: None,
+            };
+            state.listbox.forward();
+            let rows = state.create_pane(14, 10).extract(10);
+            assert_eq!(
+                vec!["❯ fix parser", "  handle empt…", "  two"],
+                rows.iter().map(|row| row.to_string()).collect::<Vec<_>>()
+            );
+            // The active style applies to the whole block, including the indent.
+            assert!(rows[1].iter().all(|g| g
+                .style()
+                .attributes
+                .has(crate::crossterm::style::Attribute::Bold)));
+
+            // The cursor moves by item.
+            state.listbox.forward();
+            let rows = state.create_pane(14, 10).extract(10);
+            assert_eq!("❯ two", rows[0].to_string());
+        }
+
+        #[test]
+        fn test_partial_item() {
+            let mut state = State {
+                listbox: Listbox::from_displayable(["one", "a long item", "three"]),
+                cursor: String::from("❯ "),
+                active_item_style: None,
+                inactive_item_style: None,
+                description_style: ContentStyle::default(),
+                overflow: Overflow::Wrap,
+                item_lines: None,
+                partial_item: PartialItem::Clip,
+                lines: None,
+            };
+            assert_eq!(
+                vec!["❯ one", "  a long", "   item"],
+                state
+                    .create_pane(8, 3)
+                    .extract(3)
+                    .iter()
+                    .map(|row| row.to_string())
+                    .collect::<Vec<_>>()
+            );
+            let rows = state.create_pane(8, 2).extract(2);
+            assert_eq!("  a long", rows[1].to_string());
+
+            state.partial_item = PartialItem::Skip;
+            let rows = state.create_pane(8, 2).extract(2);
+            assert_eq!(1, rows.len());
+        }
+
+        #[test]
+        fn test_cost_independent_of_item_count() {
+            fn elapsed(len: usize) -> std::time::Duration {
+                let mut state = State {
+                    listbox: Listbox::from_displayable(0..len),
+                    cursor: String::from("❯ "),
+                    active_item_style: None,
+                    inactive_item_style: None,
+                    description_style: ContentStyle::default(),
+                    overflow: Overflow::TruncateWithEllipsis,
+                    item_lines: None,
+                    partial_item: Default::default(),
+                    lines: None,
+                };
+                for _ in 0..len / 2 {
+                    state.listbox.forward();
                 }
-            })
-            .fold((vec![], 0), |(mut acc, pos), item| {
-                let rows = item.matrixify(width as usize, height, 0).0;
-                if pos < self.listbox.position() + height {
-                    acc.extend(rows);
+                let start = std::time::Instant::now();
+                for _ in 0..100 {
+                    assert_eq!(20, state.create_pane(80, 20).extract(20).len());
                 }
-                (acc, pos + 1)
-            });
+                start.elapsed()
+            }
+
+            let small = elapsed(100);
+            let large = elapsed(300_000);
+            assert!(
+                large < small * 5 + std::time::Duration::from_millis(20),
+                "{:?} for 300000 items, {:?} for 100 items",
+                large,
+                small
+            );
+        }
+    }
+
+    mod item_at {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let mut state = State {
+                listbox: Listbox::from_displayable(["one", "a long item", "three", "four"]),
+                cursor: String::from("❯ "),
+                active_item_style: None,
+                inactive_item_style: None,
+                description_style: ContentStyle::default(),
+                overflow: Overflow::Wrap,
+                item_lines: None,
+                partial_item: PartialItem::Clip,
+                lines: Some(3),
+            };
+            // "❯ one", "  a long", "   item" in 8 columns.
+            assert_eq!(Some(0), state.item_at(8, 10, 0));
+            assert_eq!(Some(1), state.item_at(8, 10, 1));
+            assert_eq!(Some(1), state.item_at(8, 10, 2));
+            assert_eq!(None, state.item_at(8, 10, 3));
 
-        Pane::new(matrix.0, 0)
+            // The list scrolls from the cursor.
+            state.listbox.forward();
+            state.listbox.forward();
+            assert_eq!(Some(2), state.item_at(8, 10, 0));
+            assert_eq!(Some(3), state.item_at(8, 10, 1));
+            assert_eq!(None, state.item_at(8, 10, 2));
+        }
     }
 }
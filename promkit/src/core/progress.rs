@@ -0,0 +1,164 @@
+use crate::{crossterm::style::ContentStyle, grapheme::StyledGraphemes, pane::Pane, PaneFactory};
+
+/// Represents the state of a determinate progress bar, e.g. `████░░░░ 50%`.
+#[derive(Clone)]
+pub struct State {
+    /// The progress between 0.0 and 1.0. Use `set_ratio` to keep it in range.
+    pub ratio: f64,
+
+    /// The width of the bar including the percentage,
+    /// or `None` to fill the pane width. Narrowed to the pane width if wider.
+    pub width_hint: Option<u16>,
+
+    /// Style for the filled part of the bar.
+    pub filled_style: ContentStyle,
+    /// Style for the empty part of the bar.
+    pub empty_style: ContentStyle,
+
+    /// Whether the percentage is shown after the bar.
+    pub show_percent: bool,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            ratio: 0.0,
+            width_hint: None,
+            filled_style: Default::default(),
+            empty_style: Default::default(),
+            show_percent: true,
+        }
+    }
+}
+
+impl State {
+    /// Sets the progress, clamped to 0.0..=1.0 (NaN is treated as 0.0).
+    /// Returns whether the progress has changed, i.e. whether to redraw.
+    pub fn set_ratio(&mut self, ratio: f64) -> bool {
+        let ratio = if ratio.is_nan() {
+            0.0
+        } else {
+            ratio.clamp(0.0, 1.0)
+        };
+        let changed = ratio != self.ratio;
+        self.ratio = ratio;
+        changed
+    }
+
+    /// Sets the progress from the counts, e.g. of downloaded and total bytes.
+    /// The progress is 0.0 if the total is 0.
+    pub fn set_progress(&mut self, done: u64, total: u64) -> bool {
+        if total == 0 {
+            self.set_ratio(0.0)
+        } else {
+            self.set_ratio(done as f64 / total as f64)
+        }
+    }
+}
+
+impl PaneFactory for State {
+    fn create_pane(&self, width: u16, _height: u16) -> Pane {
+        let width = self.width_hint.map_or(width, |hint| hint.min(width)) as usize;
+        if width == 0 {
+            return Pane::new(vec![], 0);
+        }
+
+        let ratio = self.ratio.clamp(0.0, 1.0);
+        let percent = if self.show_percent {
+            format!(" {:>3}%", (ratio * 100.0).floor() as u32)
+        } else {
+            String::new()
+        };
+        // The percentage is dropped if there is no room for the bar.
+        let percent = if percent.len() < width {
+            percent
+        } else {
+            String::new()
+        };
+        let bar_width = width - percent.len();
+        let filled = (bar_width as f64 * ratio).floor() as usize;
+
+        let row = StyledGraphemes::from_iter([
+            StyledGraphemes::from_str("█".repeat(filled), self.filled_style),
+            StyledGraphemes::from_str("░".repeat(bar_width - filled), self.empty_style),
+            StyledGraphemes::from(percent),
+        ]);
+        Pane::new(vec![row], 0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn render(state: &State, width: u16) -> Vec<String> {
+        state
+            .create_pane(width, 1)
+            .extract(1)
+            .iter()
+            .map(|row| row.to_string())
+            .collect()
+    }
+
+    mod create_pane {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let mut state = State::default();
+            state.set_ratio(0.5);
+            assert_eq!(vec!["████░░░░  50%"], render(&state, 13));
+            state.set_ratio(1.0);
+            assert_eq!(vec!["████████ 100%"], render(&state, 13));
+        }
+
+        #[test]
+        fn test_width_hint() {
+            let state = State {
+                ratio: 0.25,
+                width_hint: Some(4),
+                show_percent: false,
+                ..Default::default()
+            };
+            assert_eq!(vec!["█░░░"], render(&state, 80));
+            // Narrowed to the pane width.
+            assert_eq!(vec!["░░"], render(&state, 2));
+        }
+
+        #[test]
+        fn test_narrow() {
+            let state = State {
+                ratio: 0.5,
+                ..Default::default()
+            };
+            assert!(render(&state, 0).is_empty());
+            // No room for the percentage.
+            assert_eq!(vec!["██░░░"], render(&state, 5));
+        }
+    }
+
+    mod set_ratio {
+        use super::*;
+
+        #[test]
+        fn test_clamp() {
+            let mut state = State::default();
+            assert!(state.set_ratio(1.5));
+            assert_eq!(1.0, state.ratio);
+            assert!(!state.set_ratio(2.0));
+            assert!(state.set_ratio(-1.0));
+            assert_eq!(0.0, state.ratio);
+            state.set_ratio(f64::NAN);
+            assert_eq!(0.0, state.ratio);
+        }
+
+        #[test]
+        fn test_set_progress() {
+            let mut state = State::default();
+            state.set_progress(3, 4);
+            assert_eq!(0.75, state.ratio);
+            state.set_progress(1, 0);
+            assert_eq!(0.0, state.ratio);
+        }
+    }
+}
@@ -0,0 +1,92 @@
+pub mod frame;
+mod state;
+pub use state::State;
+
+/// A spinner cycling through frames, e.g. to indicate a task in progress.
+/// The caller advances it, typically on every `Renderer::tick`.
+#[derive(Clone)]
+pub struct Spinner {
+    frames: Vec<String>,
+    position: usize,
+}
+
+impl Default for Spinner {
+    fn default() -> Self {
+        Self {
+            frames: frame::from_str_frames(frame::DOTS),
+            position: 0,
+        }
+    }
+}
+
+impl Spinner {
+    /// Sets the frames, e.g. `frame::CLOCK`.
+    /// If no frames are given, the spinner renders nothing.
+    pub fn frames<I, T>(mut self, frames: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: ToString,
+    {
+        self.frames = frames.into_iter().map(|frame| frame.to_string()).collect();
+        self.position = 0;
+        self
+    }
+
+    /// Returns the current frame, or an empty string if there are no frames.
+    pub fn current(&self) -> &str {
+        self.frames
+            .get(self.position)
+            .map(String::as_str)
+            .unwrap_or_default()
+    }
+
+    /// Moves to the next frame, wrapping around to the first one.
+    pub fn advance(&mut self) {
+        if !self.frames.is_empty() {
+            self.position = (self.position + 1) % self.frames.len();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod frames {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let mut spinner = Spinner::default().frames(frame::LINE);
+            assert_eq!("-", spinner.current());
+            for _ in 0..4 {
+                spinner.advance();
+            }
+            assert_eq!("-", spinner.current());
+
+            let mut spinner = Spinner::default().frames(1..=3);
+            spinner.advance();
+            assert_eq!("2", spinner.current());
+        }
+
+        #[test]
+        fn test_empty() {
+            let mut spinner = Spinner::default().frames(Vec::<String>::new());
+            spinner.advance();
+            assert_eq!("", spinner.current());
+        }
+    }
+
+    mod from_str_frames {
+        use super::*;
+
+        #[test]
+        fn test() {
+            assert_eq!(
+                vec!["-", "\\", "|", "/"],
+                frame::from_str_frames(frame::LINE)
+            );
+            assert_eq!("⠋", Spinner::default().current());
+        }
+    }
+}
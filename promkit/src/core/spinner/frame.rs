@@ -0,0 +1,23 @@
+//! Frame sets for `Spinner`.
+
+/// Braille dots rotating clockwise (default).
+pub const DOTS: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// A line rotating in ASCII, for terminals without Unicode fonts.
+pub const LINE: &[&str] = &["-", "\\", "|", "/"];
+
+/// Clock faces advancing by an hour.
+pub const CLOCK: &[&str] = &[
+    "🕛", "🕐", "🕑", "🕒", "🕓", "🕔", "🕕", "🕖", "🕗", "🕘", "🕙", "🕚",
+];
+
+/// A bar bouncing back and forth in brackets.
+pub const BOUNCING_BAR: &[&str] = &[
+    "[    ]", "[=   ]", "[==  ]", "[=== ]", "[ ===]", "[  ==]", "[   =]", "[    ]", "[   =]",
+    "[  ==]", "[ ===]", "[====]", "[=== ]", "[==  ]", "[=   ]",
+];
+
+/// Converts a frame set like `DOTS` into owned frames, e.g. to pass to `Spinner::frames`.
+pub fn from_str_frames(frames: &[&str]) -> Vec<String> {
+    frames.iter().map(|frame| frame.to_string()).collect()
+}
@@ -0,0 +1,41 @@
+use std::time::Duration;
+
+use crate::{crossterm::style::ContentStyle, grapheme::StyledGraphemes, pane::Pane, PaneFactory};
+
+use super::Spinner;
+
+/// Represents the state of a spinner followed by a message, e.g. `⠋ Loading`.
+#[derive(Clone)]
+pub struct State {
+    pub spinner: Spinner,
+
+    /// The message shown after the spinner.
+    pub message: String,
+
+    /// Style for the spinner.
+    pub spinner_style: ContentStyle,
+    /// Style for the message.
+    pub message_style: ContentStyle,
+
+    /// How often the spinner advances.
+    pub interval: Duration,
+}
+
+impl PaneFactory for State {
+    fn create_pane(&self, width: u16, height: u16) -> Pane {
+        let mut parts = vec![];
+        if !self.spinner.current().is_empty() {
+            parts.push(StyledGraphemes::from_str(
+                self.spinner.current(),
+                self.spinner_style,
+            ));
+            if !self.message.is_empty() {
+                parts.push(StyledGraphemes::from(" "));
+            }
+        }
+        parts.push(StyledGraphemes::from_str(&self.message, self.message_style));
+        let (matrix, _) =
+            StyledGraphemes::from_iter(parts).matrixify(width as usize, height as usize, 0);
+        Pane::new(matrix, 0)
+    }
+}
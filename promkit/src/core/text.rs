@@ -1,5 +1,10 @@
 use crate::{crossterm::style::ContentStyle, grapheme::StyledGraphemes, pane::Pane, PaneFactory};
 
+mod format;
+pub use format::{format_duration, format_eta};
+mod throttled;
+pub use throttled::ThrottledText;
+
 /// Represents the state of a text-based component within the application.
 ///
 /// This state encapsulates the properties and
@@ -11,21 +16,52 @@ pub struct State {
 
     /// Style for the text string.
     pub style: ContentStyle,
+
+    /// The first row shown, e.g. scrolled by a pager.
+    pub offset: usize,
 }
 
 impl State {
     pub fn replace(&mut self, renderer: Self) {
         *self = renderer;
     }
+
+    /// Returns the number of rows the text wraps into at the width.
+    pub fn rows(&self, width: u16) -> usize {
+        self.matrixify(width).len()
+    }
+
+    /// Splits the text into its lines, and wraps each of them into rows of the width.
+    fn matrixify(&self, width: u16) -> Vec<StyledGraphemes> {
+        if self.text.is_empty() {
+            return vec![];
+        }
+        self.text
+            .split('\n')
+            .flat_map(|line| {
+                let (rows, _) = StyledGraphemes::from_str(line, self.style).matrixify(
+                    width as usize,
+                    usize::MAX,
+                    0,
+                );
+                match rows.is_empty() {
+                    // An empty line still takes a row.
+                    true => vec![StyledGraphemes::default()],
+                    false => rows,
+                }
+            })
+            .collect()
+    }
 }
 
 impl PaneFactory for State {
     fn create_pane(&self, width: u16, height: u16) -> Pane {
-        let (matrix, _) = StyledGraphemes::from_str(&self.text, self.style).matrixify(
-            width as usize,
-            height as usize,
+        let rows = self.matrixify(width);
+        // Shows the rows from the offset, or the last rows if the offset is past them.
+        let start = self.offset.min(rows.len().saturating_sub(height as usize));
+        Pane::new(
+            rows.into_iter().skip(start).take(height as usize).collect(),
             0,
-        );
-        Pane::new(matrix, 0)
+        )
     }
 }
@@ -0,0 +1,63 @@
+use std::time::Duration;
+
+/// Formats a duration with units adapted to its magnitude,
+/// e.g. `950ms`, `3.2s`, `4m 05s` and `1h 02m`.
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs == 0 {
+        format!("{}ms", duration.as_millis())
+    } else if secs < 60 {
+        format!("{}.{}s", secs, duration.subsec_millis() / 100)
+    } else if secs < 60 * 60 {
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{}h {:02}m", secs / (60 * 60), secs / 60 % 60)
+    }
+}
+
+/// Estimates the remaining time from the progress so far, formatted by `format_duration`.
+/// Returns `--` while nothing is done, since no estimate can be made.
+pub fn format_eta(done: u64, total: u64, elapsed: Duration) -> String {
+    if done == 0 {
+        return String::from("--");
+    }
+    let remaining = total.saturating_sub(done);
+    let nanos = elapsed.as_nanos() * remaining as u128 / done as u128;
+    format_duration(Duration::from_nanos(nanos.min(u64::MAX as u128) as u64))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod format_duration {
+        use super::*;
+
+        #[test]
+        fn test() {
+            assert_eq!("0ms", format_duration(Duration::ZERO));
+            assert_eq!("950ms", format_duration(Duration::from_millis(950)));
+            assert_eq!("1.0s", format_duration(Duration::from_millis(1000)));
+            assert_eq!("3.2s", format_duration(Duration::from_millis(3250)));
+            assert_eq!("59.9s", format_duration(Duration::from_millis(59_999)));
+            assert_eq!("1m 00s", format_duration(Duration::from_secs(60)));
+            assert_eq!("4m 05s", format_duration(Duration::from_secs(245)));
+            assert_eq!("59m 59s", format_duration(Duration::from_secs(3599)));
+            assert_eq!("1h 02m", format_duration(Duration::from_secs(3725)));
+            assert_eq!("100h 00m", format_duration(Duration::from_secs(360_000)));
+        }
+    }
+
+    mod format_eta {
+        use super::*;
+
+        #[test]
+        fn test() {
+            assert_eq!("--", format_eta(0, 100, Duration::from_secs(10)));
+            assert_eq!("30.0s", format_eta(25, 100, Duration::from_secs(10)));
+            assert_eq!("4m 05s", format_eta(1, 2, Duration::from_secs(245)));
+            assert_eq!("0ms", format_eta(100, 100, Duration::from_secs(10)));
+            assert_eq!("0ms", format_eta(120, 100, Duration::from_secs(10)));
+        }
+    }
+}
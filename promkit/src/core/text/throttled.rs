@@ -0,0 +1,110 @@
+use std::time::{Duration, Instant};
+
+use crate::{pane::Pane, PaneFactory};
+
+use super::State;
+
+/// Wraps a text state so that its rendered text changes at most once per interval,
+/// which avoids flicker for fast-updating values such as counters.
+///
+/// Updates arriving within the interval are kept as pending,
+/// and applied by the next update after the interval or by `flush`.
+#[derive(Clone)]
+pub struct ThrottledText {
+    pub state: State,
+    interval: Duration,
+    last_update: Option<Instant>,
+    pending: Option<String>,
+}
+
+impl ThrottledText {
+    pub fn new(state: State, interval: Duration) -> Self {
+        Self {
+            state,
+            interval,
+            last_update: None,
+            pending: None,
+        }
+    }
+
+    /// Updates the text, returning whether the rendered text has changed.
+    pub fn update<T: Into<String>>(&mut self, text: T) -> bool {
+        self.update_at(text, Instant::now())
+    }
+
+    fn update_at<T: Into<String>>(&mut self, text: T, now: Instant) -> bool {
+        let due = self
+            .last_update
+            .is_none_or(|last| now.saturating_duration_since(last) >= self.interval);
+        if due {
+            self.state.text = text.into();
+            self.last_update = Some(now);
+            self.pending = None;
+        } else {
+            self.pending = Some(text.into());
+        }
+        due
+    }
+
+    /// Applies the pending text immediately, e.g. to show the final value.
+    pub fn flush(&mut self) {
+        if let Some(text) = self.pending.take() {
+            self.state.text = text;
+            self.last_update = Some(Instant::now());
+        }
+    }
+}
+
+impl PaneFactory for ThrottledText {
+    fn create_pane(&self, width: u16, height: u16) -> Pane {
+        self.state.create_pane(width, height)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn throttled(interval: Duration) -> ThrottledText {
+        ThrottledText::new(
+            State {
+                text: Default::default(),
+                style: Default::default(),
+                offset: 0,
+            },
+            interval,
+        )
+    }
+
+    mod update {
+        use super::*;
+
+        #[test]
+        fn test_throttles_within_interval() {
+            let mut text = throttled(Duration::from_millis(100));
+            let start = Instant::now();
+
+            assert!(text.update_at("1", start));
+            assert!(!text.update_at("2", start + Duration::from_millis(50)));
+            assert_eq!("1", text.state.text);
+            assert!(!text.update_at("3", start + Duration::from_millis(99)));
+            assert_eq!("1", text.state.text);
+            assert!(text.update_at("4", start + Duration::from_millis(100)));
+            assert_eq!("4", text.state.text);
+        }
+    }
+
+    mod flush {
+        use super::*;
+
+        #[test]
+        fn test_applies_pending() {
+            let mut text = throttled(Duration::from_secs(60));
+            text.update("1");
+            text.update("2");
+            assert_eq!("1", text.state.text);
+            text.flush();
+            assert_eq!("2", text.state.text);
+        }
+    }
+}
@@ -1,8 +1,9 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, ops::Range};
 
 use crate::{
     core::cursor::Cursor,
     grapheme::{StyledGrapheme, StyledGraphemes},
+    words,
 };
 
 mod history;
@@ -20,6 +21,69 @@ pub enum Mode {
     Overwrite,
 }
 
+/// Which characters of the input are masked, e.g. for password fields.
+#[derive(Clone, Copy, Debug)]
+pub enum MaskPolicy {
+    /// Masks all characters with the char.
+    All(char),
+    /// Masks the characters after the first delimiter (the first char) with the second char.
+    /// Nothing is masked until the delimiter is typed.
+    AfterDelimiter(char, char),
+    /// Masks all characters but the last N with the char.
+    LastVisible(usize, char),
+    /// Masks the characters flagged by the function with `*`.
+    /// The function receives the input and returns a flag per character (grapheme);
+    /// missing flags are treated as masked.
+    Custom(fn(&str) -> Vec<bool>),
+}
+
+/// How the input is split into words for the word-wise movement and erasure.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WordNavigation {
+    /// Words are separated by the characters in the set, e.g. `{' '}`.
+    CharSet(HashSet<char>),
+    /// Words follow the Unicode word boundaries (UAX #29), e.g. for `foo-bar` or CJK text,
+    /// skipping whitespace and punctuation.
+    Unicode,
+}
+
+impl From<char> for MaskPolicy {
+    fn from(mask: char) -> Self {
+        Self::All(mask)
+    }
+}
+
+impl MaskPolicy {
+    /// Returns the mask character if the grapheme at the index of the input is masked.
+    fn masks(&self, input: &StyledGraphemes) -> Vec<Option<char>> {
+        match *self {
+            Self::All(mask) => vec![Some(mask); input.len()],
+            Self::AfterDelimiter(delimiter, mask) => {
+                let delimiter = delimiter.to_string();
+                let start = input
+                    .iter()
+                    .position(|g| g.as_str() == delimiter)
+                    .map_or(input.len(), |i| i + 1);
+                (0..input.len())
+                    .map(|i| (i >= start).then_some(mask))
+                    .collect()
+            }
+            Self::LastVisible(visible, mask) => {
+                let end = input.len().saturating_sub(visible);
+                (0..input.len())
+                    .map(|i| (i < end).then_some(mask))
+                    .collect()
+            }
+            Self::Custom(f) => {
+                let flags = f(&input.to_string());
+                (0..input.len())
+                    .map(|i| flags.get(i).copied().unwrap_or(true).then_some('*'))
+                    .collect()
+            }
+        }
+    }
+}
+
 /// A text editor that supports basic editing operations
 /// such as insert, delete, and overwrite.
 /// It utilizes a cursor to navigate and manipulate the text.
@@ -41,8 +105,9 @@ impl TextEditor {
     pub fn new<S: AsRef<str>>(s: S) -> Self {
         let mut buf = s.as_ref().to_owned();
         buf.push(' ');
-        let pos = buf.len() - 1;
-        Self(Cursor::new(StyledGraphemes::from(buf), pos, false))
+        let graphemes = StyledGraphemes::from(buf);
+        let pos = graphemes.len() - 1;
+        Self(Cursor::new(graphemes, pos, false))
     }
 
     /// Returns the current text including the cursor.
@@ -62,31 +127,88 @@ impl TextEditor {
         self.0.position()
     }
 
-    /// Masks all characters except the cursor with the specified mask character.
-    pub fn masking(&self, mask: char) -> StyledGraphemes {
-        self.text()
-            .chars()
-            .into_iter()
-            .enumerate()
-            .map(|(i, c)| StyledGrapheme::from(if i == self.text().len() - 1 { c } else { mask }))
+    /// Masks the characters selected by the policy (e.g. a char masking all),
+    /// leaving the cursor as is. Each character is replaced one-for-one,
+    /// so the cursor position is the same as in `text`.
+    pub fn masking<M: Into<MaskPolicy>>(&self, mask: M) -> StyledGraphemes {
+        let input = self.text_without_cursor();
+        let masks = mask.into().masks(&input);
+        input
+            .iter()
+            .zip(masks)
+            .map(|(g, mask)| match mask {
+                Some(mask) => StyledGrapheme::from(mask),
+                None => g.clone(),
+            })
+            .chain(self.text().back().cloned())
             .collect::<StyledGraphemes>()
     }
 
     /// Replaces the current text with new text and positions the cursor at the end.
     pub fn replace(&mut self, new: &str) {
-        let mut buf = new.to_owned();
-        buf.push(' ');
-        let pos = buf.len() - 1;
-        *self = Self(Cursor::new(StyledGraphemes::from(buf), pos, false));
+        *self = Self::new(new);
     }
 
     /// Inserts a character at the current cursor position.
+    /// If the character extends the grapheme before the cursor
+    /// (e.g. a combining mark or a part of an emoji ZWJ sequence),
+    /// it is merged into that grapheme instead of creating a new one.
     pub fn insert(&mut self, ch: char) {
         let pos = self.position();
+        if pos > 0 && self.0.contents_mut()[pos - 1].try_extend(ch) {
+            return;
+        }
         self.0.contents_mut().insert(pos, StyledGrapheme::from(ch));
         self.forward();
     }
 
+    /// Inserts a string at the current cursor position in a single pass
+    /// and moves the cursor to the end of the inserted text.
+    pub fn insert_str(&mut self, s: &str) {
+        let pos = self.position();
+        let mut inserted = StyledGraphemes::from(s);
+        let len = inserted.len();
+
+        let contents = self.0.contents_mut();
+        let mut tail = contents.split_off(pos);
+        contents.append(&mut inserted);
+        contents.append(&mut tail);
+
+        self.0.move_to(pos + len);
+    }
+
+    /// Deletes the graphemes within the specified range.
+    /// The range is clamped to the text, so the trailing cursor position is never removed.
+    /// The cursor keeps pointing at the same grapheme if it is outside of the range,
+    /// otherwise it moves to the start of the range.
+    pub fn delete_range(&mut self, range: Range<usize>) {
+        let text_len = self.0.contents().len() - 1;
+        let start = range.start.min(text_len);
+        let end = range.end.clamp(start, text_len);
+
+        let pos = self.position();
+        self.0.contents_mut().drain(start..end);
+
+        if pos >= end {
+            self.0.move_to(pos - (end - start));
+        } else if pos > start {
+            self.0.move_to(start);
+        }
+    }
+
+    /// Moves the cursor to the specified position,
+    /// clamping it to the tail if the position is out of range.
+    pub fn set_position(&mut self, pos: usize) {
+        self.move_to(pos);
+    }
+
+    /// Moves the cursor to the grapheme at the index,
+    /// or to the tail (after the last grapheme) if the index is out of range.
+    pub fn move_to(&mut self, index: usize) {
+        let tail = self.0.contents().len() - 1;
+        self.0.move_to(index.min(tail));
+    }
+
     pub fn insert_chars(&mut self, vch: &Vec<char>) {
         for ch in vch {
             self.insert(*ch);
@@ -138,18 +260,10 @@ impl TextEditor {
         }
     }
 
-    /// Finds the nearest previous index of any character in `word_break_chars` from the cursor position.
+    /// Finds the start of the previous word from the cursor position,
+    /// skipping any characters in `word_break_chars` just before the cursor.
     fn find_previous_nearest_index(&self, word_break_chars: &HashSet<char>) -> usize {
-        let current_position = self.position();
-        self.text()
-            .chars()
-            .iter()
-            .enumerate()
-            .filter(|&(i, _)| i < current_position.saturating_sub(1))
-            .rev()
-            .find(|&(_, c)| word_break_chars.contains(c))
-            .map(|(i, _)| i + 1)
-            .unwrap_or(0)
+        words::previous_boundary(self.0.contents(), self.position(), word_break_chars)
     }
 
     /// Erases the text from the current cursor position to the nearest previous character in `word_break_chars`.
@@ -164,23 +278,11 @@ impl TextEditor {
         self.0.move_to(pos);
     }
 
-    /// Finds the nearest next index of any character in `word_break_chars` from the cursor position.
+    /// Finds the start of the next word from the cursor position.
     fn find_next_nearest_index(&self, word_break_chars: &HashSet<char>) -> usize {
-        let current_position = self.position();
-        self.text()
-            .chars()
-            .iter()
-            .enumerate()
-            .filter(|&(i, _)| i > current_position)
-            .find(|&(_, c)| word_break_chars.contains(c))
-            .map(|(i, _)| {
-                if i < self.0.contents().len() - 1 {
-                    i + 1
-                } else {
-                    self.0.contents().len() - 1
-                }
-            })
-            .unwrap_or(self.0.contents().len() - 1)
+        // Exclude the trailing cursor position, which may be a break char itself.
+        let tail = self.0.contents().len() - 1;
+        words::next_boundary(self.0.contents(), self.position(), word_break_chars).min(tail)
     }
 
     /// Erases the text from the current cursor position to the nearest next character in `word_break_chars`.
@@ -195,6 +297,125 @@ impl TextEditor {
         self.0.move_to(pos);
     }
 
+    /// Finds the start of the previous word from the cursor position, split by `navigation`.
+    fn find_previous_word_index(&self, navigation: &WordNavigation) -> usize {
+        match navigation {
+            WordNavigation::CharSet(word_break_chars) => {
+                self.find_previous_nearest_index(word_break_chars)
+            }
+            WordNavigation::Unicode => {
+                words::unicode_previous_boundary(self.0.contents(), self.position())
+            }
+        }
+    }
+
+    /// Finds the start of the next word from the cursor position, split by `navigation`.
+    fn find_next_word_index(&self, navigation: &WordNavigation) -> usize {
+        match navigation {
+            WordNavigation::CharSet(word_break_chars) => {
+                self.find_next_nearest_index(word_break_chars)
+            }
+            WordNavigation::Unicode => {
+                // Exclude the trailing cursor position, like `find_next_nearest_index`.
+                let tail = self.0.contents().len() - 1;
+                words::unicode_next_boundary(self.0.contents(), self.position()).min(tail)
+            }
+        }
+    }
+
+    /// Moves the cursor to the start of the previous word, split by `navigation`.
+    pub fn move_word_back(&mut self, navigation: &WordNavigation) {
+        let pos = self.find_previous_word_index(navigation);
+        self.0.move_to(pos);
+    }
+
+    /// Moves the cursor to the start of the next word, split by `navigation`.
+    pub fn move_word_forward(&mut self, navigation: &WordNavigation) {
+        let pos = self.find_next_word_index(navigation);
+        self.0.move_to(pos);
+    }
+
+    /// Erases the text from the start of the previous word to the cursor, split by `navigation`.
+    pub fn erase_word_back(&mut self, navigation: &WordNavigation) {
+        let pos = self.find_previous_word_index(navigation);
+        self.erase_to_position(pos);
+    }
+
+    /// Erases the text from the cursor to the start of the next word, split by `navigation`.
+    pub fn erase_word_forward(&mut self, navigation: &WordNavigation) {
+        let pos = self.find_next_word_index(navigation);
+        self.erase_to_position(pos);
+    }
+
+    /// Swaps the grapheme before the cursor with the one at it, and moves the cursor forward.
+    /// At the tail, swaps the last two graphemes instead, leaving the cursor at the tail.
+    /// Returns `false` without changes at the head or if there are fewer than two graphemes.
+    pub fn transpose_chars(&mut self) -> bool {
+        let len = self.0.contents().len() - 1;
+        let pos = self.position();
+        if pos == 0 || len < 2 {
+            return false;
+        }
+        let pos = pos.min(len - 1);
+        self.0.contents_mut().swap(pos - 1, pos);
+        self.0.move_to(pos + 1);
+        true
+    }
+
+    /// Uppercases the text from the cursor to the end of the word (see `convert_word`).
+    pub fn upcase_word(&mut self, word_break_chars: &HashSet<char>) -> bool {
+        self.convert_word(word_break_chars, |_, s| s.to_uppercase())
+    }
+
+    /// Lowercases the text from the cursor to the end of the word (see `convert_word`).
+    pub fn downcase_word(&mut self, word_break_chars: &HashSet<char>) -> bool {
+        self.convert_word(word_break_chars, |_, s| s.to_lowercase())
+    }
+
+    /// Uppercases the grapheme at the cursor and lowercases the rest of the word
+    /// (see `convert_word`).
+    pub fn capitalize_word(&mut self, word_break_chars: &HashSet<char>) -> bool {
+        self.convert_word(word_break_chars, |i, s| match i {
+            0 => s.to_uppercase(),
+            _ => s.to_lowercase(),
+        })
+    }
+
+    /// Converts each grapheme from the cursor to the end of the word with `f`,
+    /// given its index in the range, and moves the cursor past the word.
+    /// If the cursor is on characters in `word_break_chars`, the word after them is converted.
+    /// Returns `false` without changes if there is no word at or after the cursor.
+    fn convert_word<F: Fn(usize, &str) -> String>(
+        &mut self,
+        word_break_chars: &HashSet<char>,
+        f: F,
+    ) -> bool {
+        let tail = self.0.contents().len() - 1;
+        let contents = self.0.contents();
+        let mut start = self.position();
+        while start < tail && word_break_chars.contains(&contents[start].base_char()) {
+            start += 1;
+        }
+        let end = words::token_range(contents, start, word_break_chars)
+            .end
+            .clamp(start, tail);
+        if start == end {
+            return false;
+        }
+
+        let converted = contents
+            .iter()
+            .skip(start)
+            .take(end - start)
+            .enumerate()
+            .map(|(i, g)| f(i, g.as_str()))
+            .collect::<String>();
+        let len = StyledGraphemes::from(converted.as_str()).len();
+        self.0.contents_mut().replace_range(start..end, converted);
+        self.0.move_to(start + len);
+        true
+    }
+
     /// Moves the cursor to the beginning of the text.
     pub fn move_to_head(&mut self) {
         self.0.move_to_head()
@@ -231,13 +452,70 @@ mod test {
     }
 
     mod masking {
-        use crate::{grapheme::StyledGraphemes, text_editor::test::new_with_position};
+        use crate::{
+            grapheme::StyledGraphemes,
+            text_editor::{test::new_with_position, MaskPolicy},
+        };
 
         #[test]
         fn test() {
             let txt = new_with_position(String::from("abcde "), 0);
             assert_eq!(StyledGraphemes::from("***** "), txt.masking('*'))
         }
+
+        #[test]
+        fn test_after_delimiter() {
+            let txt = new_with_position(String::from("user:pass "), 2);
+            assert_eq!(
+                StyledGraphemes::from("user:**** "),
+                txt.masking(MaskPolicy::AfterDelimiter(':', '*'))
+            );
+            // Nothing is masked until the delimiter is typed.
+            let txt = new_with_position(String::from("user "), 4);
+            assert_eq!(
+                StyledGraphemes::from("user "),
+                txt.masking(MaskPolicy::AfterDelimiter(':', '*'))
+            );
+        }
+
+        #[test]
+        fn test_after_multibyte_delimiter() {
+            let txt = new_with_position(String::from("ユーザ：秘密 "), 0);
+            let masked = txt.masking(MaskPolicy::AfterDelimiter('：', '•'));
+            assert_eq!(StyledGraphemes::from("ユーザ：•• "), masked);
+            // The characters are replaced one-for-one.
+            assert_eq!(txt.text().len(), masked.len());
+        }
+
+        #[test]
+        fn test_last_visible() {
+            let txt = new_with_position(String::from("sk-12345678 "), 0);
+            assert_eq!(
+                StyledGraphemes::from("*******5678 "),
+                txt.masking(MaskPolicy::LastVisible(4, '*'))
+            );
+            let txt = new_with_position(String::from("ab "), 0);
+            assert_eq!(
+                StyledGraphemes::from("ab "),
+                txt.masking(MaskPolicy::LastVisible(4, '*'))
+            );
+        }
+
+        #[test]
+        fn test_custom() {
+            let txt = new_with_position(String::from("a1b2c "), 0);
+            assert_eq!(
+                StyledGraphemes::from("a*b** "),
+                txt.masking(MaskPolicy::Custom(|input| {
+                    // Mask the digits, and the rest as the flags are missing.
+                    input
+                        .chars()
+                        .take(4)
+                        .map(|ch| ch.is_ascii_digit())
+                        .collect()
+                }))
+            );
+        }
     }
 
     mod erase {
@@ -291,6 +569,31 @@ mod test {
             assert_eq!(StyledGraphemes::from("abc "), txt.text());
             assert_eq!(0, txt.position());
         }
+
+        #[test]
+        fn test_with_grapheme_cluster() {
+            let mut txt = TextEditor::new("ok👨‍👩‍👧");
+            txt.erase();
+            assert_eq!(StyledGraphemes::from("ok "), txt.text());
+            assert_eq!(2, txt.position());
+        }
+    }
+
+    mod forward_and_backward {
+        use super::*;
+
+        #[test]
+        fn test_with_grapheme_clusters() {
+            let mut txt = TextEditor::new("a👨‍👩‍👧e\u{301}");
+            txt.move_to_head();
+            txt.forward();
+            assert_eq!(1, txt.position());
+            txt.forward();
+            assert_eq!(2, txt.position());
+            assert_eq!("e\u{301}", txt.text()[txt.position()].as_str());
+            txt.backward();
+            assert_eq!("👨‍👩‍👧", txt.text()[txt.position()].as_str());
+        }
     }
 
     mod find_previous_nearest_index {
@@ -311,6 +614,12 @@ mod test {
             let txt = new_with_position(String::from("koko momo jojo "), 7); // indicate `m`.
             assert_eq!(0, txt.find_previous_nearest_index(&HashSet::from(['z'])));
         }
+
+        #[test]
+        fn test_over_multiple_spaces() {
+            let txt = new_with_position(String::from("ab   cd "), 5); // indicate `c`.
+            assert_eq!(0, txt.find_previous_nearest_index(&HashSet::from([' '])));
+        }
     }
 
     mod find_next_nearest_index {
@@ -333,6 +642,87 @@ mod test {
         }
     }
 
+    mod move_word_back {
+        use std::collections::HashSet;
+
+        use crate::text_editor::{test::new_with_position, WordNavigation};
+
+        #[test]
+        fn test_hyphenated_identifier() {
+            let mut txt = new_with_position(String::from("foo-bar_baz "), 11);
+            txt.move_word_back(&WordNavigation::Unicode);
+            assert_eq!(4, txt.position());
+            txt.move_word_back(&WordNavigation::Unicode);
+            assert_eq!(0, txt.position());
+
+            // The whole identifier is a word with the default set.
+            let mut txt = new_with_position(String::from("foo-bar_baz "), 11);
+            txt.move_word_back(&WordNavigation::CharSet(HashSet::from([' '])));
+            assert_eq!(0, txt.position());
+        }
+
+        #[test]
+        fn test_cjk() {
+            let mut txt = new_with_position(String::from("日本語テスト "), 6);
+            txt.move_word_back(&WordNavigation::Unicode);
+            assert_eq!(3, txt.position());
+            txt.move_word_back(&WordNavigation::Unicode);
+            assert_eq!(2, txt.position());
+        }
+
+        #[test]
+        fn test_emoji_sequence() {
+            // Each emoji is a word, along with its modifier.
+            let mut txt = new_with_position(String::from("ok 👍🏽👍🏽 "), 5);
+            txt.move_word_back(&WordNavigation::Unicode);
+            assert_eq!(4, txt.position());
+            txt.move_word_back(&WordNavigation::Unicode);
+            assert_eq!(3, txt.position());
+        }
+    }
+
+    mod move_word_forward {
+        use crate::text_editor::{test::new_with_position, WordNavigation};
+
+        #[test]
+        fn test() {
+            let mut txt = new_with_position(String::from("foo-bar 日本 "), 0);
+            txt.move_word_forward(&WordNavigation::Unicode);
+            assert_eq!(4, txt.position());
+            txt.move_word_forward(&WordNavigation::Unicode);
+            assert_eq!(8, txt.position());
+            txt.move_word_forward(&WordNavigation::Unicode);
+            assert_eq!(9, txt.position());
+            // Stops at the tail.
+            txt.move_word_forward(&WordNavigation::Unicode);
+            assert_eq!(10, txt.position());
+        }
+    }
+
+    mod erase_word_back {
+        use crate::text_editor::{test::new_with_position, WordNavigation};
+
+        #[test]
+        fn test() {
+            let mut txt = new_with_position(String::from("foo-bar_baz "), 11);
+            txt.erase_word_back(&WordNavigation::Unicode);
+            assert_eq!("foo-", txt.text_without_cursor().to_string());
+            assert_eq!(4, txt.position());
+        }
+    }
+
+    mod erase_word_forward {
+        use crate::text_editor::{test::new_with_position, WordNavigation};
+
+        #[test]
+        fn test() {
+            let mut txt = new_with_position(String::from("日本語 "), 0);
+            txt.erase_word_forward(&WordNavigation::Unicode);
+            assert_eq!("本語", txt.text_without_cursor().to_string());
+            assert_eq!(0, txt.position());
+        }
+    }
+
     mod insert {
         use crate::text_editor::test::new_with_position;
 
@@ -394,6 +784,186 @@ mod test {
             assert_eq!(new.text(), txt.text());
             assert_eq!(new.position(), txt.position());
         }
+
+        #[test]
+        fn test_with_combining_mark() {
+            let mut txt = new_with_position(
+                String::from("cafe "),
+                4, // indicate tail.
+            );
+            let new = new_with_position(
+                String::from("cafe\u{301} "),
+                4, // indicate tail.
+            );
+            txt.insert('\u{301}');
+            assert_eq!(new.text(), txt.text());
+            assert_eq!(new.position(), txt.position());
+        }
+
+        #[test]
+        fn test_with_zwj_sequence() {
+            let mut txt = TextEditor::default();
+            for ch in "👨‍👩‍👧".chars() {
+                txt.insert(ch);
+            }
+            assert_eq!(2, txt.text().len());
+            assert_eq!(1, txt.position());
+        }
+    }
+
+    mod insert_str {
+        use crate::text_editor::test::new_with_position;
+
+        use super::super::*;
+
+        #[test]
+        fn test_for_empty() {
+            let mut txt = TextEditor::default();
+            let new = new_with_position(
+                String::from("def "),
+                3, // indicate tail.
+            );
+            txt.insert_str("def");
+            assert_eq!(new.text(), txt.text());
+            assert_eq!(new.position(), txt.position());
+        }
+
+        #[test]
+        fn test_at_non_edge() {
+            let mut txt = new_with_position(
+                String::from("abc "),
+                1, // indicate `b`.
+            );
+            let new = new_with_position(
+                String::from("adefbc "),
+                4, // indicate `b`.
+            );
+            txt.insert_str("def");
+            assert_eq!(new.text(), txt.text());
+            assert_eq!(new.position(), txt.position());
+        }
+
+        #[test]
+        fn test_at_tail() {
+            let mut txt = new_with_position(
+                String::from("abc "),
+                3, // indicate tail.
+            );
+            let new = new_with_position(
+                String::from("abcdef "),
+                6, // indicate tail.
+            );
+            txt.insert_str("def");
+            assert_eq!(new.text(), txt.text());
+            assert_eq!(new.position(), txt.position());
+        }
+
+        #[test]
+        fn test_at_head() {
+            let mut txt = new_with_position(
+                String::from("abc "),
+                0, // indicate `a`.
+            );
+            let new = new_with_position(
+                String::from("defabc "),
+                3, // indicate `a`.
+            );
+            txt.insert_str("def");
+            assert_eq!(new.text(), txt.text());
+            assert_eq!(new.position(), txt.position());
+        }
+    }
+
+    mod delete_range {
+        use crate::text_editor::test::new_with_position;
+
+        use super::super::*;
+
+        #[test]
+        fn test_for_empty() {
+            let mut txt = TextEditor::default();
+            txt.delete_range(0..3);
+            assert_eq!(StyledGraphemes::from(" "), txt.text());
+            assert_eq!(0, txt.position());
+        }
+
+        #[test]
+        fn test_before_cursor() {
+            let mut txt = new_with_position(
+                String::from("abcde "),
+                3, // indicate `d`.
+            );
+            let new = new_with_position(
+                String::from("ade "),
+                1, // indicate `d`.
+            );
+            txt.delete_range(1..3);
+            assert_eq!(new.text(), txt.text());
+            assert_eq!(new.position(), txt.position());
+        }
+
+        #[test]
+        fn test_around_cursor() {
+            let mut txt = new_with_position(
+                String::from("abcde "),
+                2, // indicate `c`.
+            );
+            let new = new_with_position(
+                String::from("ae "),
+                1, // indicate `e`.
+            );
+            txt.delete_range(1..4);
+            assert_eq!(new.text(), txt.text());
+            assert_eq!(new.position(), txt.position());
+        }
+
+        #[test]
+        fn test_after_cursor() {
+            let mut txt = new_with_position(
+                String::from("abcde "),
+                0, // indicate `a`.
+            );
+            let new = new_with_position(
+                String::from("abe "),
+                0, // indicate `a`.
+            );
+            txt.delete_range(2..4);
+            assert_eq!(new.text(), txt.text());
+            assert_eq!(new.position(), txt.position());
+        }
+
+        #[test]
+        fn test_beyond_tail() {
+            let mut txt = new_with_position(
+                String::from("abc "),
+                3, // indicate tail.
+            );
+            let new = new_with_position(
+                String::from("a "),
+                1, // indicate tail.
+            );
+            txt.delete_range(1..100);
+            assert_eq!(new.text(), txt.text());
+            assert_eq!(new.position(), txt.position());
+        }
+    }
+
+    mod set_position {
+        use crate::text_editor::test::new_with_position;
+
+        #[test]
+        fn test() {
+            let mut txt = new_with_position(String::from("abc "), 0);
+            txt.set_position(2);
+            assert_eq!(2, txt.position());
+        }
+
+        #[test]
+        fn test_beyond_tail() {
+            let mut txt = new_with_position(String::from("abc "), 0);
+            txt.set_position(100);
+            assert_eq!(3, txt.position());
+        }
     }
 
     mod overwrite {
@@ -678,4 +1248,167 @@ mod test {
             assert_eq!(new.position(), txt.position());
         }
     }
+
+    mod transpose_chars {
+        use crate::text_editor::test::new_with_position;
+
+        use super::super::*;
+
+        #[test]
+        fn test_for_empty() {
+            let mut txt = TextEditor::default();
+            assert!(!txt.transpose_chars());
+            assert_eq!(StyledGraphemes::from(" "), txt.text());
+        }
+
+        #[test]
+        fn test_at_non_edge() {
+            let mut txt = new_with_position(
+                String::from("abc "),
+                1, // indicate `b`.
+            );
+            assert!(txt.transpose_chars());
+            assert_eq!(StyledGraphemes::from("bac "), txt.text());
+            assert_eq!(2, txt.position());
+        }
+
+        #[test]
+        fn test_at_tail() {
+            let mut txt = new_with_position(
+                String::from("abc "),
+                3, // indicate tail.
+            );
+            assert!(txt.transpose_chars());
+            assert_eq!(StyledGraphemes::from("acb "), txt.text());
+            assert_eq!(3, txt.position());
+        }
+
+        #[test]
+        fn test_at_head() {
+            let mut txt = new_with_position(
+                String::from("abc "),
+                0, // indicate `a`.
+            );
+            assert!(!txt.transpose_chars());
+            assert_eq!(StyledGraphemes::from("abc "), txt.text());
+            assert_eq!(0, txt.position());
+        }
+
+        #[test]
+        fn test_with_single_char() {
+            let mut txt = TextEditor::new("a");
+            assert!(!txt.transpose_chars());
+            assert_eq!(StyledGraphemes::from("a "), txt.text());
+        }
+    }
+
+    mod upcase_word {
+        use crate::text_editor::test::new_with_position;
+
+        use super::super::*;
+
+        #[test]
+        fn test_at_non_edge() {
+            let mut txt = new_with_position(
+                String::from("abc def "),
+                1, // indicate `b`.
+            );
+            assert!(txt.upcase_word(&HashSet::from([' '])));
+            assert_eq!(StyledGraphemes::from("aBC def "), txt.text());
+            assert_eq!(3, txt.position());
+        }
+
+        #[test]
+        fn test_on_word_break() {
+            let mut txt = new_with_position(
+                String::from("abc  def "),
+                3, // indicate the space after `c`.
+            );
+            assert!(txt.upcase_word(&HashSet::from([' '])));
+            assert_eq!(StyledGraphemes::from("abc  DEF "), txt.text());
+            assert_eq!(8, txt.position());
+        }
+
+        #[test]
+        fn test_at_tail() {
+            let mut txt = new_with_position(
+                String::from("abc "),
+                3, // indicate tail.
+            );
+            assert!(!txt.upcase_word(&HashSet::from([' '])));
+            assert_eq!(StyledGraphemes::from("abc "), txt.text());
+            assert_eq!(3, txt.position());
+        }
+
+        #[test]
+        fn test_with_changing_length() {
+            let mut txt = new_with_position(String::from("straße x "), 0);
+            assert!(txt.upcase_word(&HashSet::from([' '])));
+            assert_eq!(StyledGraphemes::from("STRASSE x "), txt.text());
+            assert_eq!(7, txt.position());
+        }
+    }
+
+    mod downcase_word {
+        use crate::text_editor::test::new_with_position;
+
+        use super::super::*;
+
+        #[test]
+        fn test_at_non_edge() {
+            let mut txt = new_with_position(
+                String::from("ABC DEF "),
+                1, // indicate `B`.
+            );
+            assert!(txt.downcase_word(&HashSet::from([' '])));
+            assert_eq!(StyledGraphemes::from("Abc DEF "), txt.text());
+            assert_eq!(3, txt.position());
+        }
+
+        #[test]
+        fn test_at_head() {
+            let mut txt = new_with_position(
+                String::from("ABC "),
+                0, // indicate `A`.
+            );
+            assert!(txt.downcase_word(&HashSet::from([' '])));
+            assert_eq!(StyledGraphemes::from("abc "), txt.text());
+            assert_eq!(3, txt.position());
+        }
+    }
+
+    mod capitalize_word {
+        use crate::text_editor::test::new_with_position;
+
+        use super::super::*;
+
+        #[test]
+        fn test_at_non_edge() {
+            let mut txt = new_with_position(
+                String::from("hELLO wORLD "),
+                1, // indicate `E`.
+            );
+            assert!(txt.capitalize_word(&HashSet::from([' '])));
+            assert_eq!(StyledGraphemes::from("hEllo wORLD "), txt.text());
+            assert_eq!(5, txt.position());
+        }
+
+        #[test]
+        fn test_on_word_break() {
+            let mut txt = new_with_position(
+                String::from("hello wORLD "),
+                5, // indicate the space.
+            );
+            assert!(txt.capitalize_word(&HashSet::from([' '])));
+            assert_eq!(StyledGraphemes::from("hello World "), txt.text());
+            assert_eq!(11, txt.position());
+        }
+
+        #[test]
+        fn test_for_empty() {
+            let mut txt = TextEditor::default();
+            assert!(!txt.capitalize_word(&HashSet::from([' '])));
+            assert_eq!(StyledGraphemes::from(" "), txt.text());
+        }
+    }
 }
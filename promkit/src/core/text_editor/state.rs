@@ -1,8 +1,14 @@
-use std::collections::HashSet;
+use std::{cell::Cell, collections::HashSet};
 
-use crate::{crossterm::style::ContentStyle, grapheme::StyledGraphemes, pane::Pane, PaneFactory};
+use crate::{
+    crossterm::style::{Color, ContentStyle},
+    grapheme::StyledGraphemes,
+    pane::Pane,
+    style::StyleBuilder,
+    PaneFactory,
+};
 
-use super::{History, Mode, TextEditor};
+use super::{History, MaskPolicy, Mode, TextEditor, WordNavigation};
 
 #[derive(Clone)]
 pub struct State {
@@ -13,8 +19,8 @@ pub struct State {
 
     /// Prompt string displayed before the input text.
     pub prefix: String,
-    /// Optional character used for masking the input string (e.g., for password fields).
-    pub mask: Option<char>,
+    /// Optional policy for masking the input string (e.g., for password fields).
+    pub mask: Option<MaskPolicy>,
 
     /// Style applied to the prompt string.
     pub prefix_style: ContentStyle,
@@ -27,8 +33,121 @@ pub struct State {
     pub edit_mode: Mode,
     /// Characters to be for word break.
     pub word_break_chars: HashSet<char>,
+    /// Whether the word-wise movement and erasure follow the Unicode word boundaries
+    /// instead of `word_break_chars`.
+    pub unicode_words: bool,
+    /// Text shown flush-right on the input line (like zsh's RPROMPT), or empty for none.
+    /// It is hidden as the input grows into it, and is never part of the input.
+    pub right_prompt: String,
+    /// Style applied to the right prompt.
+    pub right_prompt_style: ContentStyle,
     /// Number of lines available for rendering.
     pub lines: Option<usize>,
+    /// The first row shown when the input spans more rows than fit,
+    /// kept across renders so that the view scrolls a row at a time with the cursor.
+    pub scroll_top: Cell<usize>,
+    /// Whether the input is shown in a single row scrolling horizontally with the cursor,
+    /// with `<` and `>` marking the hidden parts, instead of wrapping into more rows.
+    pub horizontal_scroll: bool,
+    /// The first grapheme of the input shown when scrolling horizontally,
+    /// kept across renders like `scroll_top`.
+    pub scroll_left: Cell<usize>,
+}
+
+impl Default for State {
+    /// Returns an empty editor styled like the `Readline` preset.
+    fn default() -> Self {
+        Self {
+            texteditor: Default::default(),
+            history: Default::default(),
+            prefix: String::from("❯❯ "),
+            mask: Default::default(),
+            prefix_style: StyleBuilder::new().fgc(Color::DarkGreen).build(),
+            active_char_style: StyleBuilder::new().bgc(Color::DarkCyan).build(),
+            inactive_char_style: StyleBuilder::new().build(),
+            edit_mode: Default::default(),
+            word_break_chars: HashSet::from([' ']),
+            unicode_words: false,
+            right_prompt: Default::default(),
+            right_prompt_style: Default::default(),
+            lines: Default::default(),
+            scroll_top: Default::default(),
+            horizontal_scroll: false,
+            scroll_left: Default::default(),
+        }
+    }
+}
+
+impl State {
+    /// Returns how the input is split into words for the word-wise movement and erasure.
+    pub fn word_navigation(&self) -> WordNavigation {
+        match self.unicode_words {
+            true => WordNavigation::Unicode,
+            false => WordNavigation::CharSet(self.word_break_chars.clone()),
+        }
+    }
+
+    /// Returns the position in the text of the cell at the column and the row
+    /// of the pane created for the width and the height (the row counts from its first visible row),
+    /// e.g. to move the cursor to a mouse click. Returns `None` if the row is not shown.
+    ///
+    /// A cell on the prefix maps to the head of the text,
+    /// and a cell past the text maps to the position past the last grapheme.
+    pub fn position_at(&self, width: u16, height: u16, column: u16, row: u16) -> Option<usize> {
+        if self.horizontal_scroll {
+            return (row == 0).then(|| self.position_at_column(width, column));
+        }
+
+        let height = match self.lines {
+            Some(lines) => lines.min(height as usize),
+            None => height as usize,
+        };
+        let pane = self.create_pane(width, height as u16);
+        let visible = pane.visible_range(height);
+        let row = visible.start + row as usize;
+        if row >= visible.end {
+            return None;
+        }
+
+        let before: usize = pane.rows()[..row].iter().map(|row| row.len()).sum();
+        let mut right_edge = 0;
+        let in_row = pane.rows()[row]
+            .iter()
+            .take_while(|g| {
+                right_edge += g.width();
+                right_edge <= column as usize
+            })
+            .count();
+        let prefix = StyledGraphemes::from(self.prefix.as_str()).len();
+        Some(
+            (before + in_row)
+                .saturating_sub(prefix)
+                .min(self.texteditor.text_without_cursor().len()),
+        )
+    }
+
+    /// Same as `position_at` for the single row scrolled horizontally,
+    /// where a cell on the `<` marker maps to the first grapheme shown.
+    fn position_at_column(&self, width: u16, column: u16) -> usize {
+        // Updates the scroll for the width.
+        self.create_pane(width, 1);
+        let left = self.scroll_left.get();
+        let text = match self.mask {
+            Some(mask) => self.texteditor.masking(mask),
+            None => self.texteditor.text(),
+        };
+        let mut right_edge =
+            StyledGraphemes::from(self.prefix.as_str()).widths() + (left > 0) as usize;
+        let in_row = text
+            .iter()
+            .skip(left)
+            .take_while(|g| {
+                right_edge += g.width();
+                right_edge <= column as usize
+            })
+            .count();
+        (left + in_row).min(self.texteditor.text_without_cursor().len())
+    }
 }
 
 impl PaneFactory for State {
@@ -36,6 +155,7 @@ impl PaneFactory for State {
         let mut buf = StyledGraphemes::default();
 
         let mut styled_prefix = StyledGraphemes::from_str(&self.prefix, self.prefix_style);
+        let styled_prefix_len = styled_prefix.len();
 
         buf.append(&mut styled_prefix);
 
@@ -48,21 +168,148 @@ impl PaneFactory for State {
             .apply_style(self.inactive_char_style)
             .apply_style_at(self.texteditor.position(), self.active_char_style);
 
-        buf.append(&mut styled);
+        if self.horizontal_scroll {
+            let (mut row, left) = styled.scrolled(
+                (width as usize).saturating_sub(buf.widths()),
+                self.texteditor.position(),
+                self.scroll_left.get(),
+            );
+            self.scroll_left.set(left);
+            buf.append(&mut row);
+        } else {
+            buf.append(&mut styled);
+        }
+
+        if !self.right_prompt.is_empty() {
+            // Keep a space between the input and the right prompt.
+            let available = (width as usize).saturating_sub(buf.widths() + 1);
+            if available > 0 {
+                let mut right =
+                    StyledGraphemes::from_str(&self.right_prompt, self.right_prompt_style)
+                        .truncate_with_ellipsis(available);
+                let padding = width as usize - buf.widths() - right.widths();
+                buf.append(&mut StyledGraphemes::from(" ".repeat(padding)));
+                buf.append(&mut right);
+            }
+        }
+
+        if self.horizontal_scroll {
+            return Pane::new(vec![buf], 0);
+        }
 
         let height = match self.lines {
             Some(lines) => lines.min(height as usize),
             None => height as usize,
         };
 
-        let (matrix, offset) = buf.matrixify(
-            width as usize,
-            height,
-            (StyledGraphemes::from_str(&self.prefix, self.prefix_style).widths()
-                + self.texteditor.position())
-                / width as usize,
-        );
+        let cursor = styled_prefix_len + self.texteditor.position();
+        let (matrix, top, cursor_row) =
+            buf.windowed(width as usize, height, cursor, self.scroll_top.get());
+        self.scroll_top.set(top);
+
+        // Starting from the cursor row, so that it stays visible in a shorter viewport.
+        Pane::new(matrix, cursor_row.saturating_sub(top))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod create_pane {
+        use super::*;
+
+        #[test]
+        fn test_scrolls_a_row_at_a_time() {
+            // The prefix and 28 characters fill 10 rows of 3 columns.
+            let mut state = State {
+                texteditor: TextEditor::new("a".repeat(28)),
+                prefix: String::from("> "),
+                ..Default::default()
+            };
+            state.texteditor.move_to_head();
+            let mut tops = vec![];
+            for _ in 0..10 {
+                let pane = state.create_pane(3, 3);
+                assert_eq!(3, pane.rows().len());
+                tops.push(state.scroll_top.get());
+                for _ in 0..3 {
+                    state.texteditor.forward();
+                }
+            }
+            assert_eq!(vec![0, 0, 0, 1, 2, 3, 4, 5, 6, 7], tops);
+        }
+
+        #[test]
+        fn test_scrolls_wide_chars() {
+            // A wide char never fits at the end of a row of 3 columns,
+            // so the prefix and each of the 8 graphemes take a row of their own.
+            let mut state = State {
+                texteditor: TextEditor::new("日本語😀テキスト"),
+                prefix: String::from("> "),
+                ..Default::default()
+            };
+            state.texteditor.move_to_head();
+            let mut tops = vec![];
+            for _ in 0..8 {
+                let pane = state.create_pane(3, 3);
+                let cursor = state.texteditor.text()[state.texteditor.position()].base_char();
+                assert!(pane
+                    .rows()
+                    .iter()
+                    .any(|row| row.to_string().contains(cursor)));
+                tops.push(state.scroll_top.get());
+                state.texteditor.forward();
+            }
+            assert_eq!(vec![0, 0, 1, 2, 3, 4, 5, 6], tops);
+        }
+    }
+
+    mod position_at {
+        use super::*;
+
+        fn state(text: &str) -> State {
+            State {
+                texteditor: TextEditor::new(text),
+                prefix: String::from("❯❯ "),
+                ..Default::default()
+            }
+        }
+
+        #[test]
+        fn test_single_row() {
+            let state = state("a日b");
+            assert_eq!(Some(0), state.position_at(20, 10, 0, 0));
+            assert_eq!(Some(0), state.position_at(20, 10, 3, 0));
+            // Both cells of the wide grapheme.
+            assert_eq!(Some(1), state.position_at(20, 10, 4, 0));
+            assert_eq!(Some(1), state.position_at(20, 10, 5, 0));
+            assert_eq!(Some(2), state.position_at(20, 10, 6, 0));
+            // Past the text.
+            assert_eq!(Some(3), state.position_at(20, 10, 15, 0));
+            assert_eq!(None, state.position_at(20, 10, 0, 1));
+        }
+
+        #[test]
+        fn test_wrapped() {
+            // "❯❯ abcde" and "fgh " in 8 columns.
+            let state = state("abcdefgh");
+            assert_eq!(Some(5), state.position_at(8, 10, 0, 1));
+            assert_eq!(Some(7), state.position_at(8, 10, 2, 1));
+        }
 
-        Pane::new(matrix, offset)
+        #[test]
+        fn test_horizontal_scroll() {
+            // "❯❯ <fgh " in 8 columns, with the cursor at the end.
+            let state = State {
+                horizontal_scroll: true,
+                ..state("abcdefgh")
+            };
+            assert_eq!(Some(5), state.position_at(8, 10, 3, 0));
+            assert_eq!(Some(5), state.position_at(8, 10, 4, 0));
+            assert_eq!(Some(7), state.position_at(8, 10, 6, 0));
+            assert_eq!(Some(8), state.position_at(8, 10, 7, 0));
+            assert_eq!(None, state.position_at(8, 10, 0, 1));
+        }
     }
 }
@@ -1,8 +1,10 @@
 mod node;
 
+use std::collections::BTreeMap;
+
 use crate::core::cursor::Cursor;
 
-pub use node::{Kind, Node, Path};
+pub use node::{Kind, LoadFn, Loader, Node, Path};
 mod state;
 pub use state::State;
 
@@ -12,6 +14,9 @@ pub use state::State;
 pub struct Tree {
     root: Node,
     cursor: Cursor<Vec<Kind>>,
+    /// The marked leaves with the ids along their paths, in the order of the tree.
+    /// Keyed by the index paths, which are not affected by folding.
+    marked: BTreeMap<Path, Vec<String>>,
 }
 
 impl Tree {
@@ -24,6 +29,7 @@ impl Tree {
         Self {
             root: root.clone(),
             cursor: Cursor::new(root.flatten_visibles(), 0, false),
+            marked: BTreeMap::new(),
         }
     }
 
@@ -32,6 +38,14 @@ impl Tree {
         self.cursor.contents().clone()
     }
 
+    /// Returns up to `n` visible nodes starting from the current position,
+    /// without copying the others.
+    pub fn kinds_from_current(&self, n: usize) -> &[Kind] {
+        let kinds = self.cursor.contents();
+        let start = self.position().min(kinds.len());
+        &kinds[start..(start + n).min(kinds.len())]
+    }
+
     /// Returns the current position of the cursor within the tree.
     pub fn position(&self) -> usize {
         self.cursor.position()
@@ -41,24 +55,71 @@ impl Tree {
     pub fn get(&self) -> Vec<String> {
         let kind = self.cursor.contents()[self.position()].clone();
         match kind {
-            Kind::Folded { id, path } | Kind::Unfolded { id, path } => {
+            Kind::Folded { id, path } | Kind::Unfolded { id, path } | Kind::Leaf { id, path } => {
                 let mut ret = self.root.get_waypoints(&path);
                 ret.push(id.to_string());
                 ret
             }
+            // The placeholder stands for the node being loaded.
+            Kind::Loading { path } => self.root.get_waypoints(&path),
         }
     }
 
+    /// Marks the current node if it is a leaf, or unmarks it if already marked.
+    /// Returns `false` without marking if the current node is not a leaf.
+    pub fn toggle_mark(&mut self) -> bool {
+        let Kind::Leaf { path, .. } = &self.cursor.contents()[self.position()] else {
+            return false;
+        };
+        let path = path.clone();
+        if self.marked.remove(&path).is_none() {
+            let ids = self.get();
+            self.marked.insert(path, ids);
+        }
+        true
+    }
+
+    /// Returns whether the node at the path is marked.
+    pub fn is_marked(&self, path: &Path) -> bool {
+        self.marked.contains_key(path)
+    }
+
+    /// Returns the ids along the paths of the marked leaves from the root,
+    /// e.g. `["root", "src", "main.rs"]`, in the order of the tree.
+    pub fn marked(&self) -> Vec<Vec<String>> {
+        self.marked.values().cloned().collect()
+    }
+
     /// Toggles the state of the current node and updates the cursor position accordingly.
     pub fn toggle(&mut self) {
         let path = match self.cursor.contents()[self.position()].clone() {
             Kind::Folded { path, .. } => path,
             Kind::Unfolded { path, .. } => path,
+            Kind::Leaf { path, .. } => path,
+            Kind::Loading { .. } => return,
         };
         self.root.toggle(&path);
         self.cursor = Cursor::new(self.root.flatten_visibles(), self.position(), false);
     }
 
+    /// Returns whether any expanded node is waiting for its children to be loaded.
+    pub fn is_loading(&self) -> bool {
+        self.cursor
+            .contents()
+            .iter()
+            .any(|kind| matches!(kind, Kind::Loading { .. }))
+    }
+
+    /// Loads the children of the expanded lazy nodes, replacing their placeholders.
+    /// Returns whether any node was loaded.
+    pub fn load(&mut self) -> bool {
+        if !self.root.load() {
+            return false;
+        }
+        self.cursor = Cursor::new(self.root.flatten_visibles(), self.position(), false);
+        true
+    }
+
     /// Moves the cursor backward in the tree, if possible.
     ///
     /// Returns `true` if the cursor was successfully moved backward, `false` otherwise.
@@ -83,3 +144,80 @@ impl Tree {
         self.cursor.move_to_tail()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn create_test_tree() -> Tree {
+        Tree::new(Node::NonLeaf {
+            id: "root".into(),
+            children: vec![
+                Node::NonLeaf {
+                    id: "src".into(),
+                    children: vec![Node::Leaf("lib.rs".into()), Node::Leaf("main.rs".into())],
+                    children_visible: true,
+                },
+                Node::Leaf("Cargo.toml".into()),
+            ],
+            children_visible: true,
+        })
+    }
+
+    mod get {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let mut tree = create_test_tree();
+            tree.forward();
+            tree.forward();
+            tree.forward();
+            assert_eq!(vec!["root", "src", "main.rs"], tree.get());
+        }
+    }
+
+    mod toggle_mark {
+        use super::*;
+
+        #[test]
+        fn test_only_leaves() {
+            let mut tree = create_test_tree();
+            assert!(!tree.toggle_mark());
+            tree.forward();
+            assert!(!tree.toggle_mark());
+            assert!(tree.marked().is_empty());
+
+            tree.forward();
+            assert!(tree.toggle_mark());
+            assert!(tree.is_marked(&vec![0, 0]));
+            assert!(tree.toggle_mark());
+            assert!(!tree.is_marked(&vec![0, 0]));
+        }
+
+        #[test]
+        fn test_survives_folding() {
+            let mut tree = create_test_tree();
+            tree.move_to_tail();
+            tree.toggle_mark();
+            tree.move_to_head();
+            tree.forward();
+            tree.forward();
+            tree.forward();
+            tree.toggle_mark();
+
+            // Fold and unfold `src`.
+            tree.move_to_head();
+            tree.forward();
+            tree.toggle();
+            assert_eq!(3, tree.kinds().len());
+            tree.toggle();
+
+            assert!(tree.is_marked(&vec![0, 1]));
+            assert_eq!(
+                vec![vec!["root", "src", "main.rs"], vec!["root", "Cargo.toml"],],
+                tree.marked()
+            );
+        }
+    }
+}
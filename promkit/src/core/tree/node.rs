@@ -1,10 +1,10 @@
-use std::{fs, path};
+use std::{fmt, fs, path, sync::Arc};
 
 /// Represents the kind of a node in a tree structure.
 ///
 /// This enum is used to distinguish between nodes that are currently
-/// visible in their "folded" state and those that are "unfolded" to reveal
-/// their children, if any.
+/// visible in their "folded" state, those that are "unfolded" to reveal
+/// their children, and leaves which have no children at all.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Kind {
     /// Represents a node that is folded (i.e., its children are not currently visible).
@@ -16,15 +16,61 @@ pub enum Kind {
     /// - `id`: A unique identifier for the node.
     /// - `path`: The path from the root to this node, represented as a sequence of indices.
     Unfolded { id: String, path: Path },
+
+    /// Represents a leaf node (i.e., a node that has no children to fold or unfold).
+    /// - `id`: A unique identifier for the node.
+    /// - `path`: The path from the root to this node, represented as a sequence of indices.
+    Leaf { id: String, path: Path },
+
+    /// Represents the placeholder row shown in place of the children being loaded.
+    /// - `path`: The path of the lazy node, followed by the index of its first child.
+    Loading { path: Path },
 }
 
 /// A type alias for a path in the tree, represented as a sequence of indices.
 pub type Path = Vec<usize>;
 
+/// A callback that produces the children of a `Node::Lazy` on its first expansion.
+///
+/// The callback receives the ids of the nodes along the path from the root
+/// to the lazy node (inclusive), e.g. `["root", "src"]`,
+/// which can be joined to locate the node in an external source such as a filesystem.
+#[derive(Clone)]
+pub struct Loader(pub LoadFn);
+
+/// The function a `Loader` calls, shared among the clones of the tree.
+pub type LoadFn = Arc<dyn Fn(&[String]) -> Vec<Node> + Send + Sync>;
+
+impl Loader {
+    /// Creates a loader from a closure receiving the ids from the root to the lazy node
+    /// and returning the children of the node.
+    pub fn new<F>(load: F) -> Self
+    where
+        F: Fn(&[String]) -> Vec<Node> + Send + Sync + 'static,
+    {
+        Self(Arc::new(load))
+    }
+}
+
+impl fmt::Debug for Loader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Loader")
+    }
+}
+
+impl PartialEq for Loader {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for Loader {}
+
 /// Represents a node within a tree structure.
 ///
 /// A node can either be a `NonLeaf`, containing children and a visibility flag,
-/// or a `Leaf`, representing an end node without children.
+/// a `Leaf`, representing an end node without children,
+/// or a `Lazy`, whose children are loaded on demand.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Node {
     /// Represents a non-leaf node, which can contain child nodes.
@@ -40,6 +86,19 @@ pub enum Node {
     /// Represents a leaf node, which does not contain any child nodes.
     /// - `id`: A unique identifier for the leaf node.
     Leaf(String),
+
+    /// Represents a non-leaf node whose children have not been loaded yet.
+    /// - `id`: A unique identifier for the node.
+    /// - `loader`: A callback invoked by `load` once the node is expanded.
+    ///   The node is then replaced with a `NonLeaf` holding the returned children,
+    ///   so the callback runs only once.
+    Lazy { id: String, loader: Loader },
+
+    /// Represents a lazy node that is expanded, shown with a placeholder row
+    /// until `load` replaces it with a `NonLeaf` holding the loaded children.
+    /// - `id`: A unique identifier for the node.
+    /// - `loader`: The callback producing the children.
+    Loading { id: String, loader: Loader },
 }
 
 impl TryFrom<&path::PathBuf> for Node {
@@ -80,10 +139,7 @@ impl TryFrom<&path::PathBuf> for Node {
                         path.file_name()
                             .and_then(|name| name.to_str())
                             .ok_or_else(|| {
-                                std::io::Error::new(
-                                    std::io::ErrorKind::Other,
-                                    "Failed to convert file name to string",
-                                )
+                                std::io::Error::other("Failed to convert file name to string")
                             })?
                             .to_string(),
                     ));
@@ -101,12 +157,7 @@ impl TryFrom<&path::PathBuf> for Node {
             id: dir_path
                 .file_name()
                 .and_then(|name| name.to_str())
-                .ok_or_else(|| {
-                    std::io::Error::new(
-                        std::io::ErrorKind::Other,
-                        "Failed to convert directory name to string",
-                    )
-                })?
+                .ok_or_else(|| std::io::Error::other("Failed to convert directory name to string"))?
                 .to_string(),
             children,
             children_visible: false,
@@ -119,14 +170,15 @@ impl Node {
         match self {
             Node::NonLeaf { id, .. } => id,
             Node::Leaf(id) => id,
+            Node::Lazy { id, .. } | Node::Loading { id, .. } => id,
         }
     }
 
     /// Flattens the tree structure into a vector of `Kind`, including only visible nodes.
     ///
     /// This method performs a depth-first search (DFS) to traverse the tree and collect
-    /// nodes into a vector. Each node is represented as either `Kind::Folded` or `Kind::Unfolded`
-    /// based on its visibility and whether it has children.
+    /// nodes into a vector. Each non-leaf node is represented as either `Kind::Folded` or
+    /// `Kind::Unfolded` based on its visibility, and each leaf node as `Kind::Leaf`.
     ///
     /// Returns:
     /// - Vec<Kind>: A vector of `Kind` representing the visible nodes in the tree.
@@ -156,11 +208,26 @@ impl Node {
                     }
                 }
                 Node::Leaf(item) => {
-                    ret.push(Kind::Folded {
+                    ret.push(Kind::Leaf {
                         id: item.clone(),
                         path: path.clone(),
                     });
                 }
+                Node::Lazy { id, .. } => {
+                    ret.push(Kind::Folded {
+                        id: id.clone(),
+                        path: path.clone(),
+                    });
+                }
+                Node::Loading { id, .. } => {
+                    ret.push(Kind::Unfolded {
+                        id: id.clone(),
+                        path: path.clone(),
+                    });
+                    let mut placeholder = path.clone();
+                    placeholder.push(0);
+                    ret.push(Kind::Loading { path: placeholder });
+                }
             }
         }
 
@@ -175,14 +242,52 @@ impl Node {
     /// - path: &Path - A reference to a vector of usize, representing the path to the target node.
     ///
     /// This method modifies the tree in-place. If the target node is found and is a `NonLeaf`,
-    /// its `children_visible` field is toggled.
+    /// its `children_visible` field is toggled. If it is a `Lazy`, it becomes `Loading`
+    /// for `load` to invoke its loader, and a `Loading` node becomes `Lazy` again.
     pub fn toggle(&mut self, path: &Path) {
-        if let Some(Node::NonLeaf {
-            children_visible, ..
-        }) = self.get_mut(path)
-        {
-            *children_visible = !*children_visible;
+        match self.get_mut(path) {
+            Some(Node::NonLeaf {
+                children_visible, ..
+            }) => {
+                *children_visible = !*children_visible;
+            }
+            Some(node @ (Node::Lazy { .. } | Node::Loading { .. })) => {
+                *node = match node.clone() {
+                    Node::Lazy { id, loader } => Node::Loading { id, loader },
+                    Node::Loading { id, loader } => Node::Lazy { id, loader },
+                    other => other,
+                };
+            }
+            _ => {}
+        }
+    }
+
+    /// Invokes the loaders of the `Loading` nodes, replacing each with an unfolded `NonLeaf`
+    /// holding the loaded children. Returns whether any node was loaded.
+    pub fn load(&mut self) -> bool {
+        fn dfs(node: &mut Node, mut ids: Vec<String>) -> bool {
+            match node {
+                Node::NonLeaf { id, children, .. } => {
+                    ids.push(id.clone());
+                    let mut loaded = false;
+                    for child in children {
+                        loaded |= dfs(child, ids.clone());
+                    }
+                    loaded
+                }
+                Node::Loading { id, loader } => {
+                    ids.push(id.clone());
+                    *node = Node::NonLeaf {
+                        id: id.clone(),
+                        children: (loader.0)(&ids),
+                        children_visible: true,
+                    };
+                    true
+                }
+                Node::Leaf(_) | Node::Lazy { .. } => false,
+            }
         }
+        dfs(self, Vec::new())
     }
 
     /// Retrieves the IDs of all nodes along the path to a specified node.
@@ -205,7 +310,7 @@ impl Node {
                         break;
                     }
                 }
-                Node::Leaf(id) => {
+                Node::Leaf(id) | Node::Lazy { id, .. } | Node::Loading { id, .. } => {
                     ids.push(id.clone());
                     break;
                 }
@@ -236,7 +341,7 @@ impl Node {
                         return None;
                     }
                 }
-                Node::Leaf(_) => {
+                Node::Leaf(_) | Node::Lazy { .. } | Node::Loading { .. } => {
                     return None;
                 }
             }
@@ -266,7 +371,7 @@ impl Node {
                         return None;
                     }
                 }
-                Node::Leaf(_) => {
+                Node::Leaf(_) | Node::Lazy { .. } | Node::Loading { .. } => {
                     return None;
                 }
             }
@@ -317,6 +422,85 @@ mod test {
         }
     }
 
+    mod toggle_lazy {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        use super::*;
+
+        #[test]
+        fn test() {
+            let calls = Arc::new(AtomicUsize::new(0));
+            let counter = calls.clone();
+            let mut node = Node::NonLeaf {
+                id: "root".into(),
+                children: vec![Node::Lazy {
+                    id: "src".into(),
+                    loader: Loader::new(move |ids| {
+                        counter.fetch_add(1, Ordering::SeqCst);
+                        vec![Node::Leaf(ids.join("/"))]
+                    }),
+                }],
+                children_visible: true,
+            };
+            assert_eq!(
+                vec![
+                    Kind::Unfolded {
+                        id: "root".into(),
+                        path: vec![],
+                    },
+                    Kind::Folded {
+                        id: "src".into(),
+                        path: vec![0],
+                    },
+                ],
+                node.flatten_visibles(),
+            );
+
+            // The placeholder is shown until the children are loaded.
+            node.toggle(&vec![0]);
+            assert_eq!(0, calls.load(Ordering::SeqCst));
+            assert_eq!(
+                vec![
+                    Kind::Unfolded {
+                        id: "root".into(),
+                        path: vec![],
+                    },
+                    Kind::Unfolded {
+                        id: "src".into(),
+                        path: vec![0],
+                    },
+                    Kind::Loading { path: vec![0, 0] },
+                ],
+                node.flatten_visibles(),
+            );
+
+            // Load, fold, and expand again.
+            assert!(node.load());
+            assert!(!node.load());
+            node.toggle(&vec![0]);
+            node.toggle(&vec![0]);
+
+            assert_eq!(1, calls.load(Ordering::SeqCst));
+            assert_eq!(
+                vec![
+                    Kind::Unfolded {
+                        id: "root".into(),
+                        path: vec![],
+                    },
+                    Kind::Unfolded {
+                        id: "src".into(),
+                        path: vec![0],
+                    },
+                    Kind::Leaf {
+                        id: "root/src".into(),
+                        path: vec![0, 0],
+                    },
+                ],
+                node.flatten_visibles(),
+            );
+        }
+    }
+
     mod flatten_visibles {
         use super::*;
 
@@ -333,19 +517,19 @@ mod test {
                         id: "a".into(),
                         path: vec![0],
                     },
-                    Kind::Folded {
+                    Kind::Leaf {
                         id: "aa".into(),
                         path: vec![0, 0],
                     },
-                    Kind::Folded {
+                    Kind::Leaf {
                         id: "ab".into(),
                         path: vec![0, 1],
                     },
-                    Kind::Folded {
+                    Kind::Leaf {
                         id: "b".into(),
                         path: vec![1],
                     },
-                    Kind::Folded {
+                    Kind::Leaf {
                         id: "c".into(),
                         path: vec![2],
                     },
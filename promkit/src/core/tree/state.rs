@@ -1,12 +1,21 @@
-use crate::{crossterm::style::ContentStyle, grapheme::StyledGraphemes, pane::Pane, PaneFactory};
+use crate::{
+    crossterm::style::ContentStyle,
+    grapheme::StyledGraphemes,
+    indented_rows::{self, Highlight, Layout},
+    pane::Pane,
+    Overflow, PaneFactory,
+};
 
 use super::{Kind, Tree};
 
+/// The text of the placeholder row shown while the children of a node are loaded.
+const LOADING_TEXT: &str = "loading…";
+
 /// Represents the state of a tree structure within the application.
 ///
 /// This state includes not only the tree itself but also various properties
 /// that affect how the tree is displayed and interacted with. These properties
-/// include symbols for folded and unfolded items, styles for active and inactive
+/// include symbols for folded, unfolded and leaf items, styles for active and inactive
 /// items, the number of lines available for rendering, and the indentation level
 /// for child items in the tree.
 #[derive(Clone)]
@@ -17,6 +26,16 @@ pub struct State {
     pub folded_symbol: String,
     /// Symbol representing unfolded items.
     pub unfolded_symbol: String,
+    /// Symbol representing leaf items.
+    pub leaf_symbol: String,
+
+    /// Whether leaves are rendered with marks, for selecting multiple leaves.
+    pub multi_select: bool,
+    /// Symbol used to indicate a marked leaf.
+    pub active_mark: char,
+    /// Symbol used to indicate an unmarked leaf.
+    /// If the marks have different display widths, the narrower one is padded.
+    pub inactive_mark: char,
 
     /// Style for the selected line.
     pub active_item_style: ContentStyle,
@@ -39,18 +58,28 @@ impl PaneFactory for State {
             match kind {
                 Kind::Folded { .. } => &self.folded_symbol,
                 Kind::Unfolded { .. } => &self.unfolded_symbol,
+                Kind::Leaf { .. } | Kind::Loading { .. } => &self.leaf_symbol,
             }
         };
 
-        let indent = |kind: &Kind| -> usize {
-            match kind {
-                Kind::Folded { path, .. } | Kind::Unfolded { path, .. } => path.len() * self.indent,
-            }
-        };
-
+        let mark_width = StyledGraphemes::from(self.active_mark.to_string())
+            .widths()
+            .max(StyledGraphemes::from(self.inactive_mark.to_string()).widths());
         let id = |kind: &Kind| -> String {
             match kind {
-                Kind::Folded { id, .. } | Kind::Unfolded { id, .. } => id.clone(),
+                Kind::Leaf { id, path } if self.multi_select => {
+                    let mark = if self.tree.is_marked(path) {
+                        self.active_mark
+                    } else {
+                        self.inactive_mark
+                    };
+                    let padding = mark_width - StyledGraphemes::from(mark.to_string()).widths();
+                    format!("{}{} {}", mark, " ".repeat(padding), id)
+                }
+                Kind::Folded { id, .. } | Kind::Unfolded { id, .. } | Kind::Leaf { id, .. } => {
+                    id.clone()
+                }
+                Kind::Loading { .. } => LOADING_TEXT.to_string(),
             }
         };
 
@@ -59,38 +88,125 @@ impl PaneFactory for State {
             None => height as usize,
         };
 
-        let matrix = self
-            .tree
-            .kinds()
-            .iter()
-            .enumerate()
-            .filter(|(i, _)| *i >= self.tree.position() && *i < self.tree.position() + height)
-            .map(|(i, kind)| {
-                if i == self.tree.position() {
-                    StyledGraphemes::from_str(
-                        format!("{}{}{}", symbol(kind), " ".repeat(indent(kind)), id(kind),),
-                        self.active_item_style,
-                    )
-                } else {
-                    StyledGraphemes::from_str(
-                        format!(
-                            "{}{}{}",
+        let indent_unit = " ".repeat(self.indent);
+        Layout {
+            indent_unit: &indent_unit,
+            highlight: Highlight::Style {
+                active: self.active_item_style,
+                inactive: self.inactive_item_style,
+            },
+            overflow: Overflow::Wrap,
+            search: Default::default(),
+            line_numbers: None,
+        }
+        .create_pane(
+            self.tree
+                .kinds_from_current(height)
+                .iter()
+                .enumerate()
+                .map(|(i, kind)| {
+                    let (Kind::Folded { path, .. }
+                    | Kind::Unfolded { path, .. }
+                    | Kind::Leaf { path, .. }
+                    | Kind::Loading { path }) = kind;
+                    // Only the item under the cursor shows its symbol.
+                    let marker = match i {
+                        0 => StyledGraphemes::from(symbol(kind)),
+                        _ => StyledGraphemes::from(
                             " ".repeat(StyledGraphemes::from(symbol(kind)).widths()),
-                            " ".repeat(indent(kind)),
-                            id(kind),
                         ),
-                        self.inactive_item_style,
-                    )
-                }
-            })
-            .fold((vec![], 0), |(mut acc, pos), item| {
-                let rows = item.matrixify(width as usize, height, 0).0;
-                if pos < self.tree.position() + height {
-                    acc.extend(rows);
-                }
-                (acc, pos + 1)
-            });
+                    };
+                    indented_rows::Row {
+                        depth: path.len(),
+                        marker,
+                        body: StyledGraphemes::from(id(kind)),
+                        active: i == 0,
+                        number: self.tree.position() + i,
+                        ..Default::default()
+                    }
+                }),
+            width,
+            height,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::{crossterm::style::Color, style::StyleBuilder, tree::Node};
+
+    fn state(multi_select: bool) -> State {
+        State {
+            tree: Tree::new(Node::NonLeaf {
+                id: "root".into(),
+                children: vec![Node::Leaf("abcdefgh".into()), Node::Leaf("b".into())],
+                children_visible: true,
+            }),
+            folded_symbol: String::from("▶︎ "),
+            unfolded_symbol: String::from("▼ "),
+            leaf_symbol: String::from("▶︎ "),
+            multi_select,
+            active_mark: '☒',
+            inactive_mark: '☐',
+            active_item_style: StyleBuilder::new().fgc(Color::DarkCyan).build(),
+            inactive_item_style: StyleBuilder::new().build(),
+            lines: Default::default(),
+            indent: 2,
+        }
+    }
+
+    mod create_pane {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let rows = state(false).create_pane(20, 10).extract(10);
+            assert_eq!(
+                vec!["▼ root", "    abcdefgh", "    b"],
+                rows.iter().map(|row| row.to_string()).collect::<Vec<_>>(),
+            );
+            let active = StyleBuilder::new().fgc(Color::DarkCyan).build();
+            assert!(rows[0].iter().all(|styled| *styled.style() == active));
+            assert!(rows[1]
+                .iter()
+                .all(|styled| *styled.style() == ContentStyle::default()));
+        }
+
+        #[test]
+        fn test_wrap() {
+            let mut state = state(false);
+            state.tree.forward();
+            let rows = state.create_pane(8, 10).extract(10);
+            assert_eq!(
+                vec!["▶︎   abcd", "efgh", "    b"],
+                rows.iter().map(|row| row.to_string()).collect::<Vec<_>>(),
+            );
+        }
+
+        #[test]
+        fn test_multi_select() {
+            let mut state = state(true);
+            state.tree.forward();
+            state.tree.toggle_mark();
+            let rows = state.create_pane(20, 2).extract(10);
+            assert_eq!(
+                vec!["▶︎   ☒ abcdefgh", "    ☐ b"],
+                rows.iter().map(|row| row.to_string()).collect::<Vec<_>>(),
+            );
+        }
 
-        Pane::new(matrix.0, 0)
+        #[test]
+        fn test_wrap_counts_visual_rows() {
+            let mut state = state(false);
+            state.tree.forward();
+            state.lines = Some(2);
+            let rows = state.create_pane(8, 10).extract(10);
+            assert_eq!(
+                vec!["▶︎   abcd", "efgh"],
+                rows.iter().map(|row| row.to_string()).collect::<Vec<_>>(),
+            );
+        }
     }
 }
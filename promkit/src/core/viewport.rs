@@ -0,0 +1,227 @@
+//! Layout of list items spanning multiple rows, shared by the listbox and the checkbox.
+
+use crate::{
+    grapheme::{StyledGrapheme, StyledGraphemes},
+    Overflow, PartialItem,
+};
+
+/// Lays an item out into rows of `width`: the prefix (e.g. the cursor and the mark)
+/// followed by the body, which is split at newlines and wrapped or truncated per `overflow`.
+/// The continuation rows are indented by the width of the prefix so that the item forms a block,
+/// and the indent takes the style of the first grapheme of the prefix.
+///
+/// At most `max_lines` rows are kept, and the last kept row ends with `…` if the item is cut.
+pub fn item_rows(
+    prefix: &StyledGraphemes,
+    body: &StyledGraphemes,
+    width: usize,
+    overflow: Overflow,
+    max_lines: Option<usize>,
+) -> Vec<StyledGraphemes> {
+    let mut segments = vec![StyledGraphemes::default()];
+    for g in body.iter() {
+        match g.as_str() {
+            "\n" => segments.push(StyledGraphemes::default()),
+            _ => segments.last_mut().unwrap().push_back(g.clone()),
+        }
+    }
+
+    if overflow == Overflow::TruncateWithEllipsis {
+        let line = StyledGraphemes::from_iter([prefix, &segments[0]]);
+        return if segments.len() > 1 {
+            vec![with_ellipsis(line, width)]
+        } else {
+            vec![line.truncate_with_ellipsis(width)]
+        };
+    }
+
+    // If the prefix does not leave room for the body, it is wrapped along with the body.
+    let indent = match prefix.widths() {
+        indent if indent < width => indent,
+        _ => 0,
+    };
+    let padding = StyledGraphemes::from_iter((0..indent).map(|_| match prefix.front() {
+        Some(first) => StyledGrapheme::new(' ', *first.style()),
+        None => StyledGrapheme::from(' '),
+    }));
+
+    let mut rows = vec![];
+    for (i, segment) in segments.iter().enumerate() {
+        let head = if i == 0 { prefix } else { &padding };
+        let wrapped = match (indent, i) {
+            (0, 0) => {
+                StyledGraphemes::from_iter([prefix, segment])
+                    .matrixify(width, usize::MAX, 0)
+                    .0
+            }
+            (0, _) => segment.matrixify(width, usize::MAX, 0).0,
+            _ => segment
+                .matrixify(width - indent, usize::MAX, 0)
+                .0
+                .into_iter()
+                .enumerate()
+                .map(|(j, row)| {
+                    StyledGraphemes::from_iter([if j == 0 { head } else { &padding }, &row])
+                })
+                .collect(),
+        };
+        match wrapped.is_empty() {
+            true if indent > 0 => rows.push(head.clone()),
+            true => rows.push(StyledGraphemes::default()),
+            false => rows.extend(wrapped),
+        }
+    }
+
+    match max_lines {
+        Some(max) if max > 0 && rows.len() > max => {
+            rows.truncate(max);
+            if let Some(last) = rows.pop() {
+                rows.push(with_ellipsis(last, width));
+            }
+            rows
+        }
+        _ => rows,
+    }
+}
+
+/// Stacks the rows of the items from the top of the viewport until `height` rows are filled.
+/// The first item is always shown, clipped if it is taller than the viewport.
+/// An item not fitting below it is clipped or skipped per `partial_item`.
+///
+/// The items are consumed lazily, so only the visible ones are laid out.
+/// The rows may be of any type, e.g. the indices of the items to see which item a row belongs to.
+pub fn stack<T, I: IntoIterator<Item = Vec<T>>>(
+    items: I,
+    height: usize,
+    partial_item: PartialItem,
+) -> Vec<T> {
+    let mut ret = Vec::new();
+    for (i, mut rows) in items.into_iter().enumerate() {
+        let room = height.saturating_sub(ret.len());
+        if room == 0 {
+            break;
+        }
+        if rows.len() > room {
+            if i > 0 && partial_item == PartialItem::Skip {
+                break;
+            }
+            rows.truncate(room);
+        }
+        ret.extend(rows);
+    }
+    ret
+}
+
+/// Replaces the end of the row with `…` to tell that the item continues.
+fn with_ellipsis(mut row: StyledGraphemes, width: usize) -> StyledGraphemes {
+    if width == 0 {
+        return StyledGraphemes::default();
+    }
+    let style = row.back().map(|g| *g.style()).unwrap_or_default();
+    while row.widths() + 1 > width {
+        row.pop_back();
+    }
+    row.push_back(StyledGrapheme::new('…', style));
+    row
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn to_strings(rows: &[StyledGraphemes]) -> Vec<String> {
+        rows.iter().map(|row| row.to_string()).collect()
+    }
+
+    mod item_rows {
+        use super::*;
+
+        #[test]
+        fn test_wrap_with_hanging_indent() {
+            let rows = item_rows(
+                &StyledGraphemes::from("❯ "),
+                &StyledGraphemes::from("abcdefgh"),
+                5,
+                Overflow::Wrap,
+                None,
+            );
+            assert_eq!(vec!["❯ abc", "  def", "  gh"], to_strings(&rows));
+        }
+
+        #[test]
+        fn test_newlines() {
+            let rows = item_rows(
+                &StyledGraphemes::from("❯ "),
+                &StyledGraphemes::from("fix\n\nbody"),
+                10,
+                Overflow::Wrap,
+                None,
+            );
+            assert_eq!(vec!["❯ fix", "  ", "  body"], to_strings(&rows));
+        }
+
+        #[test]
+        fn test_max_lines() {
+            let rows = item_rows(
+                &StyledGraphemes::from("❯ "),
+                &StyledGraphemes::from("abcdefgh"),
+                5,
+                Overflow::Wrap,
+                Some(2),
+            );
+            assert_eq!(vec!["❯ abc", "  de…"], to_strings(&rows));
+        }
+
+        #[test]
+        fn test_truncate_with_ellipsis() {
+            let rows = item_rows(
+                &StyledGraphemes::from("❯ "),
+                &StyledGraphemes::from("fix\nbody"),
+                10,
+                Overflow::TruncateWithEllipsis,
+                None,
+            );
+            assert_eq!(vec!["❯ fix…"], to_strings(&rows));
+        }
+
+        #[test]
+        fn test_prefix_wider_than_width() {
+            let rows = item_rows(
+                &StyledGraphemes::from("❯❯❯ "),
+                &StyledGraphemes::from("ab"),
+                3,
+                Overflow::Wrap,
+                None,
+            );
+            assert_eq!(vec!["❯❯❯", " ab"], to_strings(&rows));
+        }
+    }
+
+    mod stack {
+        use super::*;
+
+        fn item(rows: usize) -> Vec<StyledGraphemes> {
+            (0..rows)
+                .map(|i| StyledGraphemes::from(i.to_string()))
+                .collect()
+        }
+
+        #[test]
+        fn test_clip() {
+            let rows = stack([item(1), item(2), item(3)], 4, PartialItem::Clip);
+            assert_eq!(vec!["0", "0", "1", "0"], to_strings(&rows));
+        }
+
+        #[test]
+        fn test_skip() {
+            let rows = stack([item(1), item(2), item(3)], 4, PartialItem::Skip);
+            assert_eq!(vec!["0", "0", "1"], to_strings(&rows));
+        }
+
+        #[test]
+        fn test_first_item_taller_than_viewport() {
+            let rows = stack([item(5), item(1)], 3, PartialItem::Skip);
+            assert_eq!(vec!["0", "1", "2"], to_strings(&rows));
+        }
+    }
+}
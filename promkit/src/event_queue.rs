@@ -0,0 +1,291 @@
+use std::{
+    collections::VecDeque,
+    sync::mpsc,
+    time::{Duration, Instant},
+};
+
+use crate::crossterm::event::{Event, KeyCode, KeyEvent, MouseEvent, MouseEventKind};
+
+/// A FIFO queue of events waiting to be evaluated.
+///
+/// Events that arrive while a (possibly slow) render is in progress are
+/// pushed here and evaluated in order against the post-render state,
+/// so none of them are lost or applied out of order.
+///
+/// Optionally, movement events (arrow keys, page keys and mouse scroll)
+/// that have been waiting longer than the stale threshold are dropped,
+/// which prevents runaway scrolling after a long stall.
+///
+/// The events evaluated before a render are popped as a batch (see `BatchLimit`),
+/// and the batches are always popped in the order the events arrived.
+#[derive(Default)]
+pub struct EventQueue {
+    events: VecDeque<(Instant, Event)>,
+    stale_threshold: Option<Duration>,
+    batch_limit: BatchLimit,
+}
+
+/// Limits how many of the queued events are evaluated between two renders,
+/// e.g. to keep showing the progress of a large paste.
+/// Without limits, all the queued events are evaluated before rendering.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BatchLimit {
+    /// The maximum number of events in a batch.
+    pub max_events: Option<usize>,
+    /// The maximum time between the arrivals of the first and the last events in a batch.
+    pub max_duration: Option<Duration>,
+}
+
+impl EventQueue {
+    pub fn new(stale_threshold: Option<Duration>, batch_limit: BatchLimit) -> Self {
+        Self {
+            events: Default::default(),
+            stale_threshold,
+            batch_limit,
+        }
+    }
+
+    /// Pushes an event along with the (earliest possible) time it was received.
+    pub fn push(&mut self, event: Event, received_at: Instant) {
+        self.events.push_back((received_at, event));
+    }
+
+    /// Pops the oldest event, skipping stale movement events.
+    pub fn pop(&mut self) -> Option<Event> {
+        while let Some((received_at, event)) = self.events.pop_front() {
+            let stale = self
+                .stale_threshold
+                .is_some_and(|threshold| received_at.elapsed() > threshold);
+            if !(stale && is_movement(&event)) {
+                return Some(event);
+            }
+        }
+        None
+    }
+
+    /// Pops the oldest events up to the batch limit, skipping stale movement events.
+    /// Returns at least one event unless the queue is empty.
+    pub fn pop_batch(&mut self) -> Vec<Event> {
+        let mut batch = Vec::new();
+        let mut first_received_at = None;
+        while let Some((received_at, _)) = self.events.front() {
+            let full = self
+                .batch_limit
+                .max_events
+                .is_some_and(|max| batch.len() >= max.max(1));
+            let late = first_received_at.is_some_and(|first: Instant| {
+                self.batch_limit
+                    .max_duration
+                    .is_some_and(|max| received_at.saturating_duration_since(first) > max)
+            });
+            if full || late {
+                break;
+            }
+            first_received_at.get_or_insert(*received_at);
+            match self.pop() {
+                Some(event) => batch.push(event),
+                None => break,
+            }
+        }
+        batch
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+}
+
+/// Where a headless run takes its events from, in place of the terminal.
+pub(crate) trait EventSource {
+    /// Returns the next event if it has already arrived, without waiting.
+    fn try_next(&mut self) -> Option<Event>;
+    /// Waits for the next event, or returns `None` once the events run out.
+    fn next(&mut self) -> Option<Event>;
+}
+
+/// Events arriving one at a time, each after the render for the previous one.
+pub(crate) struct OneByOne<I>(pub I);
+
+impl<I: Iterator<Item = Event>> EventSource for OneByOne<I> {
+    fn try_next(&mut self) -> Option<Event> {
+        None
+    }
+
+    fn next(&mut self) -> Option<Event> {
+        self.0.next()
+    }
+}
+
+impl EventSource for mpsc::Receiver<Event> {
+    fn try_next(&mut self) -> Option<Event> {
+        self.try_recv().ok()
+    }
+
+    fn next(&mut self) -> Option<Event> {
+        self.recv().ok()
+    }
+}
+
+/// Returns whether the event only moves the cursor or scrolls,
+/// i.e. whether it is safe to drop after a long stall.
+fn is_movement(event: &Event) -> bool {
+    match event {
+        Event::Key(KeyEvent { code, .. }) => matches!(
+            code,
+            KeyCode::Up
+                | KeyCode::Down
+                | KeyCode::Left
+                | KeyCode::Right
+                | KeyCode::PageUp
+                | KeyCode::PageDown
+        ),
+        Event::Mouse(MouseEvent { kind, .. }) => {
+            matches!(kind, MouseEventKind::ScrollUp | MouseEventKind::ScrollDown)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::crossterm::event::KeyModifiers;
+
+    use super::*;
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    mod pop {
+        use super::*;
+
+        #[test]
+        fn test_keeps_order() {
+            let mut queue = EventQueue::default();
+            let now = Instant::now();
+            queue.push(key(KeyCode::Char('a')), now);
+            queue.push(key(KeyCode::Down), now);
+            queue.push(key(KeyCode::Char('b')), now);
+
+            assert_eq!(Some(key(KeyCode::Char('a'))), queue.pop());
+            assert_eq!(Some(key(KeyCode::Down)), queue.pop());
+            assert_eq!(Some(key(KeyCode::Char('b'))), queue.pop());
+            assert_eq!(None, queue.pop());
+        }
+
+        #[test]
+        fn test_drops_stale_movement_events() {
+            let mut queue = EventQueue::new(Some(Duration::from_millis(10)), Default::default());
+            let stale = Instant::now() - Duration::from_millis(100);
+            queue.push(key(KeyCode::Down), stale);
+            queue.push(key(KeyCode::Char('a')), stale);
+            queue.push(key(KeyCode::Down), Instant::now());
+
+            assert_eq!(Some(key(KeyCode::Char('a'))), queue.pop());
+            assert_eq!(Some(key(KeyCode::Down)), queue.pop());
+            assert_eq!(None, queue.pop());
+        }
+    }
+
+    mod pop_batch {
+        use super::*;
+
+        #[test]
+        fn test_max_events() {
+            let mut queue = EventQueue::new(
+                None,
+                BatchLimit {
+                    max_events: Some(2),
+                    ..Default::default()
+                },
+            );
+            let now = Instant::now();
+            for ch in ['a', 'b', 'c'] {
+                queue.push(key(KeyCode::Char(ch)), now);
+            }
+            assert_eq!(
+                vec![key(KeyCode::Char('a')), key(KeyCode::Char('b'))],
+                queue.pop_batch()
+            );
+            assert_eq!(vec![key(KeyCode::Char('c'))], queue.pop_batch());
+            assert!(queue.pop_batch().is_empty());
+        }
+
+        #[test]
+        fn test_max_duration() {
+            let mut queue = EventQueue::new(
+                None,
+                BatchLimit {
+                    max_duration: Some(Duration::from_millis(10)),
+                    ..Default::default()
+                },
+            );
+            let now = Instant::now();
+            queue.push(key(KeyCode::Char('a')), now);
+            queue.push(key(KeyCode::Up), now + Duration::from_millis(5));
+            queue.push(key(KeyCode::Char('b')), now + Duration::from_millis(20));
+            assert_eq!(
+                vec![key(KeyCode::Char('a')), key(KeyCode::Up)],
+                queue.pop_batch()
+            );
+            assert_eq!(vec![key(KeyCode::Char('b'))], queue.pop_batch());
+        }
+
+        /// Replays random sequences of interleaved chars and movements,
+        /// and checks that the batches put together are the sequence itself.
+        #[test]
+        fn test_random_sequences_keep_order() {
+            // xorshift, to be reproducible without extra dependencies.
+            let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+            let mut next = |bound: u64| {
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                seed % bound
+            };
+            let start = Instant::now();
+            for _ in 0..200 {
+                let limit = BatchLimit {
+                    max_events: [None, Some(1), Some(3), Some(8)][next(4) as usize],
+                    max_duration: [None, Some(Duration::from_millis(5))][next(2) as usize],
+                };
+                let mut queue = EventQueue::new(None, limit);
+                let events: Vec<(Instant, Event)> = (0..next(50))
+                    .map(|i| {
+                        let event = match next(3) {
+                            0 => key(KeyCode::Up),
+                            1 => key(KeyCode::Down),
+                            _ => key(KeyCode::Char(char::from(b'a' + next(26) as u8))),
+                        };
+                        (start + Duration::from_millis(i * next(4)), event)
+                    })
+                    .collect();
+
+                let mut batches = Vec::new();
+                let mut pushed = events.iter();
+                loop {
+                    // Events keep arriving between the batches.
+                    for (received_at, event) in pushed.by_ref().take(next(5) as usize) {
+                        queue.push(event.clone(), *received_at);
+                    }
+                    let batch = queue.pop_batch();
+                    if batch.is_empty() && pushed.len() == 0 {
+                        break;
+                    }
+                    if let Some(max) = limit.max_events {
+                        assert!(batch.len() <= max);
+                    }
+                    batches.push(batch);
+                }
+
+                let flattened: Vec<Event> = batches.into_iter().flatten().collect();
+                let expected: Vec<Event> = events.into_iter().map(|(_, event)| event).collect();
+                assert_eq!(expected, flattened, "{:?}", limit);
+            }
+        }
+    }
+}
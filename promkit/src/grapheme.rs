@@ -1,37 +1,37 @@
 use std::{
-    collections::VecDeque,
-    fmt,
+    collections::{HashSet, VecDeque},
+    fmt, mem,
     ops::{Deref, DerefMut},
 };
 
-use unicode_width::UnicodeWidthChar;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
 use crate::crossterm::style::{Attribute, ContentStyle};
 
-/// Represents a single grapheme (character) with its display width and optional styling.
+/// Represents a single grapheme (an extended grapheme cluster)
+/// with its display width and optional styling.
 ///
-/// This structure is similar to `Grapheme` but includes styling information directly.
+/// A grapheme may consist of multiple chars,
+/// e.g. `e` followed by a combining accent, or an emoji ZWJ sequence,
+/// and is treated as a single unit for cursor movement, erasing and width calculation.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct StyledGrapheme {
-    ch: char,
+    cluster: String,
     width: usize,
     style: ContentStyle,
 }
 
 impl From<char> for StyledGrapheme {
     fn from(ch: char) -> Self {
-        Self {
-            ch,
-            width: UnicodeWidthChar::width(ch).unwrap_or(0),
-            style: ContentStyle::default(),
-        }
+        Self::new(ch, ContentStyle::default())
     }
 }
 
 impl fmt::Display for StyledGraphemes {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         for styled_grapheme in self.iter() {
-            write!(f, "{}", styled_grapheme.ch)?;
+            write!(f, "{}", styled_grapheme.cluster)?;
         }
         Ok(())
     }
@@ -39,20 +39,54 @@ impl fmt::Display for StyledGraphemes {
 
 impl StyledGrapheme {
     pub fn new(ch: char, style: ContentStyle) -> Self {
+        Self::from_cluster(ch.to_string(), style)
+    }
+
+    /// Creates a new `StyledGrapheme` from a string representing a single grapheme cluster.
+    pub fn from_cluster<S: Into<String>>(cluster: S, style: ContentStyle) -> Self {
+        let cluster = cluster.into();
         Self {
-            ch,
-            width: UnicodeWidthChar::width(ch).unwrap_or(0),
+            width: UnicodeWidthStr::width(cluster.as_str()),
+            cluster,
             style,
         }
     }
 
+    /// Returns the grapheme cluster as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.cluster
+    }
+
+    /// Returns the first char of the grapheme cluster.
+    pub fn base_char(&self) -> char {
+        self.cluster.chars().next().unwrap_or_default()
+    }
+
     pub fn width(&self) -> usize {
         self.width
     }
 
+    pub fn style(&self) -> &ContentStyle {
+        &self.style
+    }
+
     pub fn apply_style(&mut self, style: ContentStyle) {
         self.style = style;
     }
+
+    /// Tries to append a char to this grapheme cluster.
+    /// Returns `true` if the char extends the cluster (e.g. a combining mark or a ZWJ sequence),
+    /// otherwise `false` and the cluster is left unchanged.
+    pub fn try_extend(&mut self, ch: char) -> bool {
+        let mut extended = self.cluster.clone();
+        extended.push(ch);
+        if extended.graphemes(true).count() == 1 {
+            *self = Self::from_cluster(extended, self.style);
+            true
+        } else {
+            false
+        }
+    }
 }
 
 /// A collection of `StyledGrapheme` instances.
@@ -114,24 +148,26 @@ impl<S: AsRef<str>> From<S> for StyledGraphemes {
 impl fmt::Debug for StyledGraphemes {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         for styled_grapheme in self.iter() {
-            write!(f, "{}", styled_grapheme.ch)?;
+            write!(f, "{}", styled_grapheme.cluster)?;
         }
         Ok(())
     }
 }
 
 impl StyledGraphemes {
+    /// Creates a new `StyledGraphemes` by segmenting the string into extended grapheme clusters.
     pub fn from_str<S: AsRef<str>>(string: S, style: ContentStyle) -> Self {
         string
             .as_ref()
-            .chars()
-            .map(|ch| StyledGrapheme::new(ch, style))
+            .graphemes(true)
+            .map(|cluster| StyledGrapheme::from_cluster(cluster, style))
             .collect()
     }
 
-    /// Returns a `Vec<char>` containing the characters of all `Grapheme` instances in the collection.
+    /// Returns a `Vec<char>` containing the first char of each `Grapheme` instance in the collection,
+    /// so that the indices correspond to the graphemes.
     pub fn chars(&self) -> Vec<char> {
-        self.0.iter().map(|grapheme| grapheme.ch).collect()
+        self.0.iter().map(|grapheme| grapheme.base_char()).collect()
     }
 
     /// Calculates the total display width of all `Grapheme` instances in the collection.
@@ -141,8 +177,8 @@ impl StyledGraphemes {
 
     /// Replaces all occurrences of a substring `from` with another substring `to` within the `StyledGraphemes`.
     pub fn replace<S: AsRef<str>>(mut self, from: S, to: S) -> Self {
-        let from_len = from.as_ref().chars().count();
-        let to_len = to.as_ref().chars().count();
+        let from_len = from.as_ref().graphemes(true).count();
+        let to_len = to.as_ref().graphemes(true).count();
 
         let mut offset = 0;
         let diff = from_len.abs_diff(to_len);
@@ -201,14 +237,14 @@ impl StyledGraphemes {
 
         let mut indices = Vec::new();
         let mut pos = 0;
-        let query_chars: Vec<char> = query_str.chars().collect();
-        let query_len = query_chars.len();
+        let query_clusters: Vec<&str> = query_str.graphemes(true).collect();
+        let query_len = query_clusters.len();
 
         // Iterate through each grapheme in self
         while pos + query_len <= self.0.len() {
             let mut match_found = true;
-            for (i, query_char) in query_chars.iter().enumerate() {
-                if self.0[pos + i].ch != *query_char {
+            for (i, query_cluster) in query_clusters.iter().enumerate() {
+                if self.0[pos + i].cluster != *query_cluster {
                     match_found = false;
                     break;
                 }
@@ -233,7 +269,6 @@ impl StyledGraphemes {
     ///     - with the style applied to all occurrences of the query if the query is found.
     ///     - unchanged if the query string is empty.
     /// - `None`: if the query string is not found in the collection.
-
     pub fn highlight<S: AsRef<str>>(mut self, query: S, style: ContentStyle) -> Option<Self> {
         let query_str = query.as_ref();
         if query_str.is_empty() {
@@ -245,7 +280,7 @@ impl StyledGraphemes {
             return None;
         }
 
-        let query_len = query_str.chars().count();
+        let query_len = query_str.graphemes(true).count();
 
         for &start_index in &indices {
             for i in start_index..start_index + query_len {
@@ -273,6 +308,37 @@ impl StyledGraphemes {
         }
     }
 
+    /// Truncates the graphemes to fit within the given display width,
+    /// replacing the overflowing part with `…` styled like the grapheme it replaces.
+    /// Returns the graphemes as they are if they already fit.
+    pub fn truncate_with_ellipsis(&self, width: usize) -> Self {
+        if self.widths() <= width {
+            return self.clone();
+        }
+
+        let mut ret = StyledGraphemes::default();
+        let mut current_width = 0;
+        let mut style = ContentStyle::default();
+        for styled in self.iter() {
+            if current_width + styled.width + 1 > width {
+                style = styled.style;
+                break;
+            }
+            ret.push_back(styled.clone());
+            current_width += styled.width;
+        }
+        if width > 0 {
+            // Pad with spaces when a wide char did not fit at the edge,
+            // so that the ellipsis is always placed at the last column.
+            while current_width + 1 < width {
+                ret.push_back(StyledGrapheme::new(' ', style));
+                current_width += 1;
+            }
+            ret.push_back(StyledGrapheme::new('…', style));
+        }
+        ret
+    }
+
     /// Organizes the `StyledGraphemes` into a matrix format based on specified width and height,
     /// considering an offset for pagination or scrolling.
     pub fn matrixify(
@@ -281,25 +347,185 @@ impl StyledGraphemes {
         height: usize,
         offset: usize,
     ) -> (Vec<StyledGraphemes>, usize) {
+        Self::fit_rows(self.wrap_rows(width, None), height, offset)
+    }
+
+    /// Wraps the graphemes into rows of `width`, and returns the rows of the window
+    /// of `height` rows showing the `cursor`-th grapheme,
+    /// along with the first row of the window and the row of the cursor.
+    ///
+    /// The window stays at `top` (e.g. the one returned for the previous render)
+    /// while the cursor is within it, and otherwise scrolls just enough to show the cursor,
+    /// so that moving the cursor by a row scrolls the view by at most a row.
+    /// The window never extends past the last row if the rows fill it.
+    pub fn windowed(
+        &self,
+        width: usize,
+        height: usize,
+        cursor: usize,
+        top: usize,
+    ) -> (Vec<StyledGraphemes>, usize, usize) {
+        let (rows, row_of) = self.wrap_rows_indexed(width, None);
+        let height = height.max(1);
+        let cursor_row = row_of
+            .get(cursor)
+            .copied()
+            .unwrap_or(rows.len())
+            .min(rows.len().saturating_sub(1));
+        let top = top
+            .min(cursor_row)
+            .max((cursor_row + 1).saturating_sub(height))
+            .min(rows.len().saturating_sub(height));
+        (
+            rows.into_iter().skip(top).take(height).collect(),
+            top,
+            cursor_row,
+        )
+    }
+
+    /// Returns the graphemes fitting in a single row of `width` columns,
+    /// starting from the `left`-th one and showing the `cursor`-th one,
+    /// along with the index of the first grapheme shown.
+    /// A `<` (`>`) replaces the first (last) column if graphemes are hidden on the left (right).
+    ///
+    /// Like `windowed`, the row stays at `left` while the cursor is within it,
+    /// and otherwise scrolls just enough to show the cursor.
+    pub fn scrolled(&self, width: usize, cursor: usize, left: usize) -> (StyledGraphemes, usize) {
+        let cursor = cursor.min(self.len().saturating_sub(1));
+        // Returns the index past the last grapheme shown from `left`.
+        let end_from = |left: usize| {
+            let mut available = width.saturating_sub((left > 0) as usize);
+            if self.iter().skip(left).map(|g| g.width()).sum::<usize>() > available {
+                available = available.saturating_sub(1);
+            }
+            let mut used = 0;
+            left + self
+                .iter()
+                .skip(left)
+                .take_while(|g| {
+                    used += g.width();
+                    used <= available
+                })
+                .count()
+        };
+
+        let mut left = left.min(cursor);
+        let mut end = end_from(left);
+        while cursor >= end && left < cursor {
+            left += 1;
+            end = end_from(left);
+        }
+
+        let mut row = StyledGraphemes::default();
+        if left > 0 {
+            row.push_back(StyledGrapheme::from('<'));
+        }
+        row.extend(self.iter().skip(left).take(end - left).cloned());
+        if end < self.len() {
+            row.push_back(StyledGrapheme::from('>'));
+        }
+        (row, left)
+    }
+
+    /// Same as `matrixify`, but breaks rows after any of `break_chars` (e.g. whitespace) when possible,
+    /// so that words are kept together. Only words longer than the width are split.
+    /// A break char which does not fit at the end of a row is dropped instead of starting the next row.
+    pub fn matrixify_wrapped(
+        &self,
+        width: usize,
+        height: usize,
+        offset: usize,
+        break_chars: &HashSet<char>,
+    ) -> (Vec<StyledGraphemes>, usize) {
+        Self::fit_rows(self.wrap_rows(width, Some(break_chars)), height, offset)
+    }
+
+    fn wrap_rows(
+        &self,
+        width: usize,
+        break_chars: Option<&HashSet<char>>,
+    ) -> VecDeque<StyledGraphemes> {
+        self.wrap_rows_indexed(width, break_chars).0
+    }
+
+    /// Same as `wrap_rows`, but also returns the row each grapheme ends up in,
+    /// including the graphemes dropped or replaced in wrapping.
+    fn wrap_rows_indexed(
+        &self,
+        width: usize,
+        break_chars: Option<&HashSet<char>>,
+    ) -> (VecDeque<StyledGraphemes>, Vec<usize>) {
+        let is_break = |styled: &StyledGrapheme| {
+            break_chars.is_some_and(|chars| chars.contains(&styled.base_char()))
+        };
+
         let mut all = VecDeque::new();
+        let mut row_of = Vec::with_capacity(self.len());
         let mut row = StyledGraphemes::default();
+        let mut row_width = 0;
+        // The length of the row up to (and including) the last break char.
+        let mut last_break = None;
         for styled in self.iter() {
-            let width_with_next_char = row.iter().fold(0, |mut layout, g| {
-                layout += g.width;
-                layout
-            }) + styled.width;
-            if !row.is_empty() && width < width_with_next_char {
-                all.push_back(row);
-                row = StyledGraphemes::default();
+            // A grapheme wider than the row itself (e.g. a wide char at width 1)
+            // can never fit, so it is replaced with a space to keep the alignment.
+            let styled = if styled.width > width {
+                if width == 0 {
+                    row_of.push(all.len());
+                    continue;
+                }
+                StyledGrapheme::new(' ', styled.style)
+            } else {
+                styled.clone()
+            };
+            if !row.is_empty() && width < row_width + styled.width {
+                if is_break(&styled) {
+                    all.push_back(mem::take(&mut row));
+                    row_width = 0;
+                    last_break = None;
+                    row_of.push(all.len());
+                    continue;
+                }
+                // Move the partial word to the next row.
+                if let Some(len) = last_break.take().filter(|len| *len < row.len()) {
+                    let tail = StyledGraphemes(row.split_off(len));
+                    row_width = tail.widths();
+                    all.push_back(mem::replace(&mut row, tail));
+                    let moved = row.len();
+                    for r in row_of.iter_mut().rev().take(moved) {
+                        *r += 1;
+                    }
+                }
+                // The grapheme may still not fit, e.g. a wide char after the moved word.
+                if width < row_width + styled.width {
+                    // Pad the rest of the row with spaces
+                    // instead of overflowing when a wide char is pushed to the next row.
+                    while row_width < width {
+                        row.push_back(StyledGrapheme::from(' '));
+                        row_width += 1;
+                    }
+                    all.push_back(mem::take(&mut row));
+                    row_width = 0;
+                }
             }
-            if width >= styled.width {
-                row.push_back(styled.clone());
+            row_width += styled.width;
+            let breakable = is_break(&styled);
+            row_of.push(all.len());
+            row.push_back(styled);
+            if breakable {
+                last_break = Some(row.len());
             }
         }
         if !row.is_empty() {
             all.push_back(row);
         }
+        (all, row_of)
+    }
 
+    fn fit_rows(
+        mut all: VecDeque<StyledGraphemes>,
+        height: usize,
+        offset: usize,
+    ) -> (Vec<StyledGraphemes>, usize) {
         if all.is_empty() {
             return (vec![], 0);
         }
@@ -327,7 +553,13 @@ pub struct StyledGraphemesDisplay<'a> {
 impl<'a> fmt::Display for StyledGraphemesDisplay<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         for styled_grapheme in self.styled_graphemes.iter() {
-            write!(f, "{}", styled_grapheme.style.apply(styled_grapheme.ch))?;
+            write!(
+                f,
+                "{}",
+                styled_grapheme
+                    .style
+                    .apply(styled_grapheme.cluster.as_str())
+            )?;
         }
         Ok(())
     }
@@ -343,12 +575,48 @@ mod test {
         #[test]
         fn test() {
             let style = ContentStyle::default();
-            let graphemes = StyledGraphemes::from_str("abc", style.clone());
+            let graphemes = StyledGraphemes::from_str("abc", style);
             assert_eq!(3, graphemes.0.len());
             assert!(graphemes.0.iter().all(|g| g.style == style));
         }
     }
 
+    mod from_str_clusters {
+        use super::*;
+
+        #[test]
+        fn test_with_combining_mark() {
+            let graphemes = StyledGraphemes::from("e\u{301}a");
+            assert_eq!(2, graphemes.len());
+            assert_eq!("e\u{301}", graphemes[0].as_str());
+        }
+
+        #[test]
+        fn test_with_zwj_sequence() {
+            let graphemes = StyledGraphemes::from("👨‍👩‍👧!");
+            assert_eq!(2, graphemes.len());
+            assert_eq!("👨‍👩‍👧", graphemes[0].as_str());
+        }
+    }
+
+    mod try_extend {
+        use super::*;
+
+        #[test]
+        fn test_with_combining_mark() {
+            let mut grapheme = StyledGrapheme::from('e');
+            assert!(grapheme.try_extend('\u{301}'));
+            assert_eq!("e\u{301}", grapheme.as_str());
+        }
+
+        #[test]
+        fn test_with_separate_char() {
+            let mut grapheme = StyledGrapheme::from('e');
+            assert!(!grapheme.try_extend('a'));
+            assert_eq!("e", grapheme.as_str());
+        }
+    }
+
     mod chars {
         use super::*;
 
@@ -358,6 +626,12 @@ mod test {
             let chars = graphemes.chars();
             assert_eq!(vec!['a', 'b', 'c'], chars);
         }
+
+        #[test]
+        fn test_with_clusters() {
+            let graphemes = StyledGraphemes::from("e\u{301} x");
+            assert_eq!(vec!['e', ' ', 'x'], graphemes.chars());
+        }
     }
 
     mod widths {
@@ -368,6 +642,20 @@ mod test {
             let graphemes = StyledGraphemes::from("a b");
             assert_eq!(3, graphemes.widths()); // 'a' and 'b' are each 1 width, and space is 1 width
         }
+
+        #[test]
+        fn test_with_zwj_sequence() {
+            let graphemes = StyledGraphemes::from("👨‍👩‍👧a");
+            assert_eq!(2, graphemes[0].width());
+            assert_eq!(3, graphemes.widths());
+        }
+
+        #[test]
+        fn test_with_combining_mark() {
+            let graphemes = StyledGraphemes::from("e\u{301}\u{302}a");
+            assert_eq!(1, graphemes[0].width());
+            assert_eq!(2, graphemes.widths());
+        }
     }
 
     mod replace_char {
@@ -418,7 +706,7 @@ mod test {
         fn test() {
             let mut graphemes = StyledGraphemes::from("abc");
             let new_style = StyleBuilder::new().fgc(Color::Green).build();
-            graphemes = graphemes.apply_style(new_style.clone());
+            graphemes = graphemes.apply_style(new_style);
             assert!(graphemes.iter().all(|g| g.style == new_style));
         }
     }
@@ -432,7 +720,7 @@ mod test {
         fn test_apply_style_at_specific_index() {
             let mut graphemes = StyledGraphemes::from("abc");
             let new_style = StyleBuilder::new().fgc(Color::Green).build();
-            graphemes = graphemes.apply_style_at(1, new_style.clone());
+            graphemes = graphemes.apply_style_at(1, new_style);
             assert_eq!(graphemes.0[1].style, new_style);
             assert_ne!(graphemes.0[0].style, new_style);
             assert_ne!(graphemes.0[2].style, new_style);
@@ -442,7 +730,7 @@ mod test {
         fn test_apply_style_at_out_of_bounds_index() {
             let mut graphemes = StyledGraphemes::from("abc");
             let new_style = StyleBuilder::new().fgc(Color::Green).build();
-            graphemes = graphemes.apply_style_at(5, new_style.clone()); // Out of bounds
+            graphemes = graphemes.apply_style_at(5, new_style); // Out of bounds
             assert_eq!(graphemes.0.len(), 3); // Ensure no changes in length
         }
     }
@@ -503,6 +791,14 @@ mod test {
             );
         }
 
+        #[test]
+        fn test_with_clusters() {
+            let graphemes = StyledGraphemes::from("cafe\u{301} cafe");
+            assert_eq!(vec![0, 5], graphemes.find_all("caf"));
+            assert_eq!(vec![0], graphemes.find_all("cafe\u{301}"));
+            assert_eq!(vec![5], graphemes.find_all("cafe"));
+        }
+
         #[test]
         fn test_with_full_match() {
             let graphemes = StyledGraphemes::from("Hello");
@@ -559,6 +855,81 @@ mod test {
     }
 
     #[cfg(test)]
+    mod truncate_with_ellipsis {
+        use super::*;
+
+        #[test]
+        fn test_with_fitting_input() {
+            let input = StyledGraphemes::from("abc");
+            assert_eq!("abc", input.truncate_with_ellipsis(3).to_string());
+        }
+
+        #[test]
+        fn test_with_long_input() {
+            let input = StyledGraphemes::from("abcdef");
+            assert_eq!("abc…", input.truncate_with_ellipsis(4).to_string());
+            assert_eq!("…", input.truncate_with_ellipsis(1).to_string());
+            assert_eq!("", input.truncate_with_ellipsis(0).to_string());
+        }
+
+        #[test]
+        fn test_with_wide_chars() {
+            let input = StyledGraphemes::from("日本語テキスト");
+            let truncated = input.truncate_with_ellipsis(6);
+            assert_eq!("日本 …", truncated.to_string());
+            assert_eq!(6, truncated.widths());
+        }
+
+        #[test]
+        fn test_ellipsis_style() {
+            use crate::{crossterm::style::Color, style::StyleBuilder};
+
+            let style = StyleBuilder::new().fgc(Color::Red).build();
+            let input = StyledGraphemes::from_str("abcdef", style);
+            let truncated = input.truncate_with_ellipsis(4);
+            assert_eq!(style, truncated[3].style);
+        }
+    }
+
+    mod scrolled {
+        use super::*;
+
+        fn scroll(input: &StyledGraphemes, cursor: usize, left: usize) -> (String, usize) {
+            let (row, left) = input.scrolled(6, cursor, left);
+            (row.to_string(), left)
+        }
+
+        #[test]
+        fn test_within_width() {
+            let input = StyledGraphemes::from("abcd ");
+            assert_eq!(("abcd ".to_string(), 0), scroll(&input, 4, 0));
+        }
+
+        #[test]
+        fn test_keeps_cursor_visible() {
+            let input = StyledGraphemes::from("abcdefghij ");
+            assert_eq!(("abcde>".to_string(), 0), scroll(&input, 0, 0));
+            assert_eq!(("abcde>".to_string(), 0), scroll(&input, 4, 0));
+            // Scrolls by a grapheme, showing the markers on both sides.
+            assert_eq!(("<cdef>".to_string(), 2), scroll(&input, 5, 0));
+            assert_eq!(("<ghij ".to_string(), 6), scroll(&input, 10, 2));
+            // Stays while the cursor is within the row.
+            assert_eq!(("<ghij ".to_string(), 6), scroll(&input, 7, 6));
+            // Scrolls back to the cursor.
+            assert_eq!(("<defg>".to_string(), 3), scroll(&input, 3, 6));
+            assert_eq!(("abcde>".to_string(), 0), scroll(&input, 0, 3));
+        }
+
+        #[test]
+        fn test_with_wide_chars() {
+            let input = StyledGraphemes::from("日本語です ");
+            let (row, left) = input.scrolled(6, 2, 0);
+            assert_eq!("<本語>", row.to_string());
+            assert_eq!(1, left);
+            assert!(row.widths() <= 6);
+        }
+    }
+
     mod matrixify {
         use super::*;
 
@@ -612,6 +983,140 @@ mod test {
             assert_eq!(offset, 1);
         }
 
+        #[test]
+        fn test_with_wide_chars_at_odd_width() {
+            let input = StyledGraphemes::from("日本語テキスト");
+            let (matrix, offset) = input.matrixify(5, 10, 0);
+            assert_eq!(matrix.len(), 4);
+            assert_eq!("日本 ", matrix[0].to_string());
+            assert_eq!("語テ ", matrix[1].to_string());
+            assert_eq!("キス ", matrix[2].to_string());
+            assert_eq!("ト", matrix[3].to_string());
+            assert!(matrix.iter().all(|row| row.widths() <= 5));
+            assert_eq!(offset, 0);
+        }
+
+        #[test]
+        fn test_with_wide_chars_at_width_3() {
+            let input = StyledGraphemes::from("a日本");
+            let (matrix, _) = input.matrixify(3, 10, 0);
+            assert_eq!(matrix.len(), 2);
+            assert_eq!("a日", matrix[0].to_string());
+            assert_eq!("本", matrix[1].to_string());
+        }
+
+        #[test]
+        fn test_with_wide_chars_at_width_1() {
+            let input = StyledGraphemes::from("日本語テキスト");
+            let (matrix, _) = input.matrixify(1, 10, 0);
+            assert_eq!(matrix.len(), 7);
+            assert!(matrix.iter().all(|row| row.to_string() == " "));
+        }
+
+        #[test]
+        fn test_with_clusters() {
+            let input = StyledGraphemes::from("e\u{301}👨‍👩‍👧e\u{301}");
+            let (matrix, _) = input.matrixify(3, 3, 0);
+            assert_eq!(
+                vec!["e\u{301}👨‍👩‍👧", "e\u{301}"],
+                matrix.iter().map(|row| row.to_string()).collect::<Vec<_>>()
+            );
+        }
+
+        #[test]
+        fn test_wrapped_on_spaces() {
+            let input = StyledGraphemes::from("the quick brown fox jumps");
+            let (matrix, offset) = input.matrixify_wrapped(10, 10, 0, &HashSet::from([' ']));
+            assert_eq!(
+                vec!["the quick ", "brown fox ", "jumps"],
+                matrix.iter().map(|row| row.to_string()).collect::<Vec<_>>()
+            );
+            assert_eq!(offset, 0);
+        }
+
+        #[test]
+        fn test_wrapped_with_long_token() {
+            let input = StyledGraphemes::from("a verylongtoken b");
+            let (matrix, _) = input.matrixify_wrapped(5, 10, 0, &HashSet::from([' ']));
+            assert_eq!(
+                vec!["a ", "veryl", "ongto", "ken b"],
+                matrix.iter().map(|row| row.to_string()).collect::<Vec<_>>()
+            );
+        }
+
+        #[test]
+        fn test_wrapped_with_wide_char_after_moved_word() {
+            let input = StyledGraphemes::from(" bcde漢");
+            let (matrix, _) = input.matrixify_wrapped(5, 10, 0, &HashSet::from([' ']));
+            assert_eq!(
+                vec![" ", "bcde ", "漢"],
+                matrix.iter().map(|row| row.to_string()).collect::<Vec<_>>()
+            );
+            assert!(matrix.iter().all(|row| row.widths() <= 5));
+        }
+
+        #[test]
+        fn test_wrapped_with_offset() {
+            let input = StyledGraphemes::from("aa bb cc dd");
+            let (matrix, offset) = input.matrixify_wrapped(3, 2, 2, &HashSet::from([' ']));
+            assert_eq!(
+                vec!["cc ", "dd"],
+                matrix.iter().map(|row| row.to_string()).collect::<Vec<_>>()
+            );
+            assert_eq!(offset, 0);
+        }
+
+        #[test]
+        fn test_windowed_scrolls_a_row_at_a_time() {
+            // 10 rows of 2 columns.
+            let input = StyledGraphemes::from("aabbccddeeffgghhiijj");
+            let rows = |matrix: Vec<StyledGraphemes>| {
+                matrix.iter().map(|row| row.to_string()).collect::<Vec<_>>()
+            };
+
+            let mut top = 0;
+            let mut tops = vec![];
+            for row in 0..10 {
+                let (matrix, new_top, cursor_row) = input.windowed(2, 3, row * 2, top);
+                assert_eq!(row, cursor_row);
+                assert!((new_top..new_top + 3).contains(&row));
+                assert_eq!(3, matrix.len());
+                top = new_top;
+                tops.push(top);
+            }
+            // The cursor moves down to the bottom of the window first.
+            assert_eq!(vec![0, 0, 0, 1, 2, 3, 4, 5, 6, 7], tops);
+            assert_eq!(
+                vec!["hh", "ii", "jj"],
+                rows(input.windowed(2, 3, 19, top).0)
+            );
+
+            tops.clear();
+            for row in (0..10).rev() {
+                let (_, new_top, _) = input.windowed(2, 3, row * 2 + 1, top);
+                top = new_top;
+                tops.push(top);
+            }
+            // And up to the top of the window.
+            assert_eq!(vec![7, 7, 7, 6, 5, 4, 3, 2, 1, 0], tops);
+        }
+
+        #[test]
+        fn test_windowed_after_shrinking() {
+            let input = StyledGraphemes::from("aabbcc");
+            // The window from a longer input is moved back to fill the rows.
+            let (matrix, top, _) = input.windowed(2, 2, 4, 5);
+            assert_eq!(1, top);
+            assert_eq!(
+                vec!["bb", "cc"],
+                matrix.iter().map(|row| row.to_string()).collect::<Vec<_>>()
+            );
+            assert_eq!(
+                (vec![], 0, 0),
+                StyledGraphemes::default().windowed(2, 2, 0, 3)
+            );
+        }
+
         #[test]
         fn test_with_large_offset() {
             let input = StyledGraphemes::from("1234567890");
@@ -0,0 +1,249 @@
+use std::{
+    io::{self, Write},
+    sync::Once,
+};
+
+use crate::{
+    cleanup,
+    crossterm::{
+        cursor,
+        event::{
+            DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+        },
+        execute, queue,
+        terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
+    },
+};
+
+/// Restores the terminal modes enabled through it when dropped,
+/// including when a panic unwinds through its owner.
+///
+/// `Prompt` and `Session` set up the terminal with a guard,
+/// and custom prompts running their own loop can do the same
+/// instead of calling crossterm directly, drawing through the guard as the writer:
+///
+/// ```no_run
+/// use std::io::Write;
+///
+/// use promkit::TerminalGuard;
+///
+/// let mut guard = TerminalGuard::new(std::io::stdout());
+/// guard.enable_raw_mode()?;
+/// guard.hide_cursor()?;
+/// write!(guard, "Press any key")?;
+/// guard.flush()?;
+/// // The terminal is restored here, or earlier by `restore`.
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub struct TerminalGuard<W: Write = io::Stdout> {
+    writer: W,
+    raw_mode: bool,
+    cursor_hidden: bool,
+    mouse_capture: bool,
+    bracketed_paste: bool,
+    alternate_screen: bool,
+}
+
+impl<W: Write> TerminalGuard<W> {
+    /// Creates a guard writing the sequences to the writer, with nothing enabled yet.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            raw_mode: false,
+            cursor_hidden: false,
+            mouse_capture: false,
+            bracketed_paste: false,
+            alternate_screen: false,
+        }
+    }
+
+    /// Returns a mutable reference to the writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Enters the raw mode, recording the terminal state
+    /// for a signal handler (or the panic hook) to restore on termination.
+    pub fn enable_raw_mode(&mut self) -> anyhow::Result<()> {
+        if !self.raw_mode {
+            cleanup::arm(self.alternate_screen);
+            if let Err(e) = terminal::enable_raw_mode() {
+                cleanup::disarm();
+                return Err(e.into());
+            }
+            self.raw_mode = true;
+        }
+        Ok(())
+    }
+
+    /// Returns whether the raw mode has been entered through the guard.
+    pub fn is_raw_mode_enabled(&self) -> bool {
+        self.raw_mode
+    }
+
+    /// Hides the cursor, which is shown (in the user's default shape) on restore.
+    pub fn hide_cursor(&mut self) -> anyhow::Result<()> {
+        execute!(self.writer, cursor::Hide)?;
+        self.cursor_hidden = true;
+        Ok(())
+    }
+
+    pub fn enable_mouse_capture(&mut self) -> anyhow::Result<()> {
+        execute!(self.writer, EnableMouseCapture)?;
+        self.mouse_capture = true;
+        Ok(())
+    }
+
+    pub fn disable_mouse_capture(&mut self) -> anyhow::Result<()> {
+        execute!(self.writer, DisableMouseCapture)?;
+        self.mouse_capture = false;
+        Ok(())
+    }
+
+    pub fn enable_bracketed_paste(&mut self) -> anyhow::Result<()> {
+        execute!(self.writer, EnableBracketedPaste)?;
+        self.bracketed_paste = true;
+        Ok(())
+    }
+
+    pub fn disable_bracketed_paste(&mut self) -> anyhow::Result<()> {
+        execute!(self.writer, DisableBracketedPaste)?;
+        self.bracketed_paste = false;
+        Ok(())
+    }
+
+    pub fn enter_alternate_screen(&mut self) -> anyhow::Result<()> {
+        cleanup::set_alternate_screen(true);
+        execute!(self.writer, EnterAlternateScreen)?;
+        self.alternate_screen = true;
+        Ok(())
+    }
+
+    pub fn leave_alternate_screen(&mut self) -> anyhow::Result<()> {
+        execute!(self.writer, LeaveAlternateScreen)?;
+        cleanup::set_alternate_screen(false);
+        self.alternate_screen = false;
+        Ok(())
+    }
+
+    /// Returns whether the alternate screen has been entered through the guard.
+    pub fn is_alternate_screen(&self) -> bool {
+        self.alternate_screen
+    }
+
+    /// Undoes what has been enabled through the guard. Dropping the guard does the same,
+    /// ignoring errors. Calling it again does nothing until something is enabled again.
+    pub fn restore(&mut self) -> anyhow::Result<()> {
+        let raw_mode = std::mem::take(&mut self.raw_mode);
+        let cursor_hidden = std::mem::take(&mut self.cursor_hidden);
+        let mouse_capture = std::mem::take(&mut self.mouse_capture);
+        let bracketed_paste = std::mem::take(&mut self.bracketed_paste);
+        let alternate_screen = std::mem::take(&mut self.alternate_screen);
+        // Unless the panic hook or a signal handler has already restored the terminal.
+        if raw_mode && !cleanup::disarm() {
+            return Ok(());
+        }
+
+        if cursor_hidden {
+            queue!(
+                self.writer,
+                cursor::Show,
+                cursor::SetCursorStyle::DefaultUserShape
+            )?;
+        }
+        if mouse_capture {
+            queue!(self.writer, DisableMouseCapture)?;
+        }
+        if bracketed_paste {
+            queue!(self.writer, DisableBracketedPaste)?;
+        }
+        if alternate_screen {
+            queue!(self.writer, LeaveAlternateScreen)?;
+        }
+        let flushed = self.writer.flush();
+        if raw_mode {
+            terminal::disable_raw_mode()?;
+        }
+        Ok(flushed?)
+    }
+}
+
+impl<W: Write> Write for TerminalGuard<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+impl<W: Write> Drop for TerminalGuard<W> {
+    fn drop(&mut self) {
+        self.restore().ok();
+    }
+}
+
+/// Makes panics (on any thread, e.g. in a spawned task) restore the terminal
+/// set up by a running prompt before the panic message is printed,
+/// chaining to the hook set before. Installing it more than once has no effect.
+///
+/// Without it, the terminal is still restored as the panic unwinds through the prompt,
+/// but only after the message has been printed in the raw mode,
+/// and never for a panic on another thread.
+pub fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            cleanup::restore();
+            previous(info);
+        }));
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod restore {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let mut guard = TerminalGuard::new(Vec::new());
+            guard.hide_cursor().unwrap();
+            guard.enable_mouse_capture().unwrap();
+            guard.enable_bracketed_paste().unwrap();
+            guard.get_mut().clear();
+
+            guard.restore().unwrap();
+            let mut expected = Vec::new();
+            queue!(
+                expected,
+                cursor::Show,
+                cursor::SetCursorStyle::DefaultUserShape,
+                DisableMouseCapture,
+                DisableBracketedPaste,
+            )
+            .unwrap();
+            assert_eq!(&expected, guard.get_mut());
+
+            // Restored only once.
+            guard.get_mut().clear();
+            guard.restore().unwrap();
+            assert!(guard.get_mut().is_empty());
+        }
+
+        #[test]
+        fn test_disabled_before() {
+            let mut guard = TerminalGuard::new(Vec::new());
+            guard.enable_mouse_capture().unwrap();
+            guard.disable_mouse_capture().unwrap();
+            guard.get_mut().clear();
+
+            guard.restore().unwrap();
+            assert!(guard.get_mut().is_empty());
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use std::ops::RangeInclusive;
+
 use rayon::prelude::*;
 
 pub mod format;
@@ -66,6 +68,14 @@ pub struct Row {
     pub v: Value,
 }
 
+impl Row {
+    /// Returns whether the key or the string value of the row contains the query.
+    pub fn contains(&self, query: &str) -> bool {
+        self.k.as_ref().is_some_and(|k| k.contains(query))
+            || matches!(&self.v, Value::String(s) if s.contains(query))
+    }
+}
+
 pub trait RowOperation {
     fn up(&self, current: usize) -> usize;
     fn head(&self) -> usize;
@@ -390,17 +400,98 @@ pub fn create_rows<'a, T: IntoIterator<Item = &'a serde_json::Value>>(iter: T) -
     rows
 }
 
+/// Reconstructs the value starting at the row of the given index,
+/// and returns it along with the index of the row following it.
+fn build_value(rows: &[Row], index: usize) -> (serde_json::Value, usize) {
+    match &rows[index].v {
+        Value::Null => (serde_json::Value::Null, index + 1),
+        Value::Boolean(b) => (serde_json::Value::Bool(*b), index + 1),
+        Value::Number(n) => (serde_json::Value::Number(n.clone()), index + 1),
+        Value::String(s) => (serde_json::Value::String(s.clone()), index + 1),
+        Value::Empty { typ } => (
+            match typ {
+                ContainerType::Object => serde_json::Value::Object(serde_json::Map::new()),
+                ContainerType::Array => serde_json::Value::Array(Vec::new()),
+            },
+            index + 1,
+        ),
+        Value::Open {
+            typ, close_index, ..
+        } => {
+            let mut i = index + 1;
+            let value = match typ {
+                ContainerType::Object => {
+                    let mut obj = serde_json::Map::new();
+                    while i < *close_index {
+                        let key = rows[i].k.clone().unwrap_or_default();
+                        let (value, next) = build_value(rows, i);
+                        obj.insert(key, value);
+                        i = next;
+                    }
+                    serde_json::Value::Object(obj)
+                }
+                ContainerType::Array => {
+                    let mut arr = Vec::new();
+                    while i < *close_index {
+                        let (value, next) = build_value(rows, i);
+                        arr.push(value);
+                        i = next;
+                    }
+                    serde_json::Value::Array(arr)
+                }
+            };
+            (value, close_index + 1)
+        }
+        // A closing row is never the start of a value,
+        // so skip it in case of malformed rows.
+        Value::Close { .. } => (serde_json::Value::Null, index + 1),
+    }
+}
+
+/// Reconstructs the top-level values (documents) from rows created by `create_rows`,
+/// regardless of the collapsed states. The order of the keys is preserved.
+pub fn to_value(rows: &[Row]) -> Vec<serde_json::Value> {
+    let mut values = Vec::new();
+    let mut i = 0;
+    while i < rows.len() {
+        let (value, next) = build_value(rows, i);
+        values.push(value);
+        i = next;
+    }
+    values
+}
+
+/// Reassembles rows created by `create_rows` back into a `serde_json::Value`,
+/// regardless of the collapsed states.
+/// When the rows contain multiple top-level values, they are returned as an array.
+pub fn rows_to_value(rows: &[Row]) -> serde_json::Value {
+    let mut values = to_value(rows);
+    if values.len() == 1 {
+        values.pop().unwrap_or_default()
+    } else {
+        serde_json::Value::Array(values)
+    }
+}
+
 #[derive(Debug)]
 pub struct PathIterator<'a> {
     stack: Vec<(String, &'a serde_json::Value)>,
 }
 
 impl<'a> PathIterator<'a> {
+    /// Quotes the key as a JSON string unless it is an identifier jq accepts bare,
+    /// i.e. `[A-Za-z_][A-Za-z0-9_]*`, so that the path is unambiguous and valid for jq
+    /// (e.g. `."a.b"` is a key containing a dot, while `.a.b` is a nested key).
     fn escape_json_path_key(key: &str) -> String {
-        if key.contains('.') || key.contains('-') || key.contains('@') {
-            format!("\"{}\"", key)
-        } else {
+        let mut chars = key.chars();
+        let bare = chars
+            .next()
+            .is_some_and(|ch| ch.is_ascii_alphabetic() || ch == '_')
+            && chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_');
+        if bare {
             key.to_string()
+        } else {
+            serde_json::Value::from(key).to_string()
         }
     }
 }
@@ -447,3 +538,203 @@ pub fn get_all_paths<'a, T: IntoIterator<Item = &'a serde_json::Value>>(
     }
     PathIterator { stack }
 }
+
+/// Walks through the rows, computing the path of each non-closing row
+/// in the same format as `get_all_paths`,
+/// and returns the index of the first row for which `f` returns `true`.
+fn find_row_with_path<F: FnMut(usize, &str) -> bool>(rows: &[Row], mut f: F) -> Option<usize> {
+    // Each frame holds the path of an open container, its type and the next array index.
+    let mut stack: Vec<(String, ContainerType, usize)> = Vec::new();
+
+    for (i, row) in rows.iter().enumerate() {
+        if let Value::Close { .. } = row.v {
+            stack.pop();
+            continue;
+        }
+
+        let current_path = match stack.last_mut() {
+            None => ".".to_string(),
+            Some((parent, ContainerType::Object, _)) => {
+                let escaped =
+                    PathIterator::escape_json_path_key(row.k.as_deref().unwrap_or_default());
+                if parent == "." {
+                    format!(".{}", escaped)
+                } else {
+                    format!("{}.{}", parent, escaped)
+                }
+            }
+            Some((parent, ContainerType::Array, index)) => {
+                let ret = format!("{}[{}]", parent, index);
+                *index += 1;
+                ret
+            }
+        };
+
+        if f(i, &current_path) {
+            return Some(i);
+        }
+
+        if let Value::Open { typ, .. } = &row.v {
+            stack.push((current_path, typ.clone(), 0));
+        }
+    }
+
+    None
+}
+
+/// Finds the index of the row addressed by the given path,
+/// in the same format as the paths produced by `get_all_paths`
+/// (e.g. `.spec.containers[0].image`).
+/// When multiple JSON values are present, the first match is returned.
+pub fn find_by_path(rows: &[Row], path: &str) -> Option<usize> {
+    find_row_with_path(rows, |_, current_path| current_path == path)
+}
+
+/// Returns the path of the row at the given index (e.g. `.spec.containers[0].image`),
+/// in the same format as the paths produced by `get_all_paths`.
+/// For a closing bracket, the path of the corresponding container is returned.
+/// Returns an empty string if the index is out of range.
+pub fn path_of(rows: &[Row], index: usize) -> String {
+    let index = match rows.get(index).map(|row| &row.v) {
+        Some(Value::Close { open_index, .. }) => *open_index,
+        Some(_) => index,
+        None => return String::new(),
+    };
+
+    let mut ret = String::new();
+    find_row_with_path(rows, |i, current_path| {
+        if i == index {
+            ret = current_path.to_string();
+        }
+        i == index
+    });
+    ret
+}
+
+/// Returns the value of the row at the given index, including its descendants.
+/// For a closing bracket, the value of the corresponding container is returned.
+/// Returns `None` if the index is out of range.
+pub fn value_at(rows: &[Row], index: usize) -> Option<serde_json::Value> {
+    let index = match &rows.get(index)?.v {
+        Value::Close { open_index, .. } => *open_index,
+        _ => index,
+    };
+    Some(build_value(rows, index).0)
+}
+
+/// Replaces the value of the leaf row at the given index with the scalar value,
+/// keeping its key and depth. As the number of rows is unchanged, so are the indices.
+/// Returns `false` without changes if the row is a container (including empty ones),
+/// the value is an array or an object, or the index is out of range.
+pub fn set_value(rows: &mut [Row], index: usize, value: serde_json::Value) -> bool {
+    let Some(row) = rows.get_mut(index) else {
+        return false;
+    };
+    if matches!(
+        row.v,
+        Value::Empty { .. } | Value::Open { .. } | Value::Close { .. }
+    ) {
+        return false;
+    }
+    row.v = match value {
+        serde_json::Value::Null => Value::Null,
+        serde_json::Value::Bool(b) => Value::Boolean(b),
+        serde_json::Value::Number(n) => Value::Number(n),
+        serde_json::Value::String(s) => Value::String(s),
+        serde_json::Value::Array(_) | serde_json::Value::Object(_) => return false,
+    };
+    true
+}
+
+/// Expands the rows from `start` to `end` (in either order, clamped to the rows)
+/// to the nearest enclosing boundaries making valid JSON, e.g. for a visual selection.
+///
+/// A container partly in the range is included as a whole, with both of its brackets
+/// and any rows hidden in it or in the collapsed containers inside.
+/// As keys or elements alone are not valid JSON, a range covering more than one value
+/// inside a container is widened to that container.
+/// Only top-level values (documents) may be covered side by side.
+pub fn balance_range(rows: &[Row], start: usize, end: usize) -> RangeInclusive<usize> {
+    let last = rows.len().saturating_sub(1);
+    let (mut start, mut end) = (start.min(end).min(last), start.max(end).min(last));
+
+    // The containers are properly nested, so the rows inside an included container
+    // need no checks, and a bracket is only matched outside of the range.
+    let mut i = start;
+    while i <= end && i < rows.len() {
+        match &rows[i].v {
+            Value::Open { close_index, .. } => {
+                end = end.max(*close_index);
+                i = close_index + 1;
+            }
+            Value::Close { open_index, .. } => {
+                start = start.min(*open_index);
+                i += 1;
+            }
+            _ => i += 1,
+        }
+    }
+
+    let Some(first) = rows.get(start) else {
+        return start..=end;
+    };
+    let first_end = match &first.v {
+        Value::Open { close_index, .. } => *close_index,
+        _ => start,
+    };
+    if first_end >= end || first.depth == 0 {
+        return start..=end;
+    }
+    // Widen the siblings to their parent.
+    let parent = rows[..start]
+        .iter()
+        .rposition(|row| row.depth + 1 == first.depth && matches!(row.v, Value::Open { .. }));
+    match parent.map(|parent| (parent, &rows[parent].v)) {
+        Some((parent, Value::Open { close_index, .. })) => parent..=*close_index,
+        _ => start..=end,
+    }
+}
+
+/// Returns the values covered by the range of rows, e.g. one balanced by `balance_range`,
+/// regardless of the collapsed states.
+/// The range is expected to start at a value and to end at the end of a value.
+pub fn values_in_range(rows: &[Row], range: RangeInclusive<usize>) -> Vec<serde_json::Value> {
+    let mut values = Vec::new();
+    let mut i = *range.start();
+    while i <= *range.end() && i < rows.len() {
+        let (value, next) = build_value(rows, i);
+        values.push(value);
+        i = next;
+    }
+    values
+}
+
+/// Searches the rows whose key or string value contains the query,
+/// and returns their indices in order.
+/// Rows hidden inside collapsed containers are skipped, in the same way as `RowOperation::extract`.
+pub fn search(rows: &[Row], query: &str) -> Vec<usize> {
+    let mut ret = Vec::new();
+    if query.is_empty() {
+        return ret;
+    }
+
+    let mut i = 0;
+    while i < rows.len() {
+        if rows[i].contains(query) {
+            ret.push(i);
+        }
+        match &rows[i].v {
+            Value::Open {
+                collapsed: true,
+                close_index,
+                ..
+            } => {
+                i = *close_index + 1;
+            }
+            _ => {
+                i += 1;
+            }
+        }
+    }
+    ret
+}
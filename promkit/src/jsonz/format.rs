@@ -1,9 +1,11 @@
 use crate::{
-    crossterm::style::{Attribute, ContentStyle},
+    crossterm::style::{Attribute, Attributes, Color, ContentStyle},
     grapheme::StyledGraphemes,
+    indented_rows::wrap_with_hanging_indent,
+    style::StyleBuilder,
 };
 
-use super::{ContainerType, Row, Value};
+use super::{build_value, find_row_with_path, to_value, ContainerType, PathIterator, Row, Value};
 
 #[derive(Clone)]
 pub struct RowFormatter {
@@ -32,6 +34,12 @@ pub struct RowFormatter {
     /// the total indentation space. For example, an `indent` value of 4 means each
     /// indentation level will be 4 spaces wide.
     pub indent: usize,
+
+    /// The string repeated for each indentation level when rendering to the terminal,
+    /// e.g. `"│ "` for guide lines or `"\t"`.
+    /// If empty, `indent` spaces are used instead.
+    /// Note that `format_raw_json` always indents with `indent` spaces.
+    pub indent_unit: String,
 }
 
 impl Default for RowFormatter {
@@ -47,18 +55,168 @@ impl Default for RowFormatter {
             active_item_attribute: Attribute::NoBold,
             inactive_item_attribute: Attribute::NoBold,
             indent: Default::default(),
+            indent_unit: Default::default(),
+        }
+    }
+}
+
+/// A color scheme bundling the styles of each part of JSON.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsonTheme {
+    /// Style for {}.
+    pub curly_brackets_style: ContentStyle,
+    /// Style for [].
+    pub square_brackets_style: ContentStyle,
+    /// Style for "key".
+    pub key_style: ContentStyle,
+    /// Style for string values.
+    pub string_value_style: ContentStyle,
+    /// Style for number values.
+    pub number_value_style: ContentStyle,
+    /// Style for boolean values.
+    pub boolean_value_style: ContentStyle,
+    /// Style for null values.
+    pub null_value_style: ContentStyle,
+}
+
+impl Default for JsonTheme {
+    /// The theme used by the JSON preset by default.
+    fn default() -> Self {
+        Self {
+            curly_brackets_style: StyleBuilder::new()
+                .attrs(Attributes::from(Attribute::Bold))
+                .build(),
+            square_brackets_style: StyleBuilder::new()
+                .attrs(Attributes::from(Attribute::Bold))
+                .build(),
+            key_style: StyleBuilder::new().fgc(Color::DarkBlue).build(),
+            string_value_style: StyleBuilder::new().fgc(Color::DarkGreen).build(),
+            number_value_style: StyleBuilder::new().build(),
+            boolean_value_style: StyleBuilder::new().build(),
+            null_value_style: StyleBuilder::new().fgc(Color::DarkGrey).build(),
+        }
+    }
+}
+
+impl JsonTheme {
+    /// A theme based on the Monokai color scheme.
+    pub fn monokai() -> Self {
+        let rgb = |r, g, b| StyleBuilder::new().fgc(Color::Rgb { r, g, b }).build();
+        Self {
+            curly_brackets_style: rgb(0xf8, 0xf8, 0xf2),
+            square_brackets_style: rgb(0xf8, 0xf8, 0xf2),
+            key_style: rgb(0xf9, 0x26, 0x72),
+            string_value_style: rgb(0xe6, 0xdb, 0x74),
+            number_value_style: rgb(0xae, 0x81, 0xff),
+            boolean_value_style: rgb(0xae, 0x81, 0xff),
+            null_value_style: rgb(0x75, 0x71, 0x5e),
+        }
+    }
+
+    /// A theme based on the Solarized (dark) color scheme.
+    pub fn solarized_dark() -> Self {
+        let rgb = |r, g, b| StyleBuilder::new().fgc(Color::Rgb { r, g, b }).build();
+        Self {
+            curly_brackets_style: rgb(0x93, 0xa1, 0xa1),
+            square_brackets_style: rgb(0x93, 0xa1, 0xa1),
+            key_style: rgb(0x26, 0x8b, 0xd2),
+            string_value_style: rgb(0x2a, 0xa1, 0x98),
+            number_value_style: rgb(0xd3, 0x36, 0x82),
+            boolean_value_style: rgb(0xb5, 0x89, 0x00),
+            null_value_style: rgb(0x58, 0x6e, 0x75),
         }
     }
 }
 
 impl RowFormatter {
+    /// Applies all the styles of the theme at once.
+    pub fn apply_theme(&mut self, theme: JsonTheme) {
+        self.curly_brackets_style = theme.curly_brackets_style;
+        self.square_brackets_style = theme.square_brackets_style;
+        self.key_style = theme.key_style;
+        self.string_value_style = theme.string_value_style;
+        self.number_value_style = theme.number_value_style;
+        self.boolean_value_style = theme.boolean_value_style;
+        self.null_value_style = theme.null_value_style;
+    }
+
+    /// Returns the indentation for the given depth when rendering to the terminal.
+    pub(crate) fn indentation(&self, depth: usize) -> String {
+        if self.indent_unit.is_empty() {
+            " ".repeat(self.indent * depth)
+        } else {
+            self.indent_unit.repeat(depth)
+        }
+    }
+
     /// Formats a Vec<Row> into Vec<StyledGraphemes> with appropriate styling and width limits
     pub fn format_for_terminal_display(&self, rows: &[Row], width: u16) -> Vec<StyledGraphemes> {
+        self.format_lines(rows)
+            .into_iter()
+            .map(|line| line.truncate_with_ellipsis(width as usize))
+            .collect()
+    }
+
+    /// Formats a Vec<Row> like `format_for_terminal_display`,
+    /// but wraps each row exceeding the width onto continuation rows instead of truncating it.
+    /// The visual rows of each row are returned together.
+    pub fn format_for_terminal_display_wrapped(
+        &self,
+        rows: &[Row],
+        width: u16,
+    ) -> Vec<Vec<StyledGraphemes>> {
+        self.format_lines(rows)
+            .into_iter()
+            .zip(rows)
+            .map(|(line, row)| self.wrap_line(line, row, width))
+            .collect()
+    }
+
+    /// Wraps a formatted row exceeding the width onto continuation rows,
+    /// indented to the column where the value starts (i.e. after the key).
+    /// If the value starts too far right, the continuation rows are indented
+    /// to the depth of the row instead, or not at all.
+    pub fn wrap_line(&self, line: StyledGraphemes, row: &Row, width: u16) -> Vec<StyledGraphemes> {
+        let indent_width = StyledGraphemes::from(self.indentation(row.depth)).widths();
+        let key_width = row
+            .k
+            .as_ref()
+            .map(|key| StyledGraphemes::from(format!("\"{}\": ", key)).widths())
+            .unwrap_or_default();
+        wrap_with_hanging_indent(line, &[indent_width + key_width, indent_width], width)
+    }
+
+    /// Formats each row into a single styled line regardless of the width.
+    pub fn format_lines(&self, rows: &[Row]) -> Vec<StyledGraphemes> {
+        self.format_contents(rows)
+            .into_iter()
+            .zip(rows)
+            .enumerate()
+            .map(|(i, (content, row))| {
+                // Note that `extract_rows_from_current`
+                // returns rows starting from the current position,
+                // so the first row should always be highlighted as active.
+                // The attribute covers the indentation too,
+                // so that guide lines are highlighted along with the row.
+                StyledGraphemes::from_iter([
+                    StyledGraphemes::from(self.indentation(row.depth)),
+                    content,
+                ])
+                .apply_attribute(if i == 0 {
+                    self.active_item_attribute
+                } else {
+                    self.inactive_item_attribute
+                })
+            })
+            .collect()
+    }
+
+    /// Formats each row into its styled content, i.e. the key and the value
+    /// without the indentation and the attribute of the cursor.
+    pub fn format_contents(&self, rows: &[Row]) -> Vec<StyledGraphemes> {
         let mut formatted = Vec::new();
-        let width = width as usize;
 
         for (i, row) in rows.iter().enumerate() {
-            let indent = StyledGraphemes::from(" ".repeat(self.indent * row.depth));
             let mut parts = Vec::new();
 
             if let Some(key) = &row.k {
@@ -134,34 +292,7 @@ impl RowFormatter {
                 }
             }
 
-            let mut content: StyledGraphemes = parts.into_iter().collect();
-
-            // Note that `extract_rows_from_current`
-            // returns rows starting from the current position,
-            // so the first row should always be highlighted as active
-            content = content.apply_attribute(if i == 0 {
-                self.active_item_attribute
-            } else {
-                self.inactive_item_attribute
-            });
-
-            let mut line: StyledGraphemes = vec![indent, content].into_iter().collect();
-
-            if line.widths() > width {
-                let ellipsis: StyledGraphemes = StyledGraphemes::from("…");
-                let mut truncated = StyledGraphemes::default();
-                let mut current_width = 0;
-                for g in line.iter() {
-                    if current_width + g.width() + ellipsis.widths() > width {
-                        break;
-                    }
-                    truncated.push_back(g.clone());
-                    current_width += g.width();
-                }
-                line = vec![truncated, ellipsis].into_iter().collect();
-            }
-
-            formatted.push(line);
+            formatted.push(parts.into_iter().collect());
         }
 
         formatted
@@ -244,6 +375,225 @@ impl RowFormatter {
     }
 }
 
+/// The text format of JSON values rendered by `render`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// Single-line JSON, one line per document.
+    Compact,
+    /// Multi-line JSON indented with the given number of spaces.
+    Pretty { indent: usize },
+    /// `path = value` lines, with the paths in the same format as `get_all_paths`
+    /// and the values in compact JSON.
+    PathList,
+    /// Block-style YAML, with the documents separated by `---`.
+    Yaml,
+}
+
+impl Default for Format {
+    fn default() -> Self {
+        Self::Pretty { indent: 2 }
+    }
+}
+
+/// The JSON to be rendered by `render`.
+pub enum Source<'a> {
+    /// Rows created by `create_rows`.
+    /// In `Format::PathList`, only the visible rows are listed,
+    /// i.e. a collapsed container is listed as a whole.
+    Rows(&'a [Row]),
+    /// The documents.
+    Values(&'a [serde_json::Value]),
+}
+
+impl<'a> From<&'a [Row]> for Source<'a> {
+    fn from(rows: &'a [Row]) -> Self {
+        Self::Rows(rows)
+    }
+}
+
+impl<'a> From<&'a [serde_json::Value]> for Source<'a> {
+    fn from(values: &'a [serde_json::Value]) -> Self {
+        Self::Values(values)
+    }
+}
+
+impl<'a> From<&'a serde_json::Value> for Source<'a> {
+    fn from(value: &'a serde_json::Value) -> Self {
+        Self::Values(std::slice::from_ref(value))
+    }
+}
+
+/// Renders the rows or the values in the given format, without a trailing newline.
+pub fn render<'a, S: Into<Source<'a>>>(source: S, format: Format) -> String {
+    let source = source.into();
+    if let (Source::Rows(rows), Format::PathList) = (&source, format) {
+        return path_list_of_rows(rows).join("\n");
+    }
+
+    let owned;
+    let values = match source {
+        Source::Rows(rows) => {
+            owned = to_value(rows);
+            &owned[..]
+        }
+        Source::Values(values) => values,
+    };
+    match format {
+        Format::Compact => values
+            .iter()
+            .map(|value| value.to_string())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Format::Pretty { indent } => values
+            .iter()
+            .map(|value| pretty(value, indent))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Format::PathList => {
+            let mut lines = Vec::new();
+            for value in values {
+                path_list(".", value, &mut lines);
+            }
+            lines.join("\n")
+        }
+        Format::Yaml => values
+            .iter()
+            .map(|value| {
+                let mut lines = Vec::new();
+                yaml_block(value, 0, &mut lines);
+                lines.join("\n")
+            })
+            .collect::<Vec<_>>()
+            .join("\n---\n"),
+    }
+}
+
+fn pretty(value: &serde_json::Value, indent: usize) -> String {
+    let indent = " ".repeat(indent);
+    let mut buf = Vec::new();
+    let mut serializer = serde_json::Serializer::with_formatter(
+        &mut buf,
+        serde_json::ser::PrettyFormatter::with_indent(indent.as_bytes()),
+    );
+    // Serializing a value into a vector never fails.
+    serde::Serialize::serialize(value, &mut serializer).ok();
+    String::from_utf8(buf).unwrap_or_default()
+}
+
+fn child_path(parent: &str, key: &str) -> String {
+    let escaped = PathIterator::escape_json_path_key(key);
+    if parent == "." {
+        format!(".{}", escaped)
+    } else {
+        format!("{}.{}", parent, escaped)
+    }
+}
+
+/// Lists the leaves of the value, including empty containers, in order.
+fn path_list(path: &str, value: &serde_json::Value, lines: &mut Vec<String>) {
+    match value {
+        serde_json::Value::Object(obj) if !obj.is_empty() => {
+            for (key, val) in obj {
+                path_list(&child_path(path, key), val, lines);
+            }
+        }
+        serde_json::Value::Array(arr) if !arr.is_empty() => {
+            for (i, val) in arr.iter().enumerate() {
+                path_list(&format!("{}[{}]", path, i), val, lines);
+            }
+        }
+        _ => lines.push(format!("{} = {}", path, value)),
+    }
+}
+
+/// Lists the visible leaves of the rows, and the collapsed containers as a whole.
+fn path_list_of_rows(rows: &[Row]) -> Vec<String> {
+    let mut paths = vec![String::new(); rows.len()];
+    find_row_with_path(rows, |i, path| {
+        paths[i] = path.to_string();
+        false
+    });
+
+    let mut lines = Vec::new();
+    let mut i = 0;
+    while i < rows.len() {
+        match &rows[i].v {
+            Value::Open {
+                collapsed: false, ..
+            }
+            | Value::Close { .. } => i += 1,
+            _ => {
+                let (value, next) = build_value(rows, i);
+                lines.push(format!("{} = {}", paths[i], value));
+                i = next;
+            }
+        }
+    }
+    lines
+}
+
+/// Returns the value in a single line if it is a scalar or an empty container.
+fn yaml_scalar(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(s) => Some(yaml_string(s)),
+        serde_json::Value::Object(obj) if !obj.is_empty() => None,
+        serde_json::Value::Array(arr) if !arr.is_empty() => None,
+        _ => Some(value.to_string()),
+    }
+}
+
+/// Leaves the string unquoted only if it cannot be read as anything else,
+/// and otherwise quotes it as JSON, which is also a valid YAML string.
+fn yaml_string(s: &str) -> String {
+    let plain = s
+        .chars()
+        .next()
+        .is_some_and(|ch| ch.is_alphabetic() || ch == '_')
+        && s.chars()
+            .all(|ch| ch.is_alphanumeric() || matches!(ch, '_' | '-' | '.' | '/'))
+        && !matches!(
+            s.to_lowercase().as_str(),
+            "true" | "false" | "null" | "yes" | "no" | "on" | "off" | "y" | "n"
+        );
+    if plain {
+        s.to_string()
+    } else {
+        serde_json::Value::from(s).to_string()
+    }
+}
+
+fn yaml_block(value: &serde_json::Value, indent: usize, lines: &mut Vec<String>) {
+    let pad = " ".repeat(indent);
+    match value {
+        serde_json::Value::Object(obj) if !obj.is_empty() => {
+            for (key, val) in obj {
+                let key = yaml_string(key);
+                match yaml_scalar(val) {
+                    Some(scalar) => lines.push(format!("{}{}: {}", pad, key, scalar)),
+                    None => {
+                        lines.push(format!("{}{}:", pad, key));
+                        yaml_block(val, indent + 2, lines);
+                    }
+                }
+            }
+        }
+        serde_json::Value::Array(arr) if !arr.is_empty() => {
+            for val in arr {
+                match yaml_scalar(val) {
+                    Some(scalar) => lines.push(format!("{}- {}", pad, scalar)),
+                    None => {
+                        // The first line of the nested block starts the item.
+                        let start = lines.len();
+                        yaml_block(val, indent + 2, lines);
+                        lines[start].replace_range(indent..indent + 2, "- ");
+                    }
+                }
+            }
+        }
+        _ => lines.push(format!("{}{}", pad, yaml_scalar(value).unwrap_or_default())),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     mod format_raw_json {
@@ -287,10 +637,127 @@ mod tests {
                     ..Default::default()
                 }
                 .format_raw_json(&create_rows([
-                    &serde_json::Value::from_str(&expected).unwrap()
+                    &serde_json::Value::from_str(expected).unwrap()
                 ])),
                 expected,
             );
         }
     }
+    mod format_for_terminal_display {
+        use crate::jsonz::{create_rows, format};
+
+        #[test]
+        fn test_truncate_with_wide_chars() {
+            let rows = create_rows([&serde_json::json!("日本語テキスト")]);
+            let formatted = format::RowFormatter::default().format_for_terminal_display(&rows, 7);
+            // `"日本` is 5 columns and `語` does not fit before the ellipsis,
+            // so a space is padded to keep the ellipsis at the last column.
+            assert_eq!("\"日本 …", formatted[0].to_string());
+            assert_eq!(7, formatted[0].widths());
+        }
+    }
+
+    mod format_for_terminal_display_wrapped {
+        use crate::jsonz::{create_rows, format};
+
+        fn wrap(value: serde_json::Value, width: u16) -> Vec<Vec<String>> {
+            let rows = create_rows([&value]);
+            format::RowFormatter {
+                indent: 2,
+                ..Default::default()
+            }
+            .format_for_terminal_display_wrapped(&rows, width)
+            .iter()
+            .map(|lines| lines.iter().map(|line| line.to_string()).collect())
+            .collect()
+        }
+
+        #[test]
+        fn test_with_wide_chars() {
+            let wrapped = wrap(serde_json::json!({"k": "日本語テキスト"}), 12);
+            assert_eq!(3, wrapped.len());
+            // A wide char which does not fit at the edge is pushed to the next row.
+            assert_eq!(
+                vec![
+                    "  \"k\": \"日本",
+                    "       語テ ",
+                    "       キス ",
+                    "       ト\"",
+                ],
+                wrapped[1],
+            );
+        }
+
+        #[test]
+        fn test_with_long_unbroken_token() {
+            let wrapped = wrap(serde_json::json!(["a".repeat(20)]), 10);
+            assert_eq!(vec!["  \"aaaaaaa", "  aaaaaaaa", "  aaaaa\""], wrapped[1],);
+        }
+
+        #[test]
+        fn test_with_long_key() {
+            // The value starts beyond the width, so the continuation rows are indented to the depth.
+            let wrapped = wrap(serde_json::json!({"a_very_long_key": 1}), 10);
+            assert_eq!(vec!["  \"a_very_", "  long_key", "  \": 1"], wrapped[1]);
+        }
+
+        #[test]
+        fn test_within_width() {
+            let wrapped = wrap(serde_json::json!({"k": 1}), 10);
+            assert_eq!(vec![vec!["{"], vec!["  \"k\": 1"], vec!["}"]], wrapped,);
+        }
+    }
+
+    mod indent_unit {
+        use crate::jsonz::{create_rows, format};
+
+        #[test]
+        fn test_guide_lines() {
+            let rows = create_rows([&serde_json::json!({"a": [1], "b": {}})]);
+            let formatted = format::RowFormatter {
+                indent: 4,
+                indent_unit: String::from("│ "),
+                ..Default::default()
+            }
+            .format_for_terminal_display(&rows, 20);
+            assert_eq!(
+                vec!["{", "│ \"a\": [", "│ │ 1", "│ ],", "│ \"b\": {}", "}"],
+                formatted
+                    .iter()
+                    .map(|line| line.to_string())
+                    .collect::<Vec<_>>(),
+            );
+        }
+
+        #[test]
+        fn test_fallback_to_indent() {
+            let rows = create_rows([&serde_json::json!([1])]);
+            let formatted = format::RowFormatter {
+                indent: 3,
+                ..Default::default()
+            }
+            .format_for_terminal_display(&rows, 20);
+            assert_eq!("   1", formatted[1].to_string());
+        }
+    }
+
+    mod apply_theme {
+        use crate::jsonz::format::{JsonTheme, RowFormatter};
+
+        #[test]
+        fn test() {
+            let mut formatter = RowFormatter {
+                indent: 2,
+                ..Default::default()
+            };
+            formatter.apply_theme(JsonTheme::monokai());
+            assert_eq!(JsonTheme::monokai().key_style, formatter.key_style);
+            assert_eq!(
+                JsonTheme::monokai().null_value_style,
+                formatter.null_value_style
+            );
+            // Non-color settings are kept.
+            assert_eq!(2, formatter.indent);
+        }
+    }
 }
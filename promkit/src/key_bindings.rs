@@ -0,0 +1,132 @@
+use crate::{
+    chord::KeyCombo,
+    crossterm::event::{Event, KeyCode, KeyModifiers},
+};
+
+/// Returns the combo of Ctrl and the char, e.g. `ctrl('w')`.
+pub fn ctrl(ch: char) -> KeyCombo {
+    KeyCombo::new(KeyCode::Char(ch), KeyModifiers::CONTROL)
+}
+
+/// Returns the combo of Alt and the char, e.g. `alt('b')`.
+pub fn alt(ch: char) -> KeyCombo {
+    KeyCombo::new(KeyCode::Char(ch), KeyModifiers::ALT)
+}
+
+/// Maps keys to the named actions of a preset, e.g. `Action::MoveUp`.
+///
+/// The default keymap of a preset looks up the action of a key press here,
+/// so a single key can be rebound or unbound without replacing the whole keymap.
+/// `KeyBindings::default()` of each action type holds the default bindings of its preset.
+#[derive(Clone, Debug)]
+pub struct KeyBindings<A> {
+    bindings: Vec<(KeyCombo, A)>,
+}
+
+impl<A> KeyBindings<A> {
+    /// Creates the bindings without any key bound.
+    pub fn empty() -> Self {
+        Self {
+            bindings: Default::default(),
+        }
+    }
+
+    /// Binds the key to the action, replacing the previous binding of the key.
+    pub fn bind<K: Into<KeyCombo>>(mut self, key: K, action: A) -> Self {
+        let key = key.into();
+        self.bindings.retain(|(bound, _)| *bound != key);
+        self.bindings.push((key, action));
+        self
+    }
+
+    /// Removes the binding of the key, if any.
+    pub fn unbind<K: Into<KeyCombo>>(mut self, key: K) -> Self {
+        let key = key.into();
+        self.bindings.retain(|(bound, _)| *bound != key);
+        self
+    }
+
+    /// Returns the action bound to the key press, or `None` for the other events.
+    pub fn action(&self, event: &Event) -> Option<&A> {
+        let combo = KeyCombo::from_event(event)?;
+        self.bindings
+            .iter()
+            .find(|(bound, _)| *bound == combo)
+            .map(|(_, action)| action)
+    }
+
+    /// Returns the keys bound to the action, e.g. to show them as a help.
+    pub fn keys(&self, action: &A) -> Vec<KeyCombo>
+    where
+        A: PartialEq,
+    {
+        self.bindings
+            .iter()
+            .filter(|(_, bound)| bound == action)
+            .map(|(key, _)| *key)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::crossterm::event::KeyEvent;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Action {
+        Accept,
+        DeleteWord,
+    }
+
+    fn bindings() -> KeyBindings<Action> {
+        KeyBindings::empty()
+            .bind(KeyCode::Enter, Action::Accept)
+            .bind(ctrl('w'), Action::DeleteWord)
+    }
+
+    mod action {
+        use super::*;
+
+        #[test]
+        fn test_bound() {
+            assert_eq!(
+                Some(&Action::DeleteWord),
+                bindings().action(&Event::Key(KeyEvent::new(
+                    KeyCode::Char('w'),
+                    KeyModifiers::CONTROL
+                )))
+            );
+            assert_eq!(
+                None,
+                bindings().action(&Event::Key(KeyEvent::new(
+                    KeyCode::Char('w'),
+                    KeyModifiers::NONE
+                )))
+            );
+        }
+
+        #[test]
+        fn test_rebind_and_unbind() {
+            let bindings = bindings()
+                .bind(KeyCode::Enter, Action::DeleteWord)
+                .unbind(ctrl('w'));
+            assert_eq!(
+                Some(&Action::DeleteWord),
+                bindings.action(&Event::Key(KeyEvent::new(
+                    KeyCode::Enter,
+                    KeyModifiers::NONE
+                )))
+            );
+            assert_eq!(
+                None,
+                bindings.action(&Event::Key(KeyEvent::new(
+                    KeyCode::Char('w'),
+                    KeyModifiers::CONTROL
+                )))
+            );
+            assert!(bindings.keys(&Action::Accept).is_empty());
+        }
+    }
+}
@@ -34,8 +34,14 @@
 //!   - Theme for designing the appearance of the prompt.
 //!     - e.g. cursor, text
 //!   - Validation for user input and error message construction.
+//! - Blocking API without an async runtime
+//!   - `Prompt::run` blocks until the prompt finishes, reading the events synchronously
+//!   - The background tasks of the `async` feature run on a runtime of their own,
+//!     so the prompts also block within an existing tokio runtime, without nesting it
 //! - Mouse support (partially)
 //!   - Allows scrolling through lists with the mouse wheel
+//!   - Moves the cursor of `Readline` to the clicked character, with `Prompt::mouse_capture`
+//!   - Selects the clicked item of `Listbox` and `QuerySelector`, also with `Prompt::mouse_capture`
 //!
 //! ## Examples/Demos
 //!
@@ -122,32 +128,62 @@
 //! This approach ensures consistency in UI elements even when
 //! the terminal size changes, providing a smoother user experience.
 
+pub use anyhow;
 pub use crossterm;
 pub use serde_json;
 
+pub mod chord;
+mod cleanup;
+#[cfg(feature = "clipboard")]
+pub mod clipboard;
+pub mod clock;
 mod core;
 pub use core::*;
+pub mod event_queue;
 pub mod grapheme;
+mod guard;
+pub use guard::{install_panic_hook, TerminalGuard};
 pub mod jsonz;
+pub mod key_bindings;
 pub mod pane;
+pub mod prelude;
 pub mod preset;
+pub mod read_only;
+mod resize;
+mod session;
+pub use session::{BetweenSteps, Session};
 pub mod style;
 pub mod suggest;
 pub mod switch;
+pub mod telemetry;
 pub mod terminal;
+#[cfg(feature = "test-util")]
+pub mod test_util;
+pub mod timeout;
 pub mod validate;
+pub mod words;
 
-use std::io;
+use std::{
+    io::{self, Write},
+    sync::mpsc,
+    time::{Duration, Instant},
+};
 
 use crate::{
+    chord::{ChordCondition, ChordHandler, Chords, KeyCombo, Step},
+    clock::Clock,
     crossterm::{
         cursor,
         event::{self, Event},
-        execute,
-        terminal::{disable_raw_mode, enable_raw_mode},
+        execute, queue,
+        style::{Attribute, Attributes},
     },
+    event_queue::{EventQueue, EventSource, OneByOne},
     pane::Pane,
-    terminal::Terminal,
+    resize::ResizePoller,
+    style::StyleBuilder,
+    terminal::{Terminal, TestTerminal},
+    timeout::Timeout,
 };
 
 /// Represents the signal to control the flow of a prompt.
@@ -162,6 +198,92 @@ pub enum PromptSignal {
     Quit,
 }
 
+/// The error returned when the user interrupts a prompt (e.g. with Ctrl+C).
+///
+/// Callers can distinguish the cancellation from other errors with
+/// `err.is::<promkit::Interrupted>()` or `err.downcast_ref::<promkit::Interrupted>()`.
+/// Its message is still `ctrl+c` for compatibility.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Interrupted;
+
+impl std::fmt::Display for Interrupted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ctrl+c")
+    }
+}
+
+impl std::error::Error for Interrupted {}
+
+/// The error returned when the user ends the input (e.g. with Ctrl+D on an empty readline),
+/// e.g. for a REPL to exit cleanly, unlike `Interrupted`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Eof;
+
+impl std::fmt::Display for Eof {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "end of input")
+    }
+}
+
+impl std::error::Error for Eof {}
+
+/// The error returned when a prompt times out without a default value.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct TimedOut;
+
+impl std::fmt::Display for TimedOut {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "timed out")
+    }
+}
+
+impl std::error::Error for TimedOut {}
+
+/// The error returned by `Prompt::finalize` before the prompt has run.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct NotRun;
+
+impl std::fmt::Display for NotRun {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "the prompt has not run yet")
+    }
+}
+
+impl std::error::Error for NotRun {}
+
+/// Where a prompt is in its lifecycle.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Stage {
+    /// Not run yet.
+    Ready,
+    /// Running, or a run did not finish (e.g. it panicked).
+    Running,
+    /// Run at least once.
+    Finished,
+}
+
+/// Determines when the timeout of a prompt expires.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum TimeoutMode {
+    /// Expires after no event arrives for the duration; every event restarts the countdown.
+    #[default]
+    Idle,
+    /// Expires after the duration from the start of the prompt, regardless of events.
+    Absolute,
+}
+
+/// Determines what `Prompt::run` does when the user interrupts the prompt.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum InterruptBehavior<R> {
+    /// Returns the `Interrupted` error.
+    #[default]
+    Error,
+    /// Returns the given value as the result, e.g. `false` for a confirmation.
+    ReturnDefault(R),
+    /// Ignores the interruption and keeps the prompt running.
+    Ignore,
+}
+
 pub trait Finalizer {
     /// The type of the result produced by the renderer.
     type Return;
@@ -216,6 +338,50 @@ pub trait Renderer: Finalizer {
     /// that the prompt should continue running, while `PromptSignal::Quit` indicates that
     /// the prompt should terminate its execution.
     fn evaluate(&mut self, event: &Event) -> anyhow::Result<PromptSignal>;
+
+    /// Returns how often `tick` is called while no event arrives,
+    /// or `None` (default) if the renderer changes only on events.
+    fn tick_interval(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Applies the changes made outside of events, e.g. by a background task.
+    /// Returns `true` if the panes need to be redrawn.
+    ///
+    /// The renderer is owned by the prompt loop, which evaluates, ticks and draws it in turn,
+    /// so a background task should hand its results over, e.g. through a channel received here
+    /// (see `QuerySelector::with_async_filter`). The updates then land between frames as a whole,
+    /// and never interleave with a draw.
+    fn tick(&mut self) -> anyhow::Result<bool> {
+        Ok(false)
+    }
+
+    /// Returns the signal raised outside of events, checked after every `tick`,
+    /// e.g. `PromptSignal::Quit` once a background check accepts the input,
+    /// or `None` (default) if there is none.
+    fn take_signal(&mut self) -> Option<PromptSignal> {
+        None
+    }
+
+    /// Returns the panes drawn once the prompt has quit, which stay in the scrollback,
+    /// or `None` (default) to draw `create_panes` as usual.
+    /// The rows of the previous frame beyond them are cleared,
+    /// e.g. to leave only a one-line summary of the prompt.
+    fn final_panes(&self, _width: u16, _height: u16) -> Option<Vec<Pane>> {
+        None
+    }
+
+    /// Returns the raw output to write to the terminal before the next draw,
+    /// e.g. an escape sequence setting the clipboard, or `None` (default) if there is none.
+    fn take_output(&mut self) -> Option<String> {
+        None
+    }
+
+    /// Restores the state to start the next run from, called before every run but the first,
+    /// so that a prompt can be run repeatedly (e.g. in a REPL) even after an error.
+    /// The presets clear the input (keeping e.g. the history), while the selections are kept.
+    /// The default does nothing.
+    fn reset(&mut self) {}
 }
 
 /// Represents a customizable prompt that can handle user input and produce a result.
@@ -224,22 +390,353 @@ pub trait Renderer: Finalizer {
 /// event handling, and result production for a prompt.
 pub struct Prompt<T: Renderer> {
     pub renderer: T,
+    /// If set, movement events that waited longer than this
+    /// (e.g. behind a slow render) are dropped instead of evaluated.
+    stale_event_threshold: Option<Duration>,
+    /// Limits how many of the queued events are evaluated between two renders.
+    event_batch_limit: event_queue::BatchLimit,
+    /// The destination the prompt is drawn to (stdout by default),
+    /// restoring the terminal set up by `run` on drop.
+    writer: TerminalGuard<Box<dyn Write + Send>>,
+    /// Whether the prompt is drawn on the alternate screen.
+    alternate_screen: bool,
+    /// Whether the prompt runs in a `Session`, which sets up and restores the terminal instead.
+    hosted: bool,
+    /// The row the last run started drawing from, for a `Session` to clear it.
+    origin: Option<u16>,
+    /// What to do when the renderer reports `Interrupted`.
+    on_interrupt: InterruptBehavior<Box<dyn Fn() -> T::Return + Send>>,
+    /// The duration after which the prompt gives up.
+    timeout: Option<Duration>,
+    timeout_mode: TimeoutMode,
+    /// The result to return on timeout, instead of the `TimedOut` error.
+    timeout_default: Option<Box<dyn Fn() -> T::Return + Send>>,
+    /// Whether the remaining time is shown above the panes.
+    show_countdown: bool,
+    /// The source of the current time for the timeout.
+    clock: Clock,
+    /// Whether mouse events other than scrolling (e.g. clicks) are captured.
+    mouse_capture: bool,
+    /// The maximum number of terminal rows the prompt may occupy.
+    max_rows: Option<u16>,
+    /// Multi-key sequences handled before the renderer sees the keys.
+    chords: Chords<T>,
+    /// How often the terminal size is polled, if resize events are not relied on.
+    poll_resize: Option<Duration>,
+    /// The spans of the runs, with the `tracing` feature.
+    telemetry: telemetry::Telemetry,
+    stage: Stage,
 }
 
 impl<T: Renderer> Drop for Prompt<T> {
     fn drop(&mut self) {
-        execute!(
-            io::stdout(),
-            cursor::Show,
-            event::DisableMouseCapture,
-            cursor::MoveToNextLine(1),
-        )
-        .ok();
-        disable_raw_mode().ok();
+        // Leaves the final panes above what is written next.
+        // The guard restores the rest, on both normal returns and panics unwinding through `run`.
+        if self.writer.is_raw_mode_enabled() && !self.writer.is_alternate_screen() {
+            execute!(self.writer, cursor::MoveToNextLine(1)).ok();
+        }
     }
 }
 
 impl<T: Renderer> Prompt<T> {
+    /// Creates a prompt running the renderer with the default settings.
+    ///
+    /// The settings are private fields changed by the builder methods,
+    /// so this replaces constructing the struct as `Prompt { renderer }`.
+    pub fn new(renderer: T) -> Self {
+        Self {
+            renderer,
+            stale_event_threshold: None,
+            event_batch_limit: Default::default(),
+            writer: TerminalGuard::new(Box::new(io::stdout())),
+            alternate_screen: false,
+            hosted: false,
+            origin: None,
+            on_interrupt: InterruptBehavior::Error,
+            timeout: None,
+            timeout_mode: Default::default(),
+            timeout_default: None,
+            show_countdown: false,
+            clock: Default::default(),
+            mouse_capture: false,
+            max_rows: None,
+            chords: Default::default(),
+            poll_resize: None,
+            telemetry: Default::default(),
+            stage: Stage::Ready,
+        }
+    }
+
+    /// Draws the prompt to the given writer instead of stdout,
+    /// e.g. `io::stderr()` to keep stdout free for the results.
+    pub fn writer<W: Write + Send + 'static>(mut self, writer: W) -> Self {
+        self.writer = TerminalGuard::new(Box::new(writer));
+        self
+    }
+
+    /// Draws the prompt on the alternate screen, leaving the scrollback untouched.
+    pub fn alternate_screen(mut self, enable: bool) -> Self {
+        self.alternate_screen = enable;
+        self
+    }
+
+    /// Drops movement events (arrow keys, page keys and mouse scroll)
+    /// that have been waiting longer than the given threshold,
+    /// e.g. while a slow pane was rendered, to prevent runaway scrolling.
+    pub fn stale_event_threshold(mut self, threshold: Duration) -> Self {
+        self.stale_event_threshold = Some(threshold);
+        self
+    }
+
+    /// Limits how many of the events queued behind a render are evaluated
+    /// before the next render, e.g. to show the progress of a large paste.
+    /// By default, all of them are evaluated, in the order they arrived either way.
+    pub fn event_batch_limit(mut self, limit: event_queue::BatchLimit) -> Self {
+        self.event_batch_limit = limit;
+        self
+    }
+
+    /// Sets what to do when the user interrupts the prompt (e.g. with Ctrl+C).
+    /// By default, `run` returns the `Interrupted` error.
+    pub fn on_interrupt(mut self, behavior: InterruptBehavior<T::Return>) -> Self
+    where
+        T::Return: Clone + Send + 'static,
+    {
+        self.on_interrupt = match behavior {
+            InterruptBehavior::Error => InterruptBehavior::Error,
+            InterruptBehavior::ReturnDefault(ret) => {
+                InterruptBehavior::ReturnDefault(Box::new(move || ret.clone()))
+            }
+            InterruptBehavior::Ignore => InterruptBehavior::Ignore,
+        };
+        self
+    }
+
+    /// Gives up the prompt after the duration, returning the `TimedOut` error
+    /// (or the value set by `timeout_default`) after restoring the terminal.
+    pub fn timeout(mut self, duration: Duration) -> Self {
+        self.timeout = Some(duration);
+        self
+    }
+
+    /// Sets whether the timeout counts from the last event (default) or from the start.
+    pub fn timeout_mode(mut self, mode: TimeoutMode) -> Self {
+        self.timeout_mode = mode;
+        self
+    }
+
+    /// Returns the given value on timeout instead of the `TimedOut` error.
+    pub fn timeout_default(mut self, ret: T::Return) -> Self
+    where
+        T::Return: Clone + Send + 'static,
+    {
+        self.timeout_default = Some(Box::new(move || ret.clone()));
+        self
+    }
+
+    /// Shows the remaining time until the timeout above the panes.
+    pub fn show_countdown(mut self, show: bool) -> Self {
+        self.show_countdown = show;
+        self
+    }
+
+    /// Sets the source of the current time for the timeout, e.g. a manual clock in tests.
+    pub fn clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Captures the mouse, so that the renderer receives clicks,
+    /// e.g. to move the cursor of `Readline` to the clicked character.
+    /// The rows of the mouse events count from the first row of the panes.
+    /// Note that the terminal cannot select text with the mouse while it is captured.
+    pub fn mouse_capture(mut self, enable: bool) -> Self {
+        self.mouse_capture = enable;
+        self
+    }
+
+    /// Limits the number of terminal rows the prompt may occupy,
+    /// e.g. to keep the output above the prompt visible.
+    /// The panes are laid out within min(terminal height, `rows`),
+    /// so their contents scroll instead of growing. If they still do not fit,
+    /// the panes of lower priorities (see `Pane::with_priority`) are cut or hidden first.
+    pub fn max_rows(mut self, rows: u16) -> Self {
+        self.max_rows = Some(rows);
+        self
+    }
+
+    /// Binds a multi-key sequence (chord), e.g. `g g` or `Ctrl+X Ctrl+E`, to the handler.
+    /// While the beginning of a chord is typed, the keys are held back and shown below the panes.
+    /// If a key not continuing the chord arrives, or none arrives within `chord_timeout`,
+    /// the held keys are evaluated by the renderer as usual.
+    pub fn chord<I: IntoIterator<Item = KeyCombo>>(
+        mut self,
+        sequence: I,
+        handler: ChordHandler<T>,
+    ) -> Self {
+        self.chords = std::mem::take(&mut self.chords).bind(sequence, handler);
+        self
+    }
+
+    /// Binds a chord like `chord`, recognized only while the condition holds,
+    /// e.g. `d d` only in the vi normal mode. Otherwise the keys are evaluated as usual.
+    pub fn chord_when<I: IntoIterator<Item = KeyCombo>>(
+        mut self,
+        sequence: I,
+        condition: ChordCondition<T>,
+        handler: ChordHandler<T>,
+    ) -> Self {
+        self.chords = std::mem::take(&mut self.chords).bind_when(sequence, condition, handler);
+        self
+    }
+
+    /// Sets the maximum wait between the keys of a chord (1 second by default).
+    pub fn chord_timeout(mut self, timeout: Duration) -> Self {
+        self.chords = std::mem::take(&mut self.chords).timeout(timeout);
+        self
+    }
+
+    /// Nests the `promkit.prompt` spans of the runs in the given span,
+    /// e.g. to attach attributes of your own. See `telemetry` for the emitted spans.
+    #[cfg(feature = "tracing")]
+    pub fn parent_span(mut self, span: tracing::Span) -> Self {
+        self.telemetry.parent = Some(span);
+        self
+    }
+
+    /// Polls the terminal size at the interval, and handles a change like a resize event
+    /// once the size has settled (i.e. is the same at two polls in a row).
+    /// This is for environments that do not deliver resize events reliably,
+    /// e.g. some Windows shells or serial consoles. Disabled by default.
+    pub fn poll_resize(mut self, interval: Duration) -> Self {
+        self.poll_resize = Some(interval);
+        self
+    }
+
+    /// Returns the terminal size, with the height limited by `max_rows`.
+    fn size(&self) -> anyhow::Result<(u16, u16)> {
+        let (width, height) = crossterm::terminal::size()?;
+        Ok((
+            width,
+            self.max_rows
+                .map_or(height, |max_rows| max_rows.min(height)),
+        ))
+    }
+
+    /// Returns the panes drawn once the prompt has quit: the final panes of the renderer
+    /// if it has any (e.g. a one-line summary), otherwise its usual panes.
+    fn final_panes(&self, width: u16, height: u16) -> Vec<Pane> {
+        self.renderer
+            .final_panes(width, height)
+            .unwrap_or_else(|| self.renderer.create_panes(width, height))
+    }
+
+    /// Creates the panes of the renderer, along with the countdown if enabled.
+    fn create_panes(&self, width: u16, height: u16, timeout: Option<&Timeout>) -> Vec<Pane> {
+        let mut panes = self.renderer.create_panes(width, height);
+        if let (true, Some(timeout)) = (self.show_countdown, timeout) {
+            let countdown = text::State {
+                text: format!(
+                    "Timeout in {}",
+                    timeout::format_remaining(timeout.remaining(self.clock.now()))
+                ),
+                style: Default::default(),
+                offset: 0,
+            };
+            panes.insert(0, countdown.create_pane(width, height));
+        }
+        let pending = self.chords.pending_text();
+        if !pending.is_empty() {
+            let prefix = text::State {
+                text: pending,
+                style: StyleBuilder::new()
+                    .attrs(Attributes::from(Attribute::Dim))
+                    .build(),
+                offset: 0,
+            };
+            panes.push(prefix.create_pane(width, height));
+        }
+        panes
+    }
+
+    /// Waits for the next event until `until`, redrawing the countdown
+    /// to the `timeout` every second. Returns `None` on expiry.
+    fn read_until(
+        &mut self,
+        until: Instant,
+        timeout: Option<&Timeout>,
+        terminal: &mut Terminal,
+    ) -> anyhow::Result<Option<Event>> {
+        loop {
+            let remaining = until.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Ok(None);
+            }
+            let wait = if self.show_countdown {
+                remaining.min(timeout::COUNTDOWN_INTERVAL)
+            } else {
+                remaining
+            };
+            if event::poll(wait)? {
+                return Ok(Some(event::read()?));
+            }
+            if self.show_countdown {
+                let size = self.size()?;
+                let panes = self.create_panes(size.0, size.1, timeout);
+                terminal.draw(&mut self.writer, &panes)?;
+            }
+        }
+    }
+
+    /// Evaluates the event, passing it through the chords first.
+    /// Returns `Some` to quit, holding the result if it replaces the one from `finalize`.
+    fn evaluate(&mut self, event: &Event) -> anyhow::Result<Option<Option<T::Return>>> {
+        self.telemetry.count(event);
+        let steps = self
+            .chords
+            .feed(event.clone(), Instant::now(), &self.renderer);
+        self.run_steps(steps)
+    }
+
+    fn run_steps(&mut self, steps: Vec<Step<T>>) -> anyhow::Result<Option<Option<T::Return>>> {
+        for step in steps {
+            let signal = match step {
+                Step::Event(event) => self.renderer.evaluate(&event),
+                Step::Handler(handler) => handler(&mut self.renderer),
+            };
+            if let Some(ret) = self.handle_signal(signal)? {
+                return Ok(Some(ret));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Writes the output the renderer asks for, if any.
+    fn write_output(&mut self) -> anyhow::Result<()> {
+        if let Some(output) = self.renderer.take_output() {
+            self.writer.write_all(output.as_bytes())?;
+            self.writer.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Applies `on_interrupt` if the renderer reports `Interrupted`.
+    fn handle_signal(
+        &self,
+        signal: anyhow::Result<PromptSignal>,
+    ) -> anyhow::Result<Option<Option<T::Return>>> {
+        match signal {
+            Ok(PromptSignal::Continue) => Ok(None),
+            Ok(PromptSignal::Quit) => Ok(Some(None)),
+            Err(e) if e.is::<Interrupted>() => match &self.on_interrupt {
+                InterruptBehavior::Error => Err(e),
+                InterruptBehavior::ReturnDefault(ret) => Ok(Some(Some(ret()))),
+                InterruptBehavior::Ignore => Ok(None),
+            },
+            Err(e) => Err(e),
+        }
+    }
+
     /// Runs the prompt, handling events and producing a result.
     ///
     /// This method initializes the terminal, and enters a loop
@@ -250,39 +747,419 @@ impl<T: Renderer> Prompt<T> {
     ///
     /// Returns a `Result` containing the produced result or an error.
     pub fn run(&mut self) -> anyhow::Result<T::Return> {
-        enable_raw_mode()?;
-        execute!(io::stdout(), cursor::Hide)?;
+        self.start();
+        let scope = self.telemetry.start(std::any::type_name::<T>());
+        let result = self.run_session();
+        self.telemetry.finish(scope, &result);
+        self.stage = Stage::Finished;
+        result
+    }
 
-        let size = crossterm::terminal::size()?;
-        let panes = self.renderer.create_panes(size.0, size.1);
-        let mut terminal = Terminal::start_session(&panes)?;
-        terminal.draw(&panes)?;
+    /// Runs the prompt like `run`, but gives up after no event arrives for the duration,
+    /// returning the `TimedOut` error (or the value set by `timeout_default`)
+    /// after restoring the terminal. Every event restarts the countdown.
+    /// It overrides `timeout` and `timeout_mode` for this run only.
+    pub fn run_with_timeout(&mut self, duration: Duration) -> anyhow::Result<T::Return> {
+        self.with_timeout(duration, Self::run)
+    }
+
+    /// Runs the prompt with `run` under an idle timeout of the duration,
+    /// restoring `timeout` and `timeout_mode` afterwards, whatever the result.
+    fn with_timeout<F>(&mut self, duration: Duration, run: F) -> anyhow::Result<T::Return>
+    where
+        F: FnOnce(&mut Self) -> anyhow::Result<T::Return>,
+    {
+        let timeout = self.timeout.replace(duration);
+        let mode = std::mem::replace(&mut self.timeout_mode, TimeoutMode::Idle);
+        let result = run(self);
+        self.timeout = timeout;
+        self.timeout_mode = mode;
+        result
+    }
+
+    /// Prepares the renderer for a run, resetting it if it has run before
+    /// (including a run that did not finish, e.g. because it panicked).
+    fn start(&mut self) {
+        if self.stage != Stage::Ready {
+            self.renderer.reset();
+        }
+        self.stage = Stage::Running;
+    }
+
+    /// Produces the result from the current state of the renderer,
+    /// e.g. to keep the input of a run that was interrupted or timed out.
+    /// Note that a run that succeeded has already finalized the renderer,
+    /// which may have reset its state (e.g. `Readline` clears the input).
+    ///
+    /// Returns the `NotRun` error if the prompt has not run yet.
+    pub fn finalize(&mut self) -> anyhow::Result<T::Return> {
+        match self.stage {
+            Stage::Ready => Err(NotRun.into()),
+            _ => self.renderer.finalize(),
+        }
+    }
+
+    /// Starts the countdown of the timeout, if set, as the prompt is first drawn.
+    fn start_timeout(&self) -> Option<Timeout> {
+        let timeout = Timeout::new(self.timeout?, self.timeout_mode);
+        timeout.deadline(self.clock.now());
+        Some(timeout)
+    }
+
+    /// Returns the value set by `timeout_default`, or the `TimedOut` error.
+    fn timed_out(&self) -> anyhow::Result<T::Return> {
+        match &self.timeout_default {
+            Some(ret) => Ok(ret()),
+            None => Err(TimedOut.into()),
+        }
+    }
+
+    fn run_session(&mut self) -> anyhow::Result<T::Return> {
+        // In a `Session`, the raw mode has been entered by the session.
+        if !self.hosted {
+            self.writer.enable_raw_mode()?;
+        }
+        self.writer.hide_cursor()?;
+        if self.mouse_capture {
+            self.writer.enable_mouse_capture()?;
+        }
+
+        let timeout = self.start_timeout();
+        let timeout = timeout.as_ref();
+        let clock = self.clock.clone();
+        let deadline = || timeout.map(|timeout| timeout.deadline(clock.now()));
+
+        let size = self.size()?;
+        let panes = self.create_panes(size.0, size.1, timeout);
+        let mut terminal = if self.alternate_screen {
+            self.writer.enter_alternate_screen()?;
+            Terminal::start_alternate_session(&mut self.writer)?
+        } else {
+            Terminal::start_session(&mut self.writer, &panes)?
+        };
+        terminal.max_rows = self.max_rows;
+        self.origin = Some(terminal.position.1);
+        terminal.draw(&mut self.writer, &panes)?;
+
+        let mut queue = EventQueue::new(self.stale_event_threshold, self.event_batch_limit);
+        let mut drained_at = Instant::now();
+        let mut resize_poller = match self.poll_resize {
+            Some(interval) => Some(ResizePoller::new(
+                interval,
+                crossterm::terminal::size()?,
+                Instant::now(),
+            )),
+            None => None,
+        };
 
         loop {
-            let ev = event::read()?;
-
-            match &ev {
-                Event::Resize(_, _) => {
-                    terminal.position = (0, 0);
-                    crossterm::execute!(
-                        io::stdout(),
-                        crossterm::terminal::Clear(crossterm::terminal::ClearType::Purge),
-                    )?;
+            // Events that arrived while evaluating and rendering are queued in order,
+            // and evaluated against the state after the render.
+            while event::poll(Duration::ZERO)? {
+                queue.push(event::read()?, drained_at);
+            }
+            if queue.is_empty() {
+                let chord_deadline = self.chords.deadline();
+                let tick_at = self
+                    .renderer
+                    .tick_interval()
+                    .map(|interval| Instant::now() + interval);
+                let poll_at = resize_poller.as_ref().map(ResizePoller::next_at);
+                let until = [deadline(), chord_deadline, tick_at, poll_at]
+                    .into_iter()
+                    .flatten()
+                    .min();
+                let ev = match until {
+                    Some(until) => self.read_until(until, timeout, &mut terminal)?,
+                    None => Some(event::read()?),
+                };
+                let ev = match ev {
+                    Some(ev) => ev,
+                    None => {
+                        let now = Instant::now();
+                        if timeout.is_some_and(|timeout| timeout.is_expired(clock.now())) {
+                            return self.timed_out();
+                        }
+                        let mut redraw =
+                            tick_at.is_some_and(|at| at <= now) && self.renderer.tick()?;
+                        let mut ret = match self.renderer.take_signal() {
+                            Some(signal) => self.handle_signal(Ok(signal))?,
+                            None => None,
+                        };
+                        // Evaluate the held keys if the pending chord prefix expired.
+                        let steps = self.chords.expire(now);
+                        redraw |= !steps.is_empty();
+                        if ret.is_none() {
+                            ret = self.run_steps(steps)?;
+                        }
+                        self.write_output()?;
+                        if let Some(ret) = ret {
+                            let size = self.size()?;
+                            let panes = self.final_panes(size.0, size.1);
+                            terminal.draw(&mut self.writer, &panes)?;
+                            return ret.map_or_else(|| self.renderer.finalize(), Ok);
+                        }
+                        if redraw {
+                            let size = self.size()?;
+                            let panes = self.create_panes(size.0, size.1, timeout);
+                            terminal.draw(&mut self.writer, &panes)?;
+                        }
+                        // A detected resize is handled exactly like a resize event.
+                        let resized = match &mut resize_poller {
+                            Some(poller) if poller.next_at() <= now => {
+                                poller.poll(crossterm::terminal::size()?, now)
+                            }
+                            _ => None,
+                        };
+                        match resized {
+                            Some(ev) => ev,
+                            None => continue,
+                        }
+                    }
+                };
+                queue.push(ev, Instant::now());
+            }
+            drained_at = Instant::now();
+            if let Some(timeout) = timeout {
+                timeout.activity(clock.now());
+            }
+
+            for ev in queue.pop_batch() {
+                match &ev {
+                    Event::Resize(width, height) => {
+                        if let Some(poller) = &mut resize_poller {
+                            poller.observe((*width, *height));
+                        }
+                        terminal.position = (0, 0);
+                        self.origin = Some(0);
+                        terminal.invalidate();
+                        crossterm::execute!(
+                            self.writer,
+                            crossterm::terminal::Clear(crossterm::terminal::ClearType::Purge),
+                        )?;
+                    }
+                    _ => {
+                        // The rows of mouse events are made relative to the renderer's panes,
+                        // and the events above them are dropped.
+                        let origin = terminal.position.1
+                            + u16::from(self.show_countdown && timeout.is_some());
+                        let ev = match ev {
+                            Event::Mouse(mut mouse) => match mouse.row.checked_sub(origin) {
+                                Some(row) => {
+                                    mouse.row = row;
+                                    Event::Mouse(mouse)
+                                }
+                                None => continue,
+                            },
+                            ev => ev,
+                        };
+                        let ret = self.evaluate(&ev)?;
+                        self.write_output()?;
+                        if let Some(ret) = ret {
+                            // Renderer has a possibility to disable the cursor color to indicate termination,
+                            // and so ensure to display the state of Renderer at the end.
+                            let size = self.size()?;
+                            let panes = self.final_panes(size.0, size.1);
+                            terminal.draw(&mut self.writer, &panes)?;
+                            return ret.map_or_else(|| self.renderer.finalize(), Ok);
+                        }
+                    }
+                }
+            }
+
+            let size = self.size()?;
+            let panes = self.create_panes(size.0, size.1, timeout);
+            terminal.draw(&mut self.writer, &panes)?;
+        }
+    }
+
+    /// Ends a run in a `Session`, undoing what the prompt set up for itself
+    /// and leaving the cursor where the next prompt starts:
+    /// below the final panes, or at their top after clearing them.
+    pub(crate) fn end_hosted_run(&mut self, clear: bool) -> anyhow::Result<()> {
+        if self.mouse_capture {
+            self.writer.disable_mouse_capture()?;
+        }
+        match self.origin.take() {
+            _ if self.alternate_screen => self.writer.leave_alternate_screen()?,
+            Some(row) if clear => {
+                queue!(
+                    self.writer,
+                    cursor::MoveTo(0, row),
+                    crossterm::terminal::Clear(crossterm::terminal::ClearType::FromCursorDown),
+                )?;
+            }
+            _ => queue!(self.writer, cursor::MoveToNextLine(1))?,
+        }
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// Runs the prompt headlessly against the given events instead of the terminal,
+    /// recording every rendered frame into `terminal`.
+    ///
+    /// Resize events change the size of `terminal`.
+    /// The timeout expires if the clock (see `Prompt::clock`) has passed the deadline
+    /// when the next event is taken, so a manual clock can be advanced between the events.
+    /// Returns an error if the events run out before the prompt quits.
+    pub fn run_with_events<I: IntoIterator<Item = Event>>(
+        &mut self,
+        events: I,
+        terminal: &mut TestTerminal,
+    ) -> anyhow::Result<T::Return> {
+        self.run_source(OneByOne(events.into_iter()), terminal)
+    }
+
+    /// Same as `run_with_events`, but the events are received from the channel as they arrive,
+    /// and those that arrived while rendering are evaluated before the next render like in `run`,
+    /// subject to `stale_event_threshold` and `event_batch_limit`.
+    /// Returns an error if the channel is disconnected before the prompt quits.
+    pub fn run_with_receiver(
+        &mut self,
+        events: mpsc::Receiver<Event>,
+        terminal: &mut TestTerminal,
+    ) -> anyhow::Result<T::Return> {
+        self.run_source(events, terminal)
+    }
+
+    fn run_source<S: EventSource>(
+        &mut self,
+        events: S,
+        terminal: &mut TestTerminal,
+    ) -> anyhow::Result<T::Return> {
+        self.start();
+        let scope = self.telemetry.start(std::any::type_name::<T>());
+        let result = self.run_events(events, terminal);
+        self.telemetry.finish(scope, &result);
+        self.stage = Stage::Finished;
+        result
+    }
+
+    fn run_events<S: EventSource>(
+        &mut self,
+        mut events: S,
+        terminal: &mut TestTerminal,
+    ) -> anyhow::Result<T::Return> {
+        if self.max_rows.is_some() {
+            terminal.max_rows = self.max_rows;
+        }
+        let timeout = self.start_timeout();
+        let timeout = timeout.as_ref();
+        let mut queue = EventQueue::new(self.stale_event_threshold, self.event_batch_limit);
+        let mut drained_at = Instant::now();
+        terminal.draw(&self.create_panes(terminal.size.0, terminal.height(), timeout));
+
+        loop {
+            // As in `run`, the events that arrived while evaluating and rendering are queued.
+            while let Some(ev) = events.try_next() {
+                queue.push(ev, drained_at);
+            }
+            if queue.is_empty() {
+                match events.next() {
+                    Some(ev) => queue.push(ev, Instant::now()),
+                    None => break,
+                }
+            }
+            drained_at = Instant::now();
+
+            for ev in queue.pop_batch() {
+                if let Some(timeout) = timeout {
+                    if timeout.is_expired(self.clock.now()) {
+                        return self.timed_out();
+                    }
+                    timeout.activity(self.clock.now());
                 }
-                _ => {
-                    if self.renderer.evaluate(&ev)? == PromptSignal::Quit {
-                        // Renderer has a possibility to disable the cursor color to indicate termination,
-                        // and so ensure to display the state of Renderer at the end.
-                        terminal.draw(&self.renderer.create_panes(size.0, size.1))?;
-                        break;
+                match &ev {
+                    Event::Resize(width, height) => {
+                        terminal.size = (*width, *height);
+                    }
+                    _ => {
+                        if let Some(ret) = self.evaluate(&ev)? {
+                            terminal.draw(&self.final_panes(terminal.size.0, terminal.height()));
+                            return ret.map_or_else(|| self.renderer.finalize(), Ok);
+                        }
                     }
                 }
             }
+            terminal.draw(&self.create_panes(terminal.size.0, terminal.height(), timeout));
+            // A tick due right away (e.g. loading the children of an expanded tree node)
+            // runs before the next event, as in `run`.
+            let due = self.renderer.tick_interval().is_some_and(|at| at.is_zero());
+            if due && self.renderer.tick()? {
+                terminal.draw(&self.create_panes(terminal.size.0, terminal.height(), timeout));
+            }
+        }
+
+        Err(anyhow::anyhow!("events ran out before the prompt quit"))
+    }
+}
 
-            let size = crossterm::terminal::size()?;
-            terminal.draw(&self.renderer.create_panes(size.0, size.1))?;
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod run_with_timeout {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        use super::*;
+        use crate::preset::readline::Readline;
+
+        fn key(code: KeyCode) -> Event {
+            Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
         }
 
-        self.renderer.finalize()
+        /// Runs the prompt like `run_with_timeout`, against the events
+        /// each arriving after the seconds paired with it.
+        fn run<T: Renderer>(
+            p: &mut Prompt<T>,
+            clock: &Clock,
+            events: Vec<(u64, Event)>,
+        ) -> anyhow::Result<T::Return> {
+            let clock = clock.clone();
+            let events = events.into_iter().map(move |(secs, ev)| {
+                clock.advance(Duration::from_secs(secs));
+                ev
+            });
+            p.with_timeout(Duration::from_secs(10), |p| {
+                p.run_with_events(events, &mut TestTerminal::new(20, 10))
+            })
+        }
+
+        #[test]
+        fn test() {
+            let clock = Clock::manual();
+            let mut p = Readline::default()
+                .prompt()
+                .unwrap()
+                .timeout_mode(TimeoutMode::Absolute)
+                .clock(clock.clone());
+
+            // The timeout restarts on every event, overriding the absolute mode.
+            let events = vec![(8, key(KeyCode::Char('a'))), (8, key(KeyCode::Enter))];
+            assert_eq!("a", run(&mut p, &clock, events).unwrap());
+            assert_eq!(None, p.timeout);
+            assert_eq!(TimeoutMode::Absolute, p.timeout_mode);
+
+            let events = vec![(10, key(KeyCode::Enter))];
+            assert!(run(&mut p, &clock, events).unwrap_err().is::<TimedOut>());
+            assert_eq!(None, p.timeout);
+            assert_eq!(TimeoutMode::Absolute, p.timeout_mode);
+        }
+
+        #[test]
+        fn test_with_timeout_default() {
+            let clock = Clock::manual();
+            let mut p = Readline::default()
+                .prompt()
+                .unwrap()
+                .timeout(Duration::from_secs(60))
+                .timeout_default(String::from("anonymous"))
+                .clock(clock.clone());
+
+            let events = vec![(10, key(KeyCode::Enter))];
+            assert_eq!("anonymous", run(&mut p, &clock, events).unwrap());
+            assert_eq!(Some(Duration::from_secs(60)), p.timeout);
+            assert_eq!(TimeoutMode::Idle, p.timeout_mode);
+        }
     }
 }
@@ -1,4 +1,10 @@
-use crate::grapheme::StyledGraphemes;
+use std::ops::Range;
+
+use crate::{
+    crossterm::style::ContentStyle,
+    grapheme::{StyledGrapheme, StyledGraphemes},
+    PaneFactory,
+};
 
 #[derive(Clone)]
 pub struct Pane {
@@ -8,6 +14,8 @@ pub struct Pane {
     /// The offset from the top of the pane, used when extracting graphemes to display.
     /// This value determines the starting point for grapheme extraction, allowing for scrolling behavior.
     offset: usize,
+    /// Panes of lower priorities are shrunk and hidden first when the rows run short.
+    priority: u8,
 }
 
 impl Pane {
@@ -15,7 +23,24 @@ impl Pane {
     /// - `layout`: A vector of `StyledGraphemes` representing the content of the pane.
     /// - `offset`: The initial offset from the top of the pane.
     pub fn new(layout: Vec<StyledGraphemes>, offset: usize) -> Self {
-        Pane { layout, offset }
+        Pane {
+            layout,
+            offset,
+            priority: 0,
+        }
+    }
+
+    /// Sets the priority of the pane (0 by default). When the panes do not fit into the rows,
+    /// the panes of higher priorities get their rows first, e.g. to keep the input line
+    /// while a long title is cut and the suggestions are hidden.
+    pub fn with_priority(mut self, priority: u8) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Returns the priority of the pane.
+    pub fn priority(&self) -> u8 {
+        self.priority
     }
 
     pub fn visible_row_count(&self) -> usize {
@@ -27,20 +52,195 @@ impl Pane {
         self.layout.is_empty()
     }
 
+    /// Returns all rows of the pane, including the ones scrolled out of the viewport.
+    pub fn rows(&self) -> &[StyledGraphemes] {
+        &self.layout
+    }
+
+    /// Returns the number of rows of the pane.
+    pub fn height(&self) -> usize {
+        self.layout.len()
+    }
+
+    /// Returns the display width of the widest row.
+    pub fn width_used(&self) -> usize {
+        self.layout
+            .iter()
+            .map(|row| row.widths())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the rows as plain text, one string per row.
+    ///
+    /// This is the canonical text of a pane, e.g. for golden tests:
+    /// each string is the graphemes of the row as they are, without styles,
+    /// padding to the pane width or a trailing newline.
+    pub fn to_plain_strings(&self) -> Vec<String> {
+        self.layout.iter().map(|row| row.to_string()).collect()
+    }
+
+    /// Returns the rows with their styles as ANSI escape sequences, one string per row,
+    /// exactly as they are printed to the terminal (see `StyledGraphemes::styled_display`).
+    ///
+    /// Each grapheme is written with its own style, set before it and reset after it,
+    /// so the strings stay unchanged as long as the graphemes and their styles do.
+    pub fn to_ansi_strings(&self) -> Vec<String> {
+        self.layout
+            .iter()
+            .map(|row| row.styled_display().to_string())
+            .collect()
+    }
+
     pub fn extract(&self, viewport_height: usize) -> Vec<StyledGraphemes> {
+        self.layout[self.visible_range(viewport_height)].to_vec()
+    }
+
+    /// Returns the range of the rows shown by `extract` for the viewport height.
+    pub fn visible_range(&self, viewport_height: usize) -> Range<usize> {
         let lines = self.layout.len().min(viewport_height);
         let mut start = self.offset;
         let end = self.offset + lines;
         if end > self.layout.len() {
             start = self.layout.len().saturating_sub(lines);
         }
+        start..end.min(self.layout.len())
+    }
+}
 
-        self.layout
-            .iter()
-            .enumerate()
-            .filter(|(i, _)| start <= *i && *i < end)
-            .map(|(_, row)| row.clone())
-            .collect::<Vec<_>>()
+/// The set of box-drawing characters for the border around a pane.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum BorderStyle {
+    /// No border is drawn.
+    #[default]
+    None,
+    /// `┌─┐`
+    Single,
+    /// `╭─╮`
+    Rounded,
+    /// `╔═╗`
+    Double,
+}
+
+impl BorderStyle {
+    /// Returns the corners (top-left, top-right, bottom-left, bottom-right),
+    /// the horizontal and the vertical line characters.
+    fn chars(&self) -> Option<[char; 6]> {
+        match self {
+            BorderStyle::None => None,
+            BorderStyle::Single => Some(['┌', '┐', '└', '┘', '─', '│']),
+            BorderStyle::Rounded => Some(['╭', '╮', '╰', '╯', '─', '│']),
+            BorderStyle::Double => Some(['╔', '╗', '╚', '╝', '═', '║']),
+        }
+    }
+}
+
+/// The border drawn around a pane, with an optional title in the top border.
+#[derive(Clone, Debug, Default)]
+pub struct Border {
+    pub border_style: BorderStyle,
+    /// The title rendered in the top border, truncated if it does not fit.
+    pub title: Option<String>,
+    /// The style applied to the border and the title.
+    pub style: ContentStyle,
+}
+
+impl Pane {
+    /// Surrounds the visible rows of the pane with the border, `width` columns wide
+    /// and at most `height` rows high. The pane is expected to have been created
+    /// for `width - 2` columns; shorter rows are padded.
+    ///
+    /// The pane is returned as it is if there is no room for the border,
+    /// i.e. if `width` or `height` is less than 3.
+    pub fn with_border(self, border: &Border, width: u16, height: u16) -> Self {
+        let (width, height) = (width as usize, height as usize);
+        let Some([top_left, top_right, bottom_left, bottom_right, horizontal, vertical]) =
+            border.border_style.chars()
+        else {
+            return self;
+        };
+        if width < 3 || height < 3 {
+            return self;
+        }
+        let inner_width = width - 2;
+        let line = |ch: char| StyledGrapheme::new(ch, border.style);
+
+        let mut top = StyledGraphemes::default();
+        top.push_back(line(top_left));
+        if let Some(title) = &border.title {
+            let mut title = StyledGraphemes::from_str(format!(" {} ", title), border.style)
+                .truncate_with_ellipsis(inner_width);
+            top.append(&mut title);
+        }
+        while top.widths() < width - 1 {
+            top.push_back(line(horizontal));
+        }
+        top.push_back(line(top_right));
+
+        let mut layout = vec![top];
+        for row in self.extract(height - 2) {
+            let mut row = row.truncate_with_ellipsis(inner_width);
+            let mut bordered = StyledGraphemes::default();
+            bordered.push_back(line(vertical));
+            bordered.append(&mut row);
+            while bordered.widths() < width - 1 {
+                bordered.push_back(StyledGrapheme::from(' '));
+            }
+            bordered.push_back(line(vertical));
+            layout.push(bordered);
+        }
+
+        let mut bottom = StyledGraphemes::default();
+        bottom.push_back(line(bottom_left));
+        (0..inner_width).for_each(|_| bottom.push_back(line(horizontal)));
+        bottom.push_back(line(bottom_right));
+        layout.push(bottom);
+
+        Pane::new(layout, 0).with_priority(self.priority)
+    }
+}
+
+/// Wraps a `PaneFactory` to draw its pane with a border.
+/// The inner pane is created for the width and the height reduced by the border,
+/// so it adapts on resize. If there is no room for the border, it is omitted.
+#[derive(Clone)]
+pub struct Bordered<F: PaneFactory> {
+    pub inner: F,
+    pub border: Border,
+}
+
+impl<F: PaneFactory> Bordered<F> {
+    pub fn new(inner: F, border_style: BorderStyle) -> Self {
+        Self {
+            inner,
+            border: Border {
+                border_style,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Sets the title rendered in the top border.
+    pub fn title<T: AsRef<str>>(mut self, title: T) -> Self {
+        self.border.title = Some(title.as_ref().to_string());
+        self
+    }
+
+    /// Sets the style for the border and the title.
+    pub fn style(mut self, style: ContentStyle) -> Self {
+        self.border.style = style;
+        self
+    }
+}
+
+impl<F: PaneFactory> PaneFactory for Bordered<F> {
+    fn create_pane(&self, width: u16, height: u16) -> Pane {
+        if self.border.border_style == BorderStyle::None || width < 3 || height < 3 {
+            return self.inner.create_pane(width, height);
+        }
+        self.inner
+            .create_pane(width - 2, height - 2)
+            .with_border(&self.border, width, height)
     }
 }
 
@@ -54,26 +254,49 @@ mod test {
             let state = text::State {
                 text: "".to_string(),
                 style: ContentStyle::default(),
+                offset: 0,
             };
             assert_eq!(0, state.create_pane(10, 10).visible_row_count())
         }
     }
 
-    mod is_empty {
+    mod to_strings {
         use super::super::*;
 
+        use crate::{crossterm::style::Color, style::StyleBuilder};
+
         #[test]
         fn test() {
+            let pane = Pane::new(
+                vec![
+                    StyledGraphemes::from_str("ab", StyleBuilder::new().fgc(Color::Red).build()),
+                    StyledGraphemes::from("日本"),
+                ],
+                0,
+            );
+            assert_eq!(2, pane.height());
+            assert_eq!(4, pane.width_used());
+            assert_eq!(vec!["ab", "日本"], pane.to_plain_strings());
             assert_eq!(
-                true,
-                Pane {
-                    layout: StyledGraphemes::from("").matrixify(10, 10, 0).0,
-                    offset: 0,
-                }
-                .is_empty()
+                vec!["\u{1b}[38;5;9ma\u{1b}[39m\u{1b}[38;5;9mb\u{1b}[39m", "日本"],
+                pane.to_ansi_strings()
             );
         }
     }
+
+    mod is_empty {
+        use super::super::*;
+
+        #[test]
+        fn test() {
+            assert!(Pane {
+                layout: StyledGraphemes::from("").matrixify(10, 10, 0).0,
+                offset: 0,
+                priority: 0,
+            }
+            .is_empty());
+        }
+    }
     mod extract {
         use super::super::*;
 
@@ -95,6 +318,7 @@ mod test {
                         StyledGraphemes::from("ee"),
                     ],
                     offset: 0,
+                    priority: 0,
                 }
                 .extract(3)
             );
@@ -120,6 +344,7 @@ mod test {
                         StyledGraphemes::from("ee"),
                     ],
                     offset: 0,
+                    priority: 0,
                 }
                 .extract(10)
             );
@@ -139,6 +364,7 @@ mod test {
                         StyledGraphemes::from("ee"),
                     ],
                     offset: 2, // indicate `cc`
+                    priority: 0,
                 }
                 .extract(2)
             );
@@ -162,9 +388,69 @@ mod test {
                         StyledGraphemes::from("ee"),
                     ],
                     offset: 3, // indicate `dd`
+                    priority: 0,
                 }
                 .extract(3)
             );
         }
     }
+
+    mod with_border {
+        use super::super::*;
+        use crate::text;
+
+        fn rows(pane: &Pane) -> Vec<String> {
+            pane.to_plain_strings()
+        }
+
+        fn text(s: &str) -> text::State {
+            text::State {
+                text: s.to_string(),
+                style: ContentStyle::default(),
+                offset: 0,
+            }
+        }
+
+        #[test]
+        fn test() {
+            let pane = Bordered::new(text("abcdef"), BorderStyle::Rounded)
+                .title("T")
+                .create_pane(6, 5);
+            assert_eq!(vec!["╭ T ─╮", "│abcd│", "│ef  │", "╰────╯"], rows(&pane));
+        }
+
+        #[test]
+        fn test_adapts_to_width() {
+            let bordered = Bordered::new(text("abcdef"), BorderStyle::Double);
+            assert_eq!(
+                vec!["╔══════╗", "║abcdef║", "╚══════╝"],
+                rows(&bordered.create_pane(8, 5))
+            );
+            assert_eq!(
+                vec!["╔═══╗", "║abc║", "║def║", "╚═══╝"],
+                rows(&bordered.create_pane(5, 5))
+            );
+        }
+
+        #[test]
+        fn test_long_title() {
+            let pane = Bordered::new(text("a"), BorderStyle::Single)
+                .title("long title")
+                .create_pane(6, 3);
+            assert_eq!(vec!["┌ lo…┐", "│a   │", "└────┘"], rows(&pane));
+        }
+
+        #[test]
+        fn test_without_room() {
+            let bordered = Bordered::new(text("abcdef"), BorderStyle::Single);
+            assert_eq!(vec!["abcdef"], rows(&bordered.create_pane(10, 1)));
+            assert_eq!(vec!["ab", "cd", "ef"], rows(&bordered.create_pane(2, 5)));
+        }
+
+        #[test]
+        fn test_none() {
+            let bordered = Bordered::new(text("abcdef"), BorderStyle::None);
+            assert_eq!(vec!["abcdef"], rows(&bordered.create_pane(10, 5)));
+        }
+    }
 }
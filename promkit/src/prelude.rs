@@ -0,0 +1,38 @@
+//! The presets and the types commonly used with them,
+//! so that a prompt can be built with a single import.
+//!
+//! ```no_run
+//! use promkit::prelude::*;
+//!
+//! let mut p = Readline::default()
+//!     .title("Fruit?")
+//!     .suggest(Suggest::from_iter(["apple", "banana"]))
+//!     .prompt()?;
+//! let fruit = p.run()?;
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+pub use crate::{
+    crossterm::style::{Attribute, Color, ContentStyle},
+    jsonstream::JsonStream,
+    preset::{
+        checkbox::Checkbox,
+        confirm::Confirm,
+        form::Form,
+        json::Json,
+        listbox::Listbox,
+        number::Number,
+        pager::Pager,
+        password::Password,
+        query_selector::QuerySelector,
+        readline::{EditorMode, InterruptAction, Readline},
+        tree::Tree,
+        wizard::Wizard,
+    },
+    style::StyleBuilder,
+    suggest::Suggest,
+    text_editor::{MaskPolicy, Mode},
+    tree::Node,
+    BetweenSteps, Eof, Finalizer, InterruptBehavior, Interrupted, Prompt, PromptSignal, Renderer,
+    Session,
+};
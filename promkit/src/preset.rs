@@ -11,6 +11,12 @@ pub mod json;
 /// Implements a list box for single or multiple selections from a list.
 pub mod listbox;
 
+/// Reads a number, stepped with the arrow keys and clamped to a range.
+pub mod number;
+
+/// Shows a long text, e.g. help output, scrolled with the arrow and page keys.
+pub mod pager;
+
 /// Facilitates querying and selecting from a set of options in a structured format.
 pub mod query_selector;
 
@@ -18,3 +24,6 @@ pub mod query_selector;
 pub mod tree;
 
 pub mod form;
+
+/// Runs several presets in order as a single prompt with a persistent header.
+pub mod wizard;
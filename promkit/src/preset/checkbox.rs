@@ -3,9 +3,10 @@ use std::{cell::RefCell, fmt::Display};
 use crate::{
     checkbox,
     crossterm::style::{Attribute, Attributes, Color, ContentStyle},
+    key_bindings::KeyBindings,
     style::StyleBuilder,
     switch::ActiveKeySwitcher,
-    text, Prompt,
+    text, Overflow, PartialItem, Prompt,
 };
 
 pub mod keymap;
@@ -13,15 +14,24 @@ pub mod render;
 
 /// Represents a checkbox component for creating
 /// and managing a list of selectable options.
-pub struct Checkbox {
-    keymap: ActiveKeySwitcher<keymap::Keymap>,
+///
+/// The type parameter `V` is the value returned for each picked item,
+/// which defaults to the displayed text.
+pub struct Checkbox<V = String> {
+    keymap: ActiveKeySwitcher<keymap::Keymap<V>>,
+    /// Keys bound to the actions of the default keymap.
+    key_bindings: KeyBindings<keymap::Action>,
     /// State for the title displayed above the checkbox list.
     title_state: text::State,
     /// State for the checkbox list itself.
     checkbox_state: checkbox::State,
+    /// Values returned for the items, in the same order as the items.
+    values: Vec<V>,
+    /// Whether the values are returned in the order the items were picked.
+    preserve_toggle_order: bool,
 }
 
-impl Checkbox {
+impl Checkbox<String> {
     /// Constructs a new `Checkbox` instance with a list of items
     /// to be displayed as selectable options.
     ///
@@ -30,44 +40,59 @@ impl Checkbox {
     /// * `items` - An iterator over items
     ///   that implement the `Display` trait, to be used as options.
     pub fn new<T: Display, I: IntoIterator<Item = T>>(items: I) -> Self {
-        Self {
-            title_state: text::State {
-                text: Default::default(),
-                style: StyleBuilder::new()
-                    .attrs(Attributes::from(Attribute::Bold))
-                    .build(),
-            },
-            checkbox_state: checkbox::State {
-                checkbox: checkbox::Checkbox::from_displayable(items),
-                cursor: String::from("❯ "),
-                active_mark: '☒',
-                inactive_mark: '☐',
-                active_item_style: StyleBuilder::new().fgc(Color::DarkCyan).build(),
-                inactive_item_style: StyleBuilder::new().build(),
-                lines: Default::default(),
-            },
-            keymap: ActiveKeySwitcher::new("default", self::keymap::default),
-        }
+        let labels: Vec<String> = items.into_iter().map(|item| item.to_string()).collect();
+        Checkbox::from_entries(labels.into_iter().map(|label| (label.clone(), label)))
     }
 
     pub fn new_with_checked<T: Display, I: IntoIterator<Item = (T, bool)>>(items: I) -> Self {
+        let (labels, checked): (Vec<String>, Vec<bool>) = items
+            .into_iter()
+            .map(|(item, checked)| (item.to_string(), checked))
+            .unzip();
+        Self::with_checkbox(
+            checkbox::Checkbox::new_with_checked(labels.iter().cloned().zip(checked)),
+            labels,
+        )
+    }
+}
+
+impl<V: Clone> Checkbox<V> {
+    /// Constructs a new `Checkbox` instance from pairs of a value and its displayed text.
+    /// The values of the picked items are returned when the prompt is submitted.
+    ///
+    /// # Arguments
+    ///
+    /// * `entries` - An iterator over `(value, text)` pairs.
+    pub fn from_entries<L: Display, I: IntoIterator<Item = (V, L)>>(entries: I) -> Self {
+        let (values, labels): (Vec<V>, Vec<L>) = entries.into_iter().unzip();
+        Self::with_checkbox(checkbox::Checkbox::from_displayable(labels), values)
+    }
+
+    fn with_checkbox(checkbox: checkbox::Checkbox, values: Vec<V>) -> Self {
         Self {
             title_state: text::State {
                 text: Default::default(),
                 style: StyleBuilder::new()
                     .attrs(Attributes::from(Attribute::Bold))
                     .build(),
+                offset: 0,
             },
             checkbox_state: checkbox::State {
-                checkbox: checkbox::Checkbox::new_with_checked(items),
+                checkbox,
                 cursor: String::from("❯ "),
                 active_mark: '☒',
                 inactive_mark: '☐',
                 active_item_style: StyleBuilder::new().fgc(Color::DarkCyan).build(),
                 inactive_item_style: StyleBuilder::new().build(),
+                overflow: Default::default(),
+                item_lines: None,
+                partial_item: Default::default(),
                 lines: Default::default(),
             },
-            keymap: ActiveKeySwitcher::new("default", self::keymap::default),
+            keymap: ActiveKeySwitcher::new("default", self::keymap::default as keymap::Keymap<V>),
+            key_bindings: Default::default(),
+            values,
+            preserve_toggle_order: false,
         }
     }
 
@@ -84,17 +109,31 @@ impl Checkbox {
     }
 
     /// Sets the cursor symbol used to indicate the current selection.
-    pub fn cursor<T: AsRef<str>>(mut self, cursor: T) -> Self {
+    pub fn cursor_symbol<T: AsRef<str>>(mut self, cursor: T) -> Self {
         self.checkbox_state.cursor = cursor.as_ref().to_string();
         self
     }
 
+    #[deprecated(since = "0.7.0", note = "renamed to `cursor_symbol`")]
+    pub fn cursor<T: AsRef<str>>(self, cursor: T) -> Self {
+        self.cursor_symbol(cursor)
+    }
+
     /// Sets the mark symbol used to indicate selected items.
+    /// Marks of different display widths (e.g. `✅` and `-`) are normalized
+    /// by padding the narrower one, so that the items stay aligned.
     pub fn active_mark(mut self, mark: char) -> Self {
         self.checkbox_state.active_mark = mark;
         self
     }
 
+    /// Sets the mark symbol used to indicate unselected items.
+    /// Marks of different display widths are normalized as with `active_mark`.
+    pub fn inactive_mark(mut self, mark: char) -> Self {
+        self.checkbox_state.inactive_mark = mark;
+        self
+    }
+
     /// Sets the style for active (currently selected) items.
     pub fn active_item_style(mut self, style: ContentStyle) -> Self {
         self.checkbox_state.active_item_style = style;
@@ -107,27 +146,113 @@ impl Checkbox {
         self
     }
 
+    /// Sets how items exceeding the terminal width are rendered.
+    pub fn overflow(mut self, overflow: Overflow) -> Self {
+        self.checkbox_state.overflow = overflow;
+        self
+    }
+
+    /// Sets the maximum number of rows of a wrapped item, e.g. for items of a few sentences.
+    /// The last row of a cut item ends with `…`.
+    pub fn item_lines(mut self, lines: usize) -> Self {
+        self.checkbox_state.item_lines = Some(lines);
+        self
+    }
+
+    /// Sets whether an item not fitting at the bottom of the list is clipped or omitted.
+    pub fn partial_item(mut self, partial_item: PartialItem) -> Self {
+        self.checkbox_state.partial_item = partial_item;
+        self
+    }
+
+    /// Removes items whose labels have already appeared, preserving the first occurrence.
+    /// By default, duplicates are kept and toggled independently.
+    pub fn dedup(mut self, dedup: bool) -> Self {
+        if dedup {
+            self.checkbox_state.checkbox.dedup();
+        }
+        self
+    }
+
+    /// Returns the labels appearing more than once, e.g. to log a warning.
+    pub fn duplicates(&self) -> Vec<String> {
+        self.checkbox_state.checkbox.duplicates()
+    }
+
+    /// Sets whether moving down from the last item goes to the first one and vice versa.
+    pub fn cyclic(mut self, cyclic: bool) -> Self {
+        self.checkbox_state.checkbox.set_cyclic(cyclic);
+        self
+    }
+
+    /// Sets whether the cursor is shown and the items can be moved over and toggled
+    /// (`true` by default).
+    /// If not, the checkbox is only displayed until Enter is pressed.
+    pub fn interactive(mut self, interactive: bool) -> Self {
+        self.checkbox_state.checkbox.set_interactive(interactive);
+        self
+    }
+
+    /// Picks the items at the given indices (in the order they were given), e.g. to edit
+    /// an existing configuration. Indices out of range are ignored, with a debug assertion.
+    pub fn preselect<I: IntoIterator<Item = usize>>(mut self, indices: I) -> Self {
+        self.checkbox_state.checkbox.preselect(indices);
+        self
+    }
+
+    /// Picks the items with the given texts, in the order they were given.
+    /// Texts not listed are ignored.
+    pub fn preselect_labels<S: AsRef<str>, I: IntoIterator<Item = S>>(mut self, labels: I) -> Self {
+        self.checkbox_state.checkbox.preselect_labels(labels);
+        self
+    }
+
+    /// Sets whether the values are returned in the order the items were picked,
+    /// instead of the order they are listed (`false` by default).
+    /// Preselected items come first, in the order they were preselected.
+    pub fn preserve_toggle_order(mut self, preserve: bool) -> Self {
+        self.preserve_toggle_order = preserve;
+        self
+    }
+
     /// Sets the number of lines to be used for displaying the checkbox list.
     pub fn checkbox_lines(mut self, lines: usize) -> Self {
         self.checkbox_state.lines = Some(lines);
         self
     }
 
-    pub fn register_keymap<K: AsRef<str>>(mut self, key: K, handler: keymap::Keymap) -> Self {
+    /// Sets the keys bound to the actions of the default keymap,
+    /// e.g. `KeyBindings::default().bind(KeyCode::Esc, keymap::Action::Interrupt)`.
+    pub fn key_bindings(mut self, key_bindings: KeyBindings<keymap::Action>) -> Self {
+        self.key_bindings = key_bindings;
+        self
+    }
+
+    pub fn register_keymap<K: AsRef<str>>(mut self, key: K, handler: keymap::Keymap<V>) -> Self {
         self.keymap = self.keymap.register(key, handler);
         self
     }
 
+    /// Restores the picked items and the cursor exported by `Renderer::export_state`,
+    /// e.g. in a previous session. See `checkbox::Checkbox::import_state` for
+    /// how the state applies to a changed list of items.
+    pub fn import_state(mut self, state: &serde_json::Value) -> anyhow::Result<Self> {
+        self.checkbox_state.checkbox.import_state(state)?;
+        Ok(self)
+    }
+
     /// Displays the checkbox prompt and waits for user input.
     /// Returns a `Result` containing the `Prompt` result,
     /// which is a list of selected options.
-    pub fn prompt(self) -> anyhow::Result<Prompt<render::Renderer>> {
-        Ok(Prompt {
-            renderer: render::Renderer {
-                keymap: RefCell::new(self.keymap),
-                title_state: self.title_state,
-                checkbox_state: self.checkbox_state,
-            },
-        })
+    pub fn prompt(self) -> anyhow::Result<Prompt<render::Renderer<V>>> {
+        Ok(Prompt::new(render::Renderer {
+            keymap: RefCell::new(self.keymap),
+            key_bindings: self.key_bindings,
+            title_state: self.title_state,
+            checkbox_state: self.checkbox_state,
+            values: self.values,
+            preserve_toggle_order: self.preserve_toggle_order,
+            checkbox_height: Default::default(),
+        }))
     }
 }
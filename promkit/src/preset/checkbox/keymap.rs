@@ -1,17 +1,68 @@
 use crate::{
-    crossterm::event::{
-        Event, KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers, MouseEvent,
-        MouseEventKind,
-    },
-    preset, PromptSignal,
+    chord::KeyCombo,
+    crossterm::event::{Event, KeyCode, KeyModifiers, MouseEvent, MouseEventKind},
+    key_bindings::{ctrl, KeyBindings},
+    preset, Interrupted, PromptSignal,
 };
 
-pub type Keymap = fn(
+pub type Keymap<V = String> = fn(
     event: &Event,
-    renderer: &mut preset::checkbox::render::Renderer,
+    renderer: &mut preset::checkbox::render::Renderer<V>,
 ) -> anyhow::Result<PromptSignal>;
 
-/// Default key bindings for the checkbox interface.
+/// Actions of the checkbox, bound to keys by `KeyBindings<Action>`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// Exit the interface with the checked items.
+    Accept,
+    /// Interrupt the current operation.
+    Interrupt,
+    /// Move the selection up.
+    MoveUp,
+    /// Move the selection down.
+    MoveDown,
+    /// Move the selection up by a page.
+    PageUp,
+    /// Move the selection down by a page.
+    PageDown,
+    /// Move the selection to the first item.
+    MoveToHead,
+    /// Move the selection to the last item.
+    MoveToTail,
+    /// Toggle the checkbox state for the current item.
+    Toggle,
+    /// Check all items if any is unchecked, otherwise uncheck all items.
+    ToggleAll,
+    /// Toggle the items between the last toggled one and the current item.
+    ToggleRange,
+    /// Invert the checkbox state of all items.
+    Invert,
+}
+
+impl Default for KeyBindings<Action> {
+    /// Returns the bindings listed in `default`.
+    fn default() -> Self {
+        KeyBindings::empty()
+            .bind(KeyCode::Enter, Action::Accept)
+            .bind(ctrl('c'), Action::Interrupt)
+            .bind(KeyCode::Up, Action::MoveUp)
+            .bind(KeyCode::Down, Action::MoveDown)
+            .bind(KeyCode::PageUp, Action::PageUp)
+            .bind(KeyCode::PageDown, Action::PageDown)
+            .bind(KeyCode::Home, Action::MoveToHead)
+            .bind(KeyCode::End, Action::MoveToTail)
+            .bind(' ', Action::Toggle)
+            .bind(ctrl('a'), Action::ToggleAll)
+            .bind(
+                KeyCombo::new(KeyCode::Char(' '), KeyModifiers::SHIFT),
+                Action::ToggleRange,
+            )
+            .bind('i', Action::Invert)
+    }
+}
+
+/// Default key bindings for the checkbox interface,
+/// evaluating the keys through the `KeyBindings` of the renderer.
 ///
 /// | Key                    | Action
 /// | :--------------------- | :-------------------------------------------
@@ -19,34 +70,57 @@ pub type Keymap = fn(
 /// | <kbd>Ctrl + C</kbd>    | Interrupt the current operation
 /// | <kbd>↑</kbd>           | Move the selection up
 /// | <kbd>↓</kbd>           | Move the selection down
+/// | <kbd>PageUp</kbd>      | Move the selection up by the rows of the list
+/// | <kbd>PageDown</kbd>    | Move the selection down by the rows of the list
+/// | <kbd>Home</kbd>        | Move the selection to the first item
+/// | <kbd>End</kbd>         | Move the selection to the last item
 /// | <kbd>Space</kbd>       | Toggle the checkbox state for the current item
-pub fn default(
+/// | <kbd>Ctrl + A</kbd>    | Check all items, or uncheck all if all are checked
+/// | <kbd>Shift + Space</kbd> | Toggle the items from the last toggled one to the current item
+/// | <kbd>i</kbd>           | Invert the checkbox state of all items
+/// | Wheel                  | Move the selection, by the rows of the list with <kbd>Shift</kbd>
+pub fn default<V>(
     event: &Event,
-    renderer: &mut preset::checkbox::render::Renderer,
+    renderer: &mut preset::checkbox::render::Renderer<V>,
 ) -> anyhow::Result<PromptSignal> {
-    match event {
-        Event::Key(KeyEvent {
-            code: KeyCode::Enter,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => return Ok(PromptSignal::Quit),
-        Event::Key(KeyEvent {
-            code: KeyCode::Char('c'),
-            modifiers: KeyModifiers::CONTROL,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => return Err(anyhow::anyhow!("ctrl+c")),
-
-        // Move cursor.
-        Event::Key(KeyEvent {
-            code: KeyCode::Up,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => {
-            renderer.checkbox_state.checkbox.backward();
+    if let Some(action) = renderer.key_bindings.action(event) {
+        match action {
+            Action::Accept => return Ok(PromptSignal::Quit),
+            Action::Interrupt => return Err(Interrupted.into()),
+            // A display-only checkbox only takes Enter and Ctrl+C.
+            _ if !renderer.checkbox_state.checkbox.is_interactive() => (),
+            Action::MoveUp => {
+                renderer.checkbox_state.checkbox.backward();
+            }
+            Action::MoveDown => {
+                renderer.checkbox_state.checkbox.forward();
+            }
+            Action::PageUp => {
+                renderer
+                    .checkbox_state
+                    .checkbox
+                    .page_backward(page_size(renderer));
+            }
+            Action::PageDown => {
+                renderer
+                    .checkbox_state
+                    .checkbox
+                    .page_forward(page_size(renderer));
+            }
+            Action::MoveToHead => renderer.checkbox_state.checkbox.move_to_head(),
+            Action::MoveToTail => renderer.checkbox_state.checkbox.move_to_tail(),
+            Action::Toggle => renderer.checkbox_state.checkbox.toggle(),
+            Action::ToggleAll => renderer.checkbox_state.checkbox.toggle_all(),
+            Action::ToggleRange => renderer.checkbox_state.checkbox.toggle_range_from_last(),
+            Action::Invert => renderer.checkbox_state.checkbox.invert(),
         }
+        return Ok(PromptSignal::Continue);
+    }
+    if !renderer.checkbox_state.checkbox.is_interactive() {
+        return Ok(PromptSignal::Continue);
+    }
+
+    match event {
         Event::Mouse(MouseEvent {
             kind: MouseEventKind::ScrollUp,
             column: _,
@@ -55,15 +129,6 @@ pub fn default(
         }) => {
             renderer.checkbox_state.checkbox.backward();
         }
-
-        Event::Key(KeyEvent {
-            code: KeyCode::Down,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => {
-            renderer.checkbox_state.checkbox.forward();
-        }
         Event::Mouse(MouseEvent {
             kind: MouseEventKind::ScrollDown,
             column: _,
@@ -72,15 +137,36 @@ pub fn default(
         }) => {
             renderer.checkbox_state.checkbox.forward();
         }
-
-        Event::Key(KeyEvent {
-            code: KeyCode::Char(' '),
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => renderer.checkbox_state.checkbox.toggle(),
+        Event::Mouse(MouseEvent {
+            kind: MouseEventKind::ScrollUp,
+            modifiers: KeyModifiers::SHIFT,
+            ..
+        }) => {
+            renderer
+                .checkbox_state
+                .checkbox
+                .page_backward(page_size(renderer));
+        }
+        Event::Mouse(MouseEvent {
+            kind: MouseEventKind::ScrollDown,
+            modifiers: KeyModifiers::SHIFT,
+            ..
+        }) => {
+            renderer
+                .checkbox_state
+                .checkbox
+                .page_forward(page_size(renderer));
+        }
 
         _ => (),
     }
     Ok(PromptSignal::Continue)
 }
+
+/// Returns the rows of the list last rendered, which a page moves over.
+fn page_size<V>(renderer: &preset::checkbox::render::Renderer<V>) -> usize {
+    renderer
+        .checkbox_height
+        .get()
+        .map_or(1, |height| renderer.checkbox_state.page_size(height))
+}
@@ -1,8 +1,8 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 
 use crate::{
-    checkbox, crossterm::event::Event, pane::Pane, switch::ActiveKeySwitcher, text, PaneFactory,
-    PromptSignal,
+    checkbox, crossterm::event::Event, key_bindings::KeyBindings, pane::Pane,
+    switch::ActiveKeySwitcher, text, PaneFactory, PromptSignal,
 };
 
 use super::keymap;
@@ -11,35 +11,58 @@ use super::keymap;
 ///
 /// This struct is responsible for managing the rendering process of a checkbox preset,
 /// including handling keymaps, and managing snapshots of the title and checkbox states.
-pub struct Renderer {
+pub struct Renderer<V = String> {
     /// Manages key mappings for the renderer.
-    pub keymap: RefCell<ActiveKeySwitcher<keymap::Keymap>>,
+    pub keymap: RefCell<ActiveKeySwitcher<keymap::Keymap<V>>>,
+    /// The keys bound to the actions of the default keymap.
+    pub key_bindings: KeyBindings<keymap::Action>,
     /// A title's renderer state.
     pub title_state: text::State,
     /// A checkbox's renderer state.
     pub checkbox_state: checkbox::State,
+    /// The values returned for the items, indexed by the item identities
+    /// (i.e. the indices at construction).
+    pub values: Vec<V>,
+    /// Whether the values are returned in the order the items were picked.
+    pub preserve_toggle_order: bool,
+    /// The rows available for the list when last rendered, which a page moves over.
+    pub checkbox_height: Cell<Option<u16>>,
 }
 
-impl crate::Finalizer for Renderer {
-    type Return = Vec<String>;
+impl<V> Renderer<V> {
+    /// Exports the picked items and the cursor, to be restored by
+    /// `Checkbox::import_state`, e.g. in the next session.
+    pub fn export_state(&self) -> serde_json::Value {
+        self.checkbox_state.checkbox.export_state()
+    }
+}
+
+impl<V: Clone> crate::Finalizer for Renderer<V> {
+    type Return = Vec<V>;
 
     fn finalize(&mut self) -> anyhow::Result<Self::Return> {
-        Ok(self
-            .checkbox_state
-            .checkbox
-            .get()
-            .iter()
-            .map(|e| e.to_string())
+        let checkbox = &self.checkbox_state.checkbox;
+        let ids = if self.preserve_toggle_order {
+            checkbox.picked_indexes().iter().copied().collect()
+        } else {
+            checkbox.picked_ids()
+        };
+        Ok(ids
+            .into_iter()
+            .filter_map(|id| self.values.get(id).cloned())
             .collect())
     }
 }
 
-impl crate::Renderer for Renderer {
+impl<V: Clone> crate::Renderer for Renderer<V> {
     fn create_panes(&self, width: u16, height: u16) -> Vec<Pane> {
-        vec![
-            self.title_state.create_pane(width, height),
-            self.checkbox_state.create_pane(width, height),
-        ]
+        let title = self.title_state.create_pane(width, height);
+        self.checkbox_height.set(Some(
+            height
+                .saturating_sub(title.height().min(height as usize) as u16)
+                .max(1),
+        ));
+        vec![title, self.checkbox_state.create_pane(width, height)]
     }
 
     fn evaluate(&mut self, event: &Event) -> anyhow::Result<PromptSignal> {
@@ -2,66 +2,104 @@ use std::cell::RefCell;
 
 use crate::{
     core::Cursor,
-    crossterm::style::{Attribute, Attributes},
+    crossterm::style::{Attribute, Attributes, Color, ContentStyle},
     style::StyleBuilder,
     switch::ActiveKeySwitcher,
-    text_editor, Prompt,
+    text,
+    validate::{ErrorMessageGenerator, Validator, ValidatorManager},
+    Prompt,
 };
 
+mod field;
+pub use field::{Field, Select};
 mod keymap;
 mod render;
+mod report;
+pub use report::{FieldOutcome, FormReport};
 
-/// `Form` struct provides functionality for managing multiple text input fields.
+/// `Form` struct provides functionality for managing multiple input fields,
+/// each of which is a text editor or a choice among fixed options.
 pub struct Form {
     keymap: ActiveKeySwitcher<keymap::Keymap>,
-    text_editor_states: Vec<text_editor::State>,
-    /// Overwrite the default styles of text editor states when unselected.
+    fields: Vec<Field>,
+    /// Overwrite the default styles of the fields when unselected.
     overwrite_styles: Vec<render::Style>,
+    /// Optional validators for the fields, in the same order as the fields.
+    validators: Vec<Option<ValidatorManager<str>>>,
+    /// State for displaying the error message of the invalid field.
+    error_message_state: text::State,
 }
 
 impl Form {
-    pub fn new<I: IntoIterator<Item = text_editor::State>>(states: I) -> Self {
-        let (text_editor_states, overwrite_styles): (Vec<_>, Vec<_>) = states
+    /// Creates a form from the fields, e.g. `text_editor::State` or `Select`.
+    pub fn new<F: Into<Field>, I: IntoIterator<Item = F>>(fields: I) -> Self {
+        let (fields, overwrite_styles): (Vec<_>, Vec<_>) = fields
             .into_iter()
-            .map(|state| {
+            .map(|field| {
+                let field = field.into();
+                let default_style = field.style();
                 let style = render::Style {
-                    prefix_style: StyleBuilder::from(state.prefix_style)
+                    prefix_style: StyleBuilder::from(default_style.prefix_style)
                         .attrs(Attributes::from(Attribute::Dim))
                         .build(),
-                    inactive_char_style: StyleBuilder::from(state.inactive_char_style)
+                    inactive_char_style: StyleBuilder::from(default_style.inactive_char_style)
                         .attrs(Attributes::from(Attribute::Dim))
                         .build(),
                     active_char_style: StyleBuilder::new()
                         .attrs(Attributes::from(Attribute::Dim))
                         .build(),
                 };
-                (state, style)
+                (field, style)
             })
             .unzip();
         Self {
             keymap: ActiveKeySwitcher::new("default", self::keymap::default as keymap::Keymap),
-            text_editor_states,
+            validators: fields.iter().map(|_| None).collect(),
+            fields,
             overwrite_styles,
+            error_message_state: text::State {
+                text: Default::default(),
+                style: StyleBuilder::new()
+                    .fgc(Color::DarkRed)
+                    .attrs(Attributes::from(Attribute::Bold))
+                    .build(),
+                offset: 0,
+            },
         }
     }
 
+    /// Configures a validator for the field at the index.
+    /// The cursor does not move down from an invalid field,
+    /// and the form is not submitted until all fields are valid.
+    pub fn validator(
+        mut self,
+        index: usize,
+        validator: Validator<str>,
+        error_message_generator: ErrorMessageGenerator<str>,
+    ) -> Self {
+        if let Some(slot) = self.validators.get_mut(index) {
+            *slot = Some(ValidatorManager::new(validator, error_message_generator));
+        }
+        self
+    }
+
+    /// Sets the style for the error message.
+    pub fn error_message_style(mut self, style: ContentStyle) -> Self {
+        self.error_message_state.style = style;
+        self
+    }
+
     pub fn prompt(self) -> anyhow::Result<Prompt<render::Renderer>> {
-        let default_styles = self
-            .text_editor_states
-            .iter()
-            .map(|state| render::Style {
-                prefix_style: state.prefix_style,
-                active_char_style: state.active_char_style,
-                inactive_char_style: state.inactive_char_style,
-            })
-            .collect();
+        let default_styles = self.fields.iter().map(Field::style).collect();
         let mut renderer = render::Renderer {
             keymap: RefCell::new(self.keymap),
-            text_editor_states: Cursor::new(self.text_editor_states, 0, false),
+            fields: Cursor::new(self.fields, 0, false),
             default_styles,
             overwrite_styles: self.overwrite_styles,
+            validators: self.validators,
+            error_message_state: self.error_message_state,
         };
         renderer.overwrite_styles();
-        Ok(Prompt { renderer })
+        Ok(Prompt::new(renderer))
     }
 }
@@ -0,0 +1,163 @@
+use std::fmt::Display;
+
+use crate::{
+    crossterm::style::{Attribute, Attributes, Color, ContentStyle},
+    grapheme::StyledGraphemes,
+    listbox,
+    pane::Pane,
+    style::StyleBuilder,
+    text_editor, PaneFactory,
+};
+
+use super::render::Style;
+
+/// A field of the form, either a free text or a choice among fixed options.
+#[derive(Clone)]
+pub enum Field {
+    /// A field edited as text.
+    Text(text_editor::State),
+    /// A field choosing one of the options.
+    Select(Select),
+}
+
+impl From<text_editor::State> for Field {
+    fn from(state: text_editor::State) -> Self {
+        Self::Text(state)
+    }
+}
+
+impl From<Select> for Field {
+    fn from(select: Select) -> Self {
+        Self::Select(select)
+    }
+}
+
+impl Field {
+    /// Returns the input text, or the chosen option of a select field.
+    pub fn value(&self) -> String {
+        match self {
+            Self::Text(state) => state.texteditor.text_without_cursor().to_string(),
+            Self::Select(select) => select.listbox_state.listbox.get().to_string(),
+        }
+    }
+
+    /// Returns the styles of the field.
+    pub(super) fn style(&self) -> Style {
+        match self {
+            Self::Text(state) => Style {
+                prefix_style: state.prefix_style,
+                active_char_style: state.active_char_style,
+                inactive_char_style: state.inactive_char_style,
+            },
+            Self::Select(select) => Style {
+                prefix_style: select.label_style,
+                active_char_style: select.listbox_state.active_item_style.unwrap_or_default(),
+                inactive_char_style: select.listbox_state.inactive_item_style.unwrap_or_default(),
+            },
+        }
+    }
+
+    /// Applies the styles to the field.
+    pub(super) fn set_style(&mut self, style: &Style) {
+        match self {
+            Self::Text(state) => {
+                state.prefix_style = style.prefix_style;
+                state.active_char_style = style.active_char_style;
+                state.inactive_char_style = style.inactive_char_style;
+            }
+            Self::Select(select) => {
+                select.label_style = style.prefix_style;
+                select.listbox_state.active_item_style = Some(style.active_char_style);
+                select.listbox_state.inactive_item_style = Some(style.inactive_char_style);
+            }
+        }
+    }
+
+    /// Creates the panes of the field.
+    /// A select field lists its options only while it is focused,
+    /// and shows the chosen option next to the label otherwise.
+    pub(super) fn create_panes(&self, focused: bool, width: u16, height: u16) -> Vec<Pane> {
+        match self {
+            Self::Text(state) => vec![state.create_pane(width, height)],
+            Self::Select(select) if focused => {
+                let label = StyledGraphemes::from_str(&select.label, select.label_style);
+                vec![
+                    Pane::new(label.matrixify(width as usize, height as usize, 0).0, 0),
+                    select.listbox_state.create_pane(width, height),
+                ]
+            }
+            Self::Select(select) => {
+                let line = StyledGraphemes::from_iter([
+                    StyledGraphemes::from_str(&select.label, select.label_style),
+                    StyledGraphemes::from_str(
+                        select.listbox_state.listbox.get().to_string(),
+                        select.listbox_state.inactive_item_style.unwrap_or_default(),
+                    ),
+                ]);
+                vec![Pane::new(
+                    line.matrixify(width as usize, height as usize, 0).0,
+                    0,
+                )]
+            }
+        }
+    }
+}
+
+/// A field choosing one of the options with ↑ and ↓.
+#[derive(Clone)]
+pub struct Select {
+    /// Text shown before the options.
+    pub label: String,
+    /// Style for the label.
+    pub label_style: ContentStyle,
+    /// State for the options.
+    pub listbox_state: listbox::State,
+}
+
+impl Select {
+    /// Creates a field choosing one of the options,
+    /// styled like the `Listbox` preset with the first option chosen.
+    pub fn new<L: Into<String>, T: Display, I: IntoIterator<Item = T>>(
+        label: L,
+        options: I,
+    ) -> Self {
+        Self {
+            label: label.into(),
+            label_style: StyleBuilder::new().fgc(Color::DarkGreen).build(),
+            listbox_state: listbox::State {
+                listbox: listbox::Listbox::from_displayable(options),
+                cursor: String::from("❯ "),
+                active_item_style: Some(StyleBuilder::new().fgc(Color::DarkCyan).build()),
+                inactive_item_style: Some(StyleBuilder::new().build()),
+                description_style: StyleBuilder::new()
+                    .fgc(Color::DarkGrey)
+                    .attrs(Attributes::from(Attribute::Dim))
+                    .build(),
+                overflow: Default::default(),
+                item_lines: None,
+                partial_item: Default::default(),
+                lines: Default::default(),
+            },
+        }
+    }
+
+    /// Chooses the option with the text initially, if any.
+    pub fn chosen<T: AsRef<str>>(mut self, option: T) -> Self {
+        if let Some(position) = self
+            .listbox_state
+            .listbox
+            .items()
+            .iter()
+            .position(|item| item.to_string() == option.as_ref())
+        {
+            self.listbox_state.listbox.move_to(position);
+        }
+        self
+    }
+
+    /// Sets the number of lines available for the options.
+    pub fn lines(mut self, lines: usize) -> Self {
+        self.listbox_state.lines = Some(lines);
+        self
+    }
+}
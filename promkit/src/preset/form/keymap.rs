@@ -1,6 +1,7 @@
 use crate::{
     crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers},
-    preset, text_editor, PromptSignal,
+    preset::{self, form::Field},
+    text_editor, Interrupted, PromptSignal,
 };
 
 pub type Keymap = fn(
@@ -8,11 +9,17 @@ pub type Keymap = fn(
     renderer: &mut preset::form::render::Renderer,
 ) -> anyhow::Result<PromptSignal>;
 
+/// Default key bindings for the form.
+///
+/// <kbd>↑</kbd> and <kbd>↓</kbd> move through the options of a select field,
+/// and to the previous or the next field past the first or the last option.
 pub fn default(
     event: &Event,
     renderer: &mut preset::form::render::Renderer,
 ) -> anyhow::Result<PromptSignal> {
-    let current_position = renderer.text_editor_states.position();
+    let current_position = renderer.fields.position();
+    // The error message is shown until the next key.
+    renderer.error_message_state.text = Default::default();
 
     match event {
         Event::Key(KeyEvent {
@@ -20,14 +27,55 @@ pub fn default(
             modifiers: KeyModifiers::NONE,
             kind: KeyEventKind::Press,
             state: KeyEventState::NONE,
-        }) => return Ok(PromptSignal::Quit),
+        }) if renderer.validate_all() => return Ok(PromptSignal::Quit),
         Event::Key(KeyEvent {
             code: KeyCode::Char('c'),
             modifiers: KeyModifiers::CONTROL,
             kind: KeyEventKind::Press,
             state: KeyEventState::NONE,
-        }) => return Err(anyhow::anyhow!("ctrl+c")),
+        }) => return Err(Interrupted.into()),
 
+        Event::Key(KeyEvent {
+            code: KeyCode::Up,
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => {
+            let moved = match &mut renderer.fields.contents_mut()[current_position] {
+                Field::Select(select) => select.listbox_state.listbox.backward(),
+                Field::Text(_) => false,
+            };
+            if !moved {
+                renderer.fields.backward();
+            }
+        }
+        Event::Key(KeyEvent {
+            code: KeyCode::Down,
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => {
+            let moved = match &mut renderer.fields.contents_mut()[current_position] {
+                Field::Select(select) => select.listbox_state.listbox.forward(),
+                Field::Text(_) => false,
+            };
+            if !moved && renderer.validate(current_position) {
+                renderer.fields.forward();
+            }
+        }
+
+        _ => {
+            if let Field::Text(state) = &mut renderer.fields.contents_mut()[current_position] {
+                edit(event, state);
+            }
+        }
+    }
+    Ok(PromptSignal::Continue)
+}
+
+/// Edits the text of the text field.
+fn edit(event: &Event, state: &mut text_editor::State) {
+    match event {
         // Move cursor.
         Event::Key(KeyEvent {
             code: KeyCode::Left,
@@ -35,9 +83,7 @@ pub fn default(
             kind: KeyEventKind::Press,
             state: KeyEventState::NONE,
         }) => {
-            renderer.text_editor_states.contents_mut()[current_position]
-                .texteditor
-                .backward();
+            state.texteditor.backward();
         }
         Event::Key(KeyEvent {
             code: KeyCode::Right,
@@ -45,26 +91,20 @@ pub fn default(
             kind: KeyEventKind::Press,
             state: KeyEventState::NONE,
         }) => {
-            renderer.text_editor_states.contents_mut()[current_position]
-                .texteditor
-                .forward();
+            state.texteditor.forward();
         }
         Event::Key(KeyEvent {
             code: KeyCode::Char('a'),
             modifiers: KeyModifiers::CONTROL,
             kind: KeyEventKind::Press,
             state: KeyEventState::NONE,
-        }) => renderer.text_editor_states.contents_mut()[current_position]
-            .texteditor
-            .move_to_head(),
+        }) => state.texteditor.move_to_head(),
         Event::Key(KeyEvent {
             code: KeyCode::Char('e'),
             modifiers: KeyModifiers::CONTROL,
             kind: KeyEventKind::Press,
             state: KeyEventState::NONE,
-        }) => renderer.text_editor_states.contents_mut()[current_position]
-            .texteditor
-            .move_to_tail(),
+        }) => state.texteditor.move_to_tail(),
 
         // Move cursor to the nearest character.
         Event::Key(KeyEvent {
@@ -73,12 +113,8 @@ pub fn default(
             kind: KeyEventKind::Press,
             state: KeyEventState::NONE,
         }) => {
-            let word_break_chars = renderer.text_editor_states.contents_mut()[current_position]
-                .word_break_chars
-                .clone();
-            renderer.text_editor_states.contents_mut()[current_position]
-                .texteditor
-                .move_to_previous_nearest(&word_break_chars)
+            let navigation = state.word_navigation();
+            state.texteditor.move_word_back(&navigation)
         }
 
         Event::Key(KeyEvent {
@@ -87,12 +123,8 @@ pub fn default(
             kind: KeyEventKind::Press,
             state: KeyEventState::NONE,
         }) => {
-            let word_break_chars = renderer.text_editor_states.contents_mut()[current_position]
-                .word_break_chars
-                .clone();
-            renderer.text_editor_states.contents_mut()[current_position]
-                .texteditor
-                .move_to_next_nearest(&word_break_chars)
+            let navigation = state.word_navigation();
+            state.texteditor.move_word_forward(&navigation)
         }
 
         // Erase char(s).
@@ -101,17 +133,13 @@ pub fn default(
             modifiers: KeyModifiers::NONE,
             kind: KeyEventKind::Press,
             state: KeyEventState::NONE,
-        }) => renderer.text_editor_states.contents_mut()[current_position]
-            .texteditor
-            .erase(),
+        }) => state.texteditor.erase(),
         Event::Key(KeyEvent {
             code: KeyCode::Char('u'),
             modifiers: KeyModifiers::CONTROL,
             kind: KeyEventKind::Press,
             state: KeyEventState::NONE,
-        }) => renderer.text_editor_states.contents_mut()[current_position]
-            .texteditor
-            .erase_all(),
+        }) => state.texteditor.erase_all(),
 
         // Erase to the nearest character.
         Event::Key(KeyEvent {
@@ -120,12 +148,8 @@ pub fn default(
             kind: KeyEventKind::Press,
             state: KeyEventState::NONE,
         }) => {
-            let word_break_chars = renderer.text_editor_states.contents_mut()[current_position]
-                .word_break_chars
-                .clone();
-            renderer.text_editor_states.contents_mut()[current_position]
-                .texteditor
-                .erase_to_previous_nearest(&word_break_chars)
+            let navigation = state.word_navigation();
+            state.texteditor.erase_word_back(&navigation)
         }
 
         Event::Key(KeyEvent {
@@ -134,29 +158,8 @@ pub fn default(
             kind: KeyEventKind::Press,
             state: KeyEventState::NONE,
         }) => {
-            let word_break_chars = renderer.text_editor_states.contents_mut()[current_position]
-                .word_break_chars
-                .clone();
-            renderer.text_editor_states.contents_mut()[current_position]
-                .texteditor
-                .erase_to_next_nearest(&word_break_chars)
-        }
-
-        Event::Key(KeyEvent {
-            code: KeyCode::Up,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => {
-            renderer.text_editor_states.backward();
-        }
-        Event::Key(KeyEvent {
-            code: KeyCode::Down,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => {
-            renderer.text_editor_states.forward();
+            let navigation = state.word_navigation();
+            state.texteditor.erase_word_forward(&navigation)
         }
 
         // Input char.
@@ -171,18 +174,11 @@ pub fn default(
             modifiers: KeyModifiers::SHIFT,
             kind: KeyEventKind::Press,
             state: KeyEventState::NONE,
-        }) => match renderer.text_editor_states.contents_mut()[current_position].edit_mode {
-            text_editor::Mode::Insert => renderer.text_editor_states.contents_mut()
-                [current_position]
-                .texteditor
-                .insert(*ch),
-            text_editor::Mode::Overwrite => renderer.text_editor_states.contents_mut()
-                [current_position]
-                .texteditor
-                .overwrite(*ch),
+        }) => match state.edit_mode {
+            text_editor::Mode::Insert => state.texteditor.insert(*ch),
+            text_editor::Mode::Overwrite => state.texteditor.overwrite(*ch),
         },
 
         _ => (),
     }
-    Ok(PromptSignal::Continue)
 }
@@ -5,12 +5,16 @@ use crate::{
     crossterm::{event::Event, style::ContentStyle},
     pane::Pane,
     switch::ActiveKeySwitcher,
-    text_editor, PaneFactory, PromptSignal,
+    text,
+    validate::ValidatorManager,
+    PaneFactory, PromptSignal,
 };
 
-use super::keymap;
+use super::{keymap, Field};
 
-/// Represents the visual styles for different states of text editor components.
+/// Represents the visual styles for different states of the fields.
+/// For a select field, the prefix style is of the label
+/// and the char styles are of the chosen and the other options.
 pub struct Style {
     /// Style for the prefix of the text editor.
     pub prefix_style: ContentStyle,
@@ -20,48 +24,67 @@ pub struct Style {
     pub inactive_char_style: ContentStyle,
 }
 
-/// Manages rendering logic for text editors, including handling of styles and key mappings.
+/// Manages rendering logic for the fields, including handling of styles and key mappings.
 pub struct Renderer {
     /// A mutable reference to a key switcher that manages active key mappings.
     pub keymap: RefCell<ActiveKeySwitcher<keymap::Keymap>>,
-    /// Cursor managing the state of multiple text editors.
-    pub text_editor_states: Cursor<Vec<text_editor::State>>,
-    /// Default styles applied to text editors.
+    /// Cursor managing the fields.
+    pub fields: Cursor<Vec<Field>>,
+    /// Default styles applied to the fields.
     pub default_styles: Vec<Style>,
-    /// Styles applied to text editors when they are unselected.
+    /// Styles applied to the fields when they are unselected.
     pub overwrite_styles: Vec<Style>,
+    /// Optional validators for the fields, in the same order as the fields.
+    pub validators: Vec<Option<ValidatorManager<str>>>,
+    /// State for displaying the error message of the invalid field.
+    pub error_message_state: text::State,
 }
 
 impl crate::Finalizer for Renderer {
     type Return = Vec<String>;
 
     fn finalize(&mut self) -> anyhow::Result<Self::Return> {
-        Ok(self
-            .text_editor_states
-            .contents()
-            .iter()
-            .map(|state| state.texteditor.text_without_cursor().to_string())
-            .collect())
+        Ok(self.fields.contents().iter().map(Field::value).collect())
     }
 }
 
 impl Renderer {
-    /// Updates the styles of text editor states based on their active or inactive status.
+    /// Validates the field at the index, showing the error message if it is invalid.
+    pub fn validate(&mut self, index: usize) -> bool {
+        let Some(Some(validator)) = self.validators.get(index) else {
+            return true;
+        };
+        let text = self.fields.contents()[index].value();
+        let valid = validator.validate(&text);
+        if !valid {
+            self.error_message_state.text = validator.generate_error_message(&text);
+        }
+        valid
+    }
+
+    /// Validates all fields, moving the cursor to the first invalid one.
+    pub fn validate_all(&mut self) -> bool {
+        match (0..self.validators.len()).find(|i| !self.validate(*i)) {
+            Some(invalid) => {
+                self.fields.move_to(invalid);
+                false
+            }
+            None => true,
+        }
+    }
+
+    /// Updates the styles of the fields based on their active or inactive status.
     pub fn overwrite_styles(&mut self) {
-        let current_position = self.text_editor_states.position();
-        self.text_editor_states
+        let current_position = self.fields.position();
+        self.fields
             .contents_mut()
             .iter_mut()
             .enumerate()
-            .for_each(|(i, state)| {
+            .for_each(|(i, field)| {
                 if i == current_position {
-                    state.prefix_style = self.default_styles[i].prefix_style;
-                    state.inactive_char_style = self.default_styles[i].inactive_char_style;
-                    state.active_char_style = self.default_styles[i].active_char_style;
+                    field.set_style(&self.default_styles[i]);
                 } else {
-                    state.prefix_style = self.overwrite_styles[i].prefix_style;
-                    state.inactive_char_style = self.overwrite_styles[i].inactive_char_style;
-                    state.active_char_style = self.overwrite_styles[i].active_char_style;
+                    field.set_style(&self.overwrite_styles[i]);
                 }
             });
     }
@@ -69,10 +92,13 @@ impl Renderer {
 
 impl crate::Renderer for Renderer {
     fn create_panes(&self, width: u16, height: u16) -> Vec<Pane> {
-        self.text_editor_states
+        let current_position = self.fields.position();
+        self.fields
             .contents()
             .iter()
-            .map(|state| state.create_pane(width, height))
+            .enumerate()
+            .flat_map(|(i, field)| field.create_panes(i == current_position, width, height))
+            .chain([self.error_message_state.create_pane(width, height)])
             .collect()
     }
 
@@ -0,0 +1,31 @@
+/// How a field of a form was filled in, once its input is parsed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FieldOutcome {
+    /// The input is parsed into the value of the field.
+    Answered,
+    /// The input is empty, and the field takes its default or `None`.
+    Empty,
+    /// The input cannot be parsed, and the field is `None`.
+    ParseFailed { raw: String },
+}
+
+/// The outcomes of the fields of a form, in the order of the fields.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FormReport {
+    pub fields: Vec<(String, FieldOutcome)>,
+}
+
+impl FormReport {
+    /// Appends the outcome of the field with the name.
+    pub fn push<T: AsRef<str>>(&mut self, name: T, outcome: FieldOutcome) {
+        self.fields.push((name.as_ref().to_string(), outcome));
+    }
+
+    /// Returns the outcome of the field with the name, if any.
+    pub fn get(&self, name: &str) -> Option<&FieldOutcome> {
+        self.fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, outcome)| outcome)
+    }
+}
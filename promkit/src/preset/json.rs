@@ -1,14 +1,16 @@
-use std::cell::RefCell;
+use std::{cell::RefCell, collections::HashSet};
 
 use crate::{
     crossterm::style::{Attribute, Attributes, Color, ContentStyle},
     jsonstream::{self, JsonStream},
-    jsonz::format::RowFormatter,
+    jsonz::format::{Format, JsonTheme, RowFormatter},
     style::StyleBuilder,
     switch::ActiveKeySwitcher,
-    text, Prompt,
+    text, text_editor, Overflow, Prompt,
 };
 
+pub mod formatted;
+pub mod fragment;
 pub mod keymap;
 pub mod render;
 
@@ -17,38 +19,112 @@ pub struct Json {
     keymap: ActiveKeySwitcher<keymap::Keymap>,
     title_state: text::State,
     json_state: jsonstream::State,
+    query_editor_state: text_editor::State,
+    value_editor_state: text_editor::State,
+    hint_message_state: text::State,
+    follow_state: text::State,
+    status_state: text::State,
+    copy_path_key: char,
+    copy_value_key: char,
+    on_copy: Option<fn(&str)>,
 }
 
 impl Json {
     pub fn new(stream: JsonStream) -> Self {
+        let theme = JsonTheme::default();
         Self {
             title_state: text::State {
                 text: Default::default(),
                 style: StyleBuilder::new()
                     .attrs(Attributes::from(Attribute::Bold))
                     .build(),
+                offset: 0,
             },
             json_state: jsonstream::State {
                 stream,
                 formatter: RowFormatter {
-                    curly_brackets_style: StyleBuilder::new()
-                        .attrs(Attributes::from(Attribute::Bold))
-                        .build(),
-                    square_brackets_style: StyleBuilder::new()
-                        .attrs(Attributes::from(Attribute::Bold))
-                        .build(),
-                    key_style: StyleBuilder::new().fgc(Color::DarkBlue).build(),
-                    string_value_style: StyleBuilder::new().fgc(Color::DarkGreen).build(),
-                    number_value_style: StyleBuilder::new().build(),
-                    boolean_value_style: StyleBuilder::new().build(),
-                    null_value_style: StyleBuilder::new().fgc(Color::DarkGrey).build(),
+                    curly_brackets_style: theme.curly_brackets_style,
+                    square_brackets_style: theme.square_brackets_style,
+                    key_style: theme.key_style,
+                    string_value_style: theme.string_value_style,
+                    number_value_style: theme.number_value_style,
+                    boolean_value_style: theme.boolean_value_style,
+                    null_value_style: theme.null_value_style,
                     active_item_attribute: Attribute::Undercurled,
                     inactive_item_attribute: Attribute::Dim,
                     indent: 2,
+                    indent_unit: Default::default(),
                 },
+                search_query: Default::default(),
+                search_highlight_style: StyleBuilder::new()
+                    .fgc(Color::Black)
+                    .bgc(Color::DarkYellow)
+                    .build(),
+                visual_anchor: None,
+                visual_style: StyleBuilder::new().bgc(Color::DarkBlue).build(),
+                overflow: Overflow::TruncateWithEllipsis,
                 lines: Default::default(),
             },
-            keymap: ActiveKeySwitcher::new("default", self::keymap::default),
+            query_editor_state: text_editor::State {
+                texteditor: Default::default(),
+                history: Default::default(),
+                prefix: String::from("/ "),
+                mask: Default::default(),
+                prefix_style: StyleBuilder::new().fgc(Color::DarkGreen).build(),
+                active_char_style: StyleBuilder::new().bgc(Color::DarkCyan).build(),
+                inactive_char_style: StyleBuilder::new().build(),
+                edit_mode: Default::default(),
+                word_break_chars: HashSet::from(['.']),
+                unicode_words: false,
+                right_prompt: Default::default(),
+                right_prompt_style: Default::default(),
+                lines: Some(1),
+                scroll_top: Default::default(),
+                horizontal_scroll: false,
+                scroll_left: Default::default(),
+            },
+            value_editor_state: text_editor::State {
+                texteditor: Default::default(),
+                history: Default::default(),
+                prefix: String::from("= "),
+                mask: Default::default(),
+                prefix_style: StyleBuilder::new().fgc(Color::DarkYellow).build(),
+                active_char_style: StyleBuilder::new().bgc(Color::DarkCyan).build(),
+                inactive_char_style: StyleBuilder::new().build(),
+                edit_mode: Default::default(),
+                word_break_chars: Default::default(),
+                unicode_words: false,
+                right_prompt: Default::default(),
+                right_prompt_style: Default::default(),
+                lines: Some(1),
+                scroll_top: Default::default(),
+                horizontal_scroll: false,
+                scroll_left: Default::default(),
+            },
+            hint_message_state: text::State {
+                text: Default::default(),
+                style: StyleBuilder::new()
+                    .fgc(Color::DarkRed)
+                    .attrs(Attributes::from(Attribute::Bold))
+                    .build(),
+                offset: 0,
+            },
+            follow_state: text::State {
+                text: Default::default(),
+                style: StyleBuilder::new().fgc(Color::DarkCyan).build(),
+                offset: 0,
+            },
+            status_state: text::State {
+                text: Default::default(),
+                style: StyleBuilder::new().fgc(Color::DarkGreen).build(),
+                offset: 0,
+            },
+            copy_path_key: 'y',
+            copy_value_key: 'Y',
+            on_copy: None,
+            keymap: ActiveKeySwitcher::new("default", self::keymap::default as keymap::Keymap)
+                .register("on_query", self::keymap::on_query)
+                .register("on_edit", self::keymap::on_edit),
         }
     }
 
@@ -70,12 +146,118 @@ impl Json {
         self
     }
 
+    /// Sets how rows wider than the terminal are rendered.
+    /// Defaults to `Overflow::TruncateWithEllipsis`.
+    pub fn overflow(mut self, overflow: Overflow) -> Self {
+        self.json_state.overflow = overflow;
+        self
+    }
+
     /// Sets the indentation level for rendering the JSON data.
     pub fn indent(mut self, indent: usize) -> Self {
         self.json_state.formatter.indent = indent;
         self
     }
 
+    /// Sets the string repeated for each indentation level, e.g. `"│ "` for guide lines.
+    /// It takes precedence over `indent`.
+    pub fn indent_unit<T: AsRef<str>>(mut self, unit: T) -> Self {
+        self.json_state.formatter.indent_unit = unit.as_ref().to_string();
+        self
+    }
+
+    /// Sets the styles of all the parts of JSON at once,
+    /// e.g. `JsonTheme::monokai()`. Individual styles can be adjusted afterwards.
+    pub fn theme(mut self, theme: JsonTheme) -> Self {
+        self.json_state.formatter.apply_theme(theme);
+        self
+    }
+
+    /// Sets the style for {}.
+    pub fn curly_brackets_style(mut self, style: ContentStyle) -> Self {
+        self.json_state.formatter.curly_brackets_style = style;
+        self
+    }
+
+    /// Sets the style for [].
+    pub fn square_brackets_style(mut self, style: ContentStyle) -> Self {
+        self.json_state.formatter.square_brackets_style = style;
+        self
+    }
+
+    /// Sets the style for "key".
+    pub fn key_style(mut self, style: ContentStyle) -> Self {
+        self.json_state.formatter.key_style = style;
+        self
+    }
+
+    /// Sets the style for string values.
+    pub fn string_value_style(mut self, style: ContentStyle) -> Self {
+        self.json_state.formatter.string_value_style = style;
+        self
+    }
+
+    /// Sets the style for number values.
+    pub fn number_value_style(mut self, style: ContentStyle) -> Self {
+        self.json_state.formatter.number_value_style = style;
+        self
+    }
+
+    /// Sets the style for boolean values.
+    pub fn boolean_value_style(mut self, style: ContentStyle) -> Self {
+        self.json_state.formatter.boolean_value_style = style;
+        self
+    }
+
+    /// Sets the style for null values.
+    pub fn null_value_style(mut self, style: ContentStyle) -> Self {
+        self.json_state.formatter.null_value_style = style;
+        self
+    }
+
+    /// Sets the style for the substrings matching the search query.
+    pub fn search_highlight_style(mut self, style: ContentStyle) -> Self {
+        self.json_state.search_highlight_style = style;
+        self
+    }
+
+    /// Sets the style for the rows of the visual selection.
+    pub fn visual_style(mut self, style: ContentStyle) -> Self {
+        self.json_state.visual_style = style;
+        self
+    }
+
+    /// Sets the style for the status line of the followed path.
+    pub fn follow_style(mut self, style: ContentStyle) -> Self {
+        self.follow_state.style = style;
+        self
+    }
+
+    /// Sets the style for transient statuses such as a copied path.
+    pub fn status_style(mut self, style: ContentStyle) -> Self {
+        self.status_state.style = style;
+        self
+    }
+
+    /// Sets the key copying the path of the current node (default: `y`).
+    pub fn copy_path_key(mut self, key: char) -> Self {
+        self.copy_path_key = key;
+        self
+    }
+
+    /// Sets the key copying the value of the current node (default: `Y`).
+    pub fn copy_value_key(mut self, key: char) -> Self {
+        self.copy_value_key = key;
+        self
+    }
+
+    /// Sets the callback receiving the copied text when the `clipboard` feature is disabled,
+    /// e.g. to hand it to a native clipboard library.
+    pub fn on_copy(mut self, on_copy: fn(&str)) -> Self {
+        self.on_copy = Some(on_copy);
+        self
+    }
+
     /// Sets the attribute for active (currently selected) items.
     pub fn active_item_attribute(mut self, attr: Attribute) -> Self {
         self.json_state.formatter.active_item_attribute = attr;
@@ -95,12 +277,43 @@ impl Json {
 
     /// Creates a prompt based on the current configuration of the `Json` instance.
     pub fn prompt(self) -> anyhow::Result<Prompt<render::Renderer>> {
-        Ok(Prompt {
-            renderer: render::Renderer {
-                keymap: RefCell::new(self.keymap),
-                title_state: self.title_state,
-                json_state: self.json_state,
-            },
-        })
+        Ok(Prompt::new(self.renderer()))
+    }
+
+    /// Creates a prompt returning the documents as text in the given format,
+    /// e.g. `Format::PathList` for `path = value` lines of the visible rows.
+    pub fn formatted(self, format: Format) -> anyhow::Result<Prompt<formatted::Renderer>> {
+        Ok(Prompt::new(formatted::Renderer {
+            json: self.renderer(),
+            format,
+        }))
+    }
+
+    /// Creates a prompt returning the JSON fragment selected with <kbd>V</kbd>,
+    /// or the value of the current node if nothing is selected, pretty-printed.
+    pub fn select_fragment(self) -> anyhow::Result<Prompt<fragment::Renderer>> {
+        Ok(Prompt::new(fragment::Renderer {
+            json: self.renderer(),
+        }))
+    }
+
+    /// Builds the renderer without creating a prompt.
+    pub fn renderer(self) -> render::Renderer {
+        render::Renderer {
+            keymap: RefCell::new(self.keymap),
+            title_state: self.title_state,
+            json_state: self.json_state,
+            query_editor_state: self.query_editor_state,
+            value_editor_state: self.value_editor_state,
+            hint_message_state: self.hint_message_state,
+            followed_path: None,
+            follow_state: self.follow_state,
+            copy_path_key: self.copy_path_key,
+            copy_value_key: self.copy_value_key,
+            on_copy: self.on_copy,
+            status_state: self.status_state,
+            status_expires_at: None,
+            output: Default::default(),
+        }
     }
 }
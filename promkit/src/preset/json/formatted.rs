@@ -0,0 +1,48 @@
+use std::time::Duration;
+
+use crate::{crossterm::event::Event, jsonz::format, pane::Pane, PromptSignal};
+
+use super::render;
+
+/// A renderer of the JSON preset returning the documents as text, created by `Json::formatted`.
+pub struct Renderer {
+    /// The renderer of the underlying JSON preset.
+    pub json: render::Renderer,
+    /// The format the documents are rendered in.
+    pub format: format::Format,
+}
+
+impl crate::Finalizer for Renderer {
+    /// The documents, including the edited values, rendered in the format.
+    /// In `Format::PathList`, collapsed containers are listed as a whole.
+    type Return = String;
+
+    fn finalize(&mut self) -> anyhow::Result<Self::Return> {
+        Ok(format::render(
+            self.json.json_state.stream.rows(),
+            self.format,
+        ))
+    }
+}
+
+impl crate::Renderer for Renderer {
+    fn create_panes(&self, width: u16, height: u16) -> Vec<Pane> {
+        self.json.create_panes(width, height)
+    }
+
+    fn evaluate(&mut self, event: &Event) -> anyhow::Result<PromptSignal> {
+        self.json.evaluate(event)
+    }
+
+    fn tick_interval(&self) -> Option<Duration> {
+        self.json.tick_interval()
+    }
+
+    fn tick(&mut self) -> anyhow::Result<bool> {
+        self.json.tick()
+    }
+
+    fn take_output(&mut self) -> Option<String> {
+        self.json.take_output()
+    }
+}
@@ -0,0 +1,45 @@
+use std::time::Duration;
+
+use crate::{crossterm::event::Event, pane::Pane, PromptSignal};
+
+use super::render;
+
+/// A renderer of the JSON preset returning the selected fragment, created by `Json::select_fragment`.
+///
+/// The rows selected with <kbd>V</kbd> are expanded to valid JSON (see `jsonz::balance_range`),
+/// and returned pretty-printed on <kbd>Enter</kbd>.
+pub struct Renderer {
+    /// The renderer of the underlying JSON preset.
+    pub json: render::Renderer,
+}
+
+impl crate::Finalizer for Renderer {
+    /// The pretty-printed values of the selection, or of the current node if not selecting.
+    type Return = String;
+
+    fn finalize(&mut self) -> anyhow::Result<Self::Return> {
+        Ok(self.json.fragment())
+    }
+}
+
+impl crate::Renderer for Renderer {
+    fn create_panes(&self, width: u16, height: u16) -> Vec<Pane> {
+        self.json.create_panes(width, height)
+    }
+
+    fn evaluate(&mut self, event: &Event) -> anyhow::Result<PromptSignal> {
+        self.json.evaluate(event)
+    }
+
+    fn tick_interval(&self) -> Option<Duration> {
+        self.json.tick_interval()
+    }
+
+    fn tick(&mut self) -> anyhow::Result<bool> {
+        self.json.tick()
+    }
+
+    fn take_output(&mut self) -> Option<String> {
+        self.json.take_output()
+    }
+}
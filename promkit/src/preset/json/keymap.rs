@@ -3,7 +3,7 @@ use crate::{
         Event, KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers, MouseEvent,
         MouseEventKind,
     },
-    preset, PromptSignal,
+    jsonz, preset, text_editor, Interrupted, PromptSignal,
 };
 
 pub type Keymap = fn(
@@ -20,10 +20,27 @@ pub type Keymap = fn(
 /// | <kbd>↑</kbd>           | Move the cursor up to the previous node
 /// | <kbd>↓</kbd>           | Move the cursor down to the next node
 /// | <kbd>Space</kbd>       | Toggle fold/unfold on the current node
+/// | <kbd>/</kbd>           | Open the query bar to jump to a path or search keys and string values
+/// | <kbd>n</kbd>           | Move the cursor to the next search match
+/// | <kbd>N</kbd>           | Move the cursor to the previous search match
+/// | <kbd>V</kbd>           | Start selecting the rows from the current node, or cancel the selection
+/// | <kbd>Esc</kbd>         | Cancel the selection, or clear the search if not selecting
+/// | <kbd>y</kbd>           | Copy the path of the current node (configurable, see below)
+/// | <kbd>Y</kbd>           | Copy the value of the current node (configurable, see below)
+/// | <kbd>f</kbd>           | Pin the cursor to the path of the current node across updates, or unpin it
+/// | <kbd>e</kbd>           | Edit the value of the current node if it is a scalar
+///
+/// The copy keys are set by `Json::copy_path_key` and `Json::copy_value_key`, and take precedence over the others.
+/// With the `clipboard` feature, the text is copied to the system clipboard by the terminal (OSC 52).
+/// Otherwise, it is passed to `Json::on_copy`, or shown as a hint if that is not set either.
 pub fn default(
     event: &Event,
     renderer: &mut preset::json::render::Renderer,
 ) -> anyhow::Result<PromptSignal> {
+    // Hints and statuses are shown only until the next key press.
+    renderer.hint_message_state.text.clear();
+    renderer.clear_status();
+
     match event {
         Event::Key(KeyEvent {
             code: KeyCode::Enter,
@@ -36,7 +53,21 @@ pub fn default(
             modifiers: KeyModifiers::CONTROL,
             kind: KeyEventKind::Press,
             state: KeyEventState::NONE,
-        }) => return Err(anyhow::anyhow!("ctrl+c")),
+        }) => return Err(Interrupted.into()),
+
+        // Copy path or value
+        Event::Key(KeyEvent {
+            code: KeyCode::Char(ch),
+            modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) if *ch == renderer.copy_path_key => renderer.copy_path(),
+        Event::Key(KeyEvent {
+            code: KeyCode::Char(ch),
+            modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) if *ch == renderer.copy_value_key => renderer.copy_value(),
 
         // Move cursor.
         Event::Key(KeyEvent {
@@ -79,6 +110,349 @@ pub fn default(
             renderer.json_state.stream.toggle();
         }
 
+        // Query
+        Event::Key(KeyEvent {
+            code: KeyCode::Char('/'),
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => {
+            renderer.query_editor_state.texteditor.erase_all();
+            renderer.json_state.search_query.clear();
+            renderer.keymap.borrow_mut().switch("on_query");
+        }
+
+        // Search
+        Event::Key(KeyEvent {
+            code: KeyCode::Char('n'),
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => {
+            let stream = &mut renderer.json_state.stream;
+            let matches = jsonz::search(stream.rows(), &renderer.json_state.search_query);
+            if let Some(index) = matches
+                .iter()
+                .find(|i| **i > stream.position())
+                .or(matches.first())
+            {
+                stream.move_to(*index);
+            }
+        }
+        Event::Key(KeyEvent {
+            code: KeyCode::Char('N'),
+            modifiers: KeyModifiers::SHIFT,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => {
+            let stream = &mut renderer.json_state.stream;
+            let matches = jsonz::search(stream.rows(), &renderer.json_state.search_query);
+            if let Some(index) = matches
+                .iter()
+                .rev()
+                .find(|i| **i < stream.position())
+                .or(matches.last())
+            {
+                stream.move_to(*index);
+            }
+        }
+        Event::Key(KeyEvent {
+            code: KeyCode::Esc,
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) if renderer.json_state.visual_anchor.is_some() => {
+            renderer.json_state.visual_anchor = None;
+        }
+        Event::Key(KeyEvent {
+            code: KeyCode::Esc,
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => {
+            renderer.json_state.search_query.clear();
+        }
+
+        // Visual selection
+        Event::Key(KeyEvent {
+            code: KeyCode::Char('V'),
+            modifiers: KeyModifiers::SHIFT,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => renderer.toggle_visual(),
+
+        // Follow path
+        Event::Key(KeyEvent {
+            code: KeyCode::Char('f'),
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => renderer.toggle_follow(),
+
+        // Edit value
+        Event::Key(KeyEvent {
+            code: KeyCode::Char('e'),
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => renderer.start_edit(),
+
+        _ => (),
+    }
+    Ok(PromptSignal::Continue)
+}
+
+/// Key bindings for the query bar, where a path like `.spec.containers[0].image`
+/// or a text to search in keys and string values is entered.
+/// Search matches are highlighted incrementally while typing.
+///
+/// | Key                    | Action
+/// | :--------------------- | :-------------------------------------------
+/// | <kbd>Enter</kbd>       | Jump to the row of the path, or keep the search and close the query bar, or show a hint if neither is found
+/// | <kbd>Esc</kbd>         | Clear the query and close the query bar
+/// | <kbd>Ctrl + C</kbd>    | Interrupt the current operation
+/// | <kbd>←</kbd>           | Move the cursor one character to the left
+/// | <kbd>→</kbd>           | Move the cursor one character to the right
+/// | <kbd>Ctrl + A</kbd>    | Move the cursor to the start of the line
+/// | <kbd>Ctrl + E</kbd>    | Move the cursor to the end of the line
+/// | <kbd>Backspace</kbd>   | Delete the character before the cursor
+/// | <kbd>Ctrl + U</kbd>    | Delete all characters in the current line
+pub fn on_query(
+    event: &Event,
+    renderer: &mut preset::json::render::Renderer,
+) -> anyhow::Result<PromptSignal> {
+    let query_editor = &mut renderer.query_editor_state;
+
+    match event {
+        Event::Key(KeyEvent {
+            code: KeyCode::Enter,
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => {
+            let query = query_editor.texteditor.text_without_cursor().to_string();
+            let stream = &mut renderer.json_state.stream;
+            if let Some(index) = jsonz::find_by_path(stream.rows(), &query) {
+                stream.move_to(index);
+                renderer.json_state.search_query.clear();
+                renderer.hint_message_state.text.clear();
+                renderer.keymap.borrow_mut().switch("default");
+            } else if !jsonz::search(stream.rows(), &query).is_empty() {
+                renderer.hint_message_state.text.clear();
+                renderer.keymap.borrow_mut().switch("default");
+            } else {
+                renderer.hint_message_state.text = format!("No such path or match: {}", query);
+            }
+            return Ok(PromptSignal::Continue);
+        }
+        Event::Key(KeyEvent {
+            code: KeyCode::Esc,
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => {
+            query_editor.texteditor.erase_all();
+            renderer.json_state.search_query.clear();
+            renderer.hint_message_state.text.clear();
+            renderer.keymap.borrow_mut().switch("default");
+            return Ok(PromptSignal::Continue);
+        }
+        Event::Key(KeyEvent {
+            code: KeyCode::Char('c'),
+            modifiers: KeyModifiers::CONTROL,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => return Err(Interrupted.into()),
+
+        // Move cursor.
+        Event::Key(KeyEvent {
+            code: KeyCode::Left,
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => {
+            query_editor.texteditor.backward();
+        }
+        Event::Key(KeyEvent {
+            code: KeyCode::Right,
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => {
+            query_editor.texteditor.forward();
+        }
+        Event::Key(KeyEvent {
+            code: KeyCode::Char('a'),
+            modifiers: KeyModifiers::CONTROL,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => query_editor.texteditor.move_to_head(),
+        Event::Key(KeyEvent {
+            code: KeyCode::Char('e'),
+            modifiers: KeyModifiers::CONTROL,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => query_editor.texteditor.move_to_tail(),
+
+        // Erase char(s).
+        Event::Key(KeyEvent {
+            code: KeyCode::Backspace,
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => query_editor.texteditor.erase(),
+        Event::Key(KeyEvent {
+            code: KeyCode::Char('u'),
+            modifiers: KeyModifiers::CONTROL,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => query_editor.texteditor.erase_all(),
+
+        // Input char.
+        Event::Key(KeyEvent {
+            code: KeyCode::Char(ch),
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        })
+        | Event::Key(KeyEvent {
+            code: KeyCode::Char(ch),
+            modifiers: KeyModifiers::SHIFT,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => match query_editor.edit_mode {
+            text_editor::Mode::Insert => query_editor.texteditor.insert(*ch),
+            text_editor::Mode::Overwrite => query_editor.texteditor.overwrite(*ch),
+        },
+
+        _ => (),
+    }
+
+    // Incrementally search with the current query,
+    // moving the cursor to the nearest match from the current position.
+    let query = query_editor.texteditor.text_without_cursor().to_string();
+    if query != renderer.json_state.search_query {
+        let stream = &mut renderer.json_state.stream;
+        let matches = jsonz::search(stream.rows(), &query);
+        if let Some(index) = matches
+            .iter()
+            .find(|i| **i >= stream.position())
+            .or(matches.first())
+        {
+            stream.move_to(*index);
+        }
+        renderer.json_state.search_query = query;
+        renderer.hint_message_state.text.clear();
+    }
+
+    Ok(PromptSignal::Continue)
+}
+
+/// Key bindings for the value editor, where the new value of the current node
+/// is entered as JSON, e.g. `"text"`, `42`, `true` or `null`.
+///
+/// | Key                    | Action
+/// | :--------------------- | :-------------------------------------------
+/// | <kbd>Enter</kbd>       | Write the value back and close the editor, or show a hint if it is not a JSON scalar
+/// | <kbd>Esc</kbd>         | Discard the input and close the editor
+/// | <kbd>Ctrl + C</kbd>    | Interrupt the current operation
+/// | <kbd>←</kbd>           | Move the cursor one character to the left
+/// | <kbd>→</kbd>           | Move the cursor one character to the right
+/// | <kbd>Ctrl + A</kbd>    | Move the cursor to the start of the line
+/// | <kbd>Ctrl + E</kbd>    | Move the cursor to the end of the line
+/// | <kbd>Backspace</kbd>   | Delete the character before the cursor
+/// | <kbd>Ctrl + U</kbd>    | Delete all characters in the current line
+pub fn on_edit(
+    event: &Event,
+    renderer: &mut preset::json::render::Renderer,
+) -> anyhow::Result<PromptSignal> {
+    let value_editor = &mut renderer.value_editor_state;
+
+    match event {
+        Event::Key(KeyEvent {
+            code: KeyCode::Enter,
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => renderer.commit_edit(),
+        Event::Key(KeyEvent {
+            code: KeyCode::Esc,
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => {
+            value_editor.texteditor.erase_all();
+            renderer.hint_message_state.text.clear();
+            renderer.keymap.borrow_mut().switch("default");
+        }
+        Event::Key(KeyEvent {
+            code: KeyCode::Char('c'),
+            modifiers: KeyModifiers::CONTROL,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => return Err(Interrupted.into()),
+
+        // Move cursor.
+        Event::Key(KeyEvent {
+            code: KeyCode::Left,
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => {
+            value_editor.texteditor.backward();
+        }
+        Event::Key(KeyEvent {
+            code: KeyCode::Right,
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => {
+            value_editor.texteditor.forward();
+        }
+        Event::Key(KeyEvent {
+            code: KeyCode::Char('a'),
+            modifiers: KeyModifiers::CONTROL,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => value_editor.texteditor.move_to_head(),
+        Event::Key(KeyEvent {
+            code: KeyCode::Char('e'),
+            modifiers: KeyModifiers::CONTROL,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => value_editor.texteditor.move_to_tail(),
+
+        // Erase char(s).
+        Event::Key(KeyEvent {
+            code: KeyCode::Backspace,
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => value_editor.texteditor.erase(),
+        Event::Key(KeyEvent {
+            code: KeyCode::Char('u'),
+            modifiers: KeyModifiers::CONTROL,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => value_editor.texteditor.erase_all(),
+
+        // Input char.
+        Event::Key(KeyEvent {
+            code: KeyCode::Char(ch),
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        })
+        | Event::Key(KeyEvent {
+            code: KeyCode::Char(ch),
+            modifiers: KeyModifiers::SHIFT,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => match value_editor.edit_mode {
+            text_editor::Mode::Insert => value_editor.texteditor.insert(*ch),
+            text_editor::Mode::Overwrite => value_editor.texteditor.overwrite(*ch),
+        },
+
         _ => (),
     }
     Ok(PromptSignal::Continue)
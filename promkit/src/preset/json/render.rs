@@ -1,8 +1,11 @@
-use std::cell::RefCell;
+use std::{
+    cell::RefCell,
+    time::{Duration, Instant},
+};
 
 use crate::{
-    crossterm::event::Event, jsonstream, pane::Pane, switch::ActiveKeySwitcher, text, PaneFactory,
-    PromptSignal,
+    crossterm::event::Event, jsonstream, jsonz, pane::Pane, switch::ActiveKeySwitcher, text,
+    text_editor, PaneFactory, PromptSignal,
 };
 
 use super::keymap;
@@ -16,20 +19,228 @@ pub struct Renderer {
     pub title_state: text::State,
     /// A renderer used for JSON content.
     pub json_state: jsonstream::State,
+    /// A renderer used for the query bar (path or search), shown only while querying.
+    pub query_editor_state: text_editor::State,
+    /// A renderer used for editing the value of a leaf row, shown only while editing.
+    pub value_editor_state: text_editor::State,
+    /// A renderer used for hints such as an invalid path.
+    pub hint_message_state: text::State,
+    /// The path the cursor is pinned to across updates, if any.
+    pub followed_path: Option<String>,
+    /// A renderer used for the status of the followed path and its current value.
+    pub follow_state: text::State,
+    /// The key copying the path of the current node.
+    pub copy_path_key: char,
+    /// The key copying the value of the current node.
+    pub copy_value_key: char,
+    /// Called with the copied text if the `clipboard` feature is disabled.
+    pub on_copy: Option<fn(&str)>,
+    /// A renderer used for transient statuses such as a copied path.
+    pub status_state: text::State,
+    /// When the status disappears, unless a key is pressed before.
+    pub status_expires_at: Option<Instant>,
+    /// The escape sequences to write to the terminal before the next draw.
+    pub output: String,
+}
+
+/// How long a transient status is shown.
+const STATUS_DURATION: Duration = Duration::from_secs(2);
+
+impl Renderer {
+    /// Pins the cursor to the path of the current node, or unpins it if already pinned.
+    pub fn toggle_follow(&mut self) {
+        if self.followed_path.take().is_none() {
+            let stream = &self.json_state.stream;
+            self.followed_path = Some(jsonz::path_of(stream.rows(), stream.position()));
+        }
+        self.refresh_follow_status();
+    }
+
+    /// Replaces the JSON values with the updated ones, keeping the folded containers folded.
+    /// The cursor moves to the followed path if any (unfolding its ancestors),
+    /// otherwise it stays on the same path as far as possible.
+    /// If the followed path disappeared, a warning is shown until it appears again,
+    /// and the cursor stays around its last position.
+    pub fn update<'a, I: IntoIterator<Item = &'a serde_json::Value>>(&mut self, iter: I) {
+        let stream = &mut self.json_state.stream;
+        stream.replace(iter);
+        // The rows of the selection are gone.
+        self.json_state.visual_anchor = None;
+        if let Some(path) = &self.followed_path {
+            match jsonz::find_by_path(stream.rows(), path) {
+                Some(index) => {
+                    stream.move_to(index);
+                    self.hint_message_state.text.clear();
+                }
+                None => {
+                    self.hint_message_state.text = format!("Followed path disappeared: {}", path);
+                }
+            }
+        }
+        self.refresh_follow_status();
+    }
+
+    /// Opens the value editor pre-filled with the value of the current row as JSON,
+    /// or shows a hint if the row is not a leaf.
+    pub fn start_edit(&mut self) {
+        let stream = &self.json_state.stream;
+        match stream.rows().get(stream.position()).map(|row| &row.v) {
+            Some(
+                jsonz::Value::Null
+                | jsonz::Value::Boolean(_)
+                | jsonz::Value::Number(_)
+                | jsonz::Value::String(_),
+            ) => {
+                let value = jsonz::value_at(stream.rows(), stream.position()).unwrap_or_default();
+                self.value_editor_state.texteditor =
+                    text_editor::TextEditor::new(value.to_string());
+                self.keymap.borrow_mut().switch("on_edit");
+            }
+            _ => {
+                self.hint_message_state.text = String::from("Only scalar values can be edited");
+            }
+        }
+    }
+
+    /// Writes the value in the editor back to the current row if it is a JSON scalar,
+    /// and closes the editor. Otherwise, a hint is shown and the editor is kept open.
+    pub fn commit_edit(&mut self) {
+        let text = self
+            .value_editor_state
+            .texteditor
+            .text_without_cursor()
+            .to_string();
+        let stream = &mut self.json_state.stream;
+        match serde_json::from_str::<serde_json::Value>(&text) {
+            Ok(value) if !value.is_array() && !value.is_object() => {
+                let position = stream.position();
+                stream.set_value(position, value);
+                self.hint_message_state.text.clear();
+                self.keymap.borrow_mut().switch("default");
+                self.refresh_follow_status();
+            }
+            _ => {
+                self.hint_message_state.text = format!("Not a JSON scalar: {}", text);
+            }
+        }
+    }
+
+    /// Starts the visual selection at the current node, or cancels it if already selecting.
+    pub fn toggle_visual(&mut self) {
+        self.json_state.visual_anchor = match self.json_state.visual_anchor {
+            Some(_) => None,
+            None => Some(self.json_state.stream.position()),
+        };
+    }
+
+    /// Returns the values of the visual selection, or of the current node if not selecting,
+    /// pretty-printed with the indentation of the formatter.
+    /// Multiple documents are separated by newlines.
+    pub fn fragment(&self) -> String {
+        let stream = &self.json_state.stream;
+        let range = self.json_state.selection().unwrap_or_else(|| {
+            jsonz::balance_range(stream.rows(), stream.position(), stream.position())
+        });
+        jsonz::format::render(
+            &jsonz::values_in_range(stream.rows(), range)[..],
+            jsonz::format::Format::Pretty {
+                indent: self.json_state.formatter.indent,
+            },
+        )
+    }
+
+    /// Copies the path of the current node, e.g. `.spec.containers[0].image`.
+    pub fn copy_path(&mut self) {
+        let stream = &self.json_state.stream;
+        let path = jsonz::path_of(stream.rows(), stream.position());
+        self.copy(&path, format!("Copied {}", path));
+    }
+
+    /// Copies the value of the current node,
+    /// where a string is copied without quotes and the others as compact JSON.
+    pub fn copy_value(&mut self) {
+        let stream = &self.json_state.stream;
+        let path = jsonz::path_of(stream.rows(), stream.position());
+        let text = match jsonz::value_at(stream.rows(), stream.position()) {
+            Some(serde_json::Value::String(s)) => s,
+            Some(value) => value.to_string(),
+            None => return,
+        };
+        self.copy(&text, format!("Copied the value of {}", path));
+    }
+
+    /// Copies the text to the clipboard with the `clipboard` feature, otherwise passes it to `on_copy`,
+    /// and shows the status until it expires or the next key is pressed.
+    /// If neither is available, the text itself is shown as a hint instead.
+    fn copy(&mut self, text: &str, status: String) {
+        if !self.copy_to_clipboard(text) {
+            match self.on_copy {
+                Some(on_copy) => on_copy(text),
+                None => {
+                    self.hint_message_state.text = text.to_string();
+                    return;
+                }
+            }
+        }
+        self.status_state.text = status;
+        self.status_expires_at = Some(Instant::now() + STATUS_DURATION);
+    }
+
+    #[cfg(feature = "clipboard")]
+    fn copy_to_clipboard(&mut self, text: &str) -> bool {
+        self.output
+            .push_str(&crate::clipboard::osc52_sequence(text));
+        true
+    }
+
+    #[cfg(not(feature = "clipboard"))]
+    fn copy_to_clipboard(&mut self, _text: &str) -> bool {
+        false
+    }
+
+    /// Clears the transient status.
+    pub fn clear_status(&mut self) {
+        self.status_state.text.clear();
+        self.status_expires_at = None;
+    }
+
+    fn refresh_follow_status(&mut self) {
+        self.follow_state.text = match &self.followed_path {
+            Some(path) => {
+                let rows = self.json_state.stream.rows();
+                let value = jsonz::find_by_path(rows, path)
+                    .and_then(|index| jsonz::value_at(rows, index))
+                    .map(|value| value.to_string())
+                    .unwrap_or_else(|| String::from("(missing)"));
+                format!("Following {}: {}", path, value)
+            }
+            None => String::new(),
+        };
+    }
 }
 
 impl crate::Finalizer for Renderer {
-    type Return = ();
+    /// The documents, including the edited values.
+    type Return = Vec<serde_json::Value>;
 
     fn finalize(&mut self) -> anyhow::Result<Self::Return> {
-        Ok(())
+        Ok(self.json_state.stream.to_value())
     }
 }
 
 impl crate::Renderer for Renderer {
     fn create_panes(&self, width: u16, height: u16) -> Vec<Pane> {
+        let query_editor_pane = match self.keymap.borrow().active_key() {
+            "on_query" => self.query_editor_state.create_pane(width, height),
+            "on_edit" => self.value_editor_state.create_pane(width, height),
+            _ => Pane::new(vec![], 0),
+        };
         vec![
             self.title_state.create_pane(width, height),
+            query_editor_pane,
+            self.hint_message_state.create_pane(width, height),
+            self.status_state.create_pane(width, height),
+            self.follow_state.create_pane(width, height),
             self.json_state.create_pane(width, height),
         ]
     }
@@ -38,4 +249,319 @@ impl crate::Renderer for Renderer {
         let keymap = *self.keymap.borrow_mut().get();
         keymap(event, self)
     }
+
+    fn tick_interval(&self) -> Option<Duration> {
+        self.status_expires_at.map(|_| Duration::from_millis(100))
+    }
+
+    fn tick(&mut self) -> anyhow::Result<bool> {
+        match self.status_expires_at {
+            Some(at) if at <= Instant::now() => {
+                self.clear_status();
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    fn take_output(&mut self) -> Option<String> {
+        (!self.output.is_empty()).then(|| std::mem::take(&mut self.output))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+        jsonstream::JsonStream,
+        preset::json::Json,
+        Renderer as _,
+    };
+
+    use super::*;
+
+    fn renderer(value: &serde_json::Value) -> Renderer {
+        Json::new(JsonStream::new([value])).renderer()
+    }
+
+    mod update {
+        use super::*;
+
+        #[test]
+        fn test_follows_path() {
+            let mut renderer = renderer(&serde_json::json!({"a": {"b": 1}, "c": 2}));
+            renderer.json_state.stream.move_to(2);
+            renderer
+                .evaluate(&Event::Key(KeyEvent::new(
+                    KeyCode::Char('f'),
+                    KeyModifiers::NONE,
+                )))
+                .unwrap();
+            assert_eq!(Some(String::from(".a.b")), renderer.followed_path);
+            assert_eq!("Following .a.b: 1", renderer.follow_state.text);
+
+            // The cursor moves along with the path, unfolding its ancestors.
+            let mut updated = serde_json::json!({"x": [1, 2], "a": {"z": 0, "b": 42}});
+            renderer.update([&updated]);
+            assert_eq!(7, renderer.json_state.stream.position());
+            assert_eq!("Following .a.b: 42", renderer.follow_state.text);
+
+            // The cursor stays around if the path disappears.
+            updated = serde_json::json!({"a": {}});
+            renderer.update([&updated]);
+            assert_eq!(2, renderer.json_state.stream.position());
+            assert_eq!(
+                "Followed path disappeared: .a.b",
+                renderer.hint_message_state.text
+            );
+            assert_eq!("Following .a.b: (missing)", renderer.follow_state.text);
+
+            // The warning goes away once the path appears again.
+            updated = serde_json::json!({"a": {"b": 3}});
+            renderer.update([&updated]);
+            assert_eq!(2, renderer.json_state.stream.position());
+            assert!(renderer.hint_message_state.text.is_empty());
+
+            // Unpin.
+            renderer.toggle_follow();
+            assert_eq!(None, renderer.followed_path);
+            assert!(renderer.follow_state.text.is_empty());
+        }
+    }
+
+    mod edit {
+        use crate::Finalizer as _;
+
+        use super::*;
+
+        fn key(code: KeyCode) -> Event {
+            Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+        }
+
+        fn type_text(renderer: &mut Renderer, text: &str) {
+            for ch in text.chars() {
+                renderer.evaluate(&key(KeyCode::Char(ch))).unwrap();
+            }
+        }
+
+        #[test]
+        fn test_edit_leaf() {
+            let mut renderer = renderer(&serde_json::json!({"name": "a", "n": 1}));
+            renderer.json_state.stream.move_to(1);
+            renderer.evaluate(&key(KeyCode::Char('e'))).unwrap();
+            assert_eq!("on_edit", renderer.keymap.borrow().active_key());
+            assert_eq!(
+                "\"a\"",
+                renderer
+                    .value_editor_state
+                    .texteditor
+                    .text_without_cursor()
+                    .to_string()
+            );
+
+            renderer
+                .evaluate(&Event::Key(KeyEvent::new(
+                    KeyCode::Char('u'),
+                    KeyModifiers::CONTROL,
+                )))
+                .unwrap();
+            // Containers are rejected, keeping the editor open.
+            type_text(&mut renderer, "[1]");
+            renderer.evaluate(&key(KeyCode::Enter)).unwrap();
+            assert_eq!("on_edit", renderer.keymap.borrow().active_key());
+            assert_eq!("Not a JSON scalar: [1]", renderer.hint_message_state.text);
+
+            for _ in 0..3 {
+                renderer.evaluate(&key(KeyCode::Backspace)).unwrap();
+            }
+            type_text(&mut renderer, "\"b\"");
+            renderer.evaluate(&key(KeyCode::Enter)).unwrap();
+            assert_eq!("default", renderer.keymap.borrow().active_key());
+            assert_eq!(
+                vec![serde_json::json!({"name": "b", "n": 1})],
+                renderer.finalize().unwrap()
+            );
+        }
+
+        #[test]
+        fn test_edit_container() {
+            let mut renderer = renderer(&serde_json::json!({"a": [1]}));
+            renderer.evaluate(&key(KeyCode::Char('e'))).unwrap();
+            assert_eq!("default", renderer.keymap.borrow().active_key());
+            assert_eq!(
+                "Only scalar values can be edited",
+                renderer.hint_message_state.text
+            );
+        }
+    }
+
+    mod fragment {
+        use crate::crossterm::style::Color;
+
+        use super::*;
+
+        fn key(code: KeyCode) -> Event {
+            Event::Key(KeyEvent::new(
+                code,
+                match code {
+                    KeyCode::Char(ch) if ch.is_ascii_uppercase() => KeyModifiers::SHIFT,
+                    _ => KeyModifiers::NONE,
+                },
+            ))
+        }
+
+        #[test]
+        fn test_visual_selection() {
+            // 0 {, 1 "a": 1, 2 "b": [, 3 10, 4 20, 5 ], 6 "c": true, 7 }
+            let mut renderer = renderer(&serde_json::json!({"a": 1, "b": [10, 20], "c": true}));
+            renderer.json_state.stream.move_to(3);
+            renderer.evaluate(&key(KeyCode::Char('V'))).unwrap();
+            assert_eq!("10", renderer.fragment());
+
+            // The elements are widened to the array.
+            renderer.evaluate(&key(KeyCode::Down)).unwrap();
+            assert_eq!(Some(2..=5), renderer.json_state.selection());
+            assert_eq!("[\n  10,\n  20\n]", renderer.fragment());
+            // The rows from the cursor on are styled if selected.
+            let rows = renderer.json_state.create_pane(20, 10).extract(10);
+            let background = |row: usize| rows[row].iter().last().unwrap().style().background_color;
+            assert_eq!(Some(Color::DarkBlue), background(1));
+            assert_eq!(None, background(2));
+
+            // Esc cancels the selection, leaving the current node.
+            renderer.evaluate(&key(KeyCode::Esc)).unwrap();
+            assert_eq!(None, renderer.json_state.selection());
+            assert_eq!("20", renderer.fragment());
+        }
+
+        #[test]
+        fn test_select_fragment() {
+            let value = serde_json::json!({"a": {"b": 1}, "c": 2});
+            let mut p = Json::new(JsonStream::new([&value]))
+                .indent(4)
+                .select_fragment()
+                .unwrap();
+            let mut terminal = crate::terminal::TestTerminal::new(20, 10);
+            let ret = p
+                .run_with_events(
+                    [
+                        key(KeyCode::Down),
+                        key(KeyCode::Char('V')),
+                        key(KeyCode::Enter),
+                    ],
+                    &mut terminal,
+                )
+                .unwrap();
+            assert_eq!("{\n    \"b\": 1\n}", ret);
+        }
+    }
+
+    mod copy {
+        use super::*;
+
+        fn key(ch: char) -> Event {
+            Event::Key(KeyEvent::new(
+                KeyCode::Char(ch),
+                if ch.is_ascii_uppercase() {
+                    KeyModifiers::SHIFT
+                } else {
+                    KeyModifiers::NONE
+                },
+            ))
+        }
+
+        fn document() -> serde_json::Value {
+            serde_json::json!({"spec": {"containers": [{"image": "nginx:1.25"}]}})
+        }
+
+        #[cfg(feature = "clipboard")]
+        #[test]
+        fn test_osc52() {
+            let mut renderer = renderer(&document());
+            renderer.json_state.stream.move_to(4);
+            renderer.evaluate(&key('y')).unwrap();
+            assert_eq!(
+                "Copied .spec.containers[0].image",
+                renderer.status_state.text
+            );
+            assert_eq!(
+                Some(crate::clipboard::osc52_sequence(
+                    ".spec.containers[0].image"
+                )),
+                renderer.take_output()
+            );
+            assert_eq!(None, renderer.take_output());
+
+            renderer.evaluate(&key('Y')).unwrap();
+            assert_eq!(
+                Some(crate::clipboard::osc52_sequence("nginx:1.25")),
+                renderer.take_output()
+            );
+        }
+
+        #[cfg(not(feature = "clipboard"))]
+        #[test]
+        fn test_on_copy() {
+            thread_local! {
+                static COPIED: RefCell<Vec<String>> = const { RefCell::new(vec![]) };
+            }
+            let value = document();
+            let mut renderer = Json::new(JsonStream::new([&value]))
+                .copy_value_key('v')
+                .on_copy(|text| COPIED.with(|copied| copied.borrow_mut().push(text.to_string())))
+                .renderer();
+            renderer.json_state.stream.move_to(4);
+            renderer.evaluate(&key('y')).unwrap();
+            renderer.evaluate(&key('v')).unwrap();
+            assert_eq!(
+                vec![".spec.containers[0].image", "nginx:1.25"],
+                COPIED.with(|copied| copied.borrow().clone())
+            );
+            assert_eq!(
+                "Copied the value of .spec.containers[0].image",
+                renderer.status_state.text
+            );
+            assert_eq!(None, renderer.take_output());
+        }
+
+        #[cfg(not(feature = "clipboard"))]
+        #[test]
+        fn test_without_clipboard() {
+            let mut renderer = renderer(&document());
+            renderer.json_state.stream.move_to(4);
+            renderer.evaluate(&key('y')).unwrap();
+            assert_eq!(
+                ".spec.containers[0].image",
+                renderer.hint_message_state.text
+            );
+            assert!(renderer.status_state.text.is_empty());
+        }
+
+        #[test]
+        fn test_status_disappears() {
+            let mut renderer = renderer(&document());
+            renderer.status_state.text = String::from("Copied .spec");
+            renderer.status_expires_at = Some(Instant::now() + Duration::from_secs(60));
+            assert!(renderer.tick_interval().is_some());
+            assert!(!renderer.tick().unwrap());
+
+            // After the delay.
+            renderer.status_expires_at = Some(Instant::now());
+            assert!(renderer.tick().unwrap());
+            assert!(renderer.status_state.text.is_empty());
+            assert_eq!(None, renderer.tick_interval());
+
+            // On the next key press.
+            renderer.status_state.text = String::from("Copied .spec");
+            renderer.status_expires_at = Some(Instant::now() + Duration::from_secs(60));
+            renderer
+                .evaluate(&Event::Key(KeyEvent::new(
+                    KeyCode::Down,
+                    KeyModifiers::NONE,
+                )))
+                .unwrap();
+            assert!(renderer.status_state.text.is_empty());
+            assert_eq!(None, renderer.status_expires_at);
+        }
+    }
 }
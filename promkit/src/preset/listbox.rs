@@ -2,25 +2,33 @@ use std::{cell::RefCell, fmt::Display};
 
 use crate::{
     crossterm::style::{Attribute, Attributes, Color, ContentStyle},
+    key_bindings::KeyBindings,
     listbox,
     style::StyleBuilder,
     switch::ActiveKeySwitcher,
-    text, Prompt,
+    text, Overflow, PartialItem, Prompt,
 };
 
 pub mod keymap;
 pub mod render;
 
 /// A component for creating and managing a selectable list of options.
-pub struct Listbox {
-    keymap: ActiveKeySwitcher<keymap::Keymap>,
+///
+/// The type parameter `V` is the value returned for the selected item,
+/// which defaults to the displayed text.
+pub struct Listbox<V = String> {
+    keymap: ActiveKeySwitcher<keymap::Keymap<V>>,
+    /// Keys bound to the actions of the default keymap.
+    key_bindings: KeyBindings<keymap::Action>,
     /// State for the title displayed above the selectable list.
     title_state: text::State,
     /// State for the selectable list itself.
     listbox_state: listbox::State,
+    /// Values returned for the items, in the same order as the items.
+    values: Vec<V>,
 }
 
-impl Listbox {
+impl Listbox<String> {
     /// Constructs a new `Listbox` instance
     /// with a list of items to be displayed as selectable options.
     ///
@@ -29,21 +37,45 @@ impl Listbox {
     /// * `items` - An iterator over items
     ///   that implement the `Display` trait, to be used as options.
     pub fn new<T: Display, I: IntoIterator<Item = T>>(items: I) -> Self {
+        let labels: Vec<String> = items.into_iter().map(|item| item.to_string()).collect();
+        Listbox::from_entries(labels.into_iter().map(|label| (label.clone(), label)))
+    }
+}
+
+impl<V: Clone> Listbox<V> {
+    /// Constructs a new `Listbox` instance from pairs of a value and its displayed text.
+    /// The value of the selected item is returned when the prompt is submitted.
+    ///
+    /// # Arguments
+    ///
+    /// * `entries` - An iterator over `(value, text)` pairs.
+    pub fn from_entries<L: Display, I: IntoIterator<Item = (V, L)>>(entries: I) -> Self {
+        let (values, labels): (Vec<V>, Vec<L>) = entries.into_iter().unzip();
         Self {
             title_state: text::State {
                 text: Default::default(),
                 style: StyleBuilder::new()
                     .attrs(Attributes::from(Attribute::Bold))
                     .build(),
+                offset: 0,
             },
             listbox_state: listbox::State {
-                listbox: listbox::Listbox::from_displayable(items),
+                listbox: listbox::Listbox::from_displayable(labels),
                 cursor: String::from("❯ "),
                 active_item_style: Some(StyleBuilder::new().fgc(Color::DarkCyan).build()),
                 inactive_item_style: Some(StyleBuilder::new().build()),
+                description_style: StyleBuilder::new()
+                    .fgc(Color::DarkGrey)
+                    .attrs(Attributes::from(Attribute::Dim))
+                    .build(),
+                overflow: Default::default(),
+                item_lines: None,
+                partial_item: Default::default(),
                 lines: Default::default(),
             },
-            keymap: ActiveKeySwitcher::new("default", self::keymap::default),
+            keymap: ActiveKeySwitcher::new("default", self::keymap::default as keymap::Keymap<V>),
+            key_bindings: Default::default(),
+            values,
         }
     }
 
@@ -60,11 +92,16 @@ impl Listbox {
     }
 
     /// Sets the cursor symbol used to indicate the current selection.
-    pub fn cursor<T: AsRef<str>>(mut self, cursor: T) -> Self {
+    pub fn cursor_symbol<T: AsRef<str>>(mut self, cursor: T) -> Self {
         self.listbox_state.cursor = cursor.as_ref().to_string();
         self
     }
 
+    #[deprecated(since = "0.7.0", note = "renamed to `cursor_symbol`")]
+    pub fn cursor<T: AsRef<str>>(self, cursor: T) -> Self {
+        self.cursor_symbol(cursor)
+    }
+
     /// Sets the style for active (currently selected) items.
     pub fn active_item_style(mut self, style: ContentStyle) -> Self {
         self.listbox_state.active_item_style = Some(style);
@@ -77,13 +114,52 @@ impl Listbox {
         self
     }
 
+    /// Sets how items exceeding the terminal width are rendered.
+    pub fn overflow(mut self, overflow: Overflow) -> Self {
+        self.listbox_state.overflow = overflow;
+        self
+    }
+
+    /// Sets the maximum number of rows of a wrapped item, e.g. for items of a few sentences.
+    /// The last row of a cut item ends with `…`.
+    pub fn item_lines(mut self, lines: usize) -> Self {
+        self.listbox_state.item_lines = Some(lines);
+        self
+    }
+
+    /// Sets whether an item not fitting at the bottom of the list is clipped or omitted.
+    pub fn partial_item(mut self, partial_item: PartialItem) -> Self {
+        self.listbox_state.partial_item = partial_item;
+        self
+    }
+
+    /// Sets whether moving down from the last item goes to the first one and vice versa.
+    pub fn cyclic(mut self, cyclic: bool) -> Self {
+        self.listbox_state.listbox.set_cyclic(cyclic);
+        self
+    }
+
+    /// Sets whether the cursor is shown and the items can be navigated (`true` by default).
+    /// If not, the list is only displayed until Enter is pressed.
+    pub fn interactive(mut self, interactive: bool) -> Self {
+        self.listbox_state.listbox.set_interactive(interactive);
+        self
+    }
+
     /// Sets the number of lines to be used for displaying the selectable list.
     pub fn listbox_lines(mut self, lines: usize) -> Self {
         self.listbox_state.lines = Some(lines);
         self
     }
 
-    pub fn register_keymap<K: AsRef<str>>(mut self, key: K, handler: keymap::Keymap) -> Self {
+    /// Sets the keys bound to the actions of the default keymap,
+    /// e.g. `KeyBindings::default().bind(KeyCode::Esc, keymap::Action::Interrupt)`.
+    pub fn key_bindings(mut self, key_bindings: KeyBindings<keymap::Action>) -> Self {
+        self.key_bindings = key_bindings;
+        self
+    }
+
+    pub fn register_keymap<K: AsRef<str>>(mut self, key: K, handler: keymap::Keymap<V>) -> Self {
         self.keymap = self.keymap.register(key, handler);
         self
     }
@@ -91,13 +167,24 @@ impl Listbox {
     /// Displays the select prompt and waits for user input.
     /// Returns a `Result` containing the `Prompt` result,
     /// which is the selected option.
-    pub fn prompt(self) -> anyhow::Result<Prompt<render::Renderer>> {
-        Ok(Prompt {
-            renderer: render::Renderer {
-                keymap: RefCell::new(self.keymap),
-                title_state: self.title_state,
-                listbox_state: self.listbox_state,
-            },
-        })
+    ///
+    /// Submitting a listbox without items returns the "no item is selected" error,
+    /// since there is no value to return (it used to return an empty string).
+    pub fn prompt(self) -> anyhow::Result<Prompt<render::Renderer<V>>> {
+        Ok(Prompt::new(self.renderer()))
+    }
+
+    /// Builds the renderer without creating a prompt,
+    /// e.g. to be used as a step of `Wizard`.
+    pub fn renderer(self) -> render::Renderer<V> {
+        render::Renderer {
+            keymap: RefCell::new(self.keymap),
+            key_bindings: self.key_bindings,
+            title_state: self.title_state,
+            listbox_state: self.listbox_state,
+            values: self.values,
+            listbox_area: Default::default(),
+            clicks: Default::default(),
+        }
     }
 }
@@ -1,17 +1,52 @@
 use crate::{
-    crossterm::event::{
-        Event, KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers, MouseEvent,
-        MouseEventKind,
-    },
-    preset, PromptSignal,
+    crossterm::event::{Event, KeyCode, KeyModifiers, MouseButton, MouseEvent, MouseEventKind},
+    key_bindings::{ctrl, KeyBindings},
+    preset, Interrupted, PromptSignal,
 };
 
-pub type Keymap = fn(
+pub type Keymap<V = String> = fn(
     event: &Event,
-    renderer: &mut preset::listbox::render::Renderer,
+    renderer: &mut preset::listbox::render::Renderer<V>,
 ) -> anyhow::Result<PromptSignal>;
 
-/// Default key bindings for the listbox.
+/// Actions of the listbox, bound to keys by `KeyBindings<Action>`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// Exit the listbox with the selected item.
+    Accept,
+    /// Interrupt the current operation.
+    Interrupt,
+    /// Move the selection up.
+    MoveUp,
+    /// Move the selection down.
+    MoveDown,
+    /// Move the selection up by a page.
+    PageUp,
+    /// Move the selection down by a page.
+    PageDown,
+    /// Move the selection to the first item.
+    MoveToHead,
+    /// Move the selection to the last item.
+    MoveToTail,
+}
+
+impl Default for KeyBindings<Action> {
+    /// Returns the bindings listed in `default`.
+    fn default() -> Self {
+        KeyBindings::empty()
+            .bind(KeyCode::Enter, Action::Accept)
+            .bind(ctrl('c'), Action::Interrupt)
+            .bind(KeyCode::Up, Action::MoveUp)
+            .bind(KeyCode::Down, Action::MoveDown)
+            .bind(KeyCode::PageUp, Action::PageUp)
+            .bind(KeyCode::PageDown, Action::PageDown)
+            .bind(KeyCode::Home, Action::MoveToHead)
+            .bind(KeyCode::End, Action::MoveToTail)
+    }
+}
+
+/// Default key bindings for the listbox,
+/// evaluating the keys through the `KeyBindings` of the renderer.
 ///
 /// | Key                    | Action
 /// | :--------------------- | :-------------------------------------------
@@ -19,32 +54,51 @@ pub type Keymap = fn(
 /// | <kbd>Ctrl + C</kbd>    | Interrupt the current operation
 /// | <kbd>↑</kbd>           | Move the selection up
 /// | <kbd>↓</kbd>           | Move the selection down
-pub fn default(
+/// | <kbd>PageUp</kbd>      | Move the selection up by the rows of the list
+/// | <kbd>PageDown</kbd>    | Move the selection down by the rows of the list
+/// | <kbd>Home</kbd>        | Move the selection to the first item
+/// | <kbd>End</kbd>         | Move the selection to the last item
+/// | Wheel                  | Move the selection, by the rows of the list with <kbd>Shift</kbd>
+/// | Left click             | Select the clicked item, or exit with it on a double click (with `Prompt::mouse_capture`)
+pub fn default<V>(
     event: &Event,
-    renderer: &mut preset::listbox::render::Renderer,
+    renderer: &mut preset::listbox::render::Renderer<V>,
 ) -> anyhow::Result<PromptSignal> {
-    match event {
-        Event::Key(KeyEvent {
-            code: KeyCode::Enter,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => return Ok(PromptSignal::Quit),
-        Event::Key(KeyEvent {
-            code: KeyCode::Char('c'),
-            modifiers: KeyModifiers::CONTROL,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => return Err(anyhow::anyhow!("ctrl+c")),
+    if let Some(action) = renderer.key_bindings.action(event) {
+        match action {
+            Action::Accept => return Ok(PromptSignal::Quit),
+            Action::Interrupt => return Err(Interrupted.into()),
+            // A display-only listbox only takes Enter and Ctrl+C.
+            _ if !renderer.listbox_state.listbox.is_interactive() => (),
+            Action::MoveUp => {
+                renderer.listbox_state.listbox.backward();
+            }
+            Action::MoveDown => {
+                renderer.listbox_state.listbox.forward();
+            }
+            Action::PageUp => {
+                renderer
+                    .listbox_state
+                    .listbox
+                    .page_backward(page_size(renderer));
+            }
+            Action::PageDown => {
+                renderer
+                    .listbox_state
+                    .listbox
+                    .page_forward(page_size(renderer));
+            }
+            Action::MoveToHead => renderer.listbox_state.listbox.move_to_head(),
+            Action::MoveToTail => renderer.listbox_state.listbox.move_to_tail(),
+        }
+        return Ok(PromptSignal::Continue);
+    }
+    if !renderer.listbox_state.listbox.is_interactive() {
+        return Ok(PromptSignal::Continue);
+    }
 
-        // Move cursor.
-        Event::Key(KeyEvent {
-            code: KeyCode::Up,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        })
-        | Event::Mouse(MouseEvent {
+    match event {
+        Event::Mouse(MouseEvent {
             kind: MouseEventKind::ScrollUp,
             column: _,
             row: _,
@@ -52,14 +106,7 @@ pub fn default(
         }) => {
             renderer.listbox_state.listbox.backward();
         }
-
-        Event::Key(KeyEvent {
-            code: KeyCode::Down,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        })
-        | Event::Mouse(MouseEvent {
+        Event::Mouse(MouseEvent {
             kind: MouseEventKind::ScrollDown,
             column: _,
             row: _,
@@ -67,8 +114,56 @@ pub fn default(
         }) => {
             renderer.listbox_state.listbox.forward();
         }
+        Event::Mouse(MouseEvent {
+            kind: MouseEventKind::ScrollUp,
+            modifiers: KeyModifiers::SHIFT,
+            ..
+        }) => {
+            renderer
+                .listbox_state
+                .listbox
+                .page_backward(page_size(renderer));
+        }
+        Event::Mouse(MouseEvent {
+            kind: MouseEventKind::ScrollDown,
+            modifiers: KeyModifiers::SHIFT,
+            ..
+        }) => {
+            renderer
+                .listbox_state
+                .listbox
+                .page_forward(page_size(renderer));
+        }
+        // Clicks below the list are ignored.
+        Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            row,
+            ..
+        }) => {
+            let index = renderer
+                .listbox_area
+                .get()
+                .and_then(|(top, width, height)| {
+                    let row = row.checked_sub(top)?;
+                    renderer.listbox_state.item_at(width, height, row)
+                });
+            if renderer.clicks.click(*row, index.is_some()) {
+                return Ok(PromptSignal::Quit);
+            }
+            if let Some(index) = index {
+                renderer.listbox_state.listbox.move_to(index);
+            }
+        }
 
         _ => (),
     }
     Ok(PromptSignal::Continue)
 }
+
+/// Returns the rows of the list last rendered, which a page moves over.
+fn page_size<V>(renderer: &preset::listbox::render::Renderer<V>) -> usize {
+    renderer
+        .listbox_area
+        .get()
+        .map_or(1, |(_, _, height)| renderer.listbox_state.page_size(height))
+}
@@ -1,32 +1,60 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 
 use crate::{
-    crossterm::event::Event, listbox, pane::Pane, switch::ActiveKeySwitcher, text, PaneFactory,
-    PromptSignal,
+    crossterm::event::Event, key_bindings::KeyBindings, listbox, pane::Pane,
+    switch::ActiveKeySwitcher, text, PaneFactory, PromptSignal,
 };
 
 use super::keymap;
 
-pub struct Renderer {
-    pub keymap: RefCell<ActiveKeySwitcher<keymap::Keymap>>,
+pub struct Renderer<V = String> {
+    pub keymap: RefCell<ActiveKeySwitcher<keymap::Keymap<V>>>,
+    /// The keys bound to the actions of the default keymap.
+    pub key_bindings: KeyBindings<keymap::Action>,
     pub title_state: text::State,
     pub listbox_state: listbox::State,
+    /// The values returned for the items, in the same order as the items.
+    pub values: Vec<V>,
+    /// Where the listbox was last rendered, as (top row, width, height),
+    /// used to map mouse clicks to the items.
+    pub listbox_area: Cell<Option<(u16, u16, u16)>>,
+    /// The clicks on the items, to select an item with a double click.
+    pub clicks: listbox::Clicks,
 }
 
-impl crate::Finalizer for Renderer {
-    type Return = String;
+impl<V> Renderer<V> {
+    /// Returns the item under the cursor along with its index,
+    /// e.g. to map it back to the source of the items even if the labels repeat.
+    pub fn finalize_indexed(&self) -> anyhow::Result<listbox::IndexedItem> {
+        let listbox = &self.listbox_state.listbox;
+        if listbox.is_empty() {
+            return Err(anyhow::anyhow!("no item is selected"));
+        }
+        Ok(listbox::IndexedItem {
+            index: listbox.position(),
+            item: listbox.get().to_string(),
+        })
+    }
+}
+
+impl<V: Clone> crate::Finalizer for Renderer<V> {
+    type Return = V;
 
     fn finalize(&mut self) -> anyhow::Result<Self::Return> {
-        Ok(self.listbox_state.listbox.get().to_string())
+        self.values
+            .get(self.listbox_state.listbox.position())
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("no item is selected"))
     }
 }
 
-impl crate::Renderer for Renderer {
+impl<V: Clone> crate::Renderer for Renderer<V> {
     fn create_panes(&self, width: u16, height: u16) -> Vec<Pane> {
-        vec![
-            self.title_state.create_pane(width, height),
-            self.listbox_state.create_pane(width, height),
-        ]
+        let title = self.title_state.create_pane(width, height);
+        let top = title.height().min(height as usize) as u16;
+        self.listbox_area
+            .set(Some((top, width, height.saturating_sub(top).max(1))));
+        vec![title, self.listbox_state.create_pane(width, height)]
     }
 
     fn evaluate(&mut self, event: &Event) -> anyhow::Result<PromptSignal> {
@@ -0,0 +1,161 @@
+use std::{cell::RefCell, collections::HashSet};
+
+use crate::{
+    crossterm::style::{Attribute, Attributes, Color, ContentStyle},
+    key_bindings::KeyBindings,
+    snapshot::Snapshot,
+    style::StyleBuilder,
+    switch::ActiveKeySwitcher,
+    text, text_editor, Prompt,
+};
+
+pub mod keymap;
+pub mod render;
+
+/// A prompt for a number, typed in or stepped with <kbd>↑</kbd> and <kbd>↓</kbd>.
+///
+/// Only digits, a `.` for the decimals and a leading `-` can be typed,
+/// and the number is clamped to `min` and `max` when stepped or submitted.
+/// An empty input stands for `min`, or `0` if there is no minimum.
+pub struct Number {
+    keymap: ActiveKeySwitcher<keymap::Keymap>,
+    /// Keys bound to the actions of the default keymap.
+    key_bindings: KeyBindings<keymap::Action>,
+    title_state: text::State,
+    text_editor_state: text_editor::State,
+    error_message_state: text::State,
+    step: f64,
+    min: Option<f64>,
+    max: Option<f64>,
+    precision: Option<usize>,
+}
+
+impl Default for Number {
+    fn default() -> Self {
+        Self {
+            keymap: ActiveKeySwitcher::new("default", self::keymap::default as keymap::Keymap),
+            key_bindings: Default::default(),
+            title_state: text::State {
+                text: Default::default(),
+                style: StyleBuilder::new()
+                    .attrs(Attributes::from(Attribute::Bold))
+                    .build(),
+                offset: 0,
+            },
+            text_editor_state: text_editor::State {
+                texteditor: Default::default(),
+                history: Default::default(),
+                prefix: String::from("❯❯ "),
+                mask: Default::default(),
+                prefix_style: StyleBuilder::new().fgc(Color::DarkGreen).build(),
+                active_char_style: StyleBuilder::new().bgc(Color::DarkCyan).build(),
+                inactive_char_style: StyleBuilder::new().build(),
+                edit_mode: Default::default(),
+                word_break_chars: HashSet::new(),
+                unicode_words: false,
+                right_prompt: Default::default(),
+                right_prompt_style: Default::default(),
+                lines: Some(1),
+                scroll_top: Default::default(),
+                horizontal_scroll: false,
+                scroll_left: Default::default(),
+            },
+            error_message_state: text::State {
+                text: Default::default(),
+                style: StyleBuilder::new()
+                    .fgc(Color::DarkRed)
+                    .attrs(Attributes::from(Attribute::Bold))
+                    .build(),
+                offset: 0,
+            },
+            step: 1.0,
+            min: None,
+            max: None,
+            precision: None,
+        }
+    }
+}
+
+impl Number {
+    /// Sets the title text displayed above the input field.
+    pub fn title<T: AsRef<str>>(mut self, text: T) -> Self {
+        self.title_state.text = text.as_ref().to_string();
+        self
+    }
+
+    /// Sets the style for the title text.
+    pub fn title_style(mut self, style: ContentStyle) -> Self {
+        self.title_state.style = style;
+        self
+    }
+
+    /// Sets the prefix string displayed before the input text.
+    pub fn prefix<T: AsRef<str>>(mut self, prefix: T) -> Self {
+        self.text_editor_state.prefix = prefix.as_ref().to_string();
+        self
+    }
+
+    /// Sets the number the input starts with.
+    pub fn initial_value(mut self, value: f64) -> Self {
+        self.text_editor_state.texteditor = text_editor::TextEditor::new(value.to_string());
+        self
+    }
+
+    /// Sets how much <kbd>↑</kbd> and <kbd>↓</kbd> change the number (`1` by default).
+    pub fn step(mut self, step: f64) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Sets the smallest number, which an empty input also stands for.
+    pub fn min(mut self, min: f64) -> Self {
+        self.min = Some(min);
+        self
+    }
+
+    /// Sets the largest number.
+    pub fn max(mut self, max: f64) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Sets the number of decimals, rounding the number to it (`0` allows integers only).
+    /// By default, the number is shown with as many decimals as the step has.
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+
+    /// Sets the keys bound to the actions of the default keymap,
+    /// e.g. `KeyBindings::default().bind('+', keymap::Action::Increment)`.
+    pub fn key_bindings(mut self, key_bindings: KeyBindings<keymap::Action>) -> Self {
+        self.key_bindings = key_bindings;
+        self
+    }
+
+    pub fn register_keymap<K: AsRef<str>>(mut self, key: K, handler: keymap::Keymap) -> Self {
+        self.keymap = self.keymap.register(key, handler);
+        self
+    }
+
+    /// Creates a prompt returning the number.
+    pub fn prompt(self) -> anyhow::Result<Prompt<render::Renderer>> {
+        Ok(Prompt::new(self.renderer()))
+    }
+
+    /// Builds the renderer without creating a prompt,
+    /// e.g. to be used as a step of `Wizard`.
+    pub fn renderer(self) -> render::Renderer {
+        render::Renderer {
+            keymap: RefCell::new(self.keymap),
+            key_bindings: self.key_bindings,
+            title_state: self.title_state,
+            text_editor_snapshot: Snapshot::new(self.text_editor_state),
+            error_message_state: self.error_message_state,
+            step: self.step,
+            min: self.min,
+            max: self.max,
+            precision: self.precision,
+        }
+    }
+}
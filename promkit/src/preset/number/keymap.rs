@@ -0,0 +1,126 @@
+use crate::{
+    crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    key_bindings::{ctrl, KeyBindings},
+    preset, text_editor, Interrupted, PromptSignal,
+};
+
+pub type Keymap = fn(
+    event: &Event,
+    renderer: &mut preset::number::render::Renderer,
+) -> anyhow::Result<PromptSignal>;
+
+/// Actions of the number prompt, bound to keys by `KeyBindings<Action>`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// Submit the number, clamped to the range.
+    Accept,
+    /// Interrupt the current operation.
+    Interrupt,
+    /// Increase the number by the step.
+    Increment,
+    /// Decrease the number by the step.
+    Decrement,
+    /// Move the cursor one character to the left.
+    MoveLeft,
+    /// Move the cursor one character to the right.
+    MoveRight,
+    /// Move the cursor to the start of the line.
+    MoveToHead,
+    /// Move the cursor to the end of the line.
+    MoveToTail,
+    /// Delete the character before the cursor.
+    Erase,
+    /// Delete all characters in the current line.
+    EraseAll,
+}
+
+impl Default for KeyBindings<Action> {
+    /// Returns the bindings listed in `default`.
+    fn default() -> Self {
+        KeyBindings::empty()
+            .bind(KeyCode::Enter, Action::Accept)
+            .bind(ctrl('c'), Action::Interrupt)
+            .bind(KeyCode::Up, Action::Increment)
+            .bind(KeyCode::Down, Action::Decrement)
+            .bind(KeyCode::Left, Action::MoveLeft)
+            .bind(KeyCode::Right, Action::MoveRight)
+            .bind(ctrl('a'), Action::MoveToHead)
+            .bind(ctrl('e'), Action::MoveToTail)
+            .bind(KeyCode::Backspace, Action::Erase)
+            .bind(ctrl('u'), Action::EraseAll)
+    }
+}
+
+/// Default key bindings for the number prompt,
+/// evaluating the keys through the `KeyBindings` of the renderer.
+/// Only the chars of a number are inserted.
+///
+/// | Key                    | Action
+/// | :--------------------- | :-------------------------------------------
+/// | <kbd>Enter</kbd>       | Submit the number, clamped to the range, or show why it is not a number
+/// | <kbd>Ctrl + C</kbd>    | Interrupt the current operation
+/// | <kbd>↑</kbd>           | Increase the number by the step
+/// | <kbd>↓</kbd>           | Decrease the number by the step
+/// | <kbd>←</kbd>           | Move the cursor one character to the left
+/// | <kbd>→</kbd>           | Move the cursor one character to the right
+/// | <kbd>Ctrl + A</kbd>    | Move the cursor to the start of the line
+/// | <kbd>Ctrl + E</kbd>    | Move the cursor to the end of the line
+/// | <kbd>Backspace</kbd>   | Delete the character before the cursor
+/// | <kbd>Ctrl + U</kbd>    | Delete all characters in the current line
+pub fn default(
+    event: &Event,
+    renderer: &mut preset::number::render::Renderer,
+) -> anyhow::Result<PromptSignal> {
+    if let Some(action) = renderer.key_bindings.action(event) {
+        let texteditor = &mut renderer.text_editor_snapshot.after_mut().texteditor;
+        match action {
+            Action::Accept => match renderer.value() {
+                Some(value) => {
+                    // Show the number returned, e.g. clamped.
+                    renderer.set_value(value);
+                    return Ok(PromptSignal::Quit);
+                }
+                None => {
+                    renderer.error_message_state.text =
+                        format!("not a number: {}", renderer.text());
+                }
+            },
+            Action::Interrupt => return Err(Interrupted.into()),
+            Action::Increment => {
+                renderer.step_by(1.0);
+            }
+            Action::Decrement => {
+                renderer.step_by(-1.0);
+            }
+            Action::MoveLeft => {
+                texteditor.backward();
+            }
+            Action::MoveRight => {
+                texteditor.forward();
+            }
+            Action::MoveToHead => texteditor.move_to_head(),
+            Action::MoveToTail => texteditor.move_to_tail(),
+            Action::Erase => texteditor.erase(),
+            Action::EraseAll => texteditor.erase_all(),
+        }
+        return Ok(PromptSignal::Continue);
+    }
+
+    if let Event::Key(KeyEvent {
+        code: KeyCode::Char(ch),
+        modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+        kind: KeyEventKind::Press,
+        ..
+    }) = event
+    {
+        if renderer.accepts(*ch) {
+            let state = renderer.text_editor_snapshot.after_mut();
+            match state.edit_mode {
+                text_editor::Mode::Insert => state.texteditor.insert(*ch),
+                text_editor::Mode::Overwrite => state.texteditor.overwrite(*ch),
+            }
+            renderer.error_message_state.text.clear();
+        }
+    }
+    Ok(PromptSignal::Continue)
+}
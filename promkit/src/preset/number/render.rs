@@ -0,0 +1,145 @@
+use std::cell::RefCell;
+
+use crate::{
+    crossterm::event::Event, key_bindings::KeyBindings, pane::Pane, snapshot::Snapshot,
+    switch::ActiveKeySwitcher, text, text_editor, PaneFactory, PromptSignal,
+};
+
+use super::keymap;
+
+/// A `Renderer` of the number preset, returning the number as `f64`.
+pub struct Renderer {
+    pub keymap: RefCell<ActiveKeySwitcher<keymap::Keymap>>,
+    /// The keys bound to the actions of the default keymap.
+    pub key_bindings: KeyBindings<keymap::Action>,
+    pub title_state: text::State,
+    /// The input, whose initial state (e.g. with `Number::initial_value`) starts every run.
+    pub text_editor_snapshot: Snapshot<text_editor::State>,
+    /// Shows why the input is not a number.
+    pub error_message_state: text::State,
+    /// How much a step changes the number.
+    pub step: f64,
+    /// The smallest number, which an empty input also stands for.
+    pub min: Option<f64>,
+    /// The largest number.
+    pub max: Option<f64>,
+    /// The number of decimals, or `None` for as many as the step has.
+    pub precision: Option<usize>,
+}
+
+impl Renderer {
+    /// Returns the input without the cursor.
+    pub fn text(&self) -> String {
+        self.text_editor_snapshot
+            .after()
+            .texteditor
+            .text_without_cursor()
+            .to_string()
+    }
+
+    /// Returns the number of the input, rounded to the precision and clamped to the range,
+    /// or `None` if it is not a number (e.g. only `-`).
+    /// An empty input stands for `min`, or `0` if there is no minimum.
+    pub fn value(&self) -> Option<f64> {
+        let text = self.text();
+        let value = if text.is_empty() {
+            self.min.unwrap_or_default()
+        } else {
+            text.parse::<f64>().ok().filter(|value| value.is_finite())?
+        };
+        let value = match self.precision {
+            Some(precision) => round(value, precision),
+            None => value,
+        };
+        Some(self.clamp(value))
+    }
+
+    /// Returns whether the char may be inserted at the cursor,
+    /// i.e. a digit, a `.` if decimals are allowed and there is none yet,
+    /// or a `-` at the head if negative numbers are allowed and there is none yet.
+    pub fn accepts(&self, ch: char) -> bool {
+        let text = self.text();
+        match ch {
+            '0'..='9' => true,
+            '.' => self.precision != Some(0) && !text.contains('.'),
+            '-' => {
+                self.min.is_none_or(|min| min < 0.0)
+                    && self.text_editor_snapshot.after().texteditor.position() == 0
+                    && !text.starts_with('-')
+            }
+            _ => false,
+        }
+    }
+
+    /// Changes the number by the steps (negative to decrement), clamped to the range,
+    /// and replaces the input with it. Returns `false` if the input is not a number.
+    pub fn step_by(&mut self, steps: f64) -> bool {
+        let Some(value) = self.value() else {
+            return false;
+        };
+        self.set_value(value + self.step * steps);
+        true
+    }
+
+    /// Replaces the input with the number, clamped to the range and shown with the decimals
+    /// of the precision, or else as many as the step or the input has.
+    pub fn set_value(&mut self, value: f64) {
+        let decimals = self
+            .precision
+            .unwrap_or_else(|| decimals(&self.step.abs().to_string()).max(decimals(&self.text())));
+        let value = self.clamp(round(value, decimals));
+        // Without "-0".
+        let value = if value == 0.0 { 0.0 } else { value };
+        self.text_editor_snapshot
+            .after_mut()
+            .texteditor
+            .replace(&format!("{:.*}", decimals, value));
+        self.error_message_state.text.clear();
+    }
+
+    fn clamp(&self, value: f64) -> f64 {
+        let value = self.min.map_or(value, |min| value.max(min));
+        self.max.map_or(value, |max| value.min(max))
+    }
+}
+
+/// Returns the number of decimals of the number in text.
+fn decimals(text: &str) -> usize {
+    text.split_once('.')
+        .map_or(0, |(_, decimals)| decimals.len())
+}
+
+/// Rounds the number to the decimals as it is shown, avoiding e.g. `0.30000000000000004`.
+fn round(value: f64, decimals: usize) -> f64 {
+    format!("{:.*}", decimals, value).parse().unwrap_or(value)
+}
+
+impl crate::Finalizer for Renderer {
+    type Return = f64;
+
+    fn finalize(&mut self) -> anyhow::Result<Self::Return> {
+        self.value()
+            .ok_or_else(|| anyhow::anyhow!("not a number: {}", self.text()))
+    }
+}
+
+impl crate::Renderer for Renderer {
+    fn create_panes(&self, width: u16, height: u16) -> Vec<Pane> {
+        vec![
+            self.title_state.create_pane(width, height),
+            self.error_message_state.create_pane(width, height),
+            self.text_editor_snapshot.create_pane(width, height),
+        ]
+    }
+
+    fn evaluate(&mut self, event: &Event) -> anyhow::Result<PromptSignal> {
+        let keymap = *self.keymap.borrow_mut().get();
+        keymap(event, self)
+    }
+
+    /// Restores the initial input to start the next run.
+    fn reset(&mut self) {
+        self.text_editor_snapshot.reset_after_to_init();
+        self.error_message_state.text.clear();
+    }
+}
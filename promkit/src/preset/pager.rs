@@ -0,0 +1,106 @@
+use std::cell::RefCell;
+
+use crate::{
+    crossterm::style::{Attribute, Attributes, ContentStyle},
+    key_bindings::KeyBindings,
+    style::StyleBuilder,
+    switch::ActiveKeySwitcher,
+    text, Prompt,
+};
+
+pub mod keymap;
+pub mod render;
+
+/// A read-only pager for a long text, e.g. help output,
+/// scrolled with <kbd>↑</kbd>, <kbd>↓</kbd>, <kbd>PageUp</kbd> and <kbd>PageDown</kbd>.
+/// The footer shows the first line shown out of all the lines, i.e. "line X of N".
+pub struct Pager {
+    keymap: ActiveKeySwitcher<keymap::Keymap>,
+    /// Keys bound to the actions of the default keymap.
+    key_bindings: KeyBindings<keymap::Action>,
+    title_state: text::State,
+    text_state: text::State,
+    footer_state: text::State,
+}
+
+impl Pager {
+    /// Creates a pager showing the text from its first line.
+    pub fn new<T: AsRef<str>>(text: T) -> Self {
+        Self {
+            keymap: ActiveKeySwitcher::new("default", self::keymap::default as keymap::Keymap),
+            key_bindings: Default::default(),
+            title_state: text::State {
+                text: Default::default(),
+                style: StyleBuilder::new()
+                    .attrs(Attributes::from(Attribute::Bold))
+                    .build(),
+                offset: 0,
+            },
+            text_state: text::State {
+                text: text.as_ref().to_string(),
+                style: Default::default(),
+                offset: 0,
+            },
+            footer_state: text::State {
+                text: Default::default(),
+                style: StyleBuilder::new()
+                    .attrs(Attributes::from(Attribute::Dim))
+                    .build(),
+                offset: 0,
+            },
+        }
+    }
+
+    /// Sets the title text displayed above the text.
+    pub fn title<T: AsRef<str>>(mut self, text: T) -> Self {
+        self.title_state.text = text.as_ref().to_string();
+        self
+    }
+
+    /// Sets the style for the title text.
+    pub fn title_style(mut self, style: ContentStyle) -> Self {
+        self.title_state.style = style;
+        self
+    }
+
+    /// Sets the style for the text.
+    pub fn text_style(mut self, style: ContentStyle) -> Self {
+        self.text_state.style = style;
+        self
+    }
+
+    /// Sets the style for the footer showing the position.
+    pub fn footer_style(mut self, style: ContentStyle) -> Self {
+        self.footer_state.style = style;
+        self
+    }
+
+    /// Sets the keys bound to the actions of the default keymap,
+    /// e.g. `KeyBindings::default().bind('j', keymap::Action::LineDown)`.
+    pub fn key_bindings(mut self, key_bindings: KeyBindings<keymap::Action>) -> Self {
+        self.key_bindings = key_bindings;
+        self
+    }
+
+    pub fn register_keymap<K: AsRef<str>>(mut self, key: K, handler: keymap::Keymap) -> Self {
+        self.keymap = self.keymap.register(key, handler);
+        self
+    }
+
+    /// Creates a prompt showing the text until it is closed.
+    pub fn prompt(self) -> anyhow::Result<Prompt<render::Renderer>> {
+        Ok(Prompt::new(self.renderer()))
+    }
+
+    /// Builds the renderer without creating a prompt.
+    pub fn renderer(self) -> render::Renderer {
+        render::Renderer {
+            keymap: RefCell::new(self.keymap),
+            key_bindings: self.key_bindings,
+            title_state: self.title_state,
+            text_state: self.text_state,
+            footer_state: self.footer_state,
+            text_area: Default::default(),
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use crate::{
+    crossterm::event::{Event, KeyCode},
+    key_bindings::{ctrl, KeyBindings},
+    preset, Interrupted, PromptSignal,
+};
+
+pub type Keymap = fn(
+    event: &Event,
+    renderer: &mut preset::pager::render::Renderer,
+) -> anyhow::Result<PromptSignal>;
+
+/// Actions of the pager, bound to keys by `KeyBindings<Action>`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// Close the pager.
+    Quit,
+    /// Interrupt the current operation.
+    Interrupt,
+    /// Scroll up by a line.
+    LineUp,
+    /// Scroll down by a line.
+    LineDown,
+    /// Scroll up by a page.
+    PageUp,
+    /// Scroll down by a page.
+    PageDown,
+    /// Scroll to the first line.
+    Top,
+    /// Scroll to the last line.
+    Bottom,
+}
+
+impl Default for KeyBindings<Action> {
+    /// Returns the bindings listed in `default`.
+    fn default() -> Self {
+        KeyBindings::empty()
+            .bind(KeyCode::Enter, Action::Quit)
+            .bind('q', Action::Quit)
+            .bind(ctrl('c'), Action::Interrupt)
+            .bind(KeyCode::Up, Action::LineUp)
+            .bind(KeyCode::Down, Action::LineDown)
+            .bind(KeyCode::PageUp, Action::PageUp)
+            .bind(KeyCode::PageDown, Action::PageDown)
+            .bind(' ', Action::PageDown)
+            .bind(KeyCode::Home, Action::Top)
+            .bind(KeyCode::End, Action::Bottom)
+    }
+}
+
+/// Default key bindings for the pager,
+/// evaluating the keys through the `KeyBindings` of the renderer.
+///
+/// | Key                            | Action
+/// | :----------------------------- | :-------------------------------------------
+/// | <kbd>Enter</kbd>, <kbd>q</kbd> | Close the pager
+/// | <kbd>Ctrl + C</kbd>            | Interrupt the current operation
+/// | <kbd>↑</kbd>                   | Scroll up by a line
+/// | <kbd>↓</kbd>                   | Scroll down by a line
+/// | <kbd>PageUp</kbd>              | Scroll up by a page
+/// | <kbd>PageDown</kbd>, <kbd>Space</kbd> | Scroll down by a page
+/// | <kbd>Home</kbd>                | Scroll to the first line
+/// | <kbd>End</kbd>                 | Scroll to the last line
+pub fn default(
+    event: &Event,
+    renderer: &mut preset::pager::render::Renderer,
+) -> anyhow::Result<PromptSignal> {
+    if let Some(action) = renderer.key_bindings.action(event) {
+        let page = renderer.page() as isize;
+        match action {
+            Action::Quit => return Ok(PromptSignal::Quit),
+            Action::Interrupt => return Err(Interrupted.into()),
+            Action::LineUp => {
+                renderer.scroll_by(-1);
+            }
+            Action::LineDown => {
+                renderer.scroll_by(1);
+            }
+            Action::PageUp => {
+                renderer.scroll_by(-page);
+            }
+            Action::PageDown => {
+                renderer.scroll_by(page);
+            }
+            Action::Top => {
+                renderer.scroll_to(0);
+            }
+            Action::Bottom => {
+                renderer.scroll_to(usize::MAX);
+            }
+        }
+    }
+    Ok(PromptSignal::Continue)
+}
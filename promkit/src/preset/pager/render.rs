@@ -0,0 +1,100 @@
+use std::cell::{Cell, RefCell};
+
+use crate::{
+    crossterm::event::Event, key_bindings::KeyBindings, pane::Pane, switch::ActiveKeySwitcher,
+    text, PaneFactory, PromptSignal,
+};
+
+use super::keymap;
+
+/// A `Renderer` of the pager preset.
+pub struct Renderer {
+    pub keymap: RefCell<ActiveKeySwitcher<keymap::Keymap>>,
+    /// The keys bound to the actions of the default keymap.
+    pub key_bindings: KeyBindings<keymap::Action>,
+    pub title_state: text::State,
+    /// The text, scrolled by its offset.
+    pub text_state: text::State,
+    /// Shows the first line shown out of all the lines.
+    pub footer_state: text::State,
+    /// The width and the rows available for the text when last rendered,
+    /// which the offset is clamped to and a page moves over.
+    pub text_area: Cell<Option<(u16, u16)>>,
+}
+
+impl Renderer {
+    /// Returns the largest offset, which shows the last line at the bottom.
+    pub fn max_offset(&self) -> usize {
+        match self.text_area.get() {
+            Some((width, height)) => self.text_state.rows(width).saturating_sub(height as usize),
+            None => 0,
+        }
+    }
+
+    /// Returns the number of lines a page scrolls over.
+    pub fn page(&self) -> usize {
+        self.text_area
+            .get()
+            .map_or(1, |(_, height)| height.max(1) as usize)
+    }
+
+    /// Scrolls by the lines (negative to scroll up), clamped at both ends.
+    /// Returns `true` if the offset changed.
+    pub fn scroll_by(&mut self, lines: isize) -> bool {
+        let offset = self.text_state.offset.min(self.max_offset());
+        self.scroll_to(offset.saturating_add_signed(lines))
+    }
+
+    /// Scrolls so that the line is shown first, clamped at both ends.
+    /// Returns `true` if the offset changed.
+    pub fn scroll_to(&mut self, offset: usize) -> bool {
+        let offset = offset.min(self.max_offset());
+        let changed = offset != self.text_state.offset;
+        self.text_state.offset = offset;
+        changed
+    }
+}
+
+impl crate::Finalizer for Renderer {
+    type Return = ();
+
+    fn finalize(&mut self) -> anyhow::Result<Self::Return> {
+        Ok(())
+    }
+}
+
+impl crate::Renderer for Renderer {
+    fn create_panes(&self, width: u16, height: u16) -> Vec<Pane> {
+        let title = self.title_state.create_pane(width, height);
+        let text_height = height
+            .saturating_sub(title.height().min(height as usize) as u16 + 1)
+            .max(1);
+        self.text_area.set(Some((width, text_height)));
+
+        let rows = self.text_state.rows(width);
+        // The offset may exceed the end after the terminal has grown.
+        let offset = self.text_state.offset.min(self.max_offset());
+        let text = text::State {
+            offset,
+            ..self.text_state.clone()
+        };
+        let footer = text::State {
+            text: format!("line {} of {}", (offset + 1).min(rows), rows),
+            ..self.footer_state.clone()
+        };
+        vec![
+            title,
+            text.create_pane(width, text_height),
+            footer.create_pane(width, height),
+        ]
+    }
+
+    fn evaluate(&mut self, event: &Event) -> anyhow::Result<PromptSignal> {
+        let keymap = *self.keymap.borrow_mut().get();
+        keymap(event, self)
+    }
+
+    fn reset(&mut self) {
+        self.text_state.offset = 0;
+    }
+}
@@ -8,10 +8,13 @@ use crate::{
     switch::ActiveKeySwitcher,
     text,
     text_editor::{self, Mode},
-    Prompt,
+    Overflow, Prompt,
 };
 
+pub mod filter;
 pub mod keymap;
+#[cfg(feature = "async")]
+pub mod loader;
 pub mod render;
 
 /// Represents a query selection component that combines a text editor
@@ -28,6 +31,22 @@ pub struct QuerySelector {
     /// A filter function to apply to the list box items
     /// based on the text editor input.
     filter: render::Filter,
+    /// How and with which style the matches are highlighted, if at all.
+    highlight: Option<(filter::Mode, ContentStyle)>,
+    /// A function fetching the items for the input in the background instead of `filter`.
+    #[cfg(feature = "async")]
+    async_filter: Option<loader::AsyncFilter>,
+    /// The delay after the last keystroke before `async_filter` is called.
+    #[cfg(feature = "async")]
+    debounce: std::time::Duration,
+    #[cfg(feature = "async")]
+    loading_state: text::State,
+    /// What the prompt does when loading the items fails.
+    #[cfg(feature = "async")]
+    on_load_error: loader::OnLoadError,
+    /// Shown above the list when loading the items has failed under `OnLoadError::Show`.
+    #[cfg(feature = "async")]
+    load_error_state: text::State,
 }
 
 impl QuerySelector {
@@ -52,6 +71,7 @@ impl QuerySelector {
                 style: StyleBuilder::new()
                     .attrs(Attributes::from(Attribute::Bold))
                     .build(),
+                offset: 0,
             },
             text_editor_state: text_editor::State {
                 texteditor: Default::default(),
@@ -63,20 +83,114 @@ impl QuerySelector {
                 inactive_char_style: StyleBuilder::new().build(),
                 edit_mode: Default::default(),
                 word_break_chars: Default::default(),
+                unicode_words: false,
+                right_prompt: Default::default(),
+                right_prompt_style: Default::default(),
                 lines: Default::default(),
+                scroll_top: Default::default(),
+                horizontal_scroll: false,
+                scroll_left: Default::default(),
             },
             listbox_state: listbox::State {
                 listbox: Listbox::from_displayable(items),
                 cursor: String::from("❯ "),
                 active_item_style: Some(StyleBuilder::new().fgc(Color::DarkCyan).build()),
                 inactive_item_style: Some(StyleBuilder::new().build()),
+                description_style: StyleBuilder::new()
+                    .fgc(Color::DarkGrey)
+                    .attrs(Attributes::from(Attribute::Dim))
+                    .build(),
+                overflow: Default::default(),
+                item_lines: None,
+                partial_item: Default::default(),
                 lines: Default::default(),
             },
             keymap: ActiveKeySwitcher::new("default", self::keymap::default),
             filter,
+            highlight: None,
+            #[cfg(feature = "async")]
+            async_filter: None,
+            #[cfg(feature = "async")]
+            debounce: std::time::Duration::from_millis(200),
+            #[cfg(feature = "async")]
+            loading_state: text::State {
+                text: String::from("loading…"),
+                style: StyleBuilder::new()
+                    .fgc(Color::DarkGrey)
+                    .attrs(Attributes::from(Attribute::Dim))
+                    .build(),
+                offset: 0,
+            },
+            #[cfg(feature = "async")]
+            on_load_error: Default::default(),
+            #[cfg(feature = "async")]
+            load_error_state: text::State {
+                text: Default::default(),
+                style: StyleBuilder::new()
+                    .fgc(Color::DarkRed)
+                    .attrs(Attributes::from(Attribute::Bold))
+                    .build(),
+                offset: 0,
+            },
         }
     }
 
+    /// Constructs a new `QuerySelector` instance whose items are fetched
+    /// for the current input in the background, e.g. from a web API.
+    ///
+    /// Keystrokes are debounced (see `debounce`), and the responses for
+    /// outdated inputs are discarded. While fetching, a loading row is shown.
+    /// The in-flight fetch is aborted when the prompt finishes or is dropped.
+    /// If the future panics, the prompt fails (see `on_load_error`).
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - A function returning a future that resolves to
+    ///   the items for the given input.
+    #[cfg(feature = "async")]
+    pub fn with_async_filter<F, Fut>(filter: F) -> Self
+    where
+        F: Fn(&str) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Vec<String>> + Send + 'static,
+    {
+        Self::with_try_async_filter(move |query| {
+            let fetch = filter(query);
+            async move { Ok(fetch.await) }
+        })
+    }
+
+    /// Like `with_async_filter`, but the future may fail,
+    /// e.g. on a network error. See `on_load_error` for how failures are handled.
+    #[cfg(feature = "async")]
+    pub fn with_try_async_filter<F, Fut>(filter: F) -> Self
+    where
+        F: Fn(&str) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = anyhow::Result<Vec<String>>> + Send + 'static,
+    {
+        let mut ret = Self::new(Vec::<String>::new(), |_, items| items.clone());
+        ret.async_filter = Some(std::sync::Arc::new(move |query: &str| {
+            Box::pin(filter(query))
+                as std::pin::Pin<
+                    Box<dyn std::future::Future<Output = anyhow::Result<Vec<String>>> + Send>,
+                >
+        }));
+        ret
+    }
+
+    /// Sets what the prompt does when the async filter fails or panics.
+    #[cfg(feature = "async")]
+    pub fn on_load_error(mut self, policy: loader::OnLoadError) -> Self {
+        self.on_load_error = policy;
+        self
+    }
+
+    /// Sets the delay after the last keystroke before the async filter is called.
+    #[cfg(feature = "async")]
+    pub fn debounce(mut self, delay: std::time::Duration) -> Self {
+        self.debounce = delay;
+        self
+    }
+
     /// Sets the title text displayed above the query selection.
     pub fn title<T: AsRef<str>>(mut self, text: T) -> Self {
         self.title_state.text = text.as_ref().to_string();
@@ -126,11 +240,16 @@ impl QuerySelector {
     }
 
     /// Sets the cursor symbol used in the list box component.
-    pub fn cursor<T: AsRef<str>>(mut self, cursor: T) -> Self {
+    pub fn cursor_symbol<T: AsRef<str>>(mut self, cursor: T) -> Self {
         self.listbox_state.cursor = cursor.as_ref().to_string();
         self
     }
 
+    #[deprecated(since = "0.7.0", note = "renamed to `cursor_symbol`")]
+    pub fn cursor<T: AsRef<str>>(self, cursor: T) -> Self {
+        self.cursor_symbol(cursor)
+    }
+
     /// Sets the style for active (currently selected) items in the list box component.
     pub fn active_item_style(mut self, style: ContentStyle) -> Self {
         self.listbox_state.active_item_style = Some(style);
@@ -143,12 +262,31 @@ impl QuerySelector {
         self
     }
 
+    /// Sets how items exceeding the terminal width are rendered in the list box component.
+    pub fn overflow(mut self, overflow: Overflow) -> Self {
+        self.listbox_state.overflow = overflow;
+        self
+    }
+
+    /// Sets whether moving down from the last item goes to the first one and vice versa.
+    pub fn cyclic(mut self, cyclic: bool) -> Self {
+        self.listbox_state.listbox.set_cyclic(cyclic);
+        self
+    }
+
     /// Sets the number of lines available for the list box component.
     pub fn listbox_lines(mut self, lines: usize) -> Self {
         self.listbox_state.lines = Some(lines);
         self
     }
 
+    /// Highlights the graphemes of the items matched by the query,
+    /// parsed in the syntax of the built-in filters (see `filter`) in the mode.
+    pub fn highlight_matches(mut self, mode: filter::Mode, style: ContentStyle) -> Self {
+        self.highlight = Some((mode, style));
+        self
+    }
+
     pub fn register_keymap<K: AsRef<str>>(mut self, key: K, handler: keymap::Keymap) -> Self {
         self.keymap = self.keymap.register(key, handler);
         self
@@ -158,14 +296,32 @@ impl QuerySelector {
     /// Returns a `Result` containing the `Prompt` result,
     /// which is the selected option.
     pub fn prompt(self) -> anyhow::Result<Prompt<render::Renderer>> {
-        Ok(Prompt {
-            renderer: render::Renderer {
-                keymap: RefCell::new(self.keymap),
-                title_state: self.title_state,
-                text_editor_snapshot: Snapshot::<text_editor::State>::new(self.text_editor_state),
-                listbox_snapshot: Snapshot::<listbox::State>::new(self.listbox_state),
-                filter: self.filter,
-            },
-        })
+        Ok(Prompt::new(self.renderer()))
+    }
+
+    /// Builds the renderer without creating a prompt,
+    /// e.g. to be used as a step of `Wizard`.
+    pub fn renderer(self) -> render::Renderer {
+        render::Renderer {
+            keymap: RefCell::new(self.keymap),
+            title_state: self.title_state,
+            text_editor_snapshot: Snapshot::<text_editor::State>::new(self.text_editor_state),
+            source_indices: (0..self.listbox_state.listbox.len()).map(Some).collect(),
+            listbox_snapshot: Snapshot::<listbox::State>::new(self.listbox_state),
+            filter: self.filter,
+            highlight: self.highlight,
+            listbox_area: Default::default(),
+            clicks: Default::default(),
+            #[cfg(feature = "async")]
+            loader: self
+                .async_filter
+                .map(|filter| loader::Loader::new(filter, self.debounce)),
+            #[cfg(feature = "async")]
+            loading_state: self.loading_state,
+            #[cfg(feature = "async")]
+            on_load_error: self.on_load_error,
+            #[cfg(feature = "async")]
+            load_error_state: self.load_error_state,
+        }
     }
 }
@@ -0,0 +1,280 @@
+//! Built-in filters for `QuerySelector`, understanding an fzf-like query syntax:
+//!
+//! | Query          | Matches items
+//! | :------------- | :-------------------------------------------
+//! | `foo bar`      | matching both `foo` and `bar`, in any order
+//! | `foo\|bar`     | matching `foo` or `bar`
+//! | `!foo`         | not containing `foo`
+//! | `'foo`         | containing `foo` exactly, rather than fuzzily
+//!
+//! The matching is case-insensitive unless the pattern contains an uppercase letter,
+//! and works on grapheme clusters.
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{crossterm::style::ContentStyle, grapheme::StyledGraphemes};
+
+/// How the patterns of a query match without the `'` prefix.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Mode {
+    /// The graphemes of the pattern appear in order, possibly apart.
+    #[default]
+    Fuzzy,
+    /// The pattern appears as a substring. `'` makes no difference.
+    Exact,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Pattern {
+    graphemes: Vec<String>,
+    exact: bool,
+    negated: bool,
+    /// Whether the case is ignored, i.e. the pattern has no uppercase letter.
+    ignore_case: bool,
+}
+
+impl Pattern {
+    fn parse(text: &str, mode: Mode) -> Option<Self> {
+        let (negated, text) = match text.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, text),
+        };
+        let (exact, text) = match text.strip_prefix('\'') {
+            Some(rest) => (true, rest),
+            None => (mode == Mode::Exact, text),
+        };
+        if text.is_empty() {
+            return None;
+        }
+        let ignore_case = !text.chars().any(char::is_uppercase);
+        Some(Self {
+            graphemes: text.graphemes(true).map(|g| fold(g, ignore_case)).collect(),
+            // A negated pattern excludes the items containing it as it is, like fzf.
+            exact: exact || negated,
+            negated,
+            ignore_case,
+        })
+    }
+
+    /// Returns the indices of the matched graphemes, or `None` if the item does not match.
+    fn match_indices(&self, item: &[&str]) -> Option<Vec<usize>> {
+        let item: Vec<String> = item.iter().map(|g| fold(g, self.ignore_case)).collect();
+        let found = if self.exact {
+            find_all(&item, &self.graphemes)
+        } else {
+            find_in_order(&item, &self.graphemes)
+        };
+        match (found, self.negated) {
+            (Some(_), true) => None,
+            (None, true) => Some(Vec::new()),
+            (found, false) => found,
+        }
+    }
+}
+
+fn fold(grapheme: &str, ignore_case: bool) -> String {
+    if ignore_case {
+        grapheme.to_lowercase()
+    } else {
+        grapheme.to_string()
+    }
+}
+
+/// Finds the non-overlapping occurrences of the pattern.
+fn find_all(item: &[String], pattern: &[String]) -> Option<Vec<usize>> {
+    let mut indices = Vec::new();
+    let mut pos = 0;
+    while pos + pattern.len() <= item.len() {
+        if item[pos..pos + pattern.len()] == *pattern {
+            indices.extend(pos..pos + pattern.len());
+            pos += pattern.len();
+        } else {
+            pos += 1;
+        }
+    }
+    (!indices.is_empty()).then_some(indices)
+}
+
+/// Finds the leftmost occurrence of the graphemes of the pattern in order.
+fn find_in_order(item: &[String], pattern: &[String]) -> Option<Vec<usize>> {
+    let mut indices = Vec::with_capacity(pattern.len());
+    let mut rest = item.iter().enumerate();
+    for g in pattern {
+        let (i, _) = rest.find(|(_, candidate)| *candidate == g)?;
+        indices.push(i);
+    }
+    Some(indices)
+}
+
+/// A query parsed into whitespace-separated terms that must all match,
+/// each consisting of `|`-separated patterns of which any must match.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Query {
+    terms: Vec<Vec<Pattern>>,
+}
+
+impl Query {
+    /// Parses the query. Empty patterns (e.g. a lone `!`) are ignored.
+    pub fn parse(query: &str, mode: Mode) -> Self {
+        Self {
+            terms: query
+                .split_whitespace()
+                .map(|term| {
+                    term.split('|')
+                        .filter_map(|text| Pattern::parse(text, mode))
+                        .collect::<Vec<_>>()
+                })
+                .filter(|term| !term.is_empty())
+                .collect(),
+        }
+    }
+
+    /// Returns whether the query matches every item.
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Returns the sorted indices of the graphemes of the item matched by any term,
+    /// or `None` if the item does not match.
+    pub fn match_indices(&self, item: &str) -> Option<Vec<usize>> {
+        let graphemes: Vec<&str> = item.graphemes(true).collect();
+        let mut indices = Vec::new();
+        for term in &self.terms {
+            let mut matched = false;
+            for pattern in term {
+                if let Some(found) = pattern.match_indices(&graphemes) {
+                    matched = true;
+                    indices.extend(found);
+                }
+            }
+            if !matched {
+                return None;
+            }
+        }
+        indices.sort_unstable();
+        indices.dedup();
+        Some(indices)
+    }
+
+    /// Returns whether the item matches.
+    pub fn matches(&self, item: &str) -> bool {
+        self.match_indices(item).is_some()
+    }
+
+    /// Returns the item with the style applied to the matched graphemes.
+    pub fn highlight(&self, item: &str, style: ContentStyle) -> StyledGraphemes {
+        let mut ret = StyledGraphemes::from(item);
+        for i in self.match_indices(item).unwrap_or_default() {
+            if let Some(grapheme) = ret.get_mut(i) {
+                grapheme.apply_style(style);
+            }
+        }
+        ret
+    }
+}
+
+/// Keeps the items matching the query fuzzily, e.g. `QuerySelector::new(items, filter::fuzzy)`.
+#[allow(clippy::ptr_arg)]
+pub fn fuzzy(query: &str, items: &Vec<String>) -> Vec<String> {
+    filter(Query::parse(query, Mode::Fuzzy), items)
+}
+
+/// Keeps the items containing the patterns of the query as substrings.
+#[allow(clippy::ptr_arg)]
+pub fn exact(query: &str, items: &Vec<String>) -> Vec<String> {
+    filter(Query::parse(query, Mode::Exact), items)
+}
+
+fn filter(query: Query, items: &[String]) -> Vec<String> {
+    items
+        .iter()
+        .filter(|item| query.matches(item))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod match_indices {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let cases: &[(&str, Mode, &str, Option<Vec<usize>>)] = &[
+                ("", Mode::Fuzzy, "anything", Some(vec![])),
+                ("abc", Mode::Fuzzy, "a-b-c", Some(vec![0, 2, 4])),
+                ("abc", Mode::Fuzzy, "cba", None),
+                ("abc", Mode::Exact, "a-b-c", None),
+                ("'abc", Mode::Fuzzy, "xabcx", Some(vec![1, 2, 3])),
+                ("'ab", Mode::Fuzzy, "abxab", Some(vec![0, 1, 3, 4])),
+                // AND in any order.
+                (
+                    "bar foo",
+                    Mode::Fuzzy,
+                    "foo-bar",
+                    Some(vec![0, 1, 2, 4, 5, 6]),
+                ),
+                ("foo baz", Mode::Fuzzy, "foo-bar", None),
+                // OR, highlighting all the matching alternatives.
+                ("foo|bar", Mode::Exact, "bar", Some(vec![0, 1, 2])),
+                (
+                    "foo|bar",
+                    Mode::Exact,
+                    "foobar",
+                    Some(vec![0, 1, 2, 3, 4, 5]),
+                ),
+                ("foo|baz", Mode::Exact, "bar", None),
+                // Negation.
+                ("!foo", Mode::Fuzzy, "bar", Some(vec![])),
+                ("!foo", Mode::Fuzzy, "a-foo", None),
+                ("!foo", Mode::Fuzzy, "f-o-o", Some(vec![])),
+                ("bar !foo", Mode::Fuzzy, "bar", Some(vec![0, 1, 2])),
+                ("!foo|bar", Mode::Exact, "foobar", Some(vec![3, 4, 5])),
+                // Empty patterns are ignored.
+                ("! ' |", Mode::Fuzzy, "x", Some(vec![])),
+                // Smart case.
+                ("abc", Mode::Exact, "ABC", Some(vec![0, 1, 2])),
+                ("Abc", Mode::Exact, "abc", None),
+                // Grapheme clusters.
+                ("日本", Mode::Fuzzy, "日x本", Some(vec![0, 2])),
+                ("'e\u{301}", Mode::Fuzzy, "cafe\u{301}", Some(vec![3])),
+                ("e", Mode::Exact, "cafe\u{301}", None),
+                ("👨‍👩‍👧", Mode::Exact, "a👨‍👩‍👧b", Some(vec![1])),
+            ];
+            for (query, mode, item, expected) in cases {
+                assert_eq!(
+                    *expected,
+                    Query::parse(query, *mode).match_indices(item),
+                    "{:?} ({:?}) on {:?}",
+                    query,
+                    mode,
+                    item
+                );
+            }
+        }
+    }
+
+    mod fuzzy {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let items = ["main.rs", "lib.rs", "main.toml", "README.md"]
+                .map(String::from)
+                .to_vec();
+            let cases: &[(&str, &[&str])] = &[
+                ("", &["main.rs", "lib.rs", "main.toml", "README.md"]),
+                ("mn", &["main.rs", "main.toml"]),
+                ("main rs", &["main.rs"]),
+                ("lib|toml", &["lib.rs", "main.toml"]),
+                ("!main", &["lib.rs", "README.md"]),
+                ("readme", &["README.md"]),
+            ];
+            for (query, expected) in cases {
+                assert_eq!(*expected, fuzzy(query, &items), "{:?}", query);
+            }
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use crate::{
-    crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers},
-    preset, text_editor, PromptSignal,
+    crossterm::event::{
+        Event, KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers, MouseButton,
+        MouseEvent, MouseEventKind,
+    },
+    preset, text_editor, Interrupted, PromptSignal,
 };
 
 pub type Keymap = fn(
@@ -12,6 +15,10 @@ pub fn default(
     event: &Event,
     renderer: &mut preset::query_selector::render::Renderer,
 ) -> anyhow::Result<PromptSignal> {
+    // The rows of the list last rendered, which a page moves over.
+    let page = renderer.listbox_area.get().map_or(1, |(_, _, height)| {
+        renderer.listbox_snapshot.after().page_size(height)
+    });
     let text_editor_after_mut = renderer.text_editor_snapshot.after_mut();
     let listbox_after_mut = renderer.listbox_snapshot.after_mut();
 
@@ -27,7 +34,7 @@ pub fn default(
             modifiers: KeyModifiers::CONTROL,
             kind: KeyEventKind::Press,
             state: KeyEventState::NONE,
-        }) => return Err(anyhow::anyhow!("ctrl+c")),
+        }) => return Err(Interrupted.into()),
 
         // Move cursor.
         Event::Key(KeyEvent {
@@ -91,6 +98,55 @@ pub fn default(
         }) => {
             listbox_after_mut.listbox.forward();
         }
+        Event::Key(KeyEvent {
+            code: KeyCode::PageUp,
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => {
+            listbox_after_mut.listbox.page_backward(page);
+        }
+        Event::Key(KeyEvent {
+            code: KeyCode::PageDown,
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => {
+            listbox_after_mut.listbox.page_forward(page);
+        }
+        Event::Key(KeyEvent {
+            code: KeyCode::Home,
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => listbox_after_mut.listbox.move_to_head(),
+        Event::Key(KeyEvent {
+            code: KeyCode::End,
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => listbox_after_mut.listbox.move_to_tail(),
+        // Select the clicked item, or exit with it on a double click.
+        // Clicks below the list are ignored.
+        Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            row,
+            ..
+        }) => {
+            let index = renderer
+                .listbox_area
+                .get()
+                .and_then(|(top, width, height)| {
+                    let row = row.checked_sub(top)?;
+                    listbox_after_mut.item_at(width, height, row)
+                });
+            if renderer.clicks.click(*row, index.is_some()) {
+                return Ok(PromptSignal::Quit);
+            }
+            if let Some(index) = index {
+                listbox_after_mut.listbox.move_to(index);
+            }
+        }
 
         // Input char.
         Event::Key(KeyEvent {
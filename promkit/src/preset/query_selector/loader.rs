@@ -0,0 +1,322 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc,
+    },
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+
+use tokio::{runtime::Runtime, task::JoinHandle};
+
+/// Fetches the items for a query, e.g. from a web API.
+pub type AsyncFilter = Arc<
+    dyn Fn(&str) -> Pin<Box<dyn Future<Output = anyhow::Result<Vec<String>>> + Send>> + Send + Sync,
+>;
+
+/// What the prompt does when loading the items fails,
+/// i.e. the async filter returns an error or panics.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OnLoadError {
+    /// The prompt returns a `LoadFailed` error.
+    #[default]
+    Fail,
+    /// The prompt keeps running with the previous items,
+    /// showing the error until the next query is loaded.
+    Show,
+}
+
+/// Returned by a prompt when loading the items fails under `OnLoadError::Fail`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadFailed(pub String);
+
+impl std::fmt::Display for LoadFailed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "failed to load the items: {}", self.0)
+    }
+}
+
+impl std::error::Error for LoadFailed {}
+
+type Response = (u64, Result<Vec<String>, String>);
+
+/// Loads the items for the latest query in the background.
+///
+/// Each request waits for the debounce delay before fetching,
+/// and supersedes the previous one: the in-flight task is aborted,
+/// and any response tagged with an older version is discarded.
+/// The in-flight task is aborted as well when the loader is cancelled or dropped.
+///
+/// A request fails if the filter returns an error, or if the task ends
+/// without a response (i.e. it panicked); `poll` reports it once.
+pub struct Loader {
+    runtime: Option<Runtime>,
+    filter: AsyncFilter,
+    debounce: Duration,
+    /// Incremented on every request, to tell the latest response from the stale ones.
+    version: u64,
+    /// Whether no request has been made since the creation or the last reset.
+    idle: bool,
+    in_flight: Option<JoinHandle<()>>,
+    /// The error of the latest request, if it failed.
+    error: Option<String>,
+    sender: Sender<Response>,
+    receiver: Receiver<Response>,
+}
+
+impl Loader {
+    pub fn new(filter: AsyncFilter, debounce: Duration) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            runtime: None,
+            filter,
+            debounce,
+            version: 0,
+            idle: true,
+            in_flight: None,
+            error: None,
+            sender,
+            receiver,
+        }
+    }
+
+    /// Returns whether no request has been made yet.
+    pub fn is_idle(&self) -> bool {
+        self.idle
+    }
+
+    /// Starts loading the items for the query, superseding the previous request.
+    /// The runtime running the requests is started on the first call.
+    pub fn request(&mut self, query: &str) -> anyhow::Result<()> {
+        self.cancel();
+        self.version += 1;
+        self.idle = false;
+        self.error = None;
+
+        if self.runtime.is_none() {
+            self.runtime = Some(
+                tokio::runtime::Builder::new_multi_thread()
+                    .worker_threads(1)
+                    .enable_all()
+                    .build()?,
+            );
+        }
+        let Some(runtime) = &self.runtime else {
+            return Ok(());
+        };
+        // Some futures (e.g. of HTTP clients) require the runtime on creation.
+        let _guard = runtime.enter();
+        let version = self.version;
+        let debounce = self.debounce;
+        let sender = self.sender.clone();
+        let fetch = (self.filter)(query);
+        // The span is created here to be nested in the span of the prompt.
+        #[cfg(feature = "tracing")]
+        let span = tracing::info_span!("promkit.load", promkit.items = tracing::field::Empty);
+        #[cfg(feature = "tracing")]
+        let recorded = span.clone();
+        let task = async move {
+            tokio::time::sleep(debounce).await;
+            let items = fetch.await.map_err(|e| format!("{:#}", e));
+            #[cfg(feature = "tracing")]
+            if let Ok(items) = &items {
+                recorded.record("promkit.items", items.len() as u64);
+            }
+            sender.send((version, items)).ok();
+        };
+        #[cfg(feature = "tracing")]
+        let task = tracing::Instrument::instrument(task, span);
+        self.in_flight = Some(runtime.spawn(task));
+        Ok(())
+    }
+
+    /// Returns the items for the latest query if they have arrived since the last call,
+    /// or the error if loading them has failed since the last call.
+    pub fn poll(&mut self) -> Result<Option<Vec<String>>, LoadFailed> {
+        // Checked before receiving, not to miss a response sent just before finishing.
+        let finished = self
+            .in_flight
+            .as_ref()
+            .is_some_and(|task| task.is_finished());
+        let mut latest = None;
+        while let Ok((version, items)) = self.receiver.try_recv() {
+            if version == self.version {
+                latest = Some(items);
+            }
+        }
+        if latest.is_none() && finished {
+            latest = self.in_flight.take().map(|task| Err(panic_message(task)));
+        }
+        match latest {
+            Some(Ok(items)) => {
+                self.in_flight = None;
+                Ok(Some(items))
+            }
+            Some(Err(e)) => {
+                self.in_flight = None;
+                self.error = Some(e.clone());
+                Err(LoadFailed(e))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Returns whether the items for the latest query are still being loaded.
+    pub fn is_loading(&self) -> bool {
+        self.in_flight.is_some()
+    }
+
+    /// Returns the error of the latest request, if it failed.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Aborts the in-flight request, if any.
+    pub fn cancel(&mut self) {
+        if let Some(task) = self.in_flight.take() {
+            task.abort();
+        }
+    }
+
+    /// Cancels the in-flight request and forgets the error,
+    /// so that the items are loaded again as if no request had been made.
+    pub fn reset(&mut self) {
+        self.cancel();
+        // Any response still to arrive is discarded as stale.
+        self.version += 1;
+        self.idle = true;
+        self.error = None;
+    }
+}
+
+/// Returns the message of the panic which ended the finished task without a response.
+fn panic_message(mut task: JoinHandle<()>) -> String {
+    let message = match Pin::new(&mut task).poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(Err(e)) if e.is_panic() => {
+            let payload = e.into_panic();
+            payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+        }
+        _ => None,
+    };
+    match message {
+        Some(message) => format!("the filter panicked: {}", message),
+        None => String::from("the filter ended without a response"),
+    }
+}
+
+impl Drop for Loader {
+    fn drop(&mut self) {
+        self.cancel();
+        if let Some(runtime) = self.runtime.take() {
+            // Unlike dropping, this does not block nor panic in an async context.
+            runtime.shutdown_background();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::time::Instant;
+
+    fn loader(debounce: Duration) -> Loader {
+        Loader::new(
+            Arc::new(|query: &str| {
+                let query = query.to_string();
+                Box::pin(async move {
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                    match query.as_str() {
+                        "error" => Err(anyhow::anyhow!("unavailable")),
+                        "panic" => panic!("broken"),
+                        _ => Ok(vec![format!("{} result", query)]),
+                    }
+                })
+            }),
+            debounce,
+        )
+    }
+
+    fn wait(loader: &mut Loader) -> Result<Option<Vec<String>>, LoadFailed> {
+        let start = Instant::now();
+        while start.elapsed() < Duration::from_secs(5) {
+            if let Some(items) = loader.poll()? {
+                return Ok(Some(items));
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        Ok(None)
+    }
+
+    mod request {
+        use super::*;
+
+        #[test]
+        fn test_latest_query_wins() {
+            let mut loader = loader(Duration::from_millis(20));
+            loader.request("a").unwrap();
+            loader.request("ab").unwrap();
+            assert!(loader.is_loading());
+            assert_eq!(Ok(Some(vec!["ab result".to_string()])), wait(&mut loader));
+            assert!(!loader.is_loading());
+        }
+
+        #[test]
+        fn test_stale_response_is_discarded() {
+            let mut loader = loader(Duration::ZERO);
+            loader
+                .sender
+                .send((loader.version + 1, Ok(vec![])))
+                .unwrap();
+            loader.request("a").unwrap();
+            loader.request("ab").unwrap();
+            assert_eq!(Ok(Some(vec!["ab result".to_string()])), wait(&mut loader));
+        }
+    }
+
+    mod poll {
+        use super::*;
+
+        #[test]
+        fn test_error() {
+            let mut loader = loader(Duration::ZERO);
+            loader.request("error").unwrap();
+            assert_eq!(
+                Err(LoadFailed(String::from("unavailable"))),
+                wait(&mut loader)
+            );
+            assert!(!loader.is_loading());
+            assert_eq!(Some("unavailable"), loader.error());
+            // Reported once, and cleared by the next request.
+            assert_eq!(Ok(None), loader.poll());
+            loader.request("a").unwrap();
+            assert_eq!(None, loader.error());
+        }
+
+        #[test]
+        fn test_panic() {
+            let mut loader = loader(Duration::ZERO);
+            loader.request("panic").unwrap();
+            assert_eq!(
+                Err(LoadFailed(String::from("the filter panicked: broken"))),
+                wait(&mut loader)
+            );
+            assert!(!loader.is_loading());
+        }
+
+        #[test]
+        fn test_cancel() {
+            let mut loader = loader(Duration::from_millis(20));
+            loader.request("a").unwrap();
+            loader.cancel();
+            assert!(!loader.is_loading());
+            std::thread::sleep(Duration::from_millis(50));
+            assert_eq!(Ok(None), loader.poll());
+        }
+    }
+}
@@ -1,7 +1,10 @@
-use std::cell::RefCell;
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, VecDeque},
+};
 
 use crate::{
-    crossterm::event::Event,
+    crossterm::{event::Event, style::ContentStyle},
     listbox::{self, Listbox},
     pane::Pane,
     snapshot::Snapshot,
@@ -13,7 +16,10 @@ use crate::{
 /// based on the input text in the `QuerySelector` component.
 pub type Filter = fn(&str, &Vec<String>) -> Vec<String>;
 
-use super::keymap;
+use super::{
+    filter::{Mode, Query},
+    keymap,
+};
 
 /// Represents a renderer for the query selector.
 /// This struct manages the rendering process of different components within the query selector,
@@ -27,24 +33,107 @@ pub struct Renderer {
     pub text_editor_snapshot: Snapshot<text_editor::State>,
     /// Snapshot of the listbox renderer.
     pub listbox_snapshot: Snapshot<listbox::State>,
+    /// The index of each item shown in the listbox among the unfiltered items,
+    /// or among the items last loaded by the async filter.
+    /// `None` for an item the filter has not taken from the unfiltered items.
+    pub source_indices: Vec<Option<usize>>,
     pub filter: Filter,
+    /// How and with which style the matches are highlighted, if at all.
+    pub highlight: Option<(Mode, ContentStyle)>,
+    /// Where the listbox was last rendered, as (top row, width, height),
+    /// used to map mouse clicks to the items.
+    pub listbox_area: Cell<Option<(u16, u16, u16)>>,
+    /// The clicks on the items, to select an item with a double click.
+    pub clicks: listbox::Clicks,
+    /// Loads the items for the query in the background instead of `filter`.
+    #[cfg(feature = "async")]
+    pub loader: Option<super::loader::Loader>,
+    /// Shown above the list while the items are being loaded.
+    #[cfg(feature = "async")]
+    pub loading_state: text::State,
+    /// What the prompt does when loading the items fails.
+    #[cfg(feature = "async")]
+    pub on_load_error: super::loader::OnLoadError,
+    /// Shown above the list with the error when loading the items has failed.
+    #[cfg(feature = "async")]
+    pub load_error_state: text::State,
+}
+
+/// Maps each filtered item to the index of an unfiltered item with the same label,
+/// taking the items with a repeated label in their order.
+fn source_indices(items: &[String], filtered: &[String]) -> Vec<Option<usize>> {
+    let mut indices = HashMap::<&str, VecDeque<usize>>::new();
+    for (i, item) in items.iter().enumerate() {
+        indices.entry(item).or_default().push_back(i);
+    }
+    filtered
+        .iter()
+        .map(|item| indices.get_mut(item.as_str())?.pop_front())
+        .collect()
+}
+
+impl Renderer {
+    /// Returns the item under the cursor along with its index among the unfiltered items
+    /// (or among the items loaded by the async filter),
+    /// e.g. to map it back to the source of the items even if the labels repeat.
+    pub fn finalize_indexed(&self) -> anyhow::Result<listbox::IndexedItem> {
+        let listbox = &self.listbox_snapshot.after().listbox;
+        match self
+            .source_indices
+            .get(listbox.position())
+            .copied()
+            .flatten()
+        {
+            Some(index) => Ok(listbox::IndexedItem {
+                index,
+                item: listbox.get().to_string(),
+            }),
+            None => Err(anyhow::anyhow!("no item is selected")),
+        }
+    }
 }
 
 impl crate::Finalizer for Renderer {
     type Return = String;
 
     fn finalize(&mut self) -> anyhow::Result<Self::Return> {
+        #[cfg(feature = "async")]
+        if let Some(loader) = &mut self.loader {
+            loader.cancel();
+        }
         Ok(self.listbox_snapshot.after().listbox.get().to_string())
     }
 }
 
 impl crate::Renderer for Renderer {
     fn create_panes(&self, width: u16, height: u16) -> Vec<Pane> {
-        vec![
+        let mut panes = vec![
             self.title_state.create_pane(width, height),
             self.text_editor_snapshot.create_pane(width, height),
-            self.listbox_snapshot.create_pane(width, height),
-        ]
+        ];
+        #[cfg(feature = "async")]
+        if let Some(loader) = &self.loader {
+            if loader.is_loading() {
+                panes.push(self.loading_state.create_pane(width, height));
+            } else if let Some(error) = loader.error() {
+                panes.push(
+                    text::State {
+                        text: error.to_string(),
+                        ..self.load_error_state.clone()
+                    }
+                    .create_pane(width, height),
+                );
+            }
+        }
+        let top = panes
+            .iter()
+            .map(|pane| pane.height())
+            .sum::<usize>()
+            .min(height as usize) as u16;
+        self.listbox_area
+            .set(Some((top, width, height.saturating_sub(top).max(1))));
+        panes.push(self.listbox_snapshot.create_pane(width, height));
+        panes
     }
 
     fn evaluate(&mut self, event: &Event) -> anyhow::Result<PromptSignal> {
@@ -60,19 +149,91 @@ impl crate::Renderer for Renderer {
                 .text_without_cursor()
                 .to_string();
 
-            let list = (self.filter)(
-                &query,
-                &self
-                    .listbox_snapshot
-                    .init()
-                    .listbox
-                    .items()
-                    .iter()
-                    .map(|e| e.to_string())
-                    .collect(),
-            );
-            self.listbox_snapshot.after_mut().listbox = Listbox::from_displayable(list);
+            #[cfg(feature = "async")]
+            if let Some(loader) = &mut self.loader {
+                loader.request(&query)?;
+                return signal;
+            }
+
+            let items = self
+                .listbox_snapshot
+                .init()
+                .listbox
+                .items()
+                .iter()
+                .map(|e| e.to_string())
+                .collect();
+            let list = (self.filter)(&query, &items);
+            self.source_indices = source_indices(&items, &list);
+            let cyclic = self.listbox_snapshot.init().listbox.is_cyclic();
+            let listbox = &mut self.listbox_snapshot.after_mut().listbox;
+            *listbox = match self.highlight {
+                Some((mode, style)) => {
+                    let query = Query::parse(&query, mode);
+                    Listbox::from_styled_graphemes(
+                        list.iter()
+                            .map(|item| query.highlight(item, style))
+                            .collect(),
+                    )
+                }
+                None => Listbox::from_displayable(list),
+            };
+            listbox.set_cyclic(cyclic);
         }
         signal
     }
+
+    fn reset(&mut self) {
+        self.text_editor_snapshot.reset_after_to_init();
+        self.listbox_snapshot.reset_after_to_init();
+        self.source_indices = (0..self.listbox_snapshot.init().listbox.len())
+            .map(Some)
+            .collect();
+        self.keymap.get_mut().switch("default");
+        self.clicks = Default::default();
+        #[cfg(feature = "async")]
+        if let Some(loader) = &mut self.loader {
+            loader.reset();
+        }
+    }
+
+    #[cfg(feature = "async")]
+    fn tick_interval(&self) -> Option<std::time::Duration> {
+        self.loader
+            .as_ref()
+            .map(|_| std::time::Duration::from_millis(50))
+    }
+
+    #[cfg(feature = "async")]
+    fn tick(&mut self) -> anyhow::Result<bool> {
+        let Some(loader) = &mut self.loader else {
+            return Ok(false);
+        };
+        if loader.is_idle() {
+            let query = self
+                .text_editor_snapshot
+                .after()
+                .texteditor
+                .text_without_cursor()
+                .to_string();
+            loader.request(&query)?;
+            return Ok(true);
+        }
+        match loader.poll() {
+            Ok(Some(list)) => {
+                let cyclic = self.listbox_snapshot.init().listbox.is_cyclic();
+                self.source_indices = (0..list.len()).map(Some).collect();
+                let listbox = &mut self.listbox_snapshot.after_mut().listbox;
+                *listbox = Listbox::from_displayable(list);
+                listbox.set_cyclic(cyclic);
+                Ok(true)
+            }
+            Ok(None) => Ok(false),
+            Err(e) => match self.on_load_error {
+                super::loader::OnLoadError::Fail => Err(e.into()),
+                // Redrawn to replace the loading row with the error.
+                super::loader::OnLoadError::Show => Ok(true),
+            },
+        }
+    }
 }
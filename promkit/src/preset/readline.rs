@@ -1,22 +1,65 @@
-use std::{cell::RefCell, collections::HashSet};
+use std::{cell::RefCell, collections::HashSet, fmt, str::FromStr, time::Duration};
 
 use crate::{
     crossterm::style::{Attribute, Attributes, Color, ContentStyle},
+    key_bindings::KeyBindings,
     listbox::{self, Listbox},
+    read_only::ReadOnly,
     snapshot::Snapshot,
     style::StyleBuilder,
     suggest::Suggest,
     switch::ActiveKeySwitcher,
     text,
     text_editor::{self, History},
+    timeout::Timeout,
     validate::{ErrorMessageGenerator, Validator, ValidatorManager},
-    Prompt,
+    Prompt, PromptSignal, TimeoutMode,
 };
 
+#[cfg(feature = "async")]
+pub mod checker;
 pub mod confirm;
 pub mod keymap;
 pub mod password;
 pub mod render;
+pub mod typed;
+
+/// Key bindings of the text editor.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EditorMode {
+    /// Emacs-like bindings, see `keymap::default`.
+    #[default]
+    Emacs,
+    /// Vi bindings starting in the insert mode, see `keymap::vi`.
+    Vi,
+}
+
+/// The feedback given when a keystroke is rejected,
+/// e.g. Backspace at the head of the input or ↑ at the oldest history entry.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Feedback {
+    /// No feedback.
+    None,
+    /// Rings the terminal bell (BEL).
+    Bell,
+    /// Briefly shows the prefix in reverse video.
+    #[default]
+    Flash,
+    /// Briefly shows a notice in the error message pane.
+    Notice,
+}
+
+/// What the key bound to `keymap::Action::Interrupt` (<kbd>Ctrl + C</kbd> by default) does.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InterruptAction {
+    /// Returns the `Interrupted` error, which `Prompt::on_interrupt` can handle further.
+    #[default]
+    Error,
+    /// Exits with the input typed so far, without validating it.
+    ReturnInput,
+    /// Clears the input and keeps prompting, like a shell.
+    ClearLine,
+}
 
 /// `Readline` struct provides functionality
 /// for reading a single line of input from the user.
@@ -24,6 +67,8 @@ pub mod render;
 /// such as input masking, history, suggestions, and custom styles.
 pub struct Readline {
     keymap: ActiveKeySwitcher<keymap::Keymap>,
+    /// Keys bound to the actions of the default keymap.
+    key_bindings: KeyBindings<keymap::Action>,
     /// State for the title displayed above the input field.
     title_state: text::State,
     /// State for the text editor where user input is entered.
@@ -32,20 +77,66 @@ pub struct Readline {
     suggest_state: listbox::State,
     /// Optional validator for input validation with custom error messages.
     validator: Option<ValidatorManager<str>>,
+    /// Optional validator applied to the result after the prompt has finished,
+    /// used by `run_until_valid` to decide whether to prompt again.
+    finalize_validator: Option<ValidatorManager<str>>,
     /// State for displaying error messages based on input validation.
     error_message_state: text::State,
+    /// Optional callback showing the mode of the vi key bindings.
+    mode_indicator: Option<keymap::vi::ModeIndicator>,
+    /// The feedback given when a keystroke is rejected.
+    feedback: Feedback,
+    /// Whether only a one-line summary of the prompt is left after submitting.
+    transient: bool,
+    /// Produces the summary from the submitted input, instead of the prefix and the input.
+    transient_summary: Option<fn(&str) -> String>,
+    /// Called with each accepted line instead of exiting.
+    on_submit: Option<render::OnSubmit>,
+    /// What the key bound to `keymap::Action::Interrupt` does.
+    on_interrupt: InterruptAction,
+    /// Gives up the prompt after no keystroke for the duration.
+    inactivity_timeout: Option<Duration>,
+    /// Whether the time left until the inactivity timeout is shown.
+    inactivity_countdown: bool,
+    /// Whether the input is rejected, e.g. while a background task is running.
+    read_only: ReadOnly,
+    /// Style applied to the input line while read-only.
+    read_only_style: ContentStyle,
+    /// Checks the input in the background before it is accepted.
+    #[cfg(feature = "async")]
+    async_validator: Option<checker::AsyncValidator>,
+    /// The delay after the last keystroke before the input is checked while typing, if at all.
+    #[cfg(feature = "async")]
+    validate_on_idle: Option<Duration>,
+}
+
+/// Error returned by `run_until_valid`
+/// when the input is still invalid after the maximum number of attempts.
+#[derive(Debug, Eq, PartialEq)]
+pub struct AttemptsExceeded {
+    pub attempts: usize,
+}
+
+impl fmt::Display for AttemptsExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "no valid input after {} attempts", self.attempts)
+    }
 }
 
+impl std::error::Error for AttemptsExceeded {}
+
 impl Default for Readline {
     fn default() -> Self {
         Self {
             keymap: ActiveKeySwitcher::new("default", self::keymap::default as keymap::Keymap)
                 .register("on_suggest", self::keymap::on_suggest),
+            key_bindings: Default::default(),
             title_state: text::State {
                 text: Default::default(),
                 style: StyleBuilder::new()
                     .attrs(Attributes::from(Attribute::Bold))
                     .build(),
+                offset: 0,
             },
             text_editor_state: text_editor::State {
                 texteditor: Default::default(),
@@ -57,7 +148,13 @@ impl Default for Readline {
                 inactive_char_style: StyleBuilder::new().build(),
                 edit_mode: Default::default(),
                 word_break_chars: HashSet::from([' ']),
+                unicode_words: false,
+                right_prompt: Default::default(),
+                right_prompt_style: Default::default(),
                 lines: Default::default(),
+                scroll_top: Default::default(),
+                horizontal_scroll: false,
+                scroll_left: Default::default(),
             },
             suggest: Default::default(),
             suggest_state: listbox::State {
@@ -70,16 +167,41 @@ impl Default for Readline {
                         .build(),
                 ),
                 inactive_item_style: Some(StyleBuilder::new().fgc(Color::DarkGrey).build()),
+                description_style: StyleBuilder::new()
+                    .fgc(Color::DarkGrey)
+                    .attrs(Attributes::from(Attribute::Dim))
+                    .build(),
+                overflow: Default::default(),
+                item_lines: None,
+                partial_item: Default::default(),
                 lines: Some(3),
             },
             validator: Default::default(),
+            finalize_validator: Default::default(),
             error_message_state: text::State {
                 text: Default::default(),
                 style: StyleBuilder::new()
                     .fgc(Color::DarkRed)
                     .attrs(Attributes::from(Attribute::Bold))
                     .build(),
+                offset: 0,
             },
+            mode_indicator: Default::default(),
+            feedback: Default::default(),
+            transient: false,
+            transient_summary: None,
+            on_submit: None,
+            on_interrupt: Default::default(),
+            inactivity_timeout: None,
+            inactivity_countdown: false,
+            read_only: Default::default(),
+            read_only_style: StyleBuilder::new()
+                .attrs(Attributes::from(Attribute::Dim))
+                .build(),
+            #[cfg(feature = "async")]
+            async_validator: None,
+            #[cfg(feature = "async")]
+            validate_on_idle: None,
         }
     }
 }
@@ -98,12 +220,18 @@ impl Readline {
     }
 
     /// Enables suggestion functionality with the provided `Suggest` instance.
-    pub fn enable_suggest(mut self, suggest: Suggest) -> Self {
+    pub fn suggest(mut self, suggest: Suggest) -> Self {
         self.suggest = Some(suggest);
         self
     }
 
+    #[deprecated(since = "0.7.0", note = "renamed to `suggest`")]
+    pub fn enable_suggest(self, suggest: Suggest) -> Self {
+        self.suggest(suggest)
+    }
+
     /// Enables history functionality allowing navigation through previous inputs.
+    /// With suggestions enabled, the accepted inputs are also added to the candidates.
     pub fn enable_history(mut self) -> Self {
         self.text_editor_state.history = Some(History::default());
         self
@@ -116,8 +244,9 @@ impl Readline {
     }
 
     /// Sets the character used for masking input text, typically used for password fields.
-    pub fn mask(mut self, mask: char) -> Self {
-        self.text_editor_state.mask = Some(mask);
+    /// A `MaskPolicy` masks only a part of the input, e.g. after a delimiter.
+    pub fn mask<M: Into<text_editor::MaskPolicy>>(mut self, mask: M) -> Self {
+        self.text_editor_state.mask = Some(mask.into());
         self
     }
 
@@ -139,6 +268,19 @@ impl Readline {
         self
     }
 
+    /// Sets the text shown flush-right on the input line, e.g. a clock or a git branch.
+    /// It is truncated as the input grows into it, and never included in the result.
+    pub fn right_prompt<T: AsRef<str>>(mut self, text: T) -> Self {
+        self.text_editor_state.right_prompt = text.as_ref().to_string();
+        self
+    }
+
+    /// Sets the style for the right prompt.
+    pub fn right_prompt_style(mut self, style: ContentStyle) -> Self {
+        self.text_editor_state.right_prompt_style = style;
+        self
+    }
+
     /// Sets the edit mode for the text editor, either insert or overwrite.
     pub fn edit_mode(mut self, mode: text_editor::Mode) -> Self {
         self.text_editor_state.edit_mode = mode;
@@ -151,12 +293,177 @@ impl Readline {
         self
     }
 
+    /// Sets how the input is split into words for the word-wise movement and erasure
+    /// (e.g. <kbd>Alt + B</kbd> and <kbd>Ctrl + W</kbd>).
+    /// `WordNavigation::CharSet` sets the characters like `word_break_chars`, which is the default,
+    /// and `WordNavigation::Unicode` follows the Unicode word boundaries instead,
+    /// e.g. for `foo-bar` or CJK text without spaces.
+    pub fn word_navigation(mut self, navigation: text_editor::WordNavigation) -> Self {
+        match navigation {
+            text_editor::WordNavigation::CharSet(characters) => {
+                self.text_editor_state.word_break_chars = characters;
+                self.text_editor_state.unicode_words = false;
+            }
+            text_editor::WordNavigation::Unicode => self.text_editor_state.unicode_words = true,
+        }
+        self
+    }
+
     /// Sets the number of lines available for rendering the text editor.
     pub fn text_editor_lines(mut self, lines: usize) -> Self {
         self.text_editor_state.lines = Some(lines);
         self
     }
 
+    /// Sets whether the input is kept in a single row scrolling horizontally with the cursor,
+    /// with `<` and `>` marking the hidden parts, instead of wrapping into more rows.
+    pub fn horizontal_scroll(mut self, horizontal_scroll: bool) -> Self {
+        self.text_editor_state.horizontal_scroll = horizontal_scroll;
+        self
+    }
+
+    /// Sets the key bindings of the text editor, either emacs-like or vi.
+    /// Keymaps registered by `register_keymap` are kept, except `default`.
+    pub fn editor_mode(mut self, mode: EditorMode) -> Self {
+        self.keymap = match mode {
+            EditorMode::Emacs => self
+                .keymap
+                .register("default", self::keymap::default as keymap::Keymap),
+            EditorMode::Vi => self
+                .keymap
+                .register("default", self::keymap::vi::insert as keymap::Keymap)
+                .register(keymap::vi::NORMAL, self::keymap::vi::normal),
+        };
+        self
+    }
+
+    /// Sets the callback called on every switch of the vi mode (and once on start),
+    /// e.g. to change the prefix text or style according to the mode.
+    pub fn mode_indicator(mut self, indicator: keymap::vi::ModeIndicator) -> Self {
+        self.mode_indicator = Some(indicator);
+        self
+    }
+
+    /// Leaves only a one-line summary in the scrollback after submitting,
+    /// clearing the title, the suggestions and the error message (like fish's transient prompt).
+    /// The summary is the prefix and the (masked) input unless set by `transient_summary`.
+    pub fn transient(mut self, enable: bool) -> Self {
+        self.transient = enable;
+        self
+    }
+
+    /// Sets the function producing the summary left by `transient` from the submitted input.
+    pub fn transient_summary(mut self, summary: fn(&str) -> String) -> Self {
+        self.transient_summary = Some(summary);
+        self
+    }
+
+    /// Calls the function with each accepted (i.e. valid) line without exiting,
+    /// e.g. to evaluate the lines of a REPL.
+    /// The input is cleared to read the next line while it returns `PromptSignal::Continue`,
+    /// keeping the history and the suggestions; `PromptSignal::Quit` exits with the line.
+    /// An error exits the prompt with it.
+    pub fn on_submit<F>(mut self, on_submit: F) -> Self
+    where
+        F: FnMut(&str) -> anyhow::Result<PromptSignal> + 'static,
+    {
+        self.on_submit = Some(Box::new(on_submit));
+        self
+    }
+
+    /// Sets what the key bound to `keymap::Action::Interrupt` (Ctrl+C by default) does,
+    /// e.g. `InterruptAction::ClearLine` to discard the input without exiting.
+    /// By default, it returns the `Interrupted` error.
+    pub fn on_interrupt(mut self, action: InterruptAction) -> Self {
+        self.on_interrupt = action;
+        self
+    }
+
+    /// Sends each accepted line to the channel without exiting, like `on_submit` (which it replaces),
+    /// e.g. for the input to drive a background query while the prompt keeps running.
+    ///
+    /// The prompt never waits for the receiver: a line is dropped if the channel is full,
+    /// so the capacity should cover the lines typed faster than they are received.
+    /// Once the receiver is dropped, the lines are discarded and the prompt keeps running
+    /// until it is exited, e.g. with Ctrl+C.
+    #[cfg(feature = "async")]
+    pub fn submit_sender(self, sender: tokio::sync::mpsc::Sender<String>) -> Self {
+        self.on_submit(move |line| {
+            // Neither a full nor a closed channel stops the prompt.
+            let _ = sender.try_send(line.to_string());
+            Ok(PromptSignal::Continue)
+        })
+    }
+
+    /// Gives up the prompt after no key is pressed for the duration,
+    /// returning the `TimedOut` error after restoring the terminal,
+    /// e.g. for one-time codes that expire anyway.
+    /// Unlike `Prompt::timeout`, it is kept by the renderer, e.g. within `Wizard`.
+    pub fn inactivity_timeout(mut self, duration: Duration) -> Self {
+        self.inactivity_timeout = Some(duration);
+        self
+    }
+
+    /// Shows the seconds left until the inactivity timeout, dimmed in the right prompt.
+    pub fn inactivity_countdown(mut self, show: bool) -> Self {
+        self.inactivity_countdown = show;
+        self
+    }
+
+    /// Checks the input in the background on Enter, e.g. whether a username is taken,
+    /// after the `validator` has passed.
+    /// The future returns the error message shown for an invalid input.
+    /// Enter is held with a spinner until it resolves, and the input is accepted if it passes;
+    /// Ctrl+C cancels the check.
+    #[cfg(feature = "async")]
+    pub fn async_validator<F, Fut>(mut self, validator: F) -> Self
+    where
+        F: Fn(&str) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Result<(), String>> + Send + 'static,
+    {
+        self.async_validator = Some(std::sync::Arc::new(move |input: &str| {
+            Box::pin(validator(input))
+                as std::pin::Pin<Box<dyn std::future::Future<Output = _> + Send>>
+        }));
+        self
+    }
+
+    /// Also checks the input with the `async_validator` while typing,
+    /// once no key is pressed for the debounce delay.
+    /// The results for the inputs changed since are discarded.
+    #[cfg(feature = "async")]
+    pub fn validate_on_idle(mut self, debounce: Duration) -> Self {
+        self.validate_on_idle = Some(debounce);
+        self
+    }
+
+    /// Sets the feedback given when a keystroke is rejected (`Feedback::Flash` by default).
+    pub fn feedback(mut self, feedback: Feedback) -> Self {
+        self.feedback = feedback;
+        self
+    }
+
+    /// Shares the flag making the prompt read-only, e.g. with a background task setting it
+    /// until it completes. While read-only, the keystrokes except the interruption
+    /// are rejected with the feedback, and the input line is drawn with `read_only_style`.
+    pub fn read_only(mut self, read_only: ReadOnly) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Sets the style of the input line while read-only (dimmed by default).
+    pub fn read_only_style(mut self, style: ContentStyle) -> Self {
+        self.read_only_style = style;
+        self
+    }
+
+    /// Sets the keys bound to the actions of the default keymap,
+    /// e.g. `KeyBindings::default().bind(ctrl('p'), keymap::Action::HistoryPrev)`.
+    pub fn key_bindings(mut self, key_bindings: KeyBindings<keymap::Action>) -> Self {
+        self.key_bindings = key_bindings;
+        self
+    }
+
     pub fn register_keymap<K: AsRef<str>>(mut self, key: K, handler: keymap::Keymap) -> Self {
         self.keymap = self.keymap.register(key, handler);
         self
@@ -172,19 +479,228 @@ impl Readline {
         self
     }
 
+    /// Configures a validator applied to the result after the prompt has finished.
+    /// Unlike `validator`, this does not block Enter;
+    /// instead `run_until_valid` prompts again while the result is invalid.
+    pub fn finalize_validator(
+        mut self,
+        validator: Validator<str>,
+        error_message_generator: ErrorMessageGenerator<str>,
+    ) -> Self {
+        self.finalize_validator = Some(ValidatorManager::new(validator, error_message_generator));
+        self
+    }
+
     /// Initiates the prompt process,
     /// displaying the configured UI elements and handling user input.
+    /// The vi normal mode gets <kbd>d</kbd> <kbd>d</kbd> as a chord of the prompt,
+    /// so it is missing where the renderer runs without it (e.g. as a step of `Wizard`).
     pub fn prompt(self) -> anyhow::Result<Prompt<render::Renderer>> {
-        Ok(Prompt {
-            renderer: render::Renderer {
-                keymap: RefCell::new(self.keymap),
-                title_state: self.title_state,
-                text_editor_snapshot: Snapshot::<text_editor::State>::new(self.text_editor_state),
-                suggest: self.suggest,
-                suggest_snapshot: Snapshot::<listbox::State>::new(self.suggest_state),
-                validator: self.validator,
-                error_message_snapshot: Snapshot::<text::State>::new(self.error_message_state),
-            },
-        })
+        Ok(Prompt::new(self.renderer()).chord_when(
+            ['d'.into(), 'd'.into()],
+            keymap::vi::is_normal_mode,
+            keymap::vi::delete_line,
+        ))
+    }
+
+    /// Builds the renderer without creating a prompt,
+    /// e.g. to be used as a step of `Wizard`.
+    pub fn renderer(mut self) -> render::Renderer {
+        if let Some(indicator) = self.mode_indicator {
+            indicator(keymap::vi::Mode::Insert, &mut self.text_editor_state);
+        }
+        render::Renderer {
+            keymap: RefCell::new(self.keymap),
+            key_bindings: self.key_bindings,
+            title_state: self.title_state,
+            text_editor_snapshot: Snapshot::<text_editor::State>::new(self.text_editor_state),
+            suggest: self.suggest,
+            suggest_snapshot: Snapshot::<listbox::State>::new(self.suggest_state),
+            validator: self.validator,
+            finalize_validator: self.finalize_validator,
+            error_message_snapshot: Snapshot::<text::State>::new(self.error_message_state),
+            mode_indicator: self.mode_indicator,
+            feedback: self.feedback,
+            transient: self.transient,
+            transient_summary: self.transient_summary,
+            on_submit: self.on_submit,
+            on_interrupt: self.on_interrupt,
+            inactivity_timeout: self
+                .inactivity_timeout
+                .map(|duration| Timeout::new(duration, TimeoutMode::Idle)),
+            inactivity_countdown: self.inactivity_countdown,
+            read_only: self.read_only,
+            read_only_style: self.read_only_style,
+            shown_read_only: Default::default(),
+            clock: Default::default(),
+            #[cfg(feature = "async")]
+            checker: self.async_validator.map(checker::Checker::new),
+            #[cfg(feature = "async")]
+            validate_on_idle: self.validate_on_idle,
+            #[cfg(feature = "async")]
+            submitting: false,
+            #[cfg(feature = "async")]
+            shows_check_error: false,
+            #[cfg(feature = "async")]
+            checking_spinner: Default::default(),
+            #[cfg(feature = "async")]
+            signal: None,
+            feedback_until: Default::default(),
+            pending_error: None,
+            output: Default::default(),
+            text_editor_area: Default::default(),
+        }
+    }
+}
+
+impl Prompt<render::Renderer> {
+    /// Runs the prompt repeatedly until the result passes the finalize-time validator.
+    /// The remaining attempts are shown along with the error message,
+    /// and `AttemptsExceeded` is returned once `max_attempts` runs have failed.
+    pub fn run_until_valid(&mut self, max_attempts: Option<usize>) -> anyhow::Result<String> {
+        self.run_parsed_until_valid::<String>(max_attempts)
+    }
+
+    /// Same as `run_until_valid`, but also parses the result into `T`,
+    /// treating a parse failure as an invalid attempt.
+    pub fn run_parsed_until_valid<T>(&mut self, max_attempts: Option<usize>) -> anyhow::Result<T>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.until_valid(max_attempts, Self::run)
+    }
+
+    /// Repeats `run` until its result is valid and parsed,
+    /// carrying the error message of an attempt over to the next run.
+    fn until_valid<T, F>(&mut self, max_attempts: Option<usize>, mut run: F) -> anyhow::Result<T>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+        F: FnMut(&mut Self) -> anyhow::Result<String>,
+    {
+        let mut attempts = 0;
+        loop {
+            let input = run(self)?;
+            let error_message = match &self.renderer.finalize_validator {
+                Some(validator) if !validator.validate(&input) => {
+                    validator.generate_error_message(&input)
+                }
+                _ => match input.parse::<T>() {
+                    Ok(ret) => {
+                        self.renderer.error_message_snapshot.reset_after_to_init();
+                        return Ok(ret);
+                    }
+                    Err(e) => e.to_string(),
+                },
+            };
+
+            attempts += 1;
+            self.renderer.pending_error = Some(match max_attempts {
+                Some(max) if attempts >= max => {
+                    return Err(AttemptsExceeded { attempts }.into());
+                }
+                Some(max) => format!("{} ({} attempts left)", error_message, max - attempts),
+                None => error_message,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod until_valid {
+        use crate::{
+            crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers},
+            terminal::TestTerminal,
+        };
+
+        use super::*;
+
+        fn key(code: KeyCode) -> Event {
+            Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+        }
+
+        /// Runs the prompt like `run_parsed_until_valid`, typing one line per attempt,
+        /// and returns the result along with the terminal of each attempt.
+        fn run<T>(
+            p: &mut Prompt<render::Renderer>,
+            max_attempts: Option<usize>,
+            lines: &[&str],
+        ) -> (anyhow::Result<T>, Vec<TestTerminal>)
+        where
+            T: FromStr,
+            T::Err: fmt::Display,
+        {
+            let mut lines = lines.iter();
+            let mut terminals = Vec::new();
+            let ret = p.until_valid(max_attempts, |p| {
+                let line = lines.next().expect("attempts ran out");
+                let events = line
+                    .chars()
+                    .map(|ch| key(KeyCode::Char(ch)))
+                    .chain([key(KeyCode::Enter)]);
+                let mut terminal = TestTerminal::new(80, 10);
+                let ret = p.run_with_events(events, &mut terminal);
+                terminals.push(terminal);
+                ret
+            });
+            (ret, terminals)
+        }
+
+        #[test]
+        fn test_shows_error_on_next_attempt() {
+            let mut p = Readline::default()
+                .finalize_validator(|text| text.len() > 2, |_| String::from("too short"))
+                .prompt()
+                .unwrap();
+            let (ret, terminals) = run::<String>(&mut p, Some(3), &["ab", "abc"]);
+            assert_eq!("abc", ret.unwrap());
+            assert!(terminals[1]
+                .frame(0)
+                .contains(&String::from("too short (2 attempts left)")));
+        }
+
+        #[test]
+        fn test_invalid_then_valid() {
+            let mut p = Readline::default()
+                .finalize_validator(|text| text.starts_with('y'), |_| String::from("not y"))
+                .prompt()
+                .unwrap();
+            let (ret, terminals) = run::<String>(&mut p, None, &["no", "yes"]);
+            assert_eq!("yes", ret.unwrap());
+            assert_eq!(2, terminals.len());
+            // Without the maximum, no attempts left are shown.
+            assert!(terminals[1].frame(0).contains(&String::from("not y")));
+        }
+
+        #[test]
+        fn test_parse_failure_counts_as_attempt() {
+            let mut p = Readline::default().prompt().unwrap();
+            let (ret, terminals) = run::<u8>(&mut p, Some(3), &["x", "300", "42"]);
+            assert_eq!(42, ret.unwrap());
+            assert!(terminals[1].frame(0).contains(&String::from(
+                "invalid digit found in string (2 attempts left)"
+            )));
+            assert!(terminals[2].frame(0).contains(&String::from(
+                "number too large to fit in target type (1 attempts left)"
+            )));
+        }
+
+        #[test]
+        fn test_attempts_exceeded() {
+            let mut p = Readline::default()
+                .finalize_validator(|text| !text.is_empty(), |_| String::from("empty"))
+                .prompt()
+                .unwrap();
+            let (ret, terminals) = run::<String>(&mut p, Some(2), &["", ""]);
+            assert_eq!(
+                AttemptsExceeded { attempts: 2 },
+                ret.unwrap_err().downcast::<AttemptsExceeded>().unwrap()
+            );
+            assert_eq!(2, terminals.len());
+        }
     }
 }
@@ -0,0 +1,224 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use tokio::{runtime::Runtime, task::JoinHandle};
+
+/// Checks the input in the background, e.g. whether a username is taken,
+/// returning the error message shown for an invalid input.
+pub type AsyncValidator =
+    Arc<dyn Fn(&str) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send>> + Send + Sync>;
+
+type Response = (u64, Result<(), String>);
+
+/// Runs the async validator for the latest input in the background.
+///
+/// Each request waits for its debounce delay before checking,
+/// and supersedes the previous one: the in-flight task is aborted,
+/// and any result tagged with an older version is discarded.
+/// The result of the latest input is kept until the next request.
+pub struct Checker {
+    runtime: Option<Runtime>,
+    validator: AsyncValidator,
+    /// Incremented on every request and cancellation, to tell the latest result from the stale ones.
+    version: u64,
+    /// The input being checked, and when the check starts after the debounce.
+    pending: Option<(String, Instant)>,
+    in_flight: Option<JoinHandle<()>>,
+    /// The input checked last, and its result.
+    checked: Option<(String, Result<(), String>)>,
+    sender: Sender<Response>,
+    receiver: Receiver<Response>,
+}
+
+impl Checker {
+    pub fn new(validator: AsyncValidator) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            runtime: None,
+            validator,
+            version: 0,
+            pending: None,
+            in_flight: None,
+            checked: None,
+            sender,
+            receiver,
+        }
+    }
+
+    /// Starts checking the input after the debounce, superseding the previous request.
+    /// The runtime running the checks is started on the first call.
+    pub fn request(&mut self, input: &str, debounce: Duration) -> anyhow::Result<()> {
+        self.cancel();
+        if self.runtime.is_none() {
+            self.runtime = Some(
+                tokio::runtime::Builder::new_multi_thread()
+                    .worker_threads(1)
+                    .enable_all()
+                    .build()?,
+            );
+        }
+        let Some(runtime) = &self.runtime else {
+            return Ok(());
+        };
+        let _guard = runtime.enter();
+        let version = self.version;
+        let sender = self.sender.clone();
+        let check = (self.validator)(input);
+        self.in_flight = Some(runtime.spawn(async move {
+            tokio::time::sleep(debounce).await;
+            sender.send((version, check.await)).ok();
+        }));
+        self.pending = Some((input.to_string(), Instant::now() + debounce));
+        Ok(())
+    }
+
+    /// Returns the result of the latest input if it has arrived since the last call.
+    pub fn poll(&mut self) -> Option<&Result<(), String>> {
+        // Checked before receiving, not to miss a result sent just before finishing.
+        let finished = self
+            .in_flight
+            .as_ref()
+            .is_some_and(|task| task.is_finished());
+        let mut latest = None;
+        while let Ok((version, result)) = self.receiver.try_recv() {
+            if version == self.version {
+                latest = Some(result);
+            }
+        }
+        if latest.is_none() && finished {
+            // It panicked.
+            latest = Some(Err(String::from("the validation ended without a result")));
+        }
+        let result = latest?;
+        self.in_flight = None;
+        let (input, _) = self.pending.take()?;
+        self.checked = Some((input, result));
+        self.checked.as_ref().map(|(_, result)| result)
+    }
+
+    /// Returns the result for the input, if it is the one checked last.
+    pub fn result(&self, input: &str) -> Option<&Result<(), String>> {
+        match &self.checked {
+            Some((checked, result)) if checked == input => Some(result),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the input is being checked (or waits for the debounce).
+    pub fn is_pending(&self, input: &str) -> bool {
+        self.pending
+            .as_ref()
+            .is_some_and(|(pending, _)| pending == input)
+    }
+
+    /// Returns whether any input is being checked (or waits for the debounce).
+    pub fn in_progress(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Returns whether a check is running, i.e. the debounce has passed.
+    pub fn is_checking(&self) -> bool {
+        self.pending
+            .as_ref()
+            .is_some_and(|(_, start)| *start <= Instant::now())
+    }
+
+    /// Aborts the in-flight check, if any, and discards any result still to arrive.
+    pub fn cancel(&mut self) {
+        if let Some(task) = self.in_flight.take() {
+            task.abort();
+        }
+        self.version += 1;
+        self.pending = None;
+    }
+
+    /// Cancels the in-flight check and forgets the last result.
+    pub fn reset(&mut self) {
+        self.cancel();
+        self.checked = None;
+    }
+}
+
+impl Drop for Checker {
+    fn drop(&mut self) {
+        self.cancel();
+        if let Some(runtime) = self.runtime.take() {
+            // Unlike dropping, this does not block nor panic in an async context.
+            runtime.shutdown_background();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn checker() -> Checker {
+        Checker::new(Arc::new(|input: &str| {
+            let input = input.to_string();
+            Box::pin(async move {
+                match input.as_str() {
+                    "slow" => {
+                        tokio::time::sleep(Duration::from_secs(5)).await;
+                        Ok(())
+                    }
+                    "taken" => Err(String::from("taken")),
+                    _ => Ok(()),
+                }
+            })
+        }))
+    }
+
+    fn wait(checker: &mut Checker) -> Option<Result<(), String>> {
+        let start = Instant::now();
+        while start.elapsed() < Duration::from_secs(5) {
+            if let Some(result) = checker.poll() {
+                return Some(result.clone());
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        None
+    }
+
+    mod poll {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let mut checker = checker();
+            checker.request("taken", Duration::ZERO).unwrap();
+            assert!(checker.is_pending("taken"));
+            assert_eq!(Some(Err(String::from("taken"))), wait(&mut checker));
+            assert_eq!(Some(&Err(String::from("taken"))), checker.result("taken"));
+            assert_eq!(None, checker.result("alice"));
+        }
+
+        #[test]
+        fn test_stale_result_is_discarded() {
+            let mut checker = checker();
+            checker.request("slow", Duration::ZERO).unwrap();
+            checker.request("alice", Duration::ZERO).unwrap();
+            assert!(!checker.is_pending("slow"));
+            assert_eq!(Some(Ok(())), wait(&mut checker));
+            assert_eq!(None, checker.result("slow"));
+            assert_eq!(Some(&Ok(())), checker.result("alice"));
+        }
+
+        #[test]
+        fn test_cancel() {
+            let mut checker = checker();
+            checker.request("taken", Duration::from_millis(20)).unwrap();
+            checker.cancel();
+            std::thread::sleep(Duration::from_millis(50));
+            assert_eq!(None, checker.poll());
+            assert!(!checker.is_checking());
+        }
+    }
+}
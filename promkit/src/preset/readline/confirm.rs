@@ -1,9 +1,22 @@
-use crate::Prompt;
+use crate::{
+    crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    pane::Pane,
+    Prompt, PromptSignal,
+};
 
 use super::{render, Readline};
 
-/// A wrapper around `Readline` for creating simple yes/no confirmation prompts.
-pub struct Confirm(Readline);
+/// A wrapper around `Readline` for creating simple yes/no confirmation prompts,
+/// returning whether the answer is yes.
+///
+/// Only `y` and `n` (in either case) can be typed, replacing the previous answer,
+/// and Enter submits the answer typed, or the default if nothing is typed.
+pub struct Confirm {
+    readline: Readline,
+    text: String,
+    /// The answer submitted by Enter without typing, if any.
+    default_yes: Option<bool>,
+}
 
 impl Confirm {
     /// Creates a new `Confirm` instance with a specified prompt text.
@@ -14,24 +27,157 @@ impl Confirm {
     ///
     /// * `text` - The text to display as part of the confirmation prompt.
     pub fn new<T: AsRef<str>>(text: T) -> Self {
-        Self(
-            Readline::default()
-                .prefix(format!("{} (y/n) ", text.as_ref()))
-                .validator(
-                    |text| -> bool {
-                        ["yes", "no", "y", "n", "Y", "N"]
-                            .iter()
-                            .any(|yn| *yn == text)
-                    },
-                    |_| String::from("Please type 'y' or 'n' as an answer"),
-                ),
-        )
+        Self {
+            readline: Readline::default(),
+            text: text.as_ref().to_string(),
+            default_yes: None,
+        }
+    }
+
+    /// Sets the answer submitted by Enter without typing, which is capitalized in the prompt,
+    /// i.e. "(Y/n)" for yes and "(y/N)" for no.
+    /// By default, there is no default answer, and Enter waits for one to be typed.
+    pub fn default_yes(mut self, default_yes: bool) -> Self {
+        self.default_yes = Some(default_yes);
+        self
     }
 
     /// Displays the confirmation prompt and waits for user input.
     /// Returns a `Result` containing the `Prompt` result,
-    /// which is the user's input.
-    pub fn prompt(self) -> anyhow::Result<Prompt<render::Renderer>> {
-        self.0.prompt()
+    /// which is whether the answer is yes.
+    pub fn prompt(self) -> anyhow::Result<Prompt<Renderer>> {
+        Ok(Prompt::new(self.renderer()))
+    }
+
+    /// Builds the renderer without creating a prompt,
+    /// e.g. to be used as a step of `Wizard`.
+    pub fn renderer(self) -> Renderer {
+        let choices = match self.default_yes {
+            Some(true) => "(Y/n)",
+            Some(false) => "(y/N)",
+            None => "(y/n)",
+        };
+        Renderer {
+            readline: self
+                .readline
+                .prefix(format!("{} {} ", self.text, choices))
+                .renderer(),
+            default_yes: self.default_yes,
+        }
+    }
+}
+
+/// Returns the answer of the text, i.e. whether it is yes, if it is one.
+fn parse(text: &str) -> Option<bool> {
+    match text {
+        "y" | "Y" => Some(true),
+        "n" | "N" => Some(false),
+        _ => None,
+    }
+}
+
+/// A `Renderer` of `Confirm`, rendering the readline and returning the answer.
+pub struct Renderer {
+    /// The renderer of the underlying readline, whose input is the answer.
+    pub readline: render::Renderer,
+    /// The answer submitted by Enter without typing, if any.
+    pub default_yes: Option<bool>,
+}
+
+impl Renderer {
+    /// Returns the answer typed, if any.
+    pub fn answer(&self) -> Option<bool> {
+        parse(
+            &self
+                .readline
+                .text_editor_snapshot
+                .after()
+                .texteditor
+                .text_without_cursor()
+                .to_string(),
+        )
+    }
+
+    /// Replaces the input with the answer.
+    pub fn set_answer(&mut self, yes: bool) {
+        self.readline
+            .text_editor_snapshot
+            .after_mut()
+            .texteditor
+            .replace(if yes { "y" } else { "n" });
+    }
+}
+
+impl crate::Finalizer for Renderer {
+    type Return = bool;
+
+    fn finalize(&mut self) -> anyhow::Result<Self::Return> {
+        let text = crate::Finalizer::finalize(&mut self.readline)?;
+        parse(&text)
+            .or(self.default_yes)
+            .ok_or_else(|| anyhow::anyhow!("no answer is given"))
+    }
+}
+
+impl crate::Renderer for Renderer {
+    fn create_panes(&self, width: u16, height: u16) -> Vec<Pane> {
+        self.readline.create_panes(width, height)
+    }
+
+    /// Types the answer for `y` and `n`, and ignores the other characters.
+    /// Enter submits the answer, typing the default first if nothing is typed.
+    /// The other keys (e.g. Ctrl+C or Backspace) are handled by the readline.
+    fn evaluate(&mut self, event: &Event) -> anyhow::Result<PromptSignal> {
+        match event {
+            Event::Key(KeyEvent {
+                code: KeyCode::Char(ch),
+                modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+                kind: KeyEventKind::Press,
+                ..
+            }) => {
+                if let Some(yes) = parse(&ch.to_string()) {
+                    self.set_answer(yes);
+                }
+                Ok(PromptSignal::Continue)
+            }
+            Event::Key(KeyEvent {
+                code: KeyCode::Enter,
+                modifiers: KeyModifiers::NONE,
+                kind: KeyEventKind::Press,
+                ..
+            }) => match self.answer().or(self.default_yes) {
+                Some(yes) => {
+                    self.set_answer(yes);
+                    self.readline.evaluate(event)
+                }
+                None => Ok(PromptSignal::Continue),
+            },
+            Event::Paste(_) => Ok(PromptSignal::Continue),
+            _ => self.readline.evaluate(event),
+        }
+    }
+
+    fn reset(&mut self) {
+        self.readline.reset();
+    }
+
+    fn final_panes(&self, width: u16, height: u16) -> Option<Vec<Pane>> {
+        self.readline.final_panes(width, height)
+    }
+
+    fn tick_interval(&self) -> Option<std::time::Duration> {
+        self.readline.tick_interval()
+    }
+
+    fn tick(&mut self) -> anyhow::Result<bool> {
+        self.readline.tick()
+    }
+
+    fn take_output(&mut self) -> Option<String> {
+        self.readline.take_output()
+    }
+
+    fn take_signal(&mut self) -> Option<PromptSignal> {
+        self.readline.take_signal()
     }
 }
@@ -1,22 +1,102 @@
 use crossterm::style::ContentStyle;
 
 use crate::{
-    crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers},
+    crossterm::event::{
+        Event, KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers, MouseButton,
+        MouseEvent, MouseEventKind,
+    },
+    key_bindings::{alt, ctrl, KeyBindings},
     listbox::Listbox,
-    preset, text_editor, PromptSignal,
+    preset, text_editor, Eof, Interrupted, PromptSignal,
 };
 
+pub mod vi;
+
 pub type Keymap = fn(
     event: &Event,
     renderer: &mut preset::readline::render::Renderer,
 ) -> anyhow::Result<PromptSignal>;
 
-/// Default key bindings for the text editor.
+/// Actions of the text editor, bound to keys by `KeyBindings<Action>`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// Exit the editor if the input is valid, otherwise show the error message.
+    Accept,
+    /// Interrupt the current operation.
+    Interrupt,
+    /// Autocomplete the current input based on the available suggestions.
+    Complete,
+    /// Move the cursor one character to the left.
+    MoveLeft,
+    /// Move the cursor one character to the right.
+    MoveRight,
+    /// Move the cursor to the start of the line.
+    MoveToHead,
+    /// Move the cursor to the end of the line.
+    MoveToTail,
+    /// Move the cursor to the previous nearest word break character.
+    MoveWordLeft,
+    /// Move the cursor to the next nearest word break character.
+    MoveWordRight,
+    /// Recall the previous entry from the history.
+    HistoryPrev,
+    /// Recall the next entry from the history.
+    HistoryNext,
+    /// Delete the character before the cursor.
+    Erase,
+    /// Delete all characters in the line.
+    EraseAll,
+    /// Delete the character under the cursor, or end the input with the `Eof` error if it is empty.
+    DeleteOrEof,
+    /// Erase to the previous nearest word break character.
+    EraseWordLeft,
+    /// Erase to the next nearest word break character.
+    EraseWordRight,
+    /// Swap the character before the cursor with the one at it (the last two at the end).
+    TransposeChars,
+    /// Uppercase from the cursor to the end of the word.
+    UpcaseWord,
+    /// Lowercase from the cursor to the end of the word.
+    DowncaseWord,
+    /// Capitalize from the cursor to the end of the word.
+    CapitalizeWord,
+}
+
+impl Default for KeyBindings<Action> {
+    /// Returns the bindings listed in `default`.
+    fn default() -> Self {
+        KeyBindings::empty()
+            .bind(KeyCode::Enter, Action::Accept)
+            .bind(ctrl('c'), Action::Interrupt)
+            .bind(KeyCode::Tab, Action::Complete)
+            .bind(KeyCode::Left, Action::MoveLeft)
+            .bind(KeyCode::Right, Action::MoveRight)
+            .bind(ctrl('a'), Action::MoveToHead)
+            .bind(ctrl('e'), Action::MoveToTail)
+            .bind(alt('b'), Action::MoveWordLeft)
+            .bind(alt('f'), Action::MoveWordRight)
+            .bind(KeyCode::Up, Action::HistoryPrev)
+            .bind(KeyCode::Down, Action::HistoryNext)
+            .bind(KeyCode::Backspace, Action::Erase)
+            .bind(ctrl('u'), Action::EraseAll)
+            .bind(ctrl('d'), Action::DeleteOrEof)
+            .bind(ctrl('w'), Action::EraseWordLeft)
+            .bind(alt('d'), Action::EraseWordRight)
+            .bind(ctrl('t'), Action::TransposeChars)
+            .bind(alt('u'), Action::UpcaseWord)
+            .bind(alt('l'), Action::DowncaseWord)
+            .bind(alt('c'), Action::CapitalizeWord)
+    }
+}
+
+/// Default key bindings for the text editor,
+/// evaluating the keys through the `KeyBindings` of the renderer.
+/// The keys changing nothing (e.g. <kbd>←</kbd> at the head) give the feedback of `Readline::feedback`.
 ///
 /// | Key                    | Action
 /// | :--------------------- | :-------------------------------------------
-/// | <kbd>Enter</kbd>       | Exit the editor if input is valid, otherwise show error message
-/// | <kbd>Ctrl + C</kbd>    | Interrupt the current operation
+/// | <kbd>Enter</kbd>       | Exit the editor (or call `Readline::on_submit`) if input is valid, otherwise show error message
+/// | <kbd>Ctrl + C</kbd>    | Interrupt the current operation, or as set by `Readline::on_interrupt`
 /// | <kbd>←</kbd>           | Move the cursor one character to the left
 /// | <kbd>→</kbd>           | Move the cursor one character to the right
 /// | <kbd>Ctrl + A</kbd>    | Move the cursor to the start of the line
@@ -25,188 +105,140 @@ pub type Keymap = fn(
 /// | <kbd>↓</kbd>           | Recall the next entry from history
 /// | <kbd>Backspace</kbd>   | Delete the character before the cursor
 /// | <kbd>Ctrl + U</kbd>    | Delete all characters in the current line
+/// | <kbd>Ctrl + D</kbd>    | Delete the character under the cursor, or end the input (`Eof` error) if it is empty
 /// | <kbd>Tab</kbd>         | Autocomplete the current input based on available suggestions
-/// | <kbd>Alt + B</kbd>     | Move the cursor to the previous nearest character within set (default: whitespace)
-/// | <kbd>Alt + F</kbd>     | Move the cursor to the next nearest character within set (default: whitespace)
-/// | <kbd>Ctrl + W</kbd>    | Erase to the previous nearest character within set (default: whitespace)
-/// | <kbd>Alt + D</kbd>     | Erase to the next nearest character within set (default: whitespace)
+/// | <kbd>Alt + B</kbd>     | Move the cursor to the previous nearest character within set (default: whitespace), see `Readline::word_navigation`
+/// | <kbd>Alt + F</kbd>     | Move the cursor to the next nearest character within set (default: whitespace), see `Readline::word_navigation`
+/// | <kbd>Ctrl + W</kbd>    | Erase to the previous nearest character within set (default: whitespace), see `Readline::word_navigation`
+/// | <kbd>Alt + D</kbd>     | Erase to the next nearest character within set (default: whitespace), see `Readline::word_navigation`
+/// | <kbd>Ctrl + T</kbd>    | Swap the character before the cursor with the one at it (the last two at the end)
+/// | <kbd>Alt + U</kbd>     | Uppercase from the cursor to the end of the word
+/// | <kbd>Alt + L</kbd>     | Lowercase from the cursor to the end of the word
+/// | <kbd>Alt + C</kbd>     | Capitalize from the cursor to the end of the word
+/// | Left click             | Move the cursor to the clicked character (with `Prompt::mouse_capture`)
 pub fn default(
     event: &Event,
     renderer: &mut preset::readline::render::Renderer,
 ) -> anyhow::Result<PromptSignal> {
     let text_editor_after_mut = renderer.text_editor_snapshot.after_mut();
-    let error_message_after_mut = renderer.error_message_snapshot.after_mut();
     let suggest_after_mut = renderer.suggest_snapshot.after_mut();
 
-    match event {
-        Event::Key(KeyEvent {
-            code: KeyCode::Enter,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => {
-            let text = text_editor_after_mut
+    if let Some(action) = renderer.key_bindings.action(event) {
+        // Whether the keystroke changes nothing, to give the feedback.
+        let mut rejected = false;
+        match action {
+            Action::Accept => return accept(renderer),
+            Action::Interrupt => return interrupt(renderer),
+            Action::Complete => {
+                let candidates = renderer.suggest.as_ref().and_then(|suggest| {
+                    suggest.prefix_search_with_desc(
+                        text_editor_after_mut
+                            .texteditor
+                            .text_without_cursor()
+                            .to_string(),
+                    )
+                });
+                match candidates {
+                    Some(candidates) => {
+                        suggest_after_mut.listbox =
+                            Listbox::from_displayable_with_descriptions(candidates);
+                        text_editor_after_mut
+                            .texteditor
+                            .replace(&suggest_after_mut.listbox.get().to_string());
+
+                        renderer.keymap.borrow_mut().switch("on_suggest");
+                    }
+                    None => rejected = true,
+                }
+            }
+            Action::MoveLeft => rejected = !text_editor_after_mut.texteditor.backward(),
+            Action::MoveRight => rejected = !text_editor_after_mut.texteditor.forward(),
+            Action::MoveToHead => text_editor_after_mut.texteditor.move_to_head(),
+            Action::MoveToTail => text_editor_after_mut.texteditor.move_to_tail(),
+            Action::MoveWordLeft => text_editor_after_mut
                 .texteditor
-                .text_without_cursor()
-                .to_string();
-            let valid = renderer
-                .validator
-                .as_ref()
-                .map(|validator| {
-                    let valid = validator.validate(&text);
-                    if !valid {
-                        error_message_after_mut.text = validator.generate_error_message(&text);
+                .move_word_back(&text_editor_after_mut.word_navigation()),
+            Action::MoveWordRight => text_editor_after_mut
+                .texteditor
+                .move_word_forward(&text_editor_after_mut.word_navigation()),
+            Action::HistoryPrev => {
+                rejected = true;
+                if let Some(ref mut history) = &mut text_editor_after_mut.history {
+                    if history.backward() {
+                        text_editor_after_mut.texteditor.replace(&history.get());
+                        rejected = false;
                     }
-                    valid
-                })
-                .unwrap_or(true);
-            return {
-                if valid {
-                    if let Some(ref mut history) = &mut text_editor_after_mut.history {
-                        history.insert(text);
+                }
+            }
+            Action::HistoryNext => {
+                rejected = true;
+                if let Some(ref mut history) = &mut text_editor_after_mut.history {
+                    if history.forward() {
+                        text_editor_after_mut.texteditor.replace(&history.get());
+                        rejected = false;
                     }
-                    // For representing the end of the prompt,
-                    // reset the style of the cursor to default.
-                    text_editor_after_mut.active_char_style = ContentStyle::default();
-                    Ok(PromptSignal::Quit)
-                } else {
-                    Ok(PromptSignal::Continue)
                 }
-            };
-        }
-        Event::Key(KeyEvent {
-            code: KeyCode::Char('c'),
-            modifiers: KeyModifiers::CONTROL,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => return Err(anyhow::anyhow!("ctrl+c")),
-
-        Event::Key(KeyEvent {
-            code: KeyCode::Tab,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => {
-            if let Some(suggest) = &renderer.suggest {
-                let text = text_editor_after_mut
-                    .texteditor
-                    .text_without_cursor()
-                    .to_string();
-                if let Some(candidates) = suggest.prefix_search(text) {
-                    suggest_after_mut.listbox = Listbox::from_displayable(candidates);
-                    text_editor_after_mut
-                        .texteditor
-                        .replace(&suggest_after_mut.listbox.get().to_string());
-
-                    renderer.keymap.borrow_mut().switch("on_suggest");
+            }
+            Action::Erase => {
+                rejected = text_editor_after_mut.texteditor.position() == 0;
+                text_editor_after_mut.texteditor.erase();
+            }
+            Action::EraseAll => text_editor_after_mut.texteditor.erase_all(),
+            Action::DeleteOrEof => {
+                let texteditor = &mut text_editor_after_mut.texteditor;
+                if texteditor.text_without_cursor().is_empty() {
+                    return Err(Eof.into());
+                }
+                rejected = !texteditor.forward();
+                if !rejected {
+                    texteditor.erase();
                 }
             }
+            Action::EraseWordLeft => text_editor_after_mut
+                .texteditor
+                .erase_word_back(&text_editor_after_mut.word_navigation()),
+            Action::EraseWordRight => text_editor_after_mut
+                .texteditor
+                .erase_word_forward(&text_editor_after_mut.word_navigation()),
+            Action::TransposeChars => {
+                rejected = !text_editor_after_mut.texteditor.transpose_chars()
+            }
+            Action::UpcaseWord => {
+                rejected = !text_editor_after_mut
+                    .texteditor
+                    .upcase_word(&text_editor_after_mut.word_break_chars)
+            }
+            Action::DowncaseWord => {
+                rejected = !text_editor_after_mut
+                    .texteditor
+                    .downcase_word(&text_editor_after_mut.word_break_chars)
+            }
+            Action::CapitalizeWord => {
+                rejected = !text_editor_after_mut
+                    .texteditor
+                    .capitalize_word(&text_editor_after_mut.word_break_chars)
+            }
         }
-
-        // Move cursor.
-        Event::Key(KeyEvent {
-            code: KeyCode::Left,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => {
-            text_editor_after_mut.texteditor.backward();
-        }
-        Event::Key(KeyEvent {
-            code: KeyCode::Right,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => {
-            text_editor_after_mut.texteditor.forward();
+        if rejected {
+            renderer.reject_feedback();
         }
-        Event::Key(KeyEvent {
-            code: KeyCode::Char('a'),
-            modifiers: KeyModifiers::CONTROL,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => text_editor_after_mut.texteditor.move_to_head(),
-        Event::Key(KeyEvent {
-            code: KeyCode::Char('e'),
-            modifiers: KeyModifiers::CONTROL,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => text_editor_after_mut.texteditor.move_to_tail(),
-
-        // Move cursor to the nearest character.
-        Event::Key(KeyEvent {
-            code: KeyCode::Char('b'),
-            modifiers: KeyModifiers::ALT,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => text_editor_after_mut
-            .texteditor
-            .move_to_previous_nearest(&text_editor_after_mut.word_break_chars),
-
-        Event::Key(KeyEvent {
-            code: KeyCode::Char('f'),
-            modifiers: KeyModifiers::ALT,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => text_editor_after_mut
-            .texteditor
-            .move_to_next_nearest(&text_editor_after_mut.word_break_chars),
-
-        // Erase char(s).
-        Event::Key(KeyEvent {
-            code: KeyCode::Backspace,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => text_editor_after_mut.texteditor.erase(),
-        Event::Key(KeyEvent {
-            code: KeyCode::Char('u'),
-            modifiers: KeyModifiers::CONTROL,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => text_editor_after_mut.texteditor.erase_all(),
-
-        // Erase to the nearest character.
-        Event::Key(KeyEvent {
-            code: KeyCode::Char('w'),
-            modifiers: KeyModifiers::CONTROL,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => text_editor_after_mut
-            .texteditor
-            .erase_to_previous_nearest(&text_editor_after_mut.word_break_chars),
-
-        Event::Key(KeyEvent {
-            code: KeyCode::Char('d'),
-            modifiers: KeyModifiers::ALT,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => text_editor_after_mut
-            .texteditor
-            .erase_to_next_nearest(&text_editor_after_mut.word_break_chars),
+        return Ok(PromptSignal::Continue);
+    }
 
-        // Choose history
-        Event::Key(KeyEvent {
-            code: KeyCode::Up,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => {
-            if let Some(ref mut history) = &mut text_editor_after_mut.history {
-                if history.backward() {
-                    text_editor_after_mut.texteditor.replace(&history.get())
-                }
-            }
-        }
-        Event::Key(KeyEvent {
-            code: KeyCode::Down,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
+    match event {
+        // Clicks outside the input line are ignored.
+        Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            ..
         }) => {
-            if let Some(ref mut history) = &mut text_editor_after_mut.history {
-                if history.forward() {
-                    text_editor_after_mut.texteditor.replace(&history.get())
+            if let Some((top, width, height)) = renderer.text_editor_area.get() {
+                if let Some(position) = row
+                    .checked_sub(top)
+                    .and_then(|row| text_editor_after_mut.position_at(width, height, *column, row))
+                {
+                    text_editor_after_mut.texteditor.move_to(position);
                 }
             }
         }
@@ -233,6 +265,9 @@ pub fn default(
     Ok(PromptSignal::Continue)
 }
 
+/// Key bindings while choosing a suggestion: <kbd>Tab</kbd> and <kbd>↓</kbd> choose the next one,
+/// <kbd>↑</kbd> the previous one, and any other key closes the suggestions.
+/// The key bound to `Action::Interrupt` interrupts.
 pub fn on_suggest(
     event: &Event,
     renderer: &mut preset::readline::render::Renderer,
@@ -240,14 +275,11 @@ pub fn on_suggest(
     let text_editor_after_mut = renderer.text_editor_snapshot.after_mut();
     let suggest_after_mut = renderer.suggest_snapshot.after_mut();
 
-    match event {
-        Event::Key(KeyEvent {
-            code: KeyCode::Char('c'),
-            modifiers: KeyModifiers::CONTROL,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
-        }) => return Err(anyhow::anyhow!("ctrl+c")),
+    if renderer.key_bindings.action(event) == Some(&Action::Interrupt) {
+        return interrupt(renderer);
+    }
 
+    match event {
         Event::Key(KeyEvent {
             code: KeyCode::Tab,
             modifiers: KeyModifiers::NONE,
@@ -286,3 +318,98 @@ pub fn on_suggest(
     }
     Ok(PromptSignal::Continue)
 }
+
+/// Interrupts as set by `Readline::on_interrupt`: returns the `Interrupted` error,
+/// exits with the input as it is, or clears the input (closing the suggestions).
+pub fn interrupt(
+    renderer: &mut preset::readline::render::Renderer,
+) -> anyhow::Result<PromptSignal> {
+    match renderer.on_interrupt {
+        preset::readline::InterruptAction::Error => Err(Interrupted.into()),
+        preset::readline::InterruptAction::ReturnInput => {
+            // For representing the end of the prompt,
+            // reset the style of the cursor to default.
+            renderer.text_editor_snapshot.after_mut().active_char_style = ContentStyle::default();
+            Ok(PromptSignal::Quit)
+        }
+        preset::readline::InterruptAction::ClearLine => {
+            renderer
+                .text_editor_snapshot
+                .after_mut()
+                .texteditor
+                .erase_all();
+            renderer.error_message_snapshot.after_mut().text = String::new();
+            let mut keymap = renderer.keymap.borrow_mut();
+            if keymap.active_key() == "on_suggest" {
+                renderer.suggest_snapshot.after_mut().listbox =
+                    Listbox::from_displayable(Vec::<String>::new());
+                keymap.switch("default");
+            }
+            Ok(PromptSignal::Continue)
+        }
+    }
+}
+
+/// Accepts the input: exits (or calls `Readline::on_submit`) if it is valid,
+/// otherwise shows the error message.
+/// With `Readline::async_validator`, an input not checked yet is checked first,
+/// and accepted by `Renderer::tick` once it passes.
+pub fn accept(renderer: &mut preset::readline::render::Renderer) -> anyhow::Result<PromptSignal> {
+    let text_editor_after_mut = renderer.text_editor_snapshot.after_mut();
+    let error_message_after_mut = renderer.error_message_snapshot.after_mut();
+    let text = text_editor_after_mut
+        .texteditor
+        .text_without_cursor()
+        .to_string();
+    let valid = renderer
+        .validator
+        .as_ref()
+        .map(|validator| {
+            let valid = validator.validate(&text);
+            if !valid {
+                error_message_after_mut.text = validator.generate_error_message(&text);
+            }
+            valid
+        })
+        .unwrap_or(true);
+    if !valid {
+        return Ok(PromptSignal::Continue);
+    }
+    #[cfg(feature = "async")]
+    if let Some(checker) = &mut renderer.checker {
+        match checker.result(&text) {
+            Some(Ok(())) => {}
+            Some(Err(e)) => {
+                error_message_after_mut.text = e.clone();
+                return Ok(PromptSignal::Continue);
+            }
+            None => {
+                if !checker.is_pending(&text) {
+                    checker.request(&text, std::time::Duration::ZERO)?;
+                }
+                renderer.submitting = true;
+                return Ok(PromptSignal::Continue);
+            }
+        }
+    }
+    if let Some(ref mut history) = &mut text_editor_after_mut.history {
+        // Accepted entries are also completed from then on, like the history.
+        if let Some(suggest) = &mut renderer.suggest {
+            if !text.is_empty() {
+                suggest.insert(&text);
+            }
+        }
+        history.insert(&text);
+    }
+    if let Some(on_submit) = &mut renderer.on_submit {
+        if on_submit(&text)? == PromptSignal::Continue {
+            text_editor_after_mut.texteditor.erase_all();
+            error_message_after_mut.text = String::new();
+            return Ok(PromptSignal::Continue);
+        }
+    }
+    // For representing the end of the prompt,
+    // reset the style of the cursor to default.
+    text_editor_after_mut.active_char_style = ContentStyle::default();
+    Ok(PromptSignal::Quit)
+}
@@ -0,0 +1,171 @@
+use crate::{
+    crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers},
+    preset, text_editor, PromptSignal,
+};
+
+/// The mode of the vi key bindings.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Mode {
+    /// Keys are inserted as text.
+    #[default]
+    Insert,
+    /// Keys are commands moving the cursor or editing the text.
+    Normal,
+}
+
+/// Called with the new mode whenever the mode changes (and once with `Mode::Insert` on start),
+/// e.g. to change the prefix text or style of the text editor.
+pub type ModeIndicator = fn(mode: Mode, state: &mut text_editor::State);
+
+/// Key of the keymap for the normal mode.
+pub const NORMAL: &str = "vi_normal";
+
+/// Returns the press of the key without modifiers (except Shift for the upper case).
+fn plain_key(event: &Event) -> Option<KeyCode> {
+    match event {
+        Event::Key(KeyEvent {
+            code,
+            modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }) => Some(*code),
+        _ => None,
+    }
+}
+
+fn is_interrupt(event: &Event) -> bool {
+    matches!(
+        event,
+        Event::Key(KeyEvent {
+            code: KeyCode::Char('c'),
+            modifiers: KeyModifiers::CONTROL,
+            kind: KeyEventKind::Press,
+            ..
+        })
+    )
+}
+
+/// Keeps the cursor on a character, since the normal mode never places it after the text.
+fn clamp_cursor(texteditor: &mut text_editor::TextEditor) {
+    if texteditor.position() > 0 && texteditor.position() >= texteditor.text_without_cursor().len()
+    {
+        texteditor.backward();
+    }
+}
+
+/// Key bindings for the insert mode of vi.
+///
+/// | Key                    | Action
+/// | :--------------------- | :-------------------------------------------
+/// | <kbd>Esc</kbd>         | Switch to the normal mode
+/// | Others                 | Same as `keymap::default`
+pub fn insert(
+    event: &Event,
+    renderer: &mut preset::readline::render::Renderer,
+) -> anyhow::Result<PromptSignal> {
+    match plain_key(event) {
+        Some(KeyCode::Esc) => {
+            let texteditor = &mut renderer.text_editor_snapshot.after_mut().texteditor;
+            // Like vi, the cursor moves onto the last inserted character.
+            texteditor.backward();
+            renderer.switch_vi_mode(Mode::Normal);
+            Ok(PromptSignal::Continue)
+        }
+        _ => super::default(event, renderer),
+    }
+}
+
+/// Key bindings for the normal mode of vi.
+///
+/// | Key                    | Action
+/// | :--------------------- | :-------------------------------------------
+/// | <kbd>i</kbd>           | Switch to the insert mode before the cursor
+/// | <kbd>a</kbd>           | Switch to the insert mode after the cursor
+/// | <kbd>I</kbd>           | Switch to the insert mode at the start of the line
+/// | <kbd>A</kbd>           | Switch to the insert mode at the end of the line
+/// | <kbd>h</kbd>, <kbd>←</kbd> | Move the cursor one character to the left
+/// | <kbd>l</kbd>, <kbd>→</kbd> | Move the cursor one character to the right
+/// | <kbd>w</kbd>           | Move the cursor to the next word (split as set by `Readline::word_navigation`)
+/// | <kbd>b</kbd>           | Move the cursor to the previous word (split as set by `Readline::word_navigation`)
+/// | <kbd>0</kbd>           | Move the cursor to the start of the line
+/// | <kbd>$</kbd>           | Move the cursor to the end of the line
+/// | <kbd>x</kbd>           | Delete the character under the cursor
+/// | <kbd>d</kbd> <kbd>d</kbd> | Delete all characters in the current line (a chord bound by `Readline::prompt`, see `delete_line`)
+/// | <kbd>Enter</kbd>, <kbd>Ctrl + C</kbd>, <kbd>↑</kbd>, <kbd>↓</kbd> | Same as `keymap::default`
+pub fn normal(
+    event: &Event,
+    renderer: &mut preset::readline::render::Renderer,
+) -> anyhow::Result<PromptSignal> {
+    let text_editor_after_mut = renderer.text_editor_snapshot.after_mut();
+    let navigation = text_editor_after_mut.word_navigation();
+    let texteditor = &mut text_editor_after_mut.texteditor;
+
+    match plain_key(event) {
+        Some(KeyCode::Char('i')) => renderer.switch_vi_mode(Mode::Insert),
+        Some(KeyCode::Char('a')) => {
+            texteditor.forward();
+            renderer.switch_vi_mode(Mode::Insert);
+        }
+        Some(KeyCode::Char('I')) => {
+            texteditor.move_to_head();
+            renderer.switch_vi_mode(Mode::Insert);
+        }
+        Some(KeyCode::Char('A')) => {
+            texteditor.move_to_tail();
+            renderer.switch_vi_mode(Mode::Insert);
+        }
+
+        Some(KeyCode::Char('h') | KeyCode::Left) => {
+            texteditor.backward();
+        }
+        Some(KeyCode::Char('l') | KeyCode::Right) => {
+            texteditor.forward();
+            clamp_cursor(texteditor);
+        }
+        Some(KeyCode::Char('w')) => {
+            texteditor.move_word_forward(&navigation);
+            clamp_cursor(texteditor);
+        }
+        Some(KeyCode::Char('b')) => texteditor.move_word_back(&navigation),
+        Some(KeyCode::Char('0')) => texteditor.move_to_head(),
+        Some(KeyCode::Char('$')) => {
+            texteditor.move_to_tail();
+            clamp_cursor(texteditor);
+        }
+
+        Some(KeyCode::Char('x'))
+            if texteditor.position() < texteditor.text_without_cursor().len() =>
+        {
+            texteditor.forward();
+            texteditor.erase();
+            clamp_cursor(texteditor);
+        }
+        Some(KeyCode::Enter | KeyCode::Up | KeyCode::Down) => {
+            let signal = super::default(event, renderer)?;
+            // The history may place the cursor after the text.
+            clamp_cursor(&mut renderer.text_editor_snapshot.after_mut().texteditor);
+            return Ok(signal);
+        }
+        _ if is_interrupt(event) => return super::default(event, renderer),
+        _ => (),
+    }
+    Ok(PromptSignal::Continue)
+}
+
+/// Returns whether the renderer is in the normal mode, for the chords of the normal mode.
+pub fn is_normal_mode(renderer: &preset::readline::render::Renderer) -> bool {
+    renderer.vi_mode() == Mode::Normal
+}
+
+/// Deletes all characters in the current line, bound to <kbd>d</kbd> <kbd>d</kbd>
+/// in the normal mode with `Prompt::chord_when`.
+pub fn delete_line(
+    renderer: &mut preset::readline::render::Renderer,
+) -> anyhow::Result<PromptSignal> {
+    renderer
+        .text_editor_snapshot
+        .after_mut()
+        .texteditor
+        .erase_all();
+    Ok(PromptSignal::Continue)
+}
@@ -1,5 +1,8 @@
+use std::time::Duration;
+
 use crate::{
     crossterm::style::ContentStyle,
+    text_editor,
     validate::{ErrorMessageGenerator, Validator},
     Prompt,
 };
@@ -30,8 +33,8 @@ impl Password {
         self
     }
 
-    /// Sets the character used for masking the password input.
-    pub fn mask(mut self, mask: char) -> Self {
+    /// Sets the character (or `MaskPolicy`) used for masking the password input.
+    pub fn mask<M: Into<text_editor::MaskPolicy>>(mut self, mask: M) -> Self {
         self = Password(self.0.mask(mask));
         self
     }
@@ -64,10 +67,28 @@ impl Password {
         self
     }
 
+    /// Gives up the prompt after no key is pressed for the duration (see `Readline::inactivity_timeout`).
+    pub fn inactivity_timeout(mut self, duration: Duration) -> Self {
+        self = Password(self.0.inactivity_timeout(duration));
+        self
+    }
+
+    /// Shows the seconds left until the inactivity timeout, dimmed in the right prompt.
+    pub fn inactivity_countdown(mut self, show: bool) -> Self {
+        self = Password(self.0.inactivity_countdown(show));
+        self
+    }
+
     /// Displays the password prompt and waits for user input.
     /// Returns a `Result` containing the `Prompt` result,
     /// which is the user's input.
     pub fn prompt(self) -> anyhow::Result<Prompt<render::Renderer>> {
         self.0.prompt()
     }
+
+    /// Builds the renderer without creating a prompt,
+    /// e.g. to be used as a step of `Wizard`.
+    pub fn renderer(self) -> render::Renderer {
+        self.0.renderer()
+    }
 }
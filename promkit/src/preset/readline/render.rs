@@ -1,18 +1,55 @@
-use std::cell::RefCell;
+use std::{
+    cell::{Cell, RefCell},
+    time::{Duration, Instant},
+};
 
 use crate::{
-    crossterm::event::Event, listbox, pane::Pane, snapshot::Snapshot, suggest::Suggest,
-    switch::ActiveKeySwitcher, text, text_editor, validate::ValidatorManager, PaneFactory,
-    PromptSignal,
+    clock::Clock,
+    crossterm::{
+        event::Event,
+        style::{Attribute, ContentStyle},
+    },
+    grapheme::StyledGraphemes,
+    key_bindings::KeyBindings,
+    listbox,
+    pane::Pane,
+    read_only::ReadOnly,
+    snapshot::Snapshot,
+    suggest::Suggest,
+    switch::ActiveKeySwitcher,
+    text, text_editor,
+    timeout::{self, Timeout},
+    validate::ValidatorManager,
+    PaneFactory, PromptSignal, TimedOut,
+};
+
+use super::{
+    keymap::{self, vi},
+    Feedback, InterruptAction,
 };
 
-use super::keymap;
+/// How long the prefix is flashed for `Feedback::Flash`.
+const FLASH_DURATION: Duration = Duration::from_millis(100);
+/// How long the notice is shown for `Feedback::Notice`.
+const NOTICE_DURATION: Duration = Duration::from_secs(1);
+/// The notice shown for `Feedback::Notice`.
+const NOTICE: &str = "rejected";
+/// How often a shared read-only flag is polled.
+const READ_ONLY_INTERVAL: Duration = Duration::from_millis(50);
+/// How often the check of the input is polled, advancing the spinner.
+#[cfg(feature = "async")]
+const CHECK_INTERVAL: Duration = Duration::from_millis(80);
+
+/// Called with each accepted line, returning whether to keep prompting or to exit.
+pub type OnSubmit = Box<dyn FnMut(&str) -> anyhow::Result<PromptSignal>>;
 
 /// A `Renderer` for the readline preset, responsible for managing the rendering process.
 /// It holds references to various components and their states, facilitating the rendering of the readline interface.
 pub struct Renderer {
     /// Manages key bindings and their associated actions within the readline interface.
     pub keymap: RefCell<ActiveKeySwitcher<keymap::Keymap>>,
+    /// The keys bound to the actions of the default keymap.
+    pub key_bindings: KeyBindings<keymap::Action>,
     /// Holds a title's renderer state, used for rendering the title section.
     pub title_state: text::State,
     /// Holds a snapshot of the text editor's renderer state, used for rendering the text input area.
@@ -23,8 +60,204 @@ pub struct Renderer {
     pub suggest_snapshot: Snapshot<listbox::State>,
     /// Optional validator manager for input validation.
     pub validator: Option<ValidatorManager<str>>,
+    /// Optional validator manager applied to the result after the prompt has finished.
+    pub finalize_validator: Option<ValidatorManager<str>>,
     /// Holds a snapshot of the error message's renderer state, used for rendering error messages.
     pub error_message_snapshot: Snapshot<text::State>,
+    /// The error message shown from the start of the next run instead of being cleared,
+    /// e.g. the error of the previous attempt of `Prompt::run_until_valid`.
+    pub pending_error: Option<String>,
+    /// Optional callback showing the mode of the vi key bindings.
+    pub mode_indicator: Option<vi::ModeIndicator>,
+    /// Where the text editor was last rendered, as (top row, width, height),
+    /// used to map mouse clicks to positions in the text.
+    pub text_editor_area: Cell<Option<(u16, u16, u16)>>,
+    /// The feedback given when a keystroke is rejected.
+    pub feedback: Feedback,
+    /// Until when the flash or the notice of the feedback is shown.
+    pub feedback_until: Option<Instant>,
+    /// The raw output to write before the next draw, i.e. the bell.
+    pub output: String,
+    /// Whether only a one-line summary of the prompt is left after submitting.
+    pub transient: bool,
+    /// Produces the summary from the submitted input, instead of the prefix and the input.
+    pub transient_summary: Option<fn(&str) -> String>,
+    /// Called with each accepted line, which is cleared to keep prompting
+    /// unless `PromptSignal::Quit` is returned.
+    pub on_submit: Option<OnSubmit>,
+    /// What the key bound to `keymap::Action::Interrupt` does.
+    pub on_interrupt: InterruptAction,
+    /// Gives up the prompt after no keystroke for its duration,
+    /// counting down from when the prompt is first drawn.
+    pub inactivity_timeout: Option<Timeout>,
+    /// Whether the time left until the inactivity timeout is shown in the right prompt.
+    pub inactivity_countdown: bool,
+    /// The source of the current time for the feedback and the inactivity timeout.
+    pub clock: Clock,
+    /// Whether the input is rejected, e.g. while a background task is running.
+    pub read_only: ReadOnly,
+    /// Style applied to the input line while read-only.
+    pub read_only_style: ContentStyle,
+    /// Whether the input line was last drawn read-only, to redraw it as the flag changes.
+    pub shown_read_only: Cell<bool>,
+    /// Checks the input in the background before it is accepted.
+    #[cfg(feature = "async")]
+    pub checker: Option<super::checker::Checker>,
+    /// The delay after the last keystroke before the input is checked while typing, if at all.
+    #[cfg(feature = "async")]
+    pub validate_on_idle: Option<Duration>,
+    /// Whether Enter waits for the check of the input.
+    #[cfg(feature = "async")]
+    pub submitting: bool,
+    /// Whether the error message shown is of the check.
+    #[cfg(feature = "async")]
+    pub shows_check_error: bool,
+    /// Shown in the error message pane while the input is being checked.
+    #[cfg(feature = "async")]
+    pub checking_spinner: crate::spinner::Spinner,
+    /// The signal raised by accepting the input once it passes the check.
+    #[cfg(feature = "async")]
+    pub signal: Option<PromptSignal>,
+}
+
+impl Renderer {
+    /// Returns the current mode of the vi key bindings.
+    /// It is always `vi::Mode::Insert` with the default (emacs-like) key bindings.
+    pub fn vi_mode(&self) -> vi::Mode {
+        match self.keymap.borrow().active_key() {
+            vi::NORMAL => vi::Mode::Normal,
+            _ => vi::Mode::Insert,
+        }
+    }
+
+    /// Switches the vi key bindings to the mode, and shows it with the mode indicator.
+    /// The insert mode uses the keymap registered as `default`.
+    pub fn switch_vi_mode(&mut self, mode: vi::Mode) {
+        self.keymap.borrow_mut().switch(match mode {
+            vi::Mode::Insert => "default",
+            vi::Mode::Normal => vi::NORMAL,
+        });
+        if let Some(indicator) = self.mode_indicator {
+            indicator(mode, self.text_editor_snapshot.after_mut());
+        }
+    }
+}
+
+impl Renderer {
+    /// Returns whether the input is rejected.
+    pub fn is_read_only(&self) -> bool {
+        self.read_only.get()
+    }
+
+    /// Makes the input rejected or editable again, e.g. around a background task.
+    /// See `Readline::read_only` to set it from the task itself.
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only.set(read_only);
+    }
+
+    /// Gives the feedback for a rejected keystroke.
+    /// Every keymap calls this for the keystrokes that change nothing,
+    /// e.g. Backspace at the head of the input.
+    pub fn reject_feedback(&mut self) {
+        match self.feedback {
+            Feedback::None => {}
+            Feedback::Bell => self.output.push('\x07'),
+            Feedback::Flash => self.feedback_until = Some(self.clock.now() + FLASH_DURATION),
+            Feedback::Notice => {
+                let error = self.error_message_snapshot.after_mut();
+                // A validation error is more helpful, so it is kept.
+                if error.text.is_empty() || error.text == NOTICE {
+                    error.text = String::from(NOTICE);
+                    self.feedback_until = Some(self.clock.now() + NOTICE_DURATION);
+                }
+            }
+        }
+    }
+
+    /// Stops showing the flash or the notice of the feedback.
+    fn clear_feedback(&mut self) {
+        if self.feedback_until.take().is_some() && self.feedback == Feedback::Notice {
+            let error = self.error_message_snapshot.after_mut();
+            if error.text == NOTICE {
+                error.text = String::new();
+            }
+        }
+    }
+
+    /// Returns the input without the cursor.
+    #[cfg(feature = "async")]
+    fn input(&self) -> String {
+        self.text_editor_snapshot
+            .after()
+            .texteditor
+            .text_without_cursor()
+            .to_string()
+    }
+
+    /// Cancels the check if the keymap has failed (e.g. on Ctrl+C),
+    /// or starts over if the input has changed from `before`,
+    /// checking it after the debounce of `validate_on_idle`.
+    #[cfg(feature = "async")]
+    fn recheck(&mut self, failed: bool, before: String) -> anyhow::Result<()> {
+        let input = self.input();
+        let Some(checker) = &mut self.checker else {
+            return Ok(());
+        };
+        if !failed && input == before {
+            return Ok(());
+        }
+        checker.cancel();
+        self.submitting = false;
+        if self.shows_check_error {
+            self.error_message_snapshot.after_mut().text = String::new();
+            self.shows_check_error = false;
+        }
+        if let (false, Some(debounce)) = (failed || input.is_empty(), self.validate_on_idle) {
+            checker.request(&input, debounce)?;
+        }
+        Ok(())
+    }
+
+    /// Applies the result of the check if it has arrived, accepting the input
+    /// if it was submitted and passes. Returns `true` if the panes need to be redrawn.
+    #[cfg(feature = "async")]
+    fn poll_check(&mut self) -> anyhow::Result<bool> {
+        let Some(checker) = &mut self.checker else {
+            return Ok(false);
+        };
+        if !checker.in_progress() {
+            return Ok(false);
+        }
+        self.checking_spinner.advance();
+        match checker.poll().cloned() {
+            Some(Err(e)) => {
+                self.error_message_snapshot.after_mut().text = e;
+                self.shows_check_error = true;
+                self.submitting = false;
+            }
+            Some(Ok(())) => {
+                if self.shows_check_error {
+                    self.error_message_snapshot.after_mut().text = String::new();
+                    self.shows_check_error = false;
+                }
+                if std::mem::take(&mut self.submitting) {
+                    self.signal = Some(keymap::accept(self)?);
+                }
+            }
+            None => {}
+        }
+        Ok(true)
+    }
+
+    /// Clears the input to start the next run.
+    fn clear_input(&mut self) {
+        // Keep the history across runs so that the same prompt can be reused.
+        let history = self.text_editor_snapshot.after().history.clone();
+        self.text_editor_snapshot.reset_after_to_init();
+        self.text_editor_snapshot.after_mut().history = history;
+        // Start the next run in the insert mode of the vi key bindings, if in use.
+        self.keymap.get_mut().switch("default");
+    }
 }
 
 impl crate::Finalizer for Renderer {
@@ -37,23 +270,214 @@ impl crate::Finalizer for Renderer {
             .texteditor
             .text_without_cursor()
             .to_string();
-        self.text_editor_snapshot.reset_after_to_init();
+        self.clear_input();
         Ok(ret)
     }
 }
 
 impl crate::Renderer for Renderer {
     fn create_panes(&self, width: u16, height: u16) -> Vec<Pane> {
-        vec![
+        let flash = self.feedback == Feedback::Flash && self.feedback_until.is_some();
+        let countdown = self
+            .inactivity_timeout
+            .as_ref()
+            .filter(|_| self.inactivity_countdown);
+        let read_only = self.read_only.get();
+        self.shown_read_only.set(read_only);
+        let text_editor_pane = if flash || countdown.is_some() || read_only {
+            let mut state = self.text_editor_snapshot.after().clone();
+            if read_only {
+                // The cursor is hidden by drawing it like the other characters.
+                state.prefix_style = self.read_only_style;
+                state.active_char_style = self.read_only_style;
+                state.inactive_char_style = self.read_only_style;
+            }
+            if flash {
+                state.prefix_style.attributes.toggle(Attribute::Reverse);
+            }
+            if let Some(timeout) = countdown {
+                if !state.right_prompt.is_empty() {
+                    state.right_prompt.push(' ');
+                }
+                let remaining = timeout.remaining(self.clock.now());
+                state
+                    .right_prompt
+                    .push_str(&timeout::format_remaining(remaining));
+                state.right_prompt_style.attributes.set(Attribute::Dim);
+            }
+            let pane = state.create_pane(width, height);
+            // Keep the scroll of the view for the next render.
+            let after = self.text_editor_snapshot.after();
+            after.scroll_top.set(state.scroll_top.get());
+            after.scroll_left.set(state.scroll_left.get());
+            pane
+        } else {
+            self.text_editor_snapshot.create_pane(width, height)
+        };
+        #[cfg(feature = "async")]
+        let error_message_pane = match &self.checker {
+            Some(checker) if checker.is_checking() => text::State {
+                text: format!("{} checking…", self.checking_spinner.current()),
+                style: Default::default(),
+                offset: 0,
+            }
+            .create_pane(width, height),
+            _ => self.error_message_snapshot.create_pane(width, height),
+        };
+        #[cfg(not(feature = "async"))]
+        let error_message_pane = self.error_message_snapshot.create_pane(width, height);
+        let panes = vec![
             self.title_state.create_pane(width, height),
-            self.error_message_snapshot.create_pane(width, height),
-            self.text_editor_snapshot.create_pane(width, height),
+            error_message_pane,
+            // The input line is kept while the other panes give up their rows.
+            text_editor_pane.with_priority(1),
             self.suggest_snapshot.create_pane(width, height),
-        ]
+        ];
+        let top = (panes[0].height() + panes[1].height()).min(height as usize) as u16;
+        // The suggestions below keep at least one row.
+        let bottom = u16::from(!panes[3].is_empty());
+        self.text_editor_area.set(Some((
+            top,
+            width,
+            height.saturating_sub(top + bottom).max(1),
+        )));
+        panes
     }
 
     fn evaluate(&mut self, event: &Event) -> anyhow::Result<PromptSignal> {
+        if let (Event::Key(_) | Event::Paste(_), Some(timeout)) = (event, &self.inactivity_timeout)
+        {
+            timeout.activity(self.clock.now());
+        }
+        if self.read_only.get() {
+            // The input is kept as it is while read-only, so it is not cleared.
+            if self.key_bindings.action(event) == Some(&keymap::Action::Interrupt)
+                && self.on_interrupt != InterruptAction::ClearLine
+            {
+                return keymap::interrupt(self);
+            }
+            if matches!(event, Event::Key(_) | Event::Paste(_)) {
+                self.reject_feedback();
+            }
+            return Ok(PromptSignal::Continue);
+        }
         let keymap = *self.keymap.borrow_mut().get();
+        #[cfg(feature = "async")]
+        if self.checker.is_some() {
+            let before = self.input();
+            let signal = keymap(event, self);
+            self.recheck(signal.is_err(), before)?;
+            return signal;
+        }
         keymap(event, self)
     }
+
+    fn reset(&mut self) {
+        self.clear_input();
+        self.suggest_snapshot.reset_after_to_init();
+        match self.pending_error.take() {
+            Some(error) => self.error_message_snapshot.after_mut().text = error,
+            None => self.error_message_snapshot.reset_after_to_init(),
+        }
+        self.feedback_until = None;
+        self.output.clear();
+        if let Some(timeout) = &self.inactivity_timeout {
+            timeout.reset();
+        }
+        #[cfg(feature = "async")]
+        {
+            if let Some(checker) = &mut self.checker {
+                checker.reset();
+            }
+            self.submitting = false;
+            self.shows_check_error = false;
+            self.signal = None;
+        }
+    }
+
+    fn final_panes(&self, width: u16, height: u16) -> Option<Vec<Pane>> {
+        if !self.transient {
+            return None;
+        }
+        let state = self.text_editor_snapshot.after();
+        let summary = match self.transient_summary {
+            Some(summary) => {
+                StyledGraphemes::from(summary(&state.texteditor.text_without_cursor().to_string()))
+            }
+            None => {
+                let mut input = match state.mask {
+                    Some(mask) => {
+                        let mut masked = state.texteditor.masking(mask);
+                        // Without the cursor.
+                        masked.pop_back();
+                        masked
+                    }
+                    None => state.texteditor.text_without_cursor(),
+                };
+                let mut summary = StyledGraphemes::from_str(&state.prefix, state.prefix_style);
+                summary.append(&mut input);
+                summary
+            }
+        };
+        let (matrix, offset) = summary.matrixify(width as usize, height as usize, 0);
+        Some(vec![Pane::new(matrix, offset)])
+    }
+
+    fn tick_interval(&self) -> Option<Duration> {
+        let now = self.clock.now();
+        let inactivity = self.inactivity_timeout.as_ref().map(|timeout| {
+            let remaining = timeout.remaining(now);
+            match self.inactivity_countdown {
+                true => remaining.min(timeout::COUNTDOWN_INTERVAL),
+                false => remaining,
+            }
+        });
+        let feedback = self
+            .feedback_until
+            .map(|until| until.saturating_duration_since(now));
+        let read_only = self.read_only.is_shared().then_some(READ_ONLY_INTERVAL);
+        #[cfg(feature = "async")]
+        let checking = self
+            .checker
+            .as_ref()
+            .filter(|checker| checker.in_progress())
+            .map(|_| CHECK_INTERVAL);
+        #[cfg(not(feature = "async"))]
+        let checking = None;
+        [inactivity, feedback, read_only, checking]
+            .into_iter()
+            .flatten()
+            .min()
+    }
+
+    fn tick(&mut self) -> anyhow::Result<bool> {
+        let now = self.clock.now();
+        if self
+            .inactivity_timeout
+            .as_ref()
+            .is_some_and(|timeout| timeout.is_expired(now))
+        {
+            return Err(TimedOut.into());
+        }
+        let mut redraw = self.inactivity_countdown && self.inactivity_timeout.is_some()
+            || self.read_only.get() != self.shown_read_only.get();
+        if self.feedback_until.is_some_and(|until| until <= now) {
+            self.clear_feedback();
+            redraw = true;
+        }
+        #[cfg(feature = "async")]
+        {
+            redraw |= self.poll_check()?;
+        }
+        Ok(redraw)
+    }
+
+    fn take_output(&mut self) -> Option<String> {
+        (!self.output.is_empty()).then(|| std::mem::take(&mut self.output))
+    }
+
+    #[cfg(feature = "async")]
+    fn take_signal(&mut self) -> Option<PromptSignal> {
+        self.signal.take()
+    }
 }
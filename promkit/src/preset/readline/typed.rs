@@ -0,0 +1,332 @@
+use std::{any, fmt, marker::PhantomData, ops::RangeInclusive, path::PathBuf, str::FromStr};
+
+use crate::{
+    crossterm::{
+        event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+        style::ContentStyle,
+    },
+    pane::Pane,
+    validate::{ErrorMessageGenerator, Validator},
+    Prompt, PromptSignal,
+};
+
+use super::{render, Readline};
+
+/// Parses the input of `Typed` into the value returned by the prompt.
+pub trait Parser {
+    type Output;
+
+    /// Parses the text, or returns the error message shown for it.
+    fn parse(&self, text: &str) -> Result<Self::Output, String>;
+}
+
+/// Parses the text as is with `str::parse`, showing the error of `T::from_str`.
+#[derive(Clone, Debug)]
+pub struct Parse<T> {
+    _output: PhantomData<fn() -> T>,
+}
+
+impl<T> Default for Parse<T> {
+    fn default() -> Self {
+        Self {
+            _output: PhantomData,
+        }
+    }
+}
+
+impl<T: FromStr> Parser for Parse<T>
+where
+    T::Err: fmt::Display,
+{
+    type Output = T;
+
+    fn parse(&self, text: &str) -> Result<T, String> {
+        text.parse::<T>().map_err(|e| e.to_string())
+    }
+}
+
+/// Parses a number, ignoring thousands separators (`,` and `_`),
+/// and checks that it is in the range if any.
+#[derive(Clone, Debug)]
+pub struct Number<T> {
+    range: Option<RangeInclusive<T>>,
+}
+
+impl<T> Default for Number<T> {
+    fn default() -> Self {
+        Self { range: None }
+    }
+}
+
+impl<T: FromStr + PartialOrd + fmt::Debug> Parser for Number<T> {
+    type Output = T;
+
+    fn parse(&self, text: &str) -> Result<T, String> {
+        let value = text
+            .trim()
+            .replace([',', '_'], "")
+            .parse::<T>()
+            .map_err(|_| format!("expected a number of type {}", any::type_name::<T>()))?;
+        match &self.range {
+            Some(range) if !range.contains(&value) => Err(format!("must be in {:?}", range)),
+            _ => Ok(value),
+        }
+    }
+}
+
+/// Parses a path, checking that it exists and has one of the extensions if required.
+#[derive(Clone, Debug, Default)]
+pub struct Path {
+    must_exist: bool,
+    /// The accepted extensions without the leading dot, or empty to accept any.
+    extensions: Vec<String>,
+}
+
+impl Parser for Path {
+    type Output = PathBuf;
+
+    fn parse(&self, text: &str) -> Result<PathBuf, String> {
+        if text.is_empty() {
+            return Err(String::from("expected a path"));
+        }
+        let path = PathBuf::from(text);
+        if self.must_exist && !path.exists() {
+            return Err(format!("no such file or directory: {}", text));
+        }
+        let matches = |extension: &str| {
+            path.extension()
+                .is_some_and(|ext| ext.to_string_lossy().eq_ignore_ascii_case(extension))
+        };
+        if !self.extensions.is_empty() && !self.extensions.iter().any(|ext| matches(ext)) {
+            return Err(format!(
+                "expected a file with extension {}",
+                self.extensions
+                    .iter()
+                    .map(|ext| format!(".{}", ext))
+                    .collect::<Vec<_>>()
+                    .join(" or ")
+            ));
+        }
+        Ok(path)
+    }
+}
+
+/// A `Readline` returning the input parsed by `P`, e.g. `Readline::number::<u16>()`.
+/// The input is checked as it is typed, and Enter is ignored until it parses.
+pub struct Typed<P> {
+    readline: Readline,
+    parser: P,
+}
+
+impl Readline {
+    /// Turns the prompt into one returning the input parsed by the parser.
+    pub fn typed<P: Parser>(self, parser: P) -> Typed<P> {
+        Typed {
+            readline: self,
+            parser,
+        }
+    }
+
+    /// Turns the prompt into one returning the input parsed into `T` with `str::parse`,
+    /// e.g. `Readline::default().parse_into::<u32>()`.
+    /// The error of the parse is shown below the title, keeping the input to be fixed.
+    pub fn parse_into<T: FromStr>(self) -> Typed<Parse<T>>
+    where
+        T::Err: fmt::Display,
+    {
+        self.typed(Parse::default())
+    }
+
+    /// Creates a prompt returning a number of type `T`.
+    pub fn number<T: FromStr + PartialOrd + fmt::Debug>() -> Typed<Number<T>> {
+        Readline::default().typed(Number::default())
+    }
+
+    /// Creates a prompt returning a path.
+    pub fn path() -> Typed<Path> {
+        Readline::default().typed(Path::default())
+    }
+}
+
+impl<T> Typed<Number<T>> {
+    /// Sets the range the number must be in.
+    pub fn range(mut self, range: RangeInclusive<T>) -> Self {
+        self.parser.range = Some(range);
+        self
+    }
+}
+
+impl Typed<Path> {
+    /// Sets whether the path must exist.
+    pub fn must_exist(mut self, must_exist: bool) -> Self {
+        self.parser.must_exist = must_exist;
+        self
+    }
+
+    /// Sets the extensions the path must have one of, e.g. `["toml", "yaml"]`.
+    pub fn extensions<E: AsRef<str>, I: IntoIterator<Item = E>>(mut self, extensions: I) -> Self {
+        self.parser.extensions = extensions
+            .into_iter()
+            .map(|ext| ext.as_ref().trim_start_matches('.').to_string())
+            .collect();
+        self
+    }
+}
+
+impl<P: Parser> Typed<P> {
+    /// Sets the title text displayed above the input field.
+    pub fn title<T: AsRef<str>>(mut self, text: T) -> Self {
+        self.readline = self.readline.title(text);
+        self
+    }
+
+    /// Sets the style for the title text.
+    pub fn title_style(mut self, style: ContentStyle) -> Self {
+        self.readline = self.readline.title_style(style);
+        self
+    }
+
+    /// Sets the prefix string displayed before the input text.
+    pub fn prefix<T: AsRef<str>>(mut self, prefix: T) -> Self {
+        self.readline = self.readline.prefix(prefix);
+        self
+    }
+
+    /// Sets the style for the prefix string.
+    pub fn prefix_style(mut self, style: ContentStyle) -> Self {
+        self.readline = self.readline.prefix_style(style);
+        self
+    }
+
+    /// Sets the style for the currently active character in the input field.
+    pub fn active_char_style(mut self, style: ContentStyle) -> Self {
+        self.readline = self.readline.active_char_style(style);
+        self
+    }
+
+    /// Sets the style for characters that are not currently active in the input field.
+    pub fn inactive_char_style(mut self, style: ContentStyle) -> Self {
+        self.readline = self.readline.inactive_char_style(style);
+        self
+    }
+
+    /// Configures a validator applied to the text on Enter, after it has been parsed.
+    pub fn validator(
+        mut self,
+        validator: Validator<str>,
+        error_message_generator: ErrorMessageGenerator<str>,
+    ) -> Self {
+        self.readline = self.readline.validator(validator, error_message_generator);
+        self
+    }
+
+    /// Initiates the prompt process,
+    /// displaying the configured UI elements and handling user input.
+    pub fn prompt(self) -> anyhow::Result<Prompt<Renderer<P>>> {
+        Ok(Prompt::new(Renderer {
+            readline: self.readline.renderer(),
+            parser: self.parser,
+            shows_error: false,
+        }))
+    }
+}
+
+/// A `Renderer` of `Typed`, rendering the readline and parsing its result.
+pub struct Renderer<P> {
+    /// The renderer of the underlying readline.
+    pub readline: render::Renderer,
+    pub parser: P,
+    /// Whether the error message shown is of the parser.
+    shows_error: bool,
+}
+
+impl<P: Parser> Renderer<P> {
+    fn text(&self) -> String {
+        self.readline
+            .text_editor_snapshot
+            .after()
+            .texteditor
+            .text_without_cursor()
+            .to_string()
+    }
+
+    /// Shows the error of the text, or clears the previous one if it parses.
+    /// An empty text is not reported until Enter.
+    fn check(&mut self, report_empty: bool) -> bool {
+        let text = self.text();
+        match self.parser.parse(&text) {
+            Err(e) if report_empty || !text.is_empty() => {
+                self.readline.error_message_snapshot.after_mut().text = e;
+                self.shows_error = true;
+                false
+            }
+            ret => {
+                if self.shows_error {
+                    self.readline.error_message_snapshot.after_mut().text = String::new();
+                    self.shows_error = false;
+                }
+                ret.is_ok()
+            }
+        }
+    }
+}
+
+impl<P: Parser> crate::Finalizer for Renderer<P> {
+    type Return = P::Output;
+
+    fn finalize(&mut self) -> anyhow::Result<Self::Return> {
+        let text = crate::Finalizer::finalize(&mut self.readline)?;
+        self.parser.parse(&text).map_err(|e| anyhow::anyhow!(e))
+    }
+}
+
+impl<P: Parser> crate::Renderer for Renderer<P> {
+    fn create_panes(&self, width: u16, height: u16) -> Vec<Pane> {
+        self.readline.create_panes(width, height)
+    }
+
+    fn evaluate(&mut self, event: &Event) -> anyhow::Result<PromptSignal> {
+        let submits = matches!(
+            event,
+            Event::Key(KeyEvent {
+                code: KeyCode::Enter,
+                modifiers: KeyModifiers::NONE,
+                kind: KeyEventKind::Press,
+                ..
+            })
+        ) && self.readline.keymap.borrow().active_key() != "on_suggest";
+        if submits && !self.check(true) {
+            return Ok(PromptSignal::Continue);
+        }
+        let signal = self.readline.evaluate(event)?;
+        if signal == PromptSignal::Continue {
+            self.check(false);
+        }
+        Ok(signal)
+    }
+
+    fn reset(&mut self) {
+        self.readline.reset();
+        self.shows_error = false;
+    }
+
+    fn final_panes(&self, width: u16, height: u16) -> Option<Vec<Pane>> {
+        self.readline.final_panes(width, height)
+    }
+
+    fn tick_interval(&self) -> Option<std::time::Duration> {
+        self.readline.tick_interval()
+    }
+
+    fn tick(&mut self) -> anyhow::Result<bool> {
+        self.readline.tick()
+    }
+
+    fn take_output(&mut self) -> Option<String> {
+        self.readline.take_output()
+    }
+
+    fn take_signal(&mut self) -> Option<PromptSignal> {
+        self.readline.take_signal()
+    }
+}
@@ -36,11 +36,16 @@ impl Tree {
                 style: StyleBuilder::new()
                     .attrs(Attributes::from(Attribute::Bold))
                     .build(),
+                offset: 0,
             },
             tree_state: tree::State {
                 tree: tree::Tree::new(root),
                 folded_symbol: String::from("▶︎ "),
                 unfolded_symbol: String::from("▼ "),
+                leaf_symbol: String::from("▶︎ "),
+                multi_select: false,
+                active_mark: '☒',
+                inactive_mark: '☐',
                 active_item_style: StyleBuilder::new().fgc(Color::DarkCyan).build(),
                 inactive_item_style: StyleBuilder::new().build(),
                 lines: Default::default(),
@@ -73,6 +78,24 @@ impl Tree {
         self
     }
 
+    /// Sets the symbol used to indicate a leaf node.
+    pub fn leaf_symbol<T: AsRef<str>>(mut self, symbol: T) -> Self {
+        self.tree_state.leaf_symbol = symbol.as_ref().to_string();
+        self
+    }
+
+    /// Sets the mark symbol used to indicate marked leaves in the multi-select mode.
+    pub fn active_mark(mut self, mark: char) -> Self {
+        self.tree_state.active_mark = mark;
+        self
+    }
+
+    /// Sets the mark symbol used to indicate unmarked leaves in the multi-select mode.
+    pub fn inactive_mark(mut self, mark: char) -> Self {
+        self.tree_state.inactive_mark = mark;
+        self
+    }
+
     /// Sets the style for active (currently selected) items.
     pub fn active_item_style(mut self, style: ContentStyle) -> Self {
         self.tree_state.active_item_style = style;
@@ -104,14 +127,26 @@ impl Tree {
 
     /// Displays the tree prompt and waits for user input.
     /// Returns a `Result` containing the `Prompt` result,
-    /// which is a list of selected options.
+    /// which is the ids along the path of the selected node from the root,
+    /// e.g. `["root", "src", "main.rs"]`.
     pub fn prompt(self) -> anyhow::Result<Prompt<render::Renderer>> {
-        Ok(Prompt {
-            renderer: render::Renderer {
-                keymap: RefCell::new(self.keymap),
-                title_state: self.title_state,
-                tree_state: self.tree_state,
-            },
-        })
+        Ok(Prompt::new(self.renderer()))
+    }
+
+    /// Displays the tree prompt in the multi-select mode,
+    /// where <kbd>Space</kbd> marks or unmarks leaves (and still folds the other nodes).
+    /// The result is the paths of all the marked leaves in the order of the tree.
+    /// Marks are kept while their ancestors are folded.
+    pub fn prompt_multi_select(mut self) -> anyhow::Result<Prompt<render::MultiSelectRenderer>> {
+        self.tree_state.multi_select = true;
+        Ok(Prompt::new(render::MultiSelectRenderer(self.renderer())))
+    }
+
+    fn renderer(self) -> render::Renderer {
+        render::Renderer {
+            keymap: RefCell::new(self.keymap),
+            title_state: self.title_state,
+            tree_state: self.tree_state,
+        }
     }
 }
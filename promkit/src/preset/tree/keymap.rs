@@ -3,7 +3,7 @@ use crate::{
         Event, KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers, MouseEvent,
         MouseEventKind,
     },
-    preset, PromptSignal,
+    preset, Interrupted, PromptSignal,
 };
 
 pub type Keymap = fn(
@@ -19,7 +19,7 @@ pub type Keymap = fn(
 /// | <kbd>Ctrl + C</kbd>    | Interrupt the current operation
 /// | <kbd>↑</kbd>           | Move the selection up
 /// | <kbd>↓</kbd>           | Move the selection down
-/// | <kbd>Space</kbd>       | Toggle fold/unfold at the current node
+/// | <kbd>Space</kbd>       | Toggle fold/unfold at the current node, or mark/unmark the current leaf in the multi-select mode
 pub fn default(
     event: &Event,
     renderer: &mut preset::tree::render::Renderer,
@@ -36,7 +36,7 @@ pub fn default(
             modifiers: KeyModifiers::CONTROL,
             kind: KeyEventKind::Press,
             state: KeyEventState::NONE,
-        }) => return Err(anyhow::anyhow!("ctrl+c")),
+        }) => return Err(Interrupted.into()),
 
         // Move cursor.
         Event::Key(KeyEvent {
@@ -80,7 +80,10 @@ pub fn default(
             kind: KeyEventKind::Press,
             state: KeyEventState::NONE,
         }) => {
-            renderer.tree_state.tree.toggle();
+            let state = &mut renderer.tree_state;
+            if !(state.multi_select && state.tree.toggle_mark()) {
+                state.tree.toggle();
+            }
         }
 
         _ => (),
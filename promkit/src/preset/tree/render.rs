@@ -1,4 +1,4 @@
-use std::cell::RefCell;
+use std::{cell::RefCell, time::Duration};
 
 use crate::{
     crossterm::event::Event, pane::Pane, switch::ActiveKeySwitcher, text, tree, PaneFactory,
@@ -38,4 +38,43 @@ impl crate::Renderer for Renderer {
         let keymap = *self.keymap.borrow_mut().get();
         keymap(event, self)
     }
+
+    /// Ticks right away while a node is expanded, to load its children
+    /// after its placeholder is drawn.
+    fn tick_interval(&self) -> Option<Duration> {
+        self.tree_state.tree.is_loading().then_some(Duration::ZERO)
+    }
+
+    fn tick(&mut self) -> anyhow::Result<bool> {
+        Ok(self.tree_state.tree.load())
+    }
+}
+
+/// A `Renderer` for the multi-select mode, returning the paths of all the marked leaves.
+pub struct MultiSelectRenderer(pub Renderer);
+
+impl crate::Finalizer for MultiSelectRenderer {
+    type Return = Vec<Vec<String>>;
+
+    fn finalize(&mut self) -> anyhow::Result<Self::Return> {
+        Ok(self.0.tree_state.tree.marked())
+    }
+}
+
+impl crate::Renderer for MultiSelectRenderer {
+    fn create_panes(&self, width: u16, height: u16) -> Vec<Pane> {
+        crate::Renderer::create_panes(&self.0, width, height)
+    }
+
+    fn evaluate(&mut self, event: &Event) -> anyhow::Result<PromptSignal> {
+        self.0.evaluate(event)
+    }
+
+    fn tick_interval(&self) -> Option<Duration> {
+        self.0.tick_interval()
+    }
+
+    fn tick(&mut self) -> anyhow::Result<bool> {
+        self.0.tick()
+    }
 }
@@ -0,0 +1,287 @@
+use crate::{
+    chord::KeyCombo,
+    crossterm::{
+        event::{Event, KeyCode},
+        style::{Attribute, Attributes, Color, ContentStyle},
+    },
+    listbox::Listbox,
+    pane::Pane,
+    preset::{confirm, listbox, query_selector, readline},
+    style::StyleBuilder,
+    text, Finalizer, Prompt, PromptSignal, Renderer,
+};
+
+pub mod render;
+
+/// A single step of a `Wizard`.
+///
+/// It is implemented for the renderers of the presets producing a single answer,
+/// i.e. `Readline` (including `Password`), `Confirm`, `Listbox` and `QuerySelector`,
+/// and can be implemented for custom renderers as well.
+pub trait Step {
+    /// Creates the panes of the step, rendered below the wizard header.
+    fn create_panes(&self, width: u16, height: u16) -> Vec<Pane>;
+    /// Evaluates an event, returning `PromptSignal::Quit` when the step is completed.
+    fn evaluate(&mut self, event: &Event) -> anyhow::Result<PromptSignal>;
+    /// Produces the answer of the completed step.
+    fn answer(&mut self) -> anyhow::Result<String>;
+    /// Restores the prior answer when going back to this step.
+    fn prefill(&mut self, answer: &str);
+    /// Restores the state to run the wizard again. The default does nothing.
+    fn reset(&mut self) {}
+}
+
+impl Step for readline::render::Renderer {
+    fn create_panes(&self, width: u16, height: u16) -> Vec<Pane> {
+        Renderer::create_panes(self, width, height)
+    }
+
+    fn evaluate(&mut self, event: &Event) -> anyhow::Result<PromptSignal> {
+        Renderer::evaluate(self, event)
+    }
+
+    fn answer(&mut self) -> anyhow::Result<String> {
+        self.finalize()
+    }
+
+    fn prefill(&mut self, answer: &str) {
+        self.text_editor_snapshot
+            .after_mut()
+            .texteditor
+            .replace(answer);
+    }
+
+    fn reset(&mut self) {
+        Renderer::reset(self)
+    }
+}
+
+impl Step for confirm::Renderer {
+    fn create_panes(&self, width: u16, height: u16) -> Vec<Pane> {
+        Renderer::create_panes(self, width, height)
+    }
+
+    fn evaluate(&mut self, event: &Event) -> anyhow::Result<PromptSignal> {
+        Renderer::evaluate(self, event)
+    }
+
+    fn answer(&mut self) -> anyhow::Result<String> {
+        Ok(String::from(if self.finalize()? { "y" } else { "n" }))
+    }
+
+    fn prefill(&mut self, answer: &str) {
+        self.set_answer(answer == "y");
+    }
+
+    fn reset(&mut self) {
+        Renderer::reset(self)
+    }
+}
+
+impl Step for listbox::render::Renderer {
+    fn create_panes(&self, width: u16, height: u16) -> Vec<Pane> {
+        Renderer::create_panes(self, width, height)
+    }
+
+    fn evaluate(&mut self, event: &Event) -> anyhow::Result<PromptSignal> {
+        Renderer::evaluate(self, event)
+    }
+
+    fn answer(&mut self) -> anyhow::Result<String> {
+        self.finalize()
+    }
+
+    fn prefill(&mut self, answer: &str) {
+        move_to_item(&mut self.listbox_state.listbox, answer);
+    }
+}
+
+impl Step for query_selector::render::Renderer {
+    fn create_panes(&self, width: u16, height: u16) -> Vec<Pane> {
+        Renderer::create_panes(self, width, height)
+    }
+
+    fn evaluate(&mut self, event: &Event) -> anyhow::Result<PromptSignal> {
+        Renderer::evaluate(self, event)
+    }
+
+    fn answer(&mut self) -> anyhow::Result<String> {
+        self.finalize()
+    }
+
+    fn prefill(&mut self, answer: &str) {
+        // Clear the query so that the prior answer is listed.
+        self.text_editor_snapshot.reset_after_to_init();
+        self.listbox_snapshot.reset_after_to_init();
+        move_to_item(&mut self.listbox_snapshot.after_mut().listbox, answer);
+    }
+
+    fn reset(&mut self) {
+        Renderer::reset(self)
+    }
+}
+
+fn move_to_item(listbox: &mut Listbox, item: &str) {
+    if let Some(position) = listbox.items().iter().position(|i| i.to_string() == item) {
+        listbox.move_to(position);
+    }
+}
+
+/// Runs several steps in order as a single prompt,
+/// showing a persistent header with the current step
+/// and a one-line summary of each completed step above the active step.
+/// Pressing <kbd>Esc</kbd> (see `back_key`) goes back to the previous step,
+/// pre-filled with its prior answer.
+pub struct Wizard {
+    steps: Vec<(String, Box<dyn Step>)>,
+    header_state: text::State,
+    summary_style: ContentStyle,
+    back_key: Option<KeyCombo>,
+}
+
+impl Default for Wizard {
+    fn default() -> Self {
+        Self {
+            steps: Default::default(),
+            header_state: text::State {
+                text: Default::default(),
+                style: StyleBuilder::new()
+                    .fgc(Color::DarkCyan)
+                    .attrs(Attributes::from(Attribute::Bold))
+                    .build(),
+                offset: 0,
+            },
+            summary_style: StyleBuilder::new().fgc(Color::DarkGrey).build(),
+            back_key: Some(KeyCombo::from(KeyCode::Esc)),
+        }
+    }
+}
+
+impl Wizard {
+    /// Appends a step with its title.
+    pub fn step<T: AsRef<str>, S: Step + 'static>(mut self, title: T, step: S) -> Self {
+        self.steps
+            .push((title.as_ref().to_string(), Box::new(step)));
+        self
+    }
+
+    /// Sets the style for the header showing the current step.
+    pub fn header_style(mut self, style: ContentStyle) -> Self {
+        self.header_state.style = style;
+        self
+    }
+
+    /// Sets the style for the summaries of the completed steps.
+    pub fn summary_style(mut self, style: ContentStyle) -> Self {
+        self.summary_style = style;
+        self
+    }
+
+    /// Sets the key going back to the previous step (<kbd>Esc</kbd> by default),
+    /// or `None` to disable going back.
+    /// The key never reaches the steps, so change it for a step using <kbd>Esc</kbd> itself,
+    /// e.g. a `Readline` with `EditorMode::Vi`.
+    pub fn back_key(mut self, key: Option<KeyCombo>) -> Self {
+        self.back_key = key;
+        self
+    }
+
+    /// Creates a prompt running all steps,
+    /// which returns the answers of the steps in order.
+    pub fn prompt(self) -> anyhow::Result<Prompt<render::Renderer>> {
+        if self.steps.is_empty() {
+            return Err(anyhow::anyhow!("wizard requires at least one step"));
+        }
+        Ok(Prompt::new(render::Renderer {
+            steps: self.steps,
+            answers: Vec::new(),
+            header_state: self.header_state,
+            summary_style: self.summary_style,
+            back_key: self.back_key,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::crossterm::event::{KeyEvent, KeyModifiers};
+
+    use super::*;
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn test_back_navigation() {
+        let mut renderer = Wizard::default()
+            .step("Name", readline::Readline::default().renderer())
+            .step("Kind", listbox::Listbox::new(["a", "b", "c"]).renderer())
+            .prompt()
+            .unwrap();
+        let renderer = &mut renderer.renderer;
+
+        for ev in [key(KeyCode::Char('x')), key(KeyCode::Enter)] {
+            assert!(renderer.evaluate(&ev).unwrap() == PromptSignal::Continue);
+        }
+        assert_eq!(vec!["x".to_string()], renderer.answers);
+
+        // Go back and find the prior answer pre-filled.
+        renderer.evaluate(&key(KeyCode::Esc)).unwrap();
+        assert!(renderer.answers.is_empty());
+        renderer.evaluate(&key(KeyCode::Char('y'))).unwrap();
+        renderer.evaluate(&key(KeyCode::Enter)).unwrap();
+        assert_eq!(vec!["xy".to_string()], renderer.answers);
+
+        renderer.evaluate(&key(KeyCode::Down)).unwrap();
+        assert!(renderer.evaluate(&key(KeyCode::Enter)).unwrap() == PromptSignal::Quit);
+        assert_eq!(
+            vec!["xy".to_string(), "b".to_string()],
+            renderer.finalize().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_back_key() {
+        let mut renderer = Wizard::default()
+            .step("Name", readline::Readline::default().renderer())
+            .step(
+                "Note",
+                readline::Readline::default()
+                    .editor_mode(readline::EditorMode::Vi)
+                    .renderer(),
+            )
+            .back_key(Some(KeyCombo::new(
+                KeyCode::Char('b'),
+                KeyModifiers::CONTROL,
+            )))
+            .prompt()
+            .unwrap();
+        let renderer = &mut renderer.renderer;
+
+        for ev in [key(KeyCode::Char('x')), key(KeyCode::Enter)] {
+            renderer.evaluate(&ev).unwrap();
+        }
+
+        // Esc reaches the vi step, switching it to the normal mode,
+        // where `x` deletes the character instead of inserting it.
+        for ev in [
+            key(KeyCode::Char('a')),
+            key(KeyCode::Esc),
+            key(KeyCode::Char('x')),
+        ] {
+            assert!(renderer.evaluate(&ev).unwrap() == PromptSignal::Continue);
+        }
+        assert_eq!(vec!["x".to_string()], renderer.answers);
+        assert!(renderer.evaluate(&key(KeyCode::Enter)).unwrap() == PromptSignal::Quit);
+        assert_eq!(vec!["x".to_string(), String::new()], renderer.answers);
+
+        renderer
+            .evaluate(&Event::Key(KeyEvent::new(
+                KeyCode::Char('b'),
+                KeyModifiers::CONTROL,
+            )))
+            .unwrap();
+        assert_eq!(vec!["x".to_string()], renderer.answers);
+    }
+}
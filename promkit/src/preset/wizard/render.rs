@@ -0,0 +1,95 @@
+use crate::{
+    chord::KeyCombo,
+    crossterm::{event::Event, style::ContentStyle},
+    pane::Pane,
+    text, PaneFactory, PromptSignal,
+};
+
+use super::Step;
+
+/// A `Renderer` for the wizard preset,
+/// rendering the header and the summaries of the completed steps above the active step.
+pub struct Renderer {
+    /// Titles and steps in order.
+    pub steps: Vec<(String, Box<dyn Step>)>,
+    /// Answers of the completed steps, so the active step is at `answers.len()`.
+    pub answers: Vec<String>,
+    /// A renderer used for the header showing the current step.
+    pub header_state: text::State,
+    /// Style for the summaries of the completed steps.
+    pub summary_style: ContentStyle,
+    /// Key going back to the previous step, if any.
+    pub back_key: Option<KeyCombo>,
+}
+
+impl crate::Finalizer for Renderer {
+    type Return = Vec<String>;
+
+    fn finalize(&mut self) -> anyhow::Result<Self::Return> {
+        Ok(std::mem::take(&mut self.answers))
+    }
+}
+
+impl crate::Renderer for Renderer {
+    fn create_panes(&self, width: u16, height: u16) -> Vec<Pane> {
+        let summaries = self
+            .steps
+            .iter()
+            .zip(self.answers.iter())
+            .map(|((title, _), answer)| {
+                text::State {
+                    text: format!("✔ {}: {}", title, answer),
+                    style: self.summary_style,
+                    offset: 0,
+                }
+                .create_pane(width, height)
+            });
+
+        // Once all steps are completed, only the summaries are left as the final state.
+        if self.answers.len() == self.steps.len() {
+            return summaries.collect();
+        }
+
+        let current = self.answers.len();
+        let (title, step) = &self.steps[current];
+
+        let mut header = self.header_state.clone();
+        header.text = format!("Step {}/{} — {}", current + 1, self.steps.len(), title);
+
+        let mut panes = vec![header.create_pane(width, height)];
+        panes.extend(summaries);
+        panes.extend(step.create_panes(width, height));
+        panes
+    }
+
+    fn evaluate(&mut self, event: &Event) -> anyhow::Result<PromptSignal> {
+        let current = self.answers.len();
+
+        if self.back_key.is_some() && KeyCombo::from_event(event) == self.back_key {
+            if let Some(answer) = self.answers.pop() {
+                self.steps[current - 1].1.prefill(&answer);
+            }
+            return Ok(PromptSignal::Continue);
+        }
+
+        // The wizard has completed, and waits to be finalized.
+        if current == self.steps.len() {
+            return Ok(PromptSignal::Quit);
+        }
+        let step = &mut self.steps[current].1;
+        if step.evaluate(event)? == PromptSignal::Quit {
+            self.answers.push(step.answer()?);
+            if self.answers.len() == self.steps.len() {
+                return Ok(PromptSignal::Quit);
+            }
+        }
+        Ok(PromptSignal::Continue)
+    }
+
+    fn reset(&mut self) {
+        self.answers.clear();
+        for (_, step) in &mut self.steps {
+            step.reset();
+        }
+    }
+}
@@ -0,0 +1,49 @@
+//! A flag making a prompt read-only while a background task is running,
+//! e.g. to reject keystrokes until the data they act on is loaded.
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// Whether a prompt rejects the input, shared among the clones:
+/// one is given to the prompt, and another to the task setting it.
+/// The prompt polls it, and redraws itself as it changes.
+#[derive(Clone, Debug, Default)]
+pub struct ReadOnly(Arc<AtomicBool>);
+
+impl ReadOnly {
+    /// Returns whether the prompt is read-only.
+    pub fn get(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    /// Makes the prompt read-only or editable again.
+    pub fn set(&self, read_only: bool) {
+        self.0.store(read_only, Ordering::SeqCst);
+    }
+
+    /// Returns whether a clone may set the flag, so that the prompt must poll it.
+    pub fn is_shared(&self) -> bool {
+        Arc::strong_count(&self.0) > 1
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod set {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let read_only = ReadOnly::default();
+            assert!(!read_only.is_shared());
+            let shared = read_only.clone();
+            assert!(read_only.is_shared());
+            shared.set(true);
+            assert!(read_only.get());
+        }
+    }
+}
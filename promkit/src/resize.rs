@@ -0,0 +1,93 @@
+//! Detection of terminal resizes by polling the size,
+//! for environments that do not deliver resize events reliably.
+
+use std::time::{Duration, Instant};
+
+use crate::crossterm::event::Event;
+
+pub(crate) struct ResizePoller {
+    interval: Duration,
+    /// The size the prompt is laid out for.
+    last: (u16, u16),
+    /// A new size observed once, waiting to be observed again before it is reported.
+    candidate: Option<(u16, u16)>,
+    next_at: Instant,
+}
+
+impl ResizePoller {
+    pub fn new(interval: Duration, size: (u16, u16), now: Instant) -> Self {
+        Self {
+            interval,
+            last: size,
+            candidate: None,
+            next_at: now + interval,
+        }
+    }
+
+    /// Returns when the size should be polled next.
+    pub fn next_at(&self) -> Instant {
+        self.next_at
+    }
+
+    /// Takes the size polled at `now`, and returns a resize event
+    /// once a new size is observed twice in a row,
+    /// so that a continuous drag is reported only after it settles.
+    pub fn poll(&mut self, size: (u16, u16), now: Instant) -> Option<Event> {
+        self.next_at = now + self.interval;
+        if size == self.last {
+            self.candidate = None;
+            return None;
+        }
+        if self.candidate == Some(size) {
+            self.observe(size);
+            return Some(Event::Resize(size.0, size.1));
+        }
+        self.candidate = Some(size);
+        None
+    }
+
+    /// Records the size reported by a resize event.
+    pub fn observe(&mut self, size: (u16, u16)) {
+        self.last = size;
+        self.candidate = None;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod poll {
+        use super::*;
+
+        #[test]
+        fn test_debounce() {
+            let interval = Duration::from_millis(100);
+            let now = Instant::now();
+            let mut poller = ResizePoller::new(interval, (80, 24), now);
+            assert_eq!(now + interval, poller.next_at());
+
+            assert_eq!(None, poller.poll((80, 24), now + interval));
+            // Dragging.
+            assert_eq!(None, poller.poll((90, 24), now + interval * 2));
+            assert_eq!(None, poller.poll((100, 24), now + interval * 3));
+            // Settled.
+            assert_eq!(
+                Some(Event::Resize(100, 24)),
+                poller.poll((100, 24), now + interval * 4)
+            );
+            assert_eq!(None, poller.poll((100, 24), now + interval * 5));
+            assert_eq!(now + interval * 6, poller.next_at());
+        }
+
+        #[test]
+        fn test_observed_resize() {
+            let now = Instant::now();
+            let mut poller = ResizePoller::new(Duration::from_millis(100), (80, 24), now);
+            assert_eq!(None, poller.poll((100, 24), now));
+            // The resize event arrived after all.
+            poller.observe((100, 24));
+            assert_eq!(None, poller.poll((100, 24), now));
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use std::io::{self, Write};
+
+use crate::{Prompt, Renderer, TerminalGuard};
+
+/// Determines what is left on the screen after each prompt of a `Session`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum BetweenSteps {
+    /// Keeps the final panes of each prompt, and starts the next one below them.
+    #[default]
+    KeepResults,
+    /// Clears the panes of each prompt, and starts the next one in their place.
+    ClearEach,
+}
+
+/// Runs several prompts back-to-back in one terminal session,
+/// entering the raw mode once instead of once per prompt.
+///
+/// The terminal is restored exactly once, by `finish` or on drop,
+/// including when a prompt in the middle returns an error.
+///
+/// ```no_run
+/// use promkit::{preset::{confirm::Confirm, readline::Readline}, Session};
+///
+/// let mut session = Session::new()?;
+/// let name = session.run(&mut Readline::default().title("Name").prompt()?)?;
+/// let sure = session.run(&mut Confirm::new("Sure?").prompt()?)?;
+/// session.finish()?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub struct Session {
+    /// Restores the terminal, writing the sequences to stdout by default,
+    /// which should be the destination the prompts are drawn to.
+    guard: TerminalGuard<Box<dyn Write + Send>>,
+    between_steps: BetweenSteps,
+}
+
+impl Session {
+    /// Sets up the terminal for the prompts to come.
+    pub fn new() -> anyhow::Result<Self> {
+        let mut guard = TerminalGuard::new(Box::new(io::stdout()) as Box<dyn Write + Send>);
+        guard.enable_raw_mode()?;
+        Ok(Self {
+            guard,
+            between_steps: Default::default(),
+        })
+    }
+
+    /// Writes the restoring sequences to the given writer instead of stdout,
+    /// e.g. `io::stderr()` if the prompts are drawn there.
+    pub fn writer<W: Write + Send + 'static>(mut self, writer: W) -> Self {
+        *self.guard.get_mut() = Box::new(writer);
+        self
+    }
+
+    /// Sets what is left on the screen after each prompt.
+    pub fn between_steps(mut self, policy: BetweenSteps) -> Self {
+        self.between_steps = policy;
+        self
+    }
+
+    /// Runs the prompt on the terminal set up by the session, and returns its result.
+    /// Afterwards, its panes are kept or cleared according to `between_steps`.
+    pub fn run<T: Renderer>(&mut self, prompt: &mut Prompt<T>) -> anyhow::Result<T::Return> {
+        // The prompts hide the cursor, which is shown once the session is over.
+        self.guard.hide_cursor()?;
+        prompt.hosted = true;
+        let result = prompt.run();
+        prompt.hosted = false;
+        let ended = prompt.end_hosted_run(self.between_steps == BetweenSteps::ClearEach);
+        let ret = result?;
+        ended?;
+        Ok(ret)
+    }
+
+    /// Restores the terminal. Dropping the session does the same, ignoring errors.
+    pub fn finish(mut self) -> anyhow::Result<()> {
+        self.guard.restore()
+    }
+}
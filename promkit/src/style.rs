@@ -61,6 +61,36 @@ impl StyleBuilder {
         self
     }
 
+    /// Sets the foreground color of the style from a hex string
+    /// like `"#ff8800"` or the short form `"#f80"`.
+    ///
+    /// # Arguments
+    ///
+    /// * `hex` - The hex string of the color to set as the foreground color.
+    ///
+    /// # Returns
+    ///
+    /// Returns the `Style` instance to allow for method chaining,
+    /// or an error if the hex string is malformed.
+    pub fn fg_hex(self, hex: &str) -> anyhow::Result<Self> {
+        Ok(self.fgc(parse_hex_color(hex)?))
+    }
+
+    /// Sets the background color of the style from a hex string
+    /// like `"#ff8800"` or the short form `"#f80"`.
+    ///
+    /// # Arguments
+    ///
+    /// * `hex` - The hex string of the color to set as the background color.
+    ///
+    /// # Returns
+    ///
+    /// Returns the `Style` instance to allow for method chaining,
+    /// or an error if the hex string is malformed.
+    pub fn bg_hex(self, hex: &str) -> anyhow::Result<Self> {
+        Ok(self.bgc(parse_hex_color(hex)?))
+    }
+
     /// Sets the underline color of the style.
     ///
     /// # Arguments
@@ -103,3 +133,109 @@ impl StyleBuilder {
         }
     }
 }
+
+/// Parses a hex string like `"#ff8800"`, or the short form `"#f80"`
+/// where each digit is doubled, into `Color::Rgb`.
+pub fn parse_hex_color(hex: &str) -> anyhow::Result<Color> {
+    let digits = hex
+        .strip_prefix('#')
+        .ok_or_else(|| anyhow::anyhow!("Hex color must start with '#': {:?}", hex))?;
+    if !digits.chars().all(|ch| ch.is_ascii_hexdigit()) {
+        return Err(anyhow::anyhow!("Invalid hex color: {:?}", hex));
+    }
+    let channel = |s: &str| u8::from_str_radix(s, 16);
+    let (r, g, b) = match digits.len() {
+        6 => (
+            channel(&digits[0..2])?,
+            channel(&digits[2..4])?,
+            channel(&digits[4..6])?,
+        ),
+        3 => (
+            channel(&digits[0..1])? * 0x11,
+            channel(&digits[1..2])? * 0x11,
+            channel(&digits[2..3])? * 0x11,
+        ),
+        _ => {
+            return Err(anyhow::anyhow!(
+                "Hex color must have 3 or 6 digits: {:?}",
+                hex
+            ))
+        }
+    };
+    Ok(Color::Rgb { r, g, b })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod parse_hex_color {
+        use super::*;
+
+        #[test]
+        fn test() {
+            assert_eq!(
+                Color::Rgb {
+                    r: 0xff,
+                    g: 0x88,
+                    b: 0x00
+                },
+                parse_hex_color("#ff8800").unwrap()
+            );
+            assert_eq!(
+                Color::Rgb {
+                    r: 0x1a,
+                    g: 0x2b,
+                    b: 0x3c
+                },
+                parse_hex_color("#1A2b3C").unwrap()
+            );
+        }
+
+        #[test]
+        fn test_short_form() {
+            assert_eq!(
+                Color::Rgb {
+                    r: 0xff,
+                    g: 0x88,
+                    b: 0x00
+                },
+                parse_hex_color("#f80").unwrap()
+            );
+        }
+
+        #[test]
+        fn test_invalid() {
+            for hex in ["ff8800", "#ff880", "#ff88000", "#gg8800", "#", "", "#ｆ80"] {
+                assert!(parse_hex_color(hex).is_err(), "{:?}", hex);
+            }
+        }
+    }
+
+    mod fg_hex {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let style = StyleBuilder::new()
+                .fg_hex("#000")
+                .unwrap()
+                .bg_hex("#ffffff")
+                .unwrap()
+                .build();
+            assert_eq!(
+                Some(Color::Rgb { r: 0, g: 0, b: 0 }),
+                style.foreground_color
+            );
+            assert_eq!(
+                Some(Color::Rgb {
+                    r: 0xff,
+                    g: 0xff,
+                    b: 0xff
+                }),
+                style.background_color
+            );
+            assert!(StyleBuilder::new().fg_hex("red").is_err());
+        }
+    }
+}
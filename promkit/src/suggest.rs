@@ -7,29 +7,127 @@ use radix_trie::{Trie, TrieCommon};
 /// This allows for quick lookup of suggestions based on a given prefix,
 /// making it suitable for use in text editors or command line interfaces
 /// where autocompletion features are desired.
-#[derive(Clone)]
-pub struct Suggest(Trie<String, usize>);
+///
+/// Each candidate may optionally carry a description
+/// (e.g. the meaning of a flag), which is shown next to the candidate
+/// but never inserted as part of the completion.
+#[derive(Clone, Default)]
+pub struct Suggest {
+    trie: Trie<String, Option<String>>,
+    /// Whether searches also match against the descriptions of candidates.
+    match_descriptions: bool,
+    /// The maximum number of candidates returned by a search, or `None` for no limit.
+    max_candidates: Option<usize>,
+}
 
 impl<T: fmt::Display> FromIterator<T> for Suggest {
     /// Constructs a `Suggest` instance from an iterator of displayable items.
-    /// Each item is inserted into the trie with a default value to facilitate
+    /// Each item is inserted into the trie without a description to facilitate
     /// quick prefix-based searches.
     ///
     /// # Arguments
     ///
     /// * `iter` - An iterator over items that implement the `Display` trait.
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
-        Suggest(Trie::from_iter(
-            iter.into_iter().map(|e| (format!("{}", e), 1)),
-        ))
+        let mut ret = Suggest::default();
+        ret.extend(iter);
+        ret
+    }
+}
+
+impl<T: fmt::Display> Extend<T> for Suggest {
+    /// Inserts the items without descriptions, keeping the ones already present as they are.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.insert(item);
+        }
     }
 }
 
 impl Suggest {
+    /// Inserts a candidate without a description.
+    /// A candidate already present is left as it is, keeping its description.
+    pub fn insert<T: fmt::Display>(&mut self, item: T) {
+        let item = item.to_string();
+        if self.trie.get(&item).is_none() {
+            self.trie.insert(item, None);
+        }
+    }
+
+    /// Inserts a candidate along with a description.
+    pub fn insert_with_desc<T: fmt::Display, D: fmt::Display>(&mut self, item: T, desc: D) {
+        self.trie
+            .insert(format!("{}", item), Some(format!("{}", desc)));
+    }
+
+    /// Sets whether searches also match against the descriptions of candidates.
+    /// Defaults to `false`, i.e. only the candidate text is matched.
+    pub fn match_descriptions(mut self, enable: bool) -> Self {
+        self.match_descriptions = enable;
+        self
+    }
+
+    /// Sets the maximum number of candidates returned by `prefix_search`
+    /// and `prefix_search_with_desc`, so that a short query against many candidates
+    /// does not materialize every match.
+    pub fn max_candidates(mut self, max: usize) -> Self {
+        self.max_candidates = Some(max);
+        self
+    }
+
+    /// Iterates lazily over the candidates starting with the query, in lexicographic order,
+    /// along with their descriptions.
+    pub fn prefix_iter<'a>(
+        &'a self,
+        query: &str,
+    ) -> impl Iterator<Item = (&'a str, Option<&'a str>)> + 'a {
+        self.trie
+            .get_raw_descendant(query)
+            .into_iter()
+            .flat_map(|subtrie| (&subtrie).iter())
+            .map(|(item, desc)| (item.as_str(), desc.as_deref()))
+    }
+
+    /// Searches candidates by prefix, up to `max_candidates` of them.
     pub fn prefix_search<T: AsRef<str>>(&self, query: T) -> Option<Vec<String>> {
-        self.0
-            .get_raw_descendant(query.as_ref())
-            .map(|subtrie| subtrie.iter().map(|item| item.0.clone()).collect())
+        let ret: Vec<String> = self
+            .prefix_iter(query.as_ref())
+            .take(self.max_candidates.unwrap_or(usize::MAX))
+            .map(|(item, _)| item.to_string())
+            .collect();
+        (!ret.is_empty()).then_some(ret)
+    }
+
+    /// Searches candidates by prefix and returns them with their descriptions,
+    /// up to `max_candidates` of them.
+    ///
+    /// When description matching is enabled,
+    /// candidates whose description starts with the query are appended
+    /// after the candidates matched by their text.
+    pub fn prefix_search_with_desc<T: AsRef<str>>(
+        &self,
+        query: T,
+    ) -> Option<Vec<(String, Option<String>)>> {
+        let query = query.as_ref();
+        let max = self.max_candidates.unwrap_or(usize::MAX);
+        // Scanning the descriptions visits every candidate, so it is skipped unless enabled.
+        let by_desc = self
+            .match_descriptions
+            .then(|| self.trie.iter())
+            .into_iter()
+            .flatten()
+            .filter(|(item, desc)| {
+                !item.starts_with(query) && desc.as_ref().is_some_and(|d| d.starts_with(query))
+            })
+            .map(|(item, desc)| (item.as_str(), desc.as_deref()));
+        let ret: Vec<(String, Option<String>)> = self
+            .prefix_iter(query)
+            .chain(by_desc)
+            .take(max)
+            .map(|(item, desc)| (item.to_string(), desc.map(str::to_string)))
+            .collect();
+
+        (!ret.is_empty()).then_some(ret)
     }
 }
 
@@ -43,12 +141,15 @@ mod tests {
         #[test]
         fn test() {
             let mut trie = Trie::new();
-            trie.insert("apple".to_string(), 1);
-            trie.insert("applet".to_string(), 1);
-            trie.insert("application".to_string(), 1);
-            trie.insert("banana".to_string(), 1);
+            trie.insert("apple".to_string(), None);
+            trie.insert("applet".to_string(), None);
+            trie.insert("application".to_string(), None);
+            trie.insert("banana".to_string(), None);
 
-            let suggest = Suggest(trie);
+            let suggest = Suggest {
+                trie,
+                ..Default::default()
+            };
             let ret = suggest.prefix_search("app").unwrap();
             let expected: Vec<String> = vec!["apple", "applet", "application"]
                 .into_iter()
@@ -58,4 +159,94 @@ mod tests {
             assert_eq!(ret, expected);
         }
     }
+
+    mod prefix_search_with_desc {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let mut suggest = Suggest::from_iter(["--all"]);
+            suggest.insert_with_desc("--long", "use a long listing format");
+
+            assert_eq!(
+                Some(vec![
+                    ("--all".to_string(), None),
+                    (
+                        "--long".to_string(),
+                        Some("use a long listing format".to_string())
+                    ),
+                ]),
+                suggest.prefix_search_with_desc("--"),
+            );
+            assert_eq!(None, suggest.prefix_search_with_desc("use"));
+        }
+
+        #[test]
+        fn test_with_match_descriptions() {
+            let mut suggest = Suggest::from_iter(["--all"]).match_descriptions(true);
+            suggest.insert_with_desc("--long", "use a long listing format");
+
+            assert_eq!(
+                Some(vec![(
+                    "--long".to_string(),
+                    Some("use a long listing format".to_string())
+                )]),
+                suggest.prefix_search_with_desc("use"),
+            );
+        }
+    }
+
+    mod insert {
+        use super::*;
+
+        #[test]
+        fn test_keeps_description() {
+            let mut suggest = Suggest::default();
+            suggest.insert_with_desc("--long", "use a long listing format");
+            suggest.extend(["--long", "--all"]);
+
+            assert_eq!(
+                Some(vec![
+                    ("--all".to_string(), None),
+                    (
+                        "--long".to_string(),
+                        Some("use a long listing format".to_string())
+                    ),
+                ]),
+                suggest.prefix_search_with_desc("--"),
+            );
+        }
+    }
+
+    mod prefix_search {
+        use super::*;
+
+        #[test]
+        fn test_max_candidates() {
+            let suggest = Suggest::from_iter(["ab", "abc", "abd", "b"]).max_candidates(2);
+            assert_eq!(
+                Some(vec!["ab".to_string(), "abc".to_string()]),
+                suggest.prefix_search("a")
+            );
+            assert_eq!(None, suggest.prefix_search("x"));
+        }
+
+        #[test]
+        fn test_many_candidates() {
+            let suggest =
+                Suggest::from_iter((0..50_000).map(|i| format!("git commit -m 'change {}'", i)))
+                    .max_candidates(100);
+            let start = std::time::Instant::now();
+            for _ in 0..100 {
+                assert_eq!(100, suggest.prefix_search("git").unwrap().len());
+                assert_eq!(100, suggest.prefix_search_with_desc("git c").unwrap().len());
+            }
+            let elapsed = start.elapsed() / 200;
+            assert!(
+                elapsed < std::time::Duration::from_millis(3),
+                "{:?} per search over 50000 candidates",
+                elapsed
+            );
+        }
+    }
 }
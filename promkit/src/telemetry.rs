@@ -0,0 +1,114 @@
+//! Spans emitted with the `tracing` feature,
+//! which can be exported to OpenTelemetry with e.g. `tracing-opentelemetry`.
+//!
+//! The names and fields of the spans are a stable contract:
+//!
+//! | Span               | Fields
+//! | :----------------- | :-------------------------------------------
+//! | `promkit.prompt`   | `promkit.preset`, `promkit.outcome`, `promkit.duration_ms`, `promkit.keystrokes`
+//! | `promkit.validate` | `promkit.valid`
+//! | `promkit.load`     | `promkit.items`
+//!
+//! - `promkit.prompt` covers a run of a prompt (`Prompt::run` or `Prompt::run_with_events`).
+//!   - `promkit.preset` is the name of the preset module, e.g. `readline`, or `custom` for the other renderers.
+//!   - `promkit.outcome` is one of `submitted`, `interrupted`, `timed_out` or `error`.
+//!   - `promkit.keystrokes` is the number of key presses.
+//! - `promkit.validate` covers a validation of the input, as a child of `promkit.prompt`.
+//! - `promkit.load` covers a load of items in the background (`QuerySelector::with_async_filter`),
+//!   as a child of `promkit.prompt`, with the number of loaded items.
+//!
+//! The input and the values are never recorded.
+//! To attach your own attributes, create a span with them and pass it to `Prompt::parent_span`,
+//! so that the `promkit.prompt` spans are nested in it.
+
+use crate::crossterm::event::Event;
+
+/// Tracks the runs of a prompt.
+#[derive(Default)]
+pub(crate) struct Telemetry {
+    /// The span the runs are nested in.
+    #[cfg(feature = "tracing")]
+    pub parent: Option<tracing::Span>,
+    #[cfg(feature = "tracing")]
+    keystrokes: usize,
+}
+
+/// The span of a run, entered until the run finishes.
+pub(crate) struct RunScope {
+    #[cfg(feature = "tracing")]
+    span: tracing::span::EnteredSpan,
+    #[cfg(feature = "tracing")]
+    started_at: std::time::Instant,
+}
+
+#[cfg(feature = "tracing")]
+impl Telemetry {
+    /// Starts the span of a run of the renderer of the given type name.
+    pub fn start(&mut self, renderer: &'static str) -> RunScope {
+        self.keystrokes = 0;
+        let span = {
+            let _parent = self.parent.as_ref().map(|parent| parent.enter());
+            tracing::info_span!(
+                "promkit.prompt",
+                promkit.preset = preset_name(renderer),
+                promkit.outcome = tracing::field::Empty,
+                promkit.duration_ms = tracing::field::Empty,
+                promkit.keystrokes = tracing::field::Empty,
+            )
+        };
+        RunScope {
+            span: span.entered(),
+            started_at: std::time::Instant::now(),
+        }
+    }
+
+    pub fn count(&mut self, event: &Event) {
+        use crate::crossterm::event::{KeyEvent, KeyEventKind};
+
+        if let Event::Key(KeyEvent {
+            kind: KeyEventKind::Press,
+            ..
+        }) = event
+        {
+            self.keystrokes += 1;
+        }
+    }
+
+    pub fn finish<R>(&self, scope: RunScope, result: &anyhow::Result<R>) {
+        let outcome = match result {
+            Ok(_) => "submitted",
+            Err(e) if e.is::<crate::Interrupted>() => "interrupted",
+            Err(e) if e.is::<crate::Eof>() => "eof",
+            Err(e) if e.is::<crate::TimedOut>() => "timed_out",
+            Err(_) => "error",
+        };
+        scope.span.record("promkit.outcome", outcome);
+        scope.span.record(
+            "promkit.duration_ms",
+            scope.started_at.elapsed().as_millis() as u64,
+        );
+        scope
+            .span
+            .record("promkit.keystrokes", self.keystrokes as u64);
+    }
+}
+
+#[cfg(not(feature = "tracing"))]
+impl Telemetry {
+    pub fn start(&mut self, _renderer: &'static str) -> RunScope {
+        RunScope {}
+    }
+
+    pub fn count(&mut self, _event: &Event) {}
+
+    pub fn finish<R>(&self, _scope: RunScope, _result: &anyhow::Result<R>) {}
+}
+
+/// Returns the name of the preset module the renderer type belongs to, e.g. `readline`.
+#[cfg(feature = "tracing")]
+fn preset_name(renderer: &'static str) -> &'static str {
+    renderer
+        .strip_prefix("promkit::preset::")
+        .and_then(|rest| rest.split("::").next())
+        .unwrap_or("custom")
+}
@@ -1,20 +1,100 @@
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
 
 use crate::{
     crossterm::{cursor, style, terminal},
+    grapheme::StyledGraphemes,
     pane::Pane,
 };
 
+const INSUFFICIENT_SPACE: &str = "⚠️ Insufficient Space";
+
+/// Collects the rows of the non-empty panes fitting into the given height.
+///
+/// The rows are given to the panes from the highest priority down (see `Pane::with_priority`).
+/// Within a priority, every pane gets at least a row, and the earlier panes take the rest.
+/// The panes of a lower priority without a row left are hidden,
+/// and `None` is returned if not even a row per pane of the highest priority fits.
+fn extract_rows(panes: &[Pane], height: u16) -> Option<Vec<StyledGraphemes>> {
+    let viewable_panes = panes
+        .iter()
+        .filter(|pane| !pane.is_empty())
+        .collect::<Vec<&Pane>>();
+
+    let mut priorities = viewable_panes
+        .iter()
+        .map(|pane| pane.priority())
+        .collect::<Vec<u8>>();
+    priorities.sort_unstable_by(|a, b| b.cmp(a));
+    priorities.dedup();
+
+    let mut budgets = vec![0; viewable_panes.len()];
+    let mut left = height as usize;
+    for (level, priority) in priorities.into_iter().enumerate() {
+        let mut members = (0..viewable_panes.len())
+            .filter(|i| viewable_panes[*i].priority() == priority)
+            .collect::<Vec<usize>>();
+        if left < members.len() {
+            if level == 0 {
+                return None;
+            }
+            members.truncate(left);
+        }
+        for (k, i) in members.iter().enumerate() {
+            // Leave a row for each of the following panes of the priority.
+            let budget = 1.max(left.saturating_sub(members.len() - 1 - k));
+            budgets[*i] = viewable_panes[*i].height().min(budget);
+            left -= budgets[*i];
+        }
+    }
+
+    let mut rows = Vec::new();
+    for (pane, budget) in viewable_panes.iter().zip(budgets) {
+        if budget > 0 {
+            rows.extend(pane.extract(budget));
+        }
+    }
+    Some(rows)
+}
+
 pub struct Terminal {
     /// The current cursor position within the terminal.
     pub position: (u16, u16),
+    /// Rows drawn in the previous frame, used to rewrite only the rows that changed.
+    previous: Vec<StyledGraphemes>,
+    /// Whether the next frame must be repainted entirely (e.g. after a resize).
+    full_redraw: bool,
+    /// The maximum number of rows to draw, regardless of the terminal height.
+    pub max_rows: Option<u16>,
 }
 
 impl Terminal {
-    pub fn start_session(panes: &[Pane]) -> anyhow::Result<Self> {
-        let position = cursor::position()?;
+    /// Starts a session drawing to the writer from the current cursor position.
+    ///
+    /// If stdout is not a terminal (e.g. the prompt is drawn to stderr while stdout is piped),
+    /// the cursor position cannot be queried, so the panes are placed at the bottom of the screen.
+    pub fn start_session<W: Write>(writer: &mut W, panes: &[Pane]) -> anyhow::Result<Self> {
         let size = terminal::size()?;
 
+        // Calculate the total number of rows required by all panes.
+        let lines = panes
+            .iter()
+            .map(|pane| pane.visible_row_count())
+            .sum::<usize>();
+
+        if !io::stdout().is_terminal() {
+            let lines = (lines as u16).min(size.1);
+            crossterm::queue!(
+                writer,
+                cursor::MoveToColumn(0),
+                style::Print("\n"),
+                terminal::ScrollUp(lines),
+            )?;
+            writer.flush()?;
+            return Ok(Self::new((0, size.1.saturating_sub(lines))));
+        }
+
+        let position = cursor::position()?;
+
         // If the cursor is not at the beginning of a line (position.0 != 0),
         // there are two scenarios to consider:
         // 1. If the cursor is also at the last line of the terminal (size.1 == position.1 + 1),
@@ -23,87 +103,466 @@ impl Terminal {
         //    to ensure the next output starts correctly.
         if position.0 != 0 {
             if size.1 == position.1 + 1 {
-                crossterm::queue!(io::stdout(), terminal::ScrollUp(1))?;
+                crossterm::queue!(writer, terminal::ScrollUp(1))?;
             }
-            crossterm::queue!(io::stdout(), cursor::MoveToNextLine(1))?;
+            crossterm::queue!(writer, cursor::MoveToNextLine(1))?;
         }
 
-        // Calculate the total number of rows required by all panes.
-        let lines = panes
-            .iter()
-            .map(|pane| pane.visible_row_count())
-            .sum::<usize>();
         // If the cursor is at the last line of the terminal,
         // scroll up by the number of lines required by all panes,
         // and then move the cursor up by the same number of lines
         // to maintain its relative position.
         if size.1 == position.1 + 1 {
             crossterm::queue!(
-                io::stdout(),
+                writer,
                 terminal::ScrollUp(lines as u16),
                 cursor::MoveToPreviousLine(lines as u16),
             )?;
         }
 
-        io::stdout().flush()?;
+        writer.flush()?;
 
-        Ok(Self {
-            position: cursor::position()?,
-        })
+        Ok(Self::new(cursor::position()?))
     }
 
-    pub fn draw(&mut self, panes: &[Pane]) -> anyhow::Result<()> {
-        let height = terminal::size()?.1;
+    /// Starts a session on the alternate screen, which must have been entered beforehand.
+    /// The screen is cleared and the panes are drawn from the top left corner.
+    pub fn start_alternate_session<W: Write>(writer: &mut W) -> anyhow::Result<Self> {
+        crossterm::execute!(
+            writer,
+            terminal::Clear(terminal::ClearType::All),
+            cursor::MoveTo(0, 0),
+        )?;
+        Ok(Self::new((0, 0)))
+    }
 
-        let viewable_panes = panes
-            .iter()
-            .filter(|pane| !pane.is_empty())
-            .collect::<Vec<&Pane>>();
+    /// Creates a terminal drawing from the given position, without touching the actual terminal.
+    pub fn new(position: (u16, u16)) -> Self {
+        Self {
+            position,
+            previous: Vec::new(),
+            full_redraw: true,
+            max_rows: None,
+        }
+    }
 
-        if height < viewable_panes.len() as u16 {
+    /// Forces the next `draw` to repaint all rows instead of only the changed ones.
+    /// This must be called when the terminal contents are no longer reliable, e.g. after a resize.
+    pub fn invalidate(&mut self) {
+        self.previous.clear();
+        self.full_redraw = true;
+    }
+
+    pub fn draw<W: Write>(&mut self, writer: &mut W, panes: &[Pane]) -> anyhow::Result<()> {
+        let height = terminal::size()?.1;
+        self.draw_to(writer, panes, height)
+    }
+
+    /// Draws the panes to the writer, assuming a terminal of the given height.
+    /// At most `max_rows` rows are drawn if set.
+    ///
+    /// Rows are compared with the previous frame (including their styles),
+    /// and only the changed rows are rewritten and the disappeared rows are cleared.
+    /// The whole frame is repainted if it was invalidated, would need to scroll the terminal,
+    /// or more than half of the rows changed.
+    pub fn draw_to<W: Write>(
+        &mut self,
+        writer: &mut W,
+        panes: &[Pane],
+        height: u16,
+    ) -> anyhow::Result<()> {
+        let budget = self
+            .max_rows
+            .map_or(height, |max_rows| max_rows.min(height));
+        let Some(rows) = extract_rows(panes, budget) else {
+            self.invalidate();
             return crossterm::execute!(
-                io::stdout(),
+                writer,
+                cursor::MoveTo(self.position.0, self.position.1),
                 terminal::Clear(terminal::ClearType::FromCursorDown),
-                style::Print("⚠️ Insufficient Space"),
+                style::Print(INSUFFICIENT_SPACE),
             )
             .map_err(anyhow::Error::from);
+        };
+
+        // Rewriting most rows one by one costs more than repainting them at once,
+        // e.g. when a list scrolls and shifts every row.
+        let changed = rows
+            .iter()
+            .enumerate()
+            .filter(|(i, row)| self.previous.get(*i) != Some(*row))
+            .count();
+        if self.full_redraw
+            || self.position.1 as usize + rows.len() >= height as usize
+            || changed * 2 > rows.len()
+        {
+            self.draw_all(writer, &rows, height)?;
+        } else {
+            self.draw_diff(writer, &rows)?;
         }
 
+        writer.flush()?;
+        self.previous = rows;
+        self.full_redraw = false;
+        Ok(())
+    }
+
+    /// Rewrites only the rows that differ from the previous frame.
+    fn draw_diff<W: Write>(&self, writer: &mut W, rows: &[StyledGraphemes]) -> anyhow::Result<()> {
+        for (i, row) in rows.iter().enumerate() {
+            if self.previous.get(i) != Some(row) {
+                crossterm::queue!(
+                    writer,
+                    cursor::MoveTo(
+                        if i == 0 { self.position.0 } else { 0 },
+                        self.position.1 + i as u16
+                    ),
+                    terminal::Clear(terminal::ClearType::UntilNewLine),
+                    style::Print(row.styled_display()),
+                )?;
+            }
+        }
+        if rows.len() < self.previous.len() {
+            crossterm::queue!(
+                writer,
+                cursor::MoveTo(0, self.position.1 + rows.len() as u16),
+                terminal::Clear(terminal::ClearType::FromCursorDown),
+            )?;
+        }
+        // Leave the cursor below the rows as `draw_all` does.
+        crossterm::queue!(
+            writer,
+            cursor::MoveTo(0, self.position.1 + rows.len() as u16)
+        )?;
+        Ok(())
+    }
+
+    /// Clears and repaints all rows, scrolling the terminal if the rows do not fit.
+    fn draw_all<W: Write>(
+        &mut self,
+        writer: &mut W,
+        rows: &[StyledGraphemes],
+        height: u16,
+    ) -> anyhow::Result<()> {
         crossterm::queue!(
-            io::stdout(),
+            writer,
             cursor::MoveTo(self.position.0, self.position.1),
             terminal::Clear(terminal::ClearType::FromCursorDown),
         )?;
 
-        let mut used = 0;
-
         let mut current_cursor_y = height.saturating_sub(self.position.1);
-        for (i, pane) in viewable_panes.iter().enumerate() {
-            let rows = pane.extract(
-                1.max(
-                    (height as usize)
-                        // -1 in this context signifies the exclusion of the current pane.
-                        .saturating_sub(used + viewable_panes.len() - 1 - i),
+        for (i, row) in rows.iter().enumerate() {
+            crossterm::queue!(writer, style::Print(row.styled_display()))?;
+
+            current_cursor_y = current_cursor_y.saturating_sub(1);
+
+            if i != rows.len() - 1 && current_cursor_y == 0 {
+                crossterm::queue!(writer, terminal::ScrollUp(1))?;
+                self.position.1 = self.position.1.saturating_sub(1);
+            }
+
+            crossterm::queue!(writer, cursor::MoveToNextLine(1))?;
+        }
+        Ok(())
+    }
+}
+
+/// An in-memory terminal recording every drawn frame,
+/// for driving prompts in tests without a TTY (see `Prompt::run_with_events`).
+#[derive(Clone, Debug)]
+pub struct TestTerminal {
+    /// The size of the terminal as (width, height).
+    pub size: (u16, u16),
+    /// The maximum number of rows to draw, regardless of the terminal height.
+    pub max_rows: Option<u16>,
+    frames: Vec<Vec<StyledGraphemes>>,
+}
+
+impl TestTerminal {
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            size: (width, height),
+            max_rows: None,
+            frames: Vec::new(),
+        }
+    }
+
+    /// Records the rows of the panes as a frame, as `Terminal::draw` would display them.
+    pub fn draw(&mut self, panes: &[Pane]) {
+        let rows = extract_rows(panes, self.height())
+            .unwrap_or_else(|| vec![StyledGraphemes::from(INSUFFICIENT_SPACE)]);
+        self.frames.push(rows);
+    }
+
+    /// Returns the number of rows available for drawing.
+    pub fn height(&self) -> u16 {
+        self.max_rows
+            .map_or(self.size.1, |max_rows| max_rows.min(self.size.1))
+    }
+
+    /// Returns all the frames drawn so far, oldest first.
+    pub fn frames(&self) -> &[Vec<StyledGraphemes>] {
+        &self.frames
+    }
+
+    /// Returns the frame at the index as a pane, e.g. to convert it to strings.
+    pub fn pane(&self, index: usize) -> Option<Pane> {
+        self.frames
+            .get(index)
+            .map(|rows| Pane::new(rows.clone(), 0))
+    }
+
+    /// Returns the rows of the frame at the index as plain strings
+    /// (see `Pane::to_plain_strings`), or an empty vector if there is no such frame.
+    pub fn frame(&self, index: usize) -> Vec<String> {
+        self.pane(index)
+            .map(|pane| pane.to_plain_strings())
+            .unwrap_or_default()
+    }
+
+    /// Returns the rows of the latest frame as plain strings (see `Pane::to_plain_strings`).
+    pub fn last_frame(&self) -> Vec<String> {
+        self.frame(self.frames.len().saturating_sub(1))
+    }
+
+    /// Returns the rows of the latest frame with ANSI styles (see `Pane::to_ansi_strings`).
+    pub fn last_frame_ansi(&self) -> Vec<String> {
+        self.pane(self.frames.len().saturating_sub(1))
+            .map(|pane| pane.to_ansi_strings())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// A writer counting the write calls and the bytes written.
+    #[derive(Default)]
+    struct CountingWriter {
+        calls: usize,
+        bytes: usize,
+    }
+
+    impl Write for CountingWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.calls += 1;
+            self.bytes += buf.len();
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn pane_with_rows(rows: &[String]) -> Pane {
+        Pane::new(rows.iter().map(StyledGraphemes::from).collect(), 0)
+    }
+
+    mod extract_rows {
+        use super::*;
+
+        fn rows(panes: &[Pane], height: u16) -> Option<Vec<String>> {
+            extract_rows(panes, height).map(|rows| rows.iter().map(|row| row.to_string()).collect())
+        }
+
+        fn pane(rows: &[&str]) -> Pane {
+            pane_with_rows(&rows.iter().map(|row| row.to_string()).collect::<Vec<_>>())
+        }
+
+        #[test]
+        fn test_same_priority() {
+            let panes = [
+                pane(&["a1", "a2", "a3"]),
+                pane(&["b1", "b2"]),
+                pane(&["c1"]),
+            ];
+            assert_eq!(
+                Some(
+                    vec!["a1", "a2", "a3", "b1", "c1"]
+                        .into_iter()
+                        .map(String::from)
+                        .collect()
+                ),
+                rows(&panes, 5)
+            );
+            assert_eq!(
+                Some(
+                    vec!["a1", "b1", "c1"]
+                        .into_iter()
+                        .map(String::from)
+                        .collect()
+                ),
+                rows(&panes, 3)
+            );
+            assert_eq!(None, rows(&panes, 2));
+        }
+
+        #[test]
+        fn test_more_panes_than_rows() {
+            let panes = [
+                pane(&["title1", "title2"]),
+                pane(&["error"]),
+                pane(&["input"]).with_priority(1),
+                pane(&["suggest1", "suggest2"]),
+            ];
+            assert_eq!(Some(vec![String::from("input")]), rows(&panes, 1));
+            assert_eq!(
+                Some(
+                    vec!["title1", "input"]
+                        .into_iter()
+                        .map(String::from)
+                        .collect()
+                ),
+                rows(&panes, 2)
+            );
+            assert_eq!(
+                Some(
+                    vec!["title1", "error", "input", "suggest1"]
+                        .into_iter()
+                        .map(String::from)
+                        .collect()
                 ),
+                rows(&panes, 4)
             );
-            used += rows.len();
-            for (j, row) in rows.iter().enumerate() {
-                crossterm::queue!(io::stdout(), style::Print(row.styled_display()))?;
+        }
+
+        #[test]
+        fn test_input_kept_whole() {
+            let panes = [
+                pane(&["title1", "title2", "title3"]),
+                pane(&["input1", "input2"]).with_priority(1),
+            ];
+            assert_eq!(
+                Some(
+                    vec!["title1", "input1", "input2"]
+                        .into_iter()
+                        .map(String::from)
+                        .collect()
+                ),
+                rows(&panes, 3)
+            );
+        }
+    }
+
+    mod draw_to {
+        use super::*;
+
+        #[test]
+        fn test_single_char_edit_in_100_rows() {
+            let mut rows: Vec<String> = (0..100).map(|i| format!("row {}", i)).collect();
+            let mut terminal = Terminal::new((0, 0));
 
-                current_cursor_y = current_cursor_y.saturating_sub(1);
+            let mut full = CountingWriter::default();
+            terminal
+                .draw_to(&mut full, &[pane_with_rows(&rows)], 200)
+                .unwrap();
 
-                if ((i == viewable_panes.len() - 1 && j != rows.len() - 1)
-                    || i != viewable_panes.len() - 1)
-                    && current_cursor_y == 0
-                {
-                    crossterm::queue!(io::stdout(), terminal::ScrollUp(1))?;
-                    self.position.1 = self.position.1.saturating_sub(1);
-                }
+            rows[50].push('!');
+            let mut diff = CountingWriter::default();
+            terminal
+                .draw_to(&mut diff, &[pane_with_rows(&rows)], 200)
+                .unwrap();
 
-                crossterm::queue!(io::stdout(), cursor::MoveToNextLine(1))?;
+            assert!(
+                diff.calls * 10 < full.calls,
+                "{} {}",
+                diff.calls,
+                full.calls
+            );
+            assert!(
+                diff.bytes * 10 < full.bytes,
+                "{} {}",
+                diff.bytes,
+                full.bytes
+            );
+
+            // Nothing but the cursor move is written for an unchanged frame.
+            let mut unchanged = CountingWriter::default();
+            terminal
+                .draw_to(&mut unchanged, &[pane_with_rows(&rows)], 200)
+                .unwrap();
+            assert!(unchanged.bytes < diff.bytes);
+        }
+
+        #[test]
+        fn test_json_viewer() {
+            use crate::{
+                crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers},
+                jsonstream::JsonStream,
+                preset::json::Json,
+                Renderer,
+            };
+
+            fn key(code: KeyCode) -> Event {
+                Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
             }
+
+            let value = serde_json::json!({
+                "items": (0..50)
+                    .map(|i| serde_json::json!({"id": i, "name": format!("item {}", i)}))
+                    .collect::<Vec<_>>(),
+            });
+            let mut renderer = Json::new(JsonStream::new([&value])).renderer();
+            let mut terminal = Terminal::new((0, 0));
+
+            let mut full = CountingWriter::default();
+            terminal
+                .draw_to(&mut full, &renderer.create_panes(80, 40), 50)
+                .unwrap();
+
+            // Moving the cursor scrolls every row, which falls back to a repaint.
+            renderer.evaluate(&key(KeyCode::Down)).unwrap();
+            let mut scrolled = CountingWriter::default();
+            terminal
+                .draw_to(&mut scrolled, &renderer.create_panes(80, 40), 50)
+                .unwrap();
+            terminal.invalidate();
+            let mut repainted = CountingWriter::default();
+            terminal
+                .draw_to(&mut repainted, &renderer.create_panes(80, 40), 50)
+                .unwrap();
+            assert_eq!(repainted.bytes, scrolled.bytes);
+
+            // Typing a query matching nothing rewrites the query row only.
+            renderer.evaluate(&key(KeyCode::Char('/'))).unwrap();
+            terminal
+                .draw_to(
+                    &mut CountingWriter::default(),
+                    &renderer.create_panes(80, 40),
+                    40,
+                )
+                .unwrap();
+            renderer.evaluate(&key(KeyCode::Char('z'))).unwrap();
+            let mut typed = CountingWriter::default();
+            terminal
+                .draw_to(&mut typed, &renderer.create_panes(80, 40), 50)
+                .unwrap();
+            assert!(
+                typed.bytes * 10 < full.bytes,
+                "{} {}",
+                typed.bytes,
+                full.bytes
+            );
+        }
+
+        #[test]
+        fn test_full_redraw_after_invalidate() {
+            let rows: Vec<String> = (0..100).map(|i| format!("row {}", i)).collect();
+            let mut terminal = Terminal::new((0, 0));
+
+            let mut first = CountingWriter::default();
+            terminal
+                .draw_to(&mut first, &[pane_with_rows(&rows)], 200)
+                .unwrap();
+
+            terminal.invalidate();
+            let mut second = CountingWriter::default();
+            terminal
+                .draw_to(&mut second, &[pane_with_rows(&rows)], 200)
+                .unwrap();
+            assert_eq!(first.bytes, second.bytes);
         }
-        io::stdout().flush()?;
-        Ok(())
     }
 }
@@ -0,0 +1,55 @@
+//! Helpers asserting the rendering of a `PaneFactory` in plain unit tests, without a terminal.
+//!
+//! The pane is created for the given size and cut to the height as `Terminal::draw` does,
+//! so the same wrapping and clamping (e.g. by the `lines` of a state) are exercised.
+
+use crate::{
+    crossterm::style::ContentStyle, grapheme::StyledGraphemes, terminal::TestTerminal, PaneFactory,
+};
+
+/// Draws the pane of the state as the only frame of a terminal of the size.
+fn render<F: PaneFactory + ?Sized>(state: &F, width: u16, height: u16) -> TestTerminal {
+    let mut terminal = TestTerminal::new(width, height);
+    terminal.draw(&[state.create_pane(width, height)]);
+    terminal
+}
+
+/// Returns the rows the state renders into a terminal of the size, with their styles.
+pub fn render_rows<F: PaneFactory + ?Sized>(
+    state: &F,
+    width: u16,
+    height: u16,
+) -> Vec<StyledGraphemes> {
+    render(state, width, height)
+        .frames()
+        .first()
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Returns the rows the state renders into a terminal of the size as plain strings
+/// (see `Pane::to_plain_strings`).
+pub fn render_to_strings<F: PaneFactory + ?Sized>(
+    state: &F,
+    width: u16,
+    height: u16,
+) -> Vec<String> {
+    render(state, width, height).frame(0)
+}
+
+/// Returns the graphemes of the rows the state renders into a terminal of the size,
+/// each with its style. A grapheme is a cluster of chars, e.g. an emoji with modifiers.
+pub fn render_styled<F: PaneFactory + ?Sized>(
+    state: &F,
+    width: u16,
+    height: u16,
+) -> Vec<Vec<(String, ContentStyle)>> {
+    render_rows(state, width, height)
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|grapheme| (grapheme.as_str().to_string(), *grapheme.style()))
+                .collect()
+        })
+        .collect()
+}
@@ -0,0 +1,122 @@
+//! The deadline of a timeout and the countdown to it, shared by `Prompt::timeout`
+//! and the renderers keeping their own (e.g. `Readline::inactivity_timeout`).
+
+use std::{
+    cell::Cell,
+    time::{Duration, Instant},
+};
+
+use crate::{text, TimeoutMode};
+
+/// How often a shown countdown is redrawn.
+pub const COUNTDOWN_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Counts down to a deadline, starting when it is first asked for.
+#[derive(Clone, Debug)]
+pub struct Timeout {
+    duration: Duration,
+    mode: TimeoutMode,
+    /// When the countdown started, or last restarted in idle mode.
+    started: Cell<Option<Instant>>,
+}
+
+impl Timeout {
+    pub fn new(duration: Duration, mode: TimeoutMode) -> Self {
+        Self {
+            duration,
+            mode,
+            started: Default::default(),
+        }
+    }
+
+    /// Returns when the timeout expires, starting the countdown at `now` if not started yet.
+    pub fn deadline(&self, now: Instant) -> Instant {
+        let started = match self.started.get() {
+            Some(started) => started,
+            None => {
+                self.started.set(Some(now));
+                now
+            }
+        };
+        started + self.duration
+    }
+
+    /// Returns the time left until the deadline.
+    pub fn remaining(&self, now: Instant) -> Duration {
+        self.deadline(now).saturating_duration_since(now)
+    }
+
+    /// Returns whether the deadline has passed.
+    pub fn is_expired(&self, now: Instant) -> bool {
+        self.deadline(now) <= now
+    }
+
+    /// Restarts the countdown on an event in idle mode; it does nothing in absolute mode.
+    pub fn activity(&self, now: Instant) {
+        if self.mode == TimeoutMode::Idle {
+            self.started.set(Some(now));
+        }
+    }
+
+    /// Stops the countdown, to start it again on the next run.
+    pub fn reset(&self) {
+        self.started.set(None);
+    }
+}
+
+/// Formats the time left of a countdown, e.g. `30s` and `4m 05s`.
+/// It is rounded up so that `0s` is shown only on expiry.
+pub fn format_remaining(remaining: Duration) -> String {
+    let secs = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+    match secs {
+        0..=59 => format!("{}s", secs),
+        _ => text::format_duration(Duration::from_secs(secs)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod timeout {
+        use super::*;
+
+        #[test]
+        fn test_idle() {
+            let start = Instant::now();
+            let timeout = Timeout::new(Duration::from_secs(10), TimeoutMode::Idle);
+            assert_eq!(start + Duration::from_secs(10), timeout.deadline(start));
+
+            let later = start + Duration::from_secs(8);
+            timeout.activity(later);
+            assert_eq!(Duration::from_secs(10), timeout.remaining(later));
+            assert!(!timeout.is_expired(start + Duration::from_secs(12)));
+            assert!(timeout.is_expired(start + Duration::from_secs(18)));
+
+            timeout.reset();
+            assert_eq!(later + Duration::from_secs(10), timeout.deadline(later));
+        }
+
+        #[test]
+        fn test_absolute() {
+            let start = Instant::now();
+            let timeout = Timeout::new(Duration::from_secs(10), TimeoutMode::Absolute);
+            timeout.deadline(start);
+            timeout.activity(start + Duration::from_secs(8));
+            assert!(timeout.is_expired(start + Duration::from_secs(10)));
+        }
+    }
+
+    mod format_remaining {
+        use super::*;
+
+        #[test]
+        fn test() {
+            assert_eq!("0s", format_remaining(Duration::ZERO));
+            assert_eq!("1s", format_remaining(Duration::from_millis(1)));
+            assert_eq!("30s", format_remaining(Duration::from_secs(30)));
+            assert_eq!("1m 00s", format_remaining(Duration::from_millis(59_500)));
+            assert_eq!("4m 05s", format_remaining(Duration::from_secs(245)));
+        }
+    }
+}
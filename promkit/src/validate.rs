@@ -54,7 +54,13 @@ impl<T: ?Sized> ValidatorManager<T> {
     /// Returns `true` if the input passes the validation,
     /// otherwise `false`.
     pub fn validate(&self, input: &T) -> bool {
-        (self.validator)(input)
+        #[cfg(feature = "tracing")]
+        let span = tracing::info_span!("promkit.validate", promkit.valid = tracing::field::Empty)
+            .entered();
+        let valid = (self.validator)(input);
+        #[cfg(feature = "tracing")]
+        span.record("promkit.valid", valid);
+        valid
     }
 
     /// Generates an error message for the given input
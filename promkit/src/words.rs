@@ -0,0 +1,311 @@
+//! Word boundaries shared by the components handling words,
+//! e.g. the word-wise movement and erasure of `TextEditor`.
+//!
+//! All positions are grapheme indices, and a grapheme is a delimiter
+//! if its base character is contained in the given delimiter set.
+//! The `unicode_*` variants follow the Unicode word boundaries (UAX #29) instead.
+
+use std::{collections::HashSet, ops::Range};
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::grapheme::{StyledGrapheme, StyledGraphemes};
+
+fn is_delimiter(grapheme: &StyledGrapheme, delimiters: &HashSet<char>) -> bool {
+    delimiters.contains(&grapheme.base_char())
+}
+
+/// Returns the start of the word before the index,
+/// skipping the delimiters just before it first (e.g. multiple spaces).
+/// Returns 0 if there is no such word.
+pub fn previous_boundary(
+    graphemes: &StyledGraphemes,
+    index: usize,
+    delimiters: &HashSet<char>,
+) -> usize {
+    let mut i = index.min(graphemes.len());
+    while i > 0 && is_delimiter(&graphemes[i - 1], delimiters) {
+        i -= 1;
+    }
+    while i > 0 && !is_delimiter(&graphemes[i - 1], delimiters) {
+        i -= 1;
+    }
+    i
+}
+
+/// Returns the start of the word after the index,
+/// i.e. skips the rest of the current word and the delimiters following it.
+/// Returns the length if there is no such word.
+pub fn next_boundary(
+    graphemes: &StyledGraphemes,
+    index: usize,
+    delimiters: &HashSet<char>,
+) -> usize {
+    let len = graphemes.len();
+    let mut i = index.min(len);
+    while i < len && !is_delimiter(&graphemes[i], delimiters) {
+        i += 1;
+    }
+    while i < len && is_delimiter(&graphemes[i], delimiters) {
+        i += 1;
+    }
+    i
+}
+
+/// Returns the range of the token (a run of non-delimiters) under the index.
+/// If the index is just after a token (e.g. the cursor at the end of a word), that token is returned.
+/// Returns an empty range at the index if it touches no token.
+pub fn token_range(
+    graphemes: &StyledGraphemes,
+    index: usize,
+    delimiters: &HashSet<char>,
+) -> Range<usize> {
+    let len = graphemes.len();
+    let index = index.min(len);
+    let mut start = index;
+    while start > 0 && !is_delimiter(&graphemes[start - 1], delimiters) {
+        start -= 1;
+    }
+    let mut end = index;
+    while end < len && !is_delimiter(&graphemes[end], delimiters) {
+        end += 1;
+    }
+    start..end
+}
+
+/// Splits the graphemes into the ranges of the tokens (runs of non-delimiters), in order.
+pub fn tokens(graphemes: &StyledGraphemes, delimiters: &HashSet<char>) -> Vec<Range<usize>> {
+    let mut ret = Vec::new();
+    let mut start = None;
+    for (i, grapheme) in graphemes.iter().enumerate() {
+        match (is_delimiter(grapheme, delimiters), start) {
+            (false, None) => start = Some(i),
+            (true, Some(s)) => {
+                ret.push(s..i);
+                start = None;
+            }
+            _ => (),
+        }
+    }
+    if let Some(s) = start {
+        ret.push(s..graphemes.len());
+    }
+    ret
+}
+
+/// Whether the segment is a word, i.e. has a character other than whitespace and punctuation,
+/// so that emoji count as words like letters and digits.
+fn is_word(segment: &str) -> bool {
+    segment.chars().any(|ch| {
+        !ch.is_whitespace()
+            && !ch.is_ascii_punctuation()
+            // General punctuation, CJK symbols and punctuation, and fullwidth ASCII punctuation.
+            && !matches!(
+                ch,
+                '\u{2000}'..='\u{206F}'
+                    | '\u{3000}'..='\u{303F}'
+                    | '\u{FF01}'..='\u{FF0F}'
+                    | '\u{FF1A}'..='\u{FF20}'
+            )
+    })
+}
+
+/// Splits the graphemes into the ranges of the words by the Unicode word boundaries, in order,
+/// e.g. `foo` and `bar_baz` of `foo-bar_baz`, each CJK ideograph, or an emoji sequence.
+pub fn unicode_words(graphemes: &StyledGraphemes) -> Vec<Range<usize>> {
+    // The byte offset of each grapheme in the text, to map the segments back to graphemes.
+    let mut offsets = Vec::with_capacity(graphemes.len());
+    let mut text = String::new();
+    for grapheme in graphemes.iter() {
+        offsets.push(text.len());
+        text.push_str(grapheme.as_str());
+    }
+    let index = |byte: usize| offsets.partition_point(|&offset| offset < byte);
+    text.split_word_bound_indices()
+        .filter(|(_, segment)| is_word(segment))
+        .map(|(start, segment)| index(start)..index(start + segment.len()))
+        .collect()
+}
+
+/// Returns the start of the word before the index by the Unicode word boundaries,
+/// or 0 if there is no such word.
+pub fn unicode_previous_boundary(graphemes: &StyledGraphemes, index: usize) -> usize {
+    unicode_words(graphemes)
+        .iter()
+        .rev()
+        .map(|word| word.start)
+        .find(|&start| start < index)
+        .unwrap_or(0)
+}
+
+/// Returns the start of the word after the index by the Unicode word boundaries,
+/// or the length if there is no such word.
+pub fn unicode_next_boundary(graphemes: &StyledGraphemes, index: usize) -> usize {
+    unicode_words(graphemes)
+        .iter()
+        .map(|word| word.start)
+        .find(|&start| start > index)
+        .unwrap_or(graphemes.len())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn spaces() -> HashSet<char> {
+        HashSet::from([' '])
+    }
+
+    mod previous_boundary {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let text = StyledGraphemes::from("koko momo jojo");
+            assert_eq!(10, previous_boundary(&text, 12, &spaces()));
+            assert_eq!(5, previous_boundary(&text, 10, &spaces()));
+            assert_eq!(0, previous_boundary(&text, 5, &spaces()));
+            assert_eq!(0, previous_boundary(&text, 0, &spaces()));
+        }
+
+        #[test]
+        fn test_over_multiple_delimiters() {
+            let text = StyledGraphemes::from("ab   cd  ");
+            assert_eq!(5, previous_boundary(&text, 9, &spaces()));
+            assert_eq!(0, previous_boundary(&text, 5, &spaces()));
+            assert_eq!(0, previous_boundary(&text, 4, &spaces()));
+        }
+
+        #[test]
+        fn test_with_no_delimiter() {
+            let text = StyledGraphemes::from("koko momo");
+            assert_eq!(0, previous_boundary(&text, 7, &HashSet::from(['z'])));
+        }
+
+        #[test]
+        fn test_with_grapheme_clusters() {
+            let text = StyledGraphemes::from("e\u{301}e\u{301} 👨‍👩‍👧x");
+            assert_eq!(3, previous_boundary(&text, 5, &spaces()));
+            assert_eq!(0, previous_boundary(&text, 3, &spaces()));
+        }
+    }
+
+    mod next_boundary {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let text = StyledGraphemes::from("koko momo jojo");
+            assert_eq!(10, next_boundary(&text, 7, &spaces()));
+            assert_eq!(14, next_boundary(&text, 10, &spaces()));
+            assert_eq!(14, next_boundary(&text, 14, &spaces()));
+        }
+
+        #[test]
+        fn test_over_multiple_delimiters() {
+            let text = StyledGraphemes::from("ab   cd");
+            assert_eq!(5, next_boundary(&text, 0, &spaces()));
+            assert_eq!(5, next_boundary(&text, 3, &spaces()));
+        }
+
+        #[test]
+        fn test_with_no_delimiter() {
+            let text = StyledGraphemes::from("koko momo");
+            assert_eq!(9, next_boundary(&text, 2, &HashSet::from(['z'])));
+        }
+    }
+
+    mod token_range {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let text = StyledGraphemes::from("git comm --all");
+            assert_eq!(4..8, token_range(&text, 6, &spaces()));
+            assert_eq!(4..8, token_range(&text, 8, &spaces()));
+            assert_eq!(4..8, token_range(&text, 4, &spaces()));
+            assert_eq!(9..14, token_range(&text, 14, &spaces()));
+        }
+
+        #[test]
+        fn test_between_delimiters() {
+            let text = StyledGraphemes::from("a  b");
+            assert_eq!(2..2, token_range(&text, 2, &spaces()));
+        }
+    }
+
+    mod tokens {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let text = StyledGraphemes::from("  ab   c👨‍👩‍👧 d ");
+            assert_eq!(vec![2..4, 7..9, 10..11], tokens(&text, &spaces()));
+        }
+
+        #[test]
+        fn test_with_empty_input() {
+            assert!(tokens(&StyledGraphemes::default(), &spaces()).is_empty());
+        }
+    }
+
+    mod unicode_words {
+        use super::*;
+
+        fn words(text: &str) -> Vec<String> {
+            let text = StyledGraphemes::from(text);
+            unicode_words(&text)
+                .into_iter()
+                .map(|word| {
+                    text.iter()
+                        .skip(word.start)
+                        .take(word.len())
+                        .map(|g| g.as_str())
+                        .collect()
+                })
+                .collect()
+        }
+
+        #[test]
+        fn test_hyphenated_identifier() {
+            assert_eq!(vec!["foo", "bar_baz", "qux"], words("foo-bar_baz qux"));
+        }
+
+        #[test]
+        fn test_cjk() {
+            // Each ideograph is a word, while a run of katakana is one.
+            assert_eq!(vec!["日", "本", "語", "テスト"], words("日本語、テスト。"));
+        }
+
+        #[test]
+        fn test_emoji_sequence() {
+            let text = StyledGraphemes::from("hi 👨‍👩‍👧 e\u{301}x");
+            assert_eq!(vec![0..2, 3..4, 5..7], unicode_words(&text));
+        }
+    }
+
+    mod unicode_previous_boundary {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let text = StyledGraphemes::from("foo-bar baz");
+            assert_eq!(8, unicode_previous_boundary(&text, 11));
+            assert_eq!(4, unicode_previous_boundary(&text, 8));
+            assert_eq!(0, unicode_previous_boundary(&text, 4));
+            assert_eq!(0, unicode_previous_boundary(&text, 0));
+        }
+    }
+
+    mod unicode_next_boundary {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let text = StyledGraphemes::from("foo-bar baz");
+            assert_eq!(4, unicode_next_boundary(&text, 0));
+            assert_eq!(8, unicode_next_boundary(&text, 5));
+            assert_eq!(11, unicode_next_boundary(&text, 8));
+        }
+    }
+}
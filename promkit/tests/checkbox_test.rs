@@ -0,0 +1,246 @@
+#[cfg(test)]
+mod checkbox {
+    use promkit::{
+        crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers},
+        preset::checkbox::Checkbox,
+        terminal::TestTerminal,
+    };
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn test_from_entries() {
+        let mut p = Checkbox::from_entries([(1, "one"), (2, "two"), (3, "three")])
+            .prompt()
+            .unwrap();
+        let mut terminal = TestTerminal::new(20, 10);
+        let ret = p
+            .run_with_events(
+                [
+                    key(KeyCode::Char(' ')),
+                    key(KeyCode::Down),
+                    key(KeyCode::Down),
+                    key(KeyCode::Char(' ')),
+                    key(KeyCode::Enter),
+                ],
+                &mut terminal,
+            )
+            .unwrap();
+
+        assert_eq!(vec![1, 3], ret);
+    }
+
+    #[test]
+    fn test_identical_labels_keep_their_values() {
+        let mut p = Checkbox::from_entries([("first", "same"), ("second", "same")])
+            .prompt()
+            .unwrap();
+        let mut terminal = TestTerminal::new(20, 10);
+        let ret = p
+            .run_with_events(
+                [
+                    key(KeyCode::Down),
+                    key(KeyCode::Char(' ')),
+                    key(KeyCode::Enter),
+                ],
+                &mut terminal,
+            )
+            .unwrap();
+
+        assert_eq!(vec!["second"], ret);
+    }
+
+    #[test]
+    fn test_emoji_marks_keep_labels_aligned() {
+        let mut p = Checkbox::new(["日本語", "ab"])
+            .active_mark('✅')
+            .inactive_mark('-')
+            .prompt()
+            .unwrap();
+        let mut terminal = TestTerminal::new(20, 10);
+        p.run_with_events(
+            [key(KeyCode::Char(' ')), key(KeyCode::Enter)],
+            &mut terminal,
+        )
+        .unwrap();
+
+        assert_eq!(vec!["❯ -  日本語", "  -  ab"], terminal.frame(0));
+        assert_eq!(vec!["❯ ✅ 日本語", "  -  ab"], terminal.last_frame());
+    }
+
+    #[test]
+    fn test_key_bindings() {
+        use promkit::{key_bindings::KeyBindings, preset::checkbox::keymap::Action};
+
+        let mut p = Checkbox::new(["a", "b"])
+            .key_bindings(KeyBindings::default().bind('x', Action::Toggle).unbind(' '))
+            .prompt()
+            .unwrap();
+        let ret = p
+            .run_with_events(
+                [
+                    key(KeyCode::Char(' ')),
+                    key(KeyCode::Down),
+                    key(KeyCode::Char('x')),
+                    key(KeyCode::Enter),
+                ],
+                &mut TestTerminal::new(20, 10),
+            )
+            .unwrap();
+        assert_eq!(vec!["b"], ret);
+    }
+
+    #[test]
+    fn test_bulk_toggles() {
+        let mut p = Checkbox::new(["a", "b", "c", "d"]).prompt().unwrap();
+        let mut terminal = TestTerminal::new(20, 10);
+        let ret = p
+            .run_with_events(
+                [
+                    // Check all, then uncheck all.
+                    Event::Key(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::CONTROL)),
+                    Event::Key(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::CONTROL)),
+                    // Check b..=d.
+                    key(KeyCode::Down),
+                    key(KeyCode::Char(' ')),
+                    key(KeyCode::Down),
+                    key(KeyCode::Down),
+                    Event::Key(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::SHIFT)),
+                    // Invert into a.
+                    key(KeyCode::Char('i')),
+                    key(KeyCode::Enter),
+                ],
+                &mut terminal,
+            )
+            .unwrap();
+        assert_eq!(vec!["a"], ret);
+
+        // The marks of all items change within a single frame.
+        assert_eq!(vec!["❯ ☒ a", "  ☒ b", "  ☒ c", "  ☒ d"], terminal.frame(1));
+    }
+
+    #[test]
+    fn test_not_interactive() {
+        let mut p = Checkbox::new_with_checked([("a", true), ("b", false)])
+            .interactive(false)
+            .prompt()
+            .unwrap();
+        let mut terminal = TestTerminal::new(20, 10);
+        let ret = p
+            .run_with_events(
+                [
+                    key(KeyCode::Down),
+                    key(KeyCode::Char(' ')),
+                    key(KeyCode::Enter),
+                ],
+                &mut terminal,
+            )
+            .unwrap();
+        assert_eq!(vec!["a"], ret);
+        assert_eq!(vec!["☒ a", "☐ b"], terminal.last_frame());
+    }
+
+    #[test]
+    fn test_cyclic_and_paging() {
+        let mut p = Checkbox::new(["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"])
+            .checkbox_lines(3)
+            .cyclic(true)
+            .prompt()
+            .unwrap();
+        let mut terminal = TestTerminal::new(20, 10);
+        let ret = p
+            .run_with_events(
+                [
+                    key(KeyCode::Up),
+                    key(KeyCode::Char(' ')),
+                    key(KeyCode::PageUp),
+                    key(KeyCode::Char(' ')),
+                    key(KeyCode::Home),
+                    key(KeyCode::PageDown),
+                    key(KeyCode::Char(' ')),
+                    key(KeyCode::Enter),
+                ],
+                &mut terminal,
+            )
+            .unwrap();
+        assert_eq!(vec!["3", "6", "9"], ret);
+    }
+
+    #[test]
+    fn test_resume_session() {
+        let mut p = Checkbox::new(["lint", "test", "docs", "release"])
+            .prompt()
+            .unwrap();
+        let mut terminal = TestTerminal::new(20, 10);
+        p.run_with_events(
+            [
+                key(KeyCode::Down),
+                key(KeyCode::Char(' ')),
+                key(KeyCode::Down),
+                key(KeyCode::Char(' ')),
+                key(KeyCode::Enter),
+            ],
+            &mut terminal,
+        )
+        .unwrap();
+        let state = p.renderer.export_state();
+
+        // "docs" is removed and the items are reordered in the next session.
+        let mut p = Checkbox::new(["release", "test", "lint"])
+            .import_state(&state)
+            .unwrap()
+            .prompt()
+            .unwrap();
+        let ret = p
+            .run_with_events(
+                [key(KeyCode::Char(' ')), key(KeyCode::Enter)],
+                &mut terminal,
+            )
+            .unwrap();
+        // The cursor falls back to the position of the removed "docs", i.e. "lint".
+        assert_eq!(vec!["test", "lint"], ret);
+    }
+
+    #[test]
+    fn test_preselect() {
+        let mut p = Checkbox::new(["release", "test", "lint"])
+            .preselect([2, 0])
+            .prompt()
+            .unwrap();
+        let mut terminal = TestTerminal::new(20, 10);
+        let ret = p
+            .run_with_events(
+                [key(KeyCode::Char(' ')), key(KeyCode::Enter)],
+                &mut terminal,
+            )
+            .unwrap();
+        assert_eq!(vec!["lint"], ret);
+    }
+
+    #[test]
+    fn test_preserve_toggle_order() {
+        let mut p = Checkbox::new(["release", "test", "lint"])
+            .preselect_labels(["test"])
+            .preserve_toggle_order(true)
+            .prompt()
+            .unwrap();
+        let mut terminal = TestTerminal::new(20, 10);
+        let ret = p
+            .run_with_events(
+                [
+                    key(KeyCode::Down),
+                    key(KeyCode::Down),
+                    key(KeyCode::Char(' ')),
+                    key(KeyCode::Up),
+                    key(KeyCode::Up),
+                    key(KeyCode::Char(' ')),
+                    key(KeyCode::Enter),
+                ],
+                &mut terminal,
+            )
+            .unwrap();
+        assert_eq!(vec!["test", "lint", "release"], ret);
+    }
+}
@@ -0,0 +1,90 @@
+#[cfg(test)]
+#[allow(deprecated)]
+mod deprecated {
+    use promkit::{
+        crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers},
+        prelude::*,
+        preset::query_selector::filter,
+        terminal::TestTerminal,
+    };
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn frames<T: Renderer>(mut p: Prompt<T>, events: Vec<Event>) -> Vec<Vec<String>> {
+        let mut terminal = TestTerminal::new(20, 10);
+        p.run_with_events(events, &mut terminal).ok();
+        (0..terminal.frames().len())
+            .map(|i| terminal.frame(i))
+            .collect()
+    }
+
+    #[test]
+    fn test_cursor() {
+        let enter = vec![key(KeyCode::Enter)];
+        assert_eq!(
+            frames(
+                Listbox::new(["a", "b"]).cursor("-> ").prompt().unwrap(),
+                enter.clone()
+            ),
+            frames(
+                Listbox::new(["a", "b"])
+                    .cursor_symbol("-> ")
+                    .prompt()
+                    .unwrap(),
+                enter.clone()
+            ),
+        );
+        assert_eq!(
+            frames(
+                Checkbox::new(["a", "b"]).cursor("-> ").prompt().unwrap(),
+                enter.clone()
+            ),
+            frames(
+                Checkbox::new(["a", "b"])
+                    .cursor_symbol("-> ")
+                    .prompt()
+                    .unwrap(),
+                enter.clone()
+            ),
+        );
+        assert_eq!(
+            frames(
+                QuerySelector::new(["a", "b"], filter::fuzzy)
+                    .cursor("-> ")
+                    .prompt()
+                    .unwrap(),
+                enter.clone()
+            ),
+            frames(
+                QuerySelector::new(["a", "b"], filter::fuzzy)
+                    .cursor_symbol("-> ")
+                    .prompt()
+                    .unwrap(),
+                enter
+            ),
+        );
+    }
+
+    #[test]
+    fn test_enable_suggest() {
+        let events = vec![key(KeyCode::Char('a')), key(KeyCode::Tab)];
+        assert_eq!(
+            frames(
+                Readline::default()
+                    .enable_suggest(Suggest::from_iter(["apple", "apricot"]))
+                    .prompt()
+                    .unwrap(),
+                events.clone()
+            ),
+            frames(
+                Readline::default()
+                    .suggest(Suggest::from_iter(["apple", "apricot"]))
+                    .prompt()
+                    .unwrap(),
+                events
+            ),
+        );
+    }
+}
@@ -0,0 +1,126 @@
+#[cfg(test)]
+mod balance_range {
+    use promkit::jsonz::*;
+
+    fn rows() -> Vec<Row> {
+        // 0 {
+        // 1   "a": 1,
+        // 2   "b": [
+        // 3     10,
+        // 4     20,
+        // 5     {
+        // 6       "c": true
+        // 7     }
+        // 8   ],
+        // 9   "d": {
+        // 10    "e": null
+        // 11  },
+        // 12  "f": "x"
+        // 13 }
+        create_rows([&serde_json::json!({
+            "a": 1,
+            "b": [10, 20, {"c": true}],
+            "d": {"e": null},
+            "f": "x",
+        })])
+    }
+
+    #[test]
+    fn test_single_value() {
+        let rows = rows();
+        assert_eq!(1..=1, balance_range(&rows, 1, 1));
+        assert_eq!(
+            vec![serde_json::json!(1)],
+            values_in_range(&rows, balance_range(&rows, 1, 1))
+        );
+        // A container is completed from either bracket.
+        assert_eq!(2..=8, balance_range(&rows, 2, 2));
+        assert_eq!(2..=8, balance_range(&rows, 8, 8));
+        assert_eq!(
+            vec![serde_json::json!([10, 20, {"c": true}])],
+            values_in_range(&rows, balance_range(&rows, 8, 8))
+        );
+    }
+
+    #[test]
+    fn test_siblings_are_widened_to_their_parent() {
+        let rows = rows();
+        assert_eq!(2..=8, balance_range(&rows, 3, 4));
+        // In either order.
+        assert_eq!(2..=8, balance_range(&rows, 4, 3));
+        assert_eq!(0..=13, balance_range(&rows, 1, 12));
+    }
+
+    #[test]
+    fn test_crossing_containers() {
+        let rows = rows();
+        // From inside "b" to inside "d": both containers are completed,
+        // and then widened to the root as siblings.
+        assert_eq!(0..=13, balance_range(&rows, 6, 10));
+        // From the last element of "b" to its closing bracket.
+        assert_eq!(5..=7, balance_range(&rows, 5, 7));
+        assert_eq!(2..=8, balance_range(&rows, 6, 8));
+    }
+
+    #[test]
+    fn test_collapsed_containers_in_range() {
+        // 0 {
+        // 1   "x": {
+        // 2     "y": [
+        // 3       1,
+        // 4       2
+        // 5     ],
+        // 6     "z": 3
+        // 7   }
+        // 8 }
+        let mut rows = create_rows([&serde_json::json!({"x": {"y": [1, 2], "z": 3}})]);
+        rows.toggle(2);
+        // The cursor moves over the collapsed "y" from row 2 to row 6.
+        assert_eq!(6, rows.down(2));
+        assert_eq!(1..=7, balance_range(&rows, 2, 6));
+        assert_eq!(
+            vec![serde_json::json!({"y": [1, 2], "z": 3})],
+            values_in_range(&rows, balance_range(&rows, 2, 6))
+        );
+        // The rows hidden in the collapsed container are included.
+        assert_eq!(2..=5, balance_range(&rows, 2, 2));
+        assert_eq!(
+            vec![serde_json::json!([1, 2])],
+            values_in_range(&rows, balance_range(&rows, 2, 2))
+        );
+
+        rows.toggle(1);
+        assert_eq!(0..=8, balance_range(&rows, 0, 1));
+        assert_eq!(1..=7, balance_range(&rows, 1, 1));
+    }
+
+    #[test]
+    fn test_documents() {
+        // 0 1
+        // 1 {
+        // 2   "a": 2
+        // 3 }
+        // 4 [
+        // 5   3
+        // 6 ]
+        let rows = create_rows([
+            &serde_json::json!(1),
+            &serde_json::json!({"a": 2}),
+            &serde_json::json!([3]),
+        ]);
+        // The documents are kept side by side.
+        assert_eq!(0..=3, balance_range(&rows, 0, 2));
+        assert_eq!(
+            vec![serde_json::json!(1), serde_json::json!({"a": 2})],
+            values_in_range(&rows, balance_range(&rows, 0, 2))
+        );
+        assert_eq!(1..=6, balance_range(&rows, 3, 5));
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        let rows = rows();
+        assert_eq!(0..=13, balance_range(&rows, 0, 100));
+        assert!(values_in_range(&[], balance_range(&[], 0, 3)).is_empty());
+    }
+}
@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod find_by_path {
+    use std::str::FromStr;
+
+    use promkit::jsonz::*;
+
+    #[test]
+    fn test_nested_objects_and_arrays() {
+        let input = serde_json::Value::from_str(
+            r#"
+                {
+                    "spec": {
+                        "containers": [
+                            {
+                                "name": "app",
+                                "image": "nginx"
+                            },
+                            {
+                                "name": "sidecar",
+                                "image": "envoy"
+                            }
+                        ]
+                    },
+                    "dotted.key": [1, [2, 3]]
+                }
+            "#,
+        )
+        .unwrap();
+
+        let rows = create_rows([&input]);
+
+        assert_eq!(find_by_path(&rows, "."), Some(0));
+        assert_eq!(find_by_path(&rows, ".spec"), Some(1));
+        assert_eq!(find_by_path(&rows, ".spec.containers"), Some(2));
+        assert_eq!(find_by_path(&rows, ".spec.containers[0]"), Some(3));
+        assert_eq!(find_by_path(&rows, ".spec.containers[0].image"), Some(5));
+        assert_eq!(find_by_path(&rows, ".spec.containers[1].name"), Some(8));
+        assert_eq!(find_by_path(&rows, ".\"dotted.key\"[1][0]"), Some(16));
+    }
+
+    #[test]
+    fn test_every_path_is_found() {
+        let input = serde_json::Value::from_str(
+            r#"
+                {
+                    "a": [{"b": null}, []],
+                    "c": {"d": {}}
+                }
+            "#,
+        )
+        .unwrap();
+
+        let rows = create_rows([&input]);
+        for path in get_all_paths([&input]) {
+            assert!(find_by_path(&rows, &path).is_some(), "{}", path);
+        }
+    }
+
+    #[test]
+    fn test_invalid_paths() {
+        let input = serde_json::Value::from_str(r#"{"a": [1, 2]}"#).unwrap();
+
+        let rows = create_rows([&input]);
+
+        assert_eq!(find_by_path(&rows, ".b"), None);
+        assert_eq!(find_by_path(&rows, ".a[2]"), None);
+        assert_eq!(find_by_path(&rows, "a"), None);
+    }
+}
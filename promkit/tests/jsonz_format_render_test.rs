@@ -0,0 +1,110 @@
+#[cfg(test)]
+mod render {
+    use std::str::FromStr;
+
+    use promkit::jsonz::{
+        create_rows,
+        format::{render, Format},
+        RowOperation,
+    };
+
+    fn input() -> serde_json::Value {
+        serde_json::Value::from_str(
+            r#"
+                {
+                    "name": "promkit",
+                    "tags": ["tui", "prompt"],
+                    "nested": {"count": 2, "empty": []},
+                    "a.b": true,
+                    "say \"hi\"": null,
+                    "list": [{"id": 1, "ok": "yes"}]
+                }
+            "#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_compact() {
+        let values = [input(), serde_json::json!(1)];
+        assert_eq!(
+            render(&values[..], Format::Compact),
+            [
+                r#"{"name":"promkit","tags":["tui","prompt"],"nested":{"count":2,"empty":[]},"a.b":true,"say \"hi\"":null,"list":[{"id":1,"ok":"yes"}]}"#,
+                "1",
+            ]
+            .join("\n"),
+        );
+    }
+
+    #[test]
+    fn test_pretty() {
+        let value = serde_json::json!({"a": [1]});
+        assert_eq!(
+            render(&value, Format::Pretty { indent: 4 }),
+            "{\n    \"a\": [\n        1\n    ]\n}",
+        );
+    }
+
+    #[test]
+    fn test_path_list() {
+        assert_eq!(
+            render(&input(), Format::PathList),
+            [
+                r#".name = "promkit""#,
+                r#".tags[0] = "tui""#,
+                r#".tags[1] = "prompt""#,
+                ".nested.count = 2",
+                ".nested.empty = []",
+                r#"."a.b" = true"#,
+                r#"."say \"hi\"" = null"#,
+                ".list[0].id = 1",
+                r#".list[0].ok = "yes""#,
+            ]
+            .join("\n"),
+        );
+    }
+
+    #[test]
+    fn test_path_list_of_visible_rows() {
+        let mut rows = create_rows([&input()]);
+        // Collapses "tags".
+        rows.toggle(2);
+        assert_eq!(
+            render(&rows[..], Format::PathList)
+                .lines()
+                .take(3)
+                .collect::<Vec<_>>(),
+            vec![
+                r#".name = "promkit""#,
+                r#".tags = ["tui","prompt"]"#,
+                ".nested.count = 2",
+            ],
+        );
+    }
+
+    #[test]
+    fn test_yaml() {
+        let values = [input(), serde_json::json!("text")];
+        assert_eq!(
+            render(&values[..], Format::Yaml),
+            [
+                "name: promkit",
+                "tags:",
+                "  - tui",
+                "  - prompt",
+                "nested:",
+                "  count: 2",
+                "  empty: []",
+                "a.b: true",
+                r#""say \"hi\"": null"#,
+                "list:",
+                "  - id: 1",
+                r#"    ok: "yes""#,
+                "---",
+                "text",
+            ]
+            .join("\n"),
+        );
+    }
+}
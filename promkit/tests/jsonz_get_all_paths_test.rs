@@ -138,4 +138,33 @@ mod get_all_paths {
 
         assert_eq!(actual, expected, "Paths do not match expected values");
     }
+
+    #[test]
+    fn test_get_all_paths_quotes_keys() {
+        let v = serde_json::json!({
+            "_id1": 1,
+            "1abc": 2,
+            "日本": 3,
+            "a.b": 4,
+            "say \"hi\"": 5,
+            "": 6,
+        });
+
+        let actual = jsonz::get_all_paths([&v]).collect::<HashSet<_>>();
+        let expected = HashSet::from_iter(
+            [
+                ".",
+                "._id1",
+                r#"."1abc""#,
+                r#"."日本""#,
+                r#"."a.b""#,
+                r#"."say \"hi\"""#,
+                r#"."""#,
+            ]
+            .into_iter()
+            .map(|e| e.to_string()),
+        );
+
+        assert_eq!(actual, expected, "Paths do not match expected values");
+    }
 }
@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod path_of {
+    use std::str::FromStr;
+
+    use promkit::jsonz::*;
+
+    #[test]
+    fn test_nested_objects_and_arrays() {
+        let input = serde_json::Value::from_str(
+            r#"
+                {
+                    "a": {
+                        "b": [
+                            0,
+                            1,
+                            {
+                                "c": "value"
+                            }
+                        ]
+                    },
+                    "with-dash": null
+                }
+            "#,
+        )
+        .unwrap();
+
+        let rows = create_rows([&input]);
+
+        assert_eq!(path_of(&rows, 0), ".");
+        assert_eq!(path_of(&rows, 1), ".a");
+        assert_eq!(path_of(&rows, 2), ".a.b");
+        assert_eq!(path_of(&rows, 4), ".a.b[1]");
+        assert_eq!(path_of(&rows, 5), ".a.b[2]");
+        assert_eq!(path_of(&rows, 6), ".a.b[2].c");
+        // Closing brackets share the path of their container.
+        assert_eq!(path_of(&rows, 7), ".a.b[2]");
+        assert_eq!(path_of(&rows, 10), ".\"with-dash\"");
+        assert_eq!(path_of(&rows, 11), ".");
+        assert_eq!(path_of(&rows, 12), "");
+    }
+
+    #[test]
+    fn test_roundtrip_with_find_by_path() {
+        let input = serde_json::Value::from_str(r#"[{"x": [[], {}]}, "y"]"#).unwrap();
+
+        let rows = create_rows([&input]);
+        for path in get_all_paths([&input]) {
+            let index = find_by_path(&rows, &path).unwrap();
+            assert_eq!(path_of(&rows, index), path);
+        }
+    }
+
+    #[test]
+    fn test_unambiguous_keys() {
+        let input =
+            serde_json::Value::from_str(r#"{"a.b": 1, "a": {"b": 2}, "q\"": 3, "": 4}"#).unwrap();
+
+        let rows = create_rows([&input]);
+        assert_eq!(path_of(&rows, 1), r#"."a.b""#);
+        assert_eq!(path_of(&rows, 3), ".a.b");
+        assert_eq!(path_of(&rows, 5), r#"."q\"""#);
+        assert_eq!(path_of(&rows, 6), r#"."""#);
+        for path in get_all_paths([&input]) {
+            let index = find_by_path(&rows, &path).unwrap();
+            assert_eq!(path_of(&rows, index), path);
+        }
+    }
+}
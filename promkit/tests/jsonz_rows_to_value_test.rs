@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod rows_to_value {
+    use std::str::FromStr;
+
+    use promkit::jsonz::*;
+    use serde_json::Deserializer;
+
+    /// Generates a nested value deterministically from the seed.
+    fn generate(seed: &mut u64, depth: usize) -> serde_json::Value {
+        *seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        let r = (*seed >> 33) as usize;
+        let kind = if depth == 0 { r % 4 } else { r % 6 };
+        match kind {
+            0 => serde_json::Value::Null,
+            1 => serde_json::Value::Bool(r.is_multiple_of(2)),
+            2 => serde_json::json!(r % 1000),
+            3 => serde_json::Value::String(format!("s{}", r % 100)),
+            4 => serde_json::Value::Array((0..r % 4).map(|_| generate(seed, depth - 1)).collect()),
+            _ => serde_json::Value::Object(
+                (0..r % 4)
+                    .map(|i| (format!("k{}", i), generate(seed, depth - 1)))
+                    .collect(),
+            ),
+        }
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let mut seed = 42;
+        for _ in 0..200 {
+            let input = generate(&mut seed, 4);
+            let rows = create_rows([&input]);
+            assert_eq!(rows_to_value(&rows), input);
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_with_collapsed_containers() {
+        let input =
+            serde_json::Value::from_str(r#"{"a": {"b": [1, {}]}, "c": [], "d": "e"}"#).unwrap();
+
+        let mut rows = create_rows([&input]);
+        rows.toggle(1);
+        rows.toggle(0);
+        assert_eq!(rows_to_value(&rows), input);
+    }
+
+    #[test]
+    fn test_multiple_documents() {
+        let values: Vec<_> = Deserializer::from_str(
+            r#"
+                {"a": 1}
+                []
+                "text"
+            "#,
+        )
+        .into_iter::<serde_json::Value>()
+        .filter_map(serde_json::Result::ok)
+        .collect();
+
+        let rows = create_rows(values.iter());
+        assert_eq!(
+            rows_to_value(&rows),
+            serde_json::json!([{"a": 1}, [], "text"])
+        );
+    }
+}
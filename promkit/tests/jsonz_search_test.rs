@@ -0,0 +1,49 @@
+#[cfg(test)]
+mod search {
+    use std::str::FromStr;
+
+    use promkit::jsonz::*;
+
+    #[test]
+    fn test_keys_and_string_values() {
+        let input = serde_json::Value::from_str(
+            r#"
+                {
+                    "image": "nginx",
+                    "name": "app-image",
+                    "count": 1,
+                    "tags": ["image", "latest"]
+                }
+            "#,
+        )
+        .unwrap();
+
+        let rows = create_rows([&input]);
+
+        assert_eq!(search(&rows, "image"), vec![1, 2, 5]);
+        assert_eq!(search(&rows, "1"), Vec::<usize>::new());
+        assert_eq!(search(&rows, ""), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_skip_collapsed_containers() {
+        let input = serde_json::Value::from_str(
+            r#"
+                {
+                    "hidden": {
+                        "key": "value"
+                    },
+                    "key": "visible"
+                }
+            "#,
+        )
+        .unwrap();
+
+        let mut rows = create_rows([&input]);
+        assert_eq!(search(&rows, "key"), vec![2, 4]);
+
+        rows.toggle(1);
+        assert_eq!(search(&rows, "key"), vec![4]);
+        assert_eq!(search(&rows, "hidden"), vec![1]);
+    }
+}
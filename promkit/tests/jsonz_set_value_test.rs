@@ -0,0 +1,55 @@
+#[cfg(test)]
+mod set_value {
+    use promkit::jsonz::*;
+
+    #[test]
+    fn test() {
+        let input = serde_json::json!({"z": 1, "a": [true, "x"], "m": {}});
+        let mut rows = create_rows([&input]);
+
+        assert!(set_value(&mut rows, 1, serde_json::json!("one")));
+        assert!(set_value(&mut rows, 4, serde_json::json!(null)));
+        assert_eq!(
+            vec![serde_json::json!({"z": "one", "a": [true, null], "m": {}})],
+            to_value(&rows)
+        );
+        // Key order is preserved.
+        assert_eq!(
+            vec!["z", "a", "m"],
+            to_value(&rows)[0]
+                .as_object()
+                .unwrap()
+                .keys()
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_rejects_structural_changes() {
+        let input = serde_json::json!({"a": [1], "b": {}, "c": 2});
+        let mut rows = create_rows([&input]);
+        let before = rows.clone();
+
+        // Open, close and empty container rows.
+        assert!(!set_value(&mut rows, 1, serde_json::json!(0)));
+        assert!(!set_value(&mut rows, 3, serde_json::json!(0)));
+        assert!(!set_value(&mut rows, 4, serde_json::json!(0)));
+        // Container values.
+        assert!(!set_value(&mut rows, 5, serde_json::json!([1])));
+        assert!(!set_value(&mut rows, 5, serde_json::json!({"d": 1})));
+        assert!(!set_value(&mut rows, 100, serde_json::json!(0)));
+        assert_eq!(before, rows);
+    }
+
+    #[test]
+    fn test_multiple_documents() {
+        let inputs = [serde_json::json!({"a": 1}), serde_json::json!(2)];
+        let mut rows = create_rows(&inputs);
+
+        assert!(set_value(&mut rows, 3, serde_json::json!(3)));
+        assert_eq!(
+            vec![serde_json::json!({"a": 1}), serde_json::json!(3)],
+            to_value(&rows)
+        );
+    }
+}
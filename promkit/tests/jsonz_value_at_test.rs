@@ -0,0 +1,21 @@
+#[cfg(test)]
+mod value_at {
+    use promkit::jsonz::*;
+
+    #[test]
+    fn test() {
+        let input = serde_json::json!({"a": [1, {"b": null}], "c": {}});
+        let rows = create_rows([&input]);
+
+        assert_eq!(Some(input.clone()), value_at(&rows, 0));
+        assert_eq!(
+            Some(serde_json::json!([1, {"b": null}])),
+            value_at(&rows, 1)
+        );
+        assert_eq!(Some(serde_json::json!(1)), value_at(&rows, 2));
+        assert_eq!(Some(serde_json::json!({})), value_at(&rows, 7));
+        // A closing bracket addresses its container.
+        assert_eq!(Some(serde_json::json!({"b": null})), value_at(&rows, 5));
+        assert_eq!(None, value_at(&rows, 100));
+    }
+}
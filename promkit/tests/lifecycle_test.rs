@@ -0,0 +1,208 @@
+#[cfg(test)]
+mod lifecycle {
+    use promkit::{
+        crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers},
+        jsonstream::JsonStream,
+        preset::{
+            checkbox::Checkbox,
+            confirm::Confirm,
+            form::{Form, Select},
+            json::Json,
+            listbox::Listbox,
+            query_selector::{filter, QuerySelector},
+            readline::Readline,
+            tree::Tree,
+            wizard::Wizard,
+        },
+        terminal::TestTerminal,
+        text_editor,
+        tree::Node,
+        Interrupted, NotRun, Prompt, PromptSignal, Renderer,
+    };
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn ctrl_c() -> Event {
+        Event::Key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL))
+    }
+
+    fn chars(s: &str) -> Vec<Event> {
+        s.chars().map(|ch| key(KeyCode::Char(ch))).collect()
+    }
+
+    /// Checks the sequences every prompt must survive:
+    /// finalize before run, run after an interrupted run, run after run,
+    /// and evaluate after finalize.
+    fn check<T>(mut p: Prompt<T>, events: Vec<Event>) -> Vec<T::Return>
+    where
+        T: Renderer,
+        T::Return: std::fmt::Debug,
+    {
+        let mut terminal = TestTerminal::new(30, 20);
+        assert!(p.finalize().unwrap_err().is::<NotRun>());
+
+        let interrupted = p
+            .run_with_events(events.iter().cloned().chain([ctrl_c()]), &mut terminal)
+            .unwrap_err();
+        assert!(interrupted.is::<Interrupted>());
+        p.finalize().unwrap();
+
+        let first = p
+            .run_with_events(
+                events.iter().cloned().chain([key(KeyCode::Enter)]),
+                &mut terminal,
+            )
+            .unwrap();
+        let second = p
+            .run_with_events(
+                events.iter().cloned().chain([key(KeyCode::Enter)]),
+                &mut terminal,
+            )
+            .unwrap();
+
+        // It may fail on the cleared input (e.g. a number), but must not panic.
+        p.finalize().ok();
+        for ev in &events {
+            p.renderer.evaluate(ev).unwrap();
+        }
+        vec![first, second]
+    }
+
+    #[test]
+    fn test_readline() {
+        let p = Readline::default().enable_history().prompt().unwrap();
+        // The input of the interrupted run is cleared.
+        assert_eq!(vec!["ab", "ab"], check(p, chars("ab")));
+    }
+
+    #[test]
+    fn test_readline_after_invalid_input() {
+        let mut p = Readline::default()
+            .validator(|text| text.len() > 1, |_| String::from("Too short"))
+            .prompt()
+            .unwrap();
+        let mut terminal = TestTerminal::new(30, 10);
+        p.run_with_events(
+            chars("a")
+                .into_iter()
+                .chain([key(KeyCode::Enter), ctrl_c()]),
+            &mut terminal,
+        )
+        .unwrap_err();
+        let ret = p
+            .run_with_events(
+                chars("ab").into_iter().chain([key(KeyCode::Enter)]),
+                &mut terminal,
+            )
+            .unwrap();
+        assert_eq!("ab", ret);
+        // The error message of the previous run is gone.
+        assert_eq!(vec!["❯❯ ab "], terminal.frame(terminal.frames().len() - 2));
+    }
+
+    #[test]
+    fn test_number() {
+        let p = Readline::number::<u16>().prompt().unwrap();
+        assert_eq!(vec![12, 12], check(p, chars("12")));
+    }
+
+    #[test]
+    fn test_confirm() {
+        let p = Confirm::new("Continue?").prompt().unwrap();
+        assert_eq!(vec![true, true], check(p, chars("y")));
+    }
+
+    #[test]
+    fn test_listbox() {
+        let p = Listbox::new(["a", "b", "c"]).prompt().unwrap();
+        // The selection is kept across runs.
+        assert_eq!(vec!["c", "c"], check(p, vec![key(KeyCode::Down)]));
+    }
+
+    #[test]
+    fn test_checkbox() {
+        let p = Checkbox::new(["a", "b"]).prompt().unwrap();
+        // The picks are kept across runs, so each run toggles "a" again,
+        // including the interrupted one.
+        let ret = check(p, vec![key(KeyCode::Char(' '))]);
+        assert_eq!(vec![vec![], vec!["a".to_string()]], ret);
+    }
+
+    #[test]
+    fn test_query_selector() {
+        let p = QuerySelector::new(["apple", "banana"], filter::fuzzy)
+            .prompt()
+            .unwrap();
+        // The query is cleared, so the same query gives the same result.
+        assert_eq!(vec!["banana", "banana"], check(p, chars("ban")));
+    }
+
+    #[test]
+    fn test_form() {
+        let p = Form::new([
+            Select::new("Env: ", ["dev", "prod"]).into(),
+            promkit::preset::form::Field::from(text_editor::State::default()),
+        ])
+        .prompt()
+        .unwrap();
+        let ret = check(p, vec![key(KeyCode::Down)]);
+        assert_eq!(vec!["prod".to_string(), String::new()], ret[0]);
+    }
+
+    #[test]
+    fn test_tree() {
+        let p = Tree::new(Node::NonLeaf {
+            id: "root".into(),
+            children: vec![Node::Leaf("a".into())],
+            children_visible: true,
+        })
+        .prompt()
+        .unwrap();
+        assert_eq!(
+            vec!["root".to_string(), "a".to_string()],
+            check(p, vec![key(KeyCode::Down)])[1]
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        let value = serde_json::json!({"a": 1});
+        let p = Json::new(JsonStream::new([&value])).prompt().unwrap();
+        assert_eq!(
+            vec![vec![value.clone()], vec![value]],
+            check(p, vec![key(KeyCode::Down)])
+        );
+    }
+
+    #[test]
+    fn test_wizard() {
+        let p = Wizard::default()
+            .step("Name", Readline::default().renderer())
+            .step("Kind", Listbox::new(["a", "b"]).renderer())
+            .prompt()
+            .unwrap();
+        let ret = check(
+            p,
+            chars("x")
+                .into_iter()
+                .chain([key(KeyCode::Enter), key(KeyCode::Down)])
+                .collect(),
+        );
+        assert_eq!(vec!["x".to_string(), "b".to_string()], ret[0]);
+        assert_eq!(ret[0], ret[1]);
+    }
+
+    #[test]
+    fn test_wizard_evaluate_after_completion() {
+        let mut p = Wizard::default()
+            .step("Name", Readline::default().renderer())
+            .prompt()
+            .unwrap();
+        let renderer = &mut p.renderer;
+        renderer.evaluate(&key(KeyCode::Char('x'))).unwrap();
+        assert!(renderer.evaluate(&key(KeyCode::Enter)).unwrap() == PromptSignal::Quit);
+        assert!(renderer.evaluate(&key(KeyCode::Enter)).unwrap() == PromptSignal::Quit);
+    }
+}
@@ -0,0 +1,233 @@
+#[cfg(test)]
+mod listbox {
+    use promkit::{
+        crossterm::event::{
+            Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+        },
+        preset::listbox::Listbox,
+        terminal::TestTerminal,
+    };
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn click(row: u16) -> Event {
+        Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: 3,
+            row,
+            modifiers: KeyModifiers::NONE,
+        })
+    }
+
+    #[test]
+    fn test_finalize_indexed_with_duplicates() {
+        let mut p = Listbox::new(["a", "b", "a"]).prompt().unwrap();
+        p.run_with_events(
+            [key(KeyCode::Down), key(KeyCode::Down), key(KeyCode::Enter)],
+            &mut TestTerminal::new(20, 10),
+        )
+        .unwrap();
+        let indexed = p.renderer.finalize_indexed().unwrap();
+        assert_eq!(2, indexed.index);
+        assert_eq!("a", indexed.item);
+    }
+
+    #[test]
+    fn test_empty() {
+        let mut p = Listbox::new(Vec::<String>::new()).prompt().unwrap();
+        let err = p
+            .run_with_events([key(KeyCode::Enter)], &mut TestTerminal::new(20, 10))
+            .unwrap_err();
+        assert_eq!("no item is selected", err.to_string());
+    }
+
+    #[test]
+    fn test_mouse_click() {
+        let mut p = Listbox::new(["a", "b", "c", "d"])
+            .title("Pick")
+            .prompt()
+            .unwrap();
+        let mut terminal = TestTerminal::new(20, 10);
+        let ret = p
+            .run_with_events(
+                // On "c" below the title, on the title, and below the list.
+                [click(3), click(0), click(5), key(KeyCode::Enter)],
+                &mut terminal,
+            )
+            .unwrap();
+        assert_eq!("c", ret);
+        assert_eq!(vec!["Pick", "❯ c", "  d"], terminal.frame(1));
+
+        // A double click exits with the item selected by the first click,
+        // even though the list has scrolled to it.
+        let ret = p
+            .run_with_events([click(2), click(2)], &mut terminal)
+            .unwrap();
+        assert_eq!("d", ret);
+    }
+
+    #[test]
+    fn test_cyclic_and_paging() {
+        let mut p = Listbox::new(["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"])
+            .listbox_lines(3)
+            .cyclic(true)
+            .prompt()
+            .unwrap();
+        let mut terminal = TestTerminal::new(20, 10);
+        let ret = p
+            .run_with_events(
+                [key(KeyCode::Up), key(KeyCode::PageUp), key(KeyCode::Enter)],
+                &mut terminal,
+            )
+            .unwrap();
+        assert_eq!("6", ret);
+
+        let shift_scroll_down = Event::Mouse(MouseEvent {
+            kind: MouseEventKind::ScrollDown,
+            column: 0,
+            row: 0,
+            modifiers: KeyModifiers::SHIFT,
+        });
+        let ret = p
+            .run_with_events(
+                [
+                    key(KeyCode::Home),
+                    key(KeyCode::PageDown),
+                    shift_scroll_down,
+                    key(KeyCode::Enter),
+                ],
+                &mut terminal,
+            )
+            .unwrap();
+        assert_eq!("6", ret);
+
+        let ret = p
+            .run_with_events(
+                [key(KeyCode::End), key(KeyCode::Down), key(KeyCode::Enter)],
+                &mut terminal,
+            )
+            .unwrap();
+        assert_eq!("0", ret);
+    }
+
+    #[test]
+    fn test_not_interactive() {
+        let mut p = Listbox::new(["a", "b", "c"])
+            .interactive(false)
+            .prompt()
+            .unwrap();
+        let mut terminal = TestTerminal::new(20, 10);
+        let ret = p
+            .run_with_events(
+                [key(KeyCode::Down), click(1), click(1), key(KeyCode::Enter)],
+                &mut terminal,
+            )
+            .unwrap();
+        assert_eq!("a", ret);
+        // No cursor is drawn, and the frames do not change.
+        assert_eq!(vec!["a", "b", "c"], terminal.last_frame());
+        assert!((0..terminal.frames().len()).all(|i| terminal.frame(i) == terminal.last_frame()));
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum Level {
+        Low,
+        High,
+    }
+
+    #[test]
+    fn test_from_entries() {
+        let mut p = Listbox::from_entries([(Level::Low, "low"), (Level::High, "high")])
+            .prompt()
+            .unwrap();
+        let mut terminal = TestTerminal::new(20, 10);
+        let ret = p
+            .run_with_events([key(KeyCode::Down), key(KeyCode::Enter)], &mut terminal)
+            .unwrap();
+
+        assert_eq!(Level::High, ret);
+    }
+
+    #[test]
+    fn test_new_returns_text() {
+        let mut p = Listbox::new([1, 2, 3]).prompt().unwrap();
+        let mut terminal = TestTerminal::new(20, 10);
+        let ret = p
+            .run_with_events(
+                [key(KeyCode::Down), key(KeyCode::Down), key(KeyCode::Enter)],
+                &mut terminal,
+            )
+            .unwrap();
+
+        assert_eq!("3", ret);
+    }
+
+    #[test]
+    fn test_multi_row_item_returns_full_text() {
+        let mut p = Listbox::new(["one", "fix parser\nhandle empty input"])
+            .item_lines(1)
+            .prompt()
+            .unwrap();
+        let mut terminal = TestTerminal::new(20, 10);
+        let ret = p
+            .run_with_events([key(KeyCode::Down), key(KeyCode::Enter)], &mut terminal)
+            .unwrap();
+        assert_eq!("fix parser\nhandle empty input", ret);
+        assert_eq!(vec!["❯ fix parser…"], terminal.last_frame());
+    }
+
+    mod key_bindings {
+        use super::*;
+
+        use promkit::{
+            key_bindings::{ctrl, KeyBindings},
+            preset::listbox::keymap::Action,
+            Interrupted,
+        };
+
+        #[test]
+        fn test_rebind() {
+            let mut p = Listbox::new(["a", "b", "c"])
+                .key_bindings(
+                    KeyBindings::default()
+                        .bind('j', Action::MoveDown)
+                        .bind('k', Action::MoveUp)
+                        .unbind(KeyCode::Down),
+                )
+                .prompt()
+                .unwrap();
+            let ret = p
+                .run_with_events(
+                    [
+                        key(KeyCode::Char('j')),
+                        key(KeyCode::Char('j')),
+                        key(KeyCode::Char('k')),
+                        // Unbound, so ignored.
+                        key(KeyCode::Down),
+                        key(KeyCode::Enter),
+                    ],
+                    &mut TestTerminal::new(20, 10),
+                )
+                .unwrap();
+            assert_eq!("b", ret);
+        }
+
+        #[test]
+        fn test_esc_interrupts() {
+            let mut p = Listbox::new(["a", "b"])
+                .key_bindings(
+                    KeyBindings::default()
+                        .bind(KeyCode::Esc, Action::Interrupt)
+                        .unbind(ctrl('c')),
+                )
+                .prompt()
+                .unwrap();
+            let err = p
+                .run_with_events([key(KeyCode::Esc)], &mut TestTerminal::new(20, 10))
+                .unwrap_err();
+            assert!(err.is::<Interrupted>());
+        }
+    }
+}
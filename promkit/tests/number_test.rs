@@ -0,0 +1,145 @@
+#[cfg(test)]
+mod number {
+    use promkit::{
+        crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers},
+        preset::number::Number,
+        terminal::TestTerminal,
+    };
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn chars(s: &str) -> Vec<Event> {
+        s.chars().map(|ch| key(KeyCode::Char(ch))).collect()
+    }
+
+    #[test]
+    fn test_rejects_non_numeric_chars() {
+        let mut p = Number::default().prompt().unwrap();
+        let mut terminal = TestTerminal::new(20, 10);
+        let ret = p
+            .run_with_events(
+                chars("-1a2.5.0x-").into_iter().chain([key(KeyCode::Enter)]),
+                &mut terminal,
+            )
+            .unwrap();
+        assert_eq!(-12.50, ret);
+        assert_eq!(vec!["❯❯ -12.50 "], terminal.last_frame());
+    }
+
+    #[test]
+    fn test_step_and_clamp() {
+        let mut p = Number::default()
+            .step(0.1)
+            .min(0.0)
+            .max(0.3)
+            .prompt()
+            .unwrap();
+        let mut terminal = TestTerminal::new(20, 10);
+        // An empty input stands for the minimum.
+        let ret = p
+            .run_with_events(
+                [
+                    key(KeyCode::Up),
+                    key(KeyCode::Up),
+                    key(KeyCode::Up),
+                    key(KeyCode::Up),
+                    key(KeyCode::Enter),
+                ],
+                &mut terminal,
+            )
+            .unwrap();
+        assert_eq!(0.3, ret);
+        assert_eq!(vec!["❯❯ 0.1 "], terminal.frame(1));
+        assert_eq!(vec!["❯❯ 0.3 "], terminal.last_frame());
+
+        // The minimum rules out negative numbers.
+        let ret = p
+            .run_with_events(
+                chars("-5").into_iter().chain([key(KeyCode::Enter)]),
+                &mut terminal,
+            )
+            .unwrap();
+        assert_eq!(0.3, ret);
+    }
+
+    #[test]
+    fn test_submit_clamps() {
+        let mut p = Number::default().max(10.0).prompt().unwrap();
+        let mut terminal = TestTerminal::new(20, 10);
+        let ret = p
+            .run_with_events(
+                chars("42").into_iter().chain([key(KeyCode::Enter)]),
+                &mut terminal,
+            )
+            .unwrap();
+        assert_eq!(10.0, ret);
+        // The clamped number is shown.
+        assert_eq!(vec!["❯❯ 10 "], terminal.last_frame());
+
+        let ret = p
+            .run_with_events([key(KeyCode::Enter)], &mut terminal)
+            .unwrap();
+        assert_eq!(0.0, ret);
+    }
+
+    #[test]
+    fn test_precision() {
+        let mut p = Number::default()
+            .precision(2)
+            .initial_value(1.005)
+            .step(0.5)
+            .prompt()
+            .unwrap();
+        let mut terminal = TestTerminal::new(20, 10);
+        let ret = p
+            .run_with_events([key(KeyCode::Down), key(KeyCode::Enter)], &mut terminal)
+            .unwrap();
+        assert_eq!(0.5, ret);
+        assert_eq!(vec!["❯❯ 0.50 "], terminal.last_frame());
+
+        // No decimals can be typed with a precision of 0.
+        let mut p = Number::default().precision(0).prompt().unwrap();
+        let ret = p
+            .run_with_events(
+                chars("3.7").into_iter().chain([key(KeyCode::Enter)]),
+                &mut terminal,
+            )
+            .unwrap();
+        assert_eq!(37.0, ret);
+    }
+
+    #[test]
+    fn test_not_a_number() {
+        let mut p = Number::default().title("Count").prompt().unwrap();
+        let mut terminal = TestTerminal::new(20, 10);
+        let ret = p.run_with_events(
+            chars("-").into_iter().chain([key(KeyCode::Enter)]),
+            &mut terminal,
+        );
+        assert!(ret.is_err());
+        assert_eq!(
+            vec!["Count", "not a number: -", "❯❯ - "],
+            terminal.last_frame()
+        );
+    }
+
+    #[test]
+    fn test_initial_value_on_every_run() {
+        let mut p = Number::default().initial_value(5.0).prompt().unwrap();
+        let mut terminal = TestTerminal::new(20, 10);
+        let ret = p
+            .run_with_events([key(KeyCode::Up), key(KeyCode::Enter)], &mut terminal)
+            .unwrap();
+        assert_eq!(6.0, ret);
+
+        // The next run starts from the initial value again, not from an empty input.
+        let mut terminal = TestTerminal::new(20, 10);
+        let ret = p
+            .run_with_events([key(KeyCode::Enter)], &mut terminal)
+            .unwrap();
+        assert_eq!(5.0, ret);
+        assert_eq!(vec!["❯❯ 5 "], terminal.frame(0));
+    }
+}
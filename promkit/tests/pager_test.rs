@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod pager {
+    use promkit::{
+        crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers},
+        preset::pager::Pager,
+        terminal::TestTerminal,
+    };
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn text() -> String {
+        (1..=10)
+            .map(|i| format!("line{}", i))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_scroll() {
+        let mut p = Pager::new(text()).prompt().unwrap();
+        // 3 rows for the text and 1 for the footer.
+        let mut terminal = TestTerminal::new(20, 4);
+        p.run_with_events(
+            [
+                key(KeyCode::Down),
+                key(KeyCode::PageDown),
+                key(KeyCode::Up),
+                key(KeyCode::Enter),
+            ],
+            &mut terminal,
+        )
+        .unwrap();
+        assert_eq!(
+            vec!["line1", "line2", "line3", "line 1 of 10"],
+            terminal.frame(0)
+        );
+        assert_eq!(
+            vec!["line2", "line3", "line4", "line 2 of 10"],
+            terminal.frame(1)
+        );
+        assert_eq!(
+            vec!["line5", "line6", "line7", "line 5 of 10"],
+            terminal.frame(2)
+        );
+        assert_eq!(
+            vec!["line4", "line5", "line6", "line 4 of 10"],
+            terminal.frame(3)
+        );
+    }
+
+    #[test]
+    fn test_clamp() {
+        let mut p = Pager::new(text()).title("Help").prompt().unwrap();
+        // 3 rows for the text below the title.
+        let mut terminal = TestTerminal::new(20, 5);
+        p.run_with_events(
+            [
+                key(KeyCode::Up),
+                key(KeyCode::PageDown),
+                key(KeyCode::PageDown),
+                key(KeyCode::PageDown),
+                key(KeyCode::Down),
+                key(KeyCode::Up),
+                key(KeyCode::Home),
+                key(KeyCode::End),
+                key(KeyCode::Char('q')),
+            ],
+            &mut terminal,
+        )
+        .unwrap();
+        assert_eq!(
+            vec!["Help", "line1", "line2", "line3", "line 1 of 10"],
+            terminal.frame(1)
+        );
+        // The last line stays at the bottom.
+        assert_eq!(
+            vec!["Help", "line8", "line9", "line10", "line 8 of 10"],
+            terminal.frame(4)
+        );
+        assert_eq!(terminal.frame(4), terminal.frame(5));
+        assert_eq!(
+            vec!["Help", "line7", "line8", "line9", "line 7 of 10"],
+            terminal.frame(6)
+        );
+        assert_eq!(terminal.frame(0), terminal.frame(7));
+        assert_eq!(terminal.frame(4), terminal.frame(8));
+    }
+}
@@ -0,0 +1,211 @@
+#[cfg(test)]
+mod query_selector {
+    use promkit::{
+        crossterm::{
+            event::{
+                Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+            },
+            style::{Attribute, Attributes, ContentStyle},
+        },
+        preset::query_selector::{filter, QuerySelector},
+        style::StyleBuilder,
+        terminal::TestTerminal,
+    };
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn chars(s: &str) -> Vec<Event> {
+        s.chars().map(|ch| key(KeyCode::Char(ch))).collect()
+    }
+
+    #[test]
+    fn test_query_syntax_with_highlight() {
+        let bold = StyleBuilder::new()
+            .attrs(Attributes::from(Attribute::Bold))
+            .build();
+        let mut p = QuerySelector::new(
+            [
+                "src/main.rs",
+                "src/lib.rs",
+                "Cargo.toml",
+                "tests/main_test.rs",
+            ],
+            filter::fuzzy,
+        )
+        .highlight_matches(filter::Mode::Fuzzy, bold)
+        .prompt()
+        .unwrap();
+        let mut terminal = TestTerminal::new(30, 10);
+        let ret = p
+            .run_with_events(
+                chars("main !test").into_iter().chain([key(KeyCode::Enter)]),
+                &mut terminal,
+            )
+            .unwrap();
+
+        assert_eq!("src/main.rs", ret);
+        let frame = terminal.frame(terminal.frames().len() - 2);
+        assert_eq!(vec!["❯❯ main !test ", "❯ src/main.rs"], frame);
+        // Only the matched graphemes keep the highlight.
+        let item = &terminal.frames()[terminal.frames().len() - 2][1];
+        let bolds: String = item
+            .iter()
+            .filter(|g| *g.style() == bold)
+            .map(|g| g.as_str())
+            .collect();
+        assert_eq!("main", bolds);
+        assert!(item
+            .iter()
+            .any(|g| *g.style() != ContentStyle::default() && *g.style() != bold));
+    }
+
+    #[test]
+    fn test_finalize_indexed_with_duplicates() {
+        let mut p = QuerySelector::new(["banana", "apple", "cherry", "apple"], filter::fuzzy)
+            .prompt()
+            .unwrap();
+        let ret = p
+            .run_with_events(
+                chars("app")
+                    .into_iter()
+                    .chain([key(KeyCode::Down), key(KeyCode::Enter)]),
+                &mut TestTerminal::new(20, 10),
+            )
+            .unwrap();
+        assert_eq!("apple", ret);
+        // The second "apple" among the unfiltered items.
+        let indexed = p.renderer.finalize_indexed().unwrap();
+        assert_eq!(3, indexed.index);
+        assert_eq!("apple", indexed.item);
+    }
+
+    #[test]
+    fn test_mouse_click() {
+        let mut p = QuerySelector::new(["apple", "apricot", "banana"], filter::fuzzy)
+            .prompt()
+            .unwrap();
+        let mut terminal = TestTerminal::new(20, 10);
+        let click = Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: 2,
+            row: 2,
+            modifiers: KeyModifiers::NONE,
+        });
+        let ret = p
+            .run_with_events(
+                chars("ap").into_iter().chain([click, key(KeyCode::Enter)]),
+                &mut terminal,
+            )
+            .unwrap();
+        assert_eq!("apricot", ret);
+    }
+
+    #[test]
+    fn test_cyclic_and_paging() {
+        let mut p = QuerySelector::new(["apple", "apricot", "avocado", "banana"], filter::fuzzy)
+            .listbox_lines(2)
+            .cyclic(true)
+            .prompt()
+            .unwrap();
+        let mut terminal = TestTerminal::new(20, 10);
+        let ret = p
+            .run_with_events(
+                [key(KeyCode::End), key(KeyCode::PageUp), key(KeyCode::Enter)],
+                &mut terminal,
+            )
+            .unwrap();
+        assert_eq!("apricot", ret);
+
+        // The filtered list stays cyclic.
+        let ret = p
+            .run_with_events(
+                chars("ap")
+                    .into_iter()
+                    .chain([key(KeyCode::Up), key(KeyCode::Enter)]),
+                &mut terminal,
+            )
+            .unwrap();
+        assert_eq!("apricot", ret);
+    }
+
+    #[cfg(feature = "async")]
+    mod load_error {
+        use std::time::{Duration, Instant};
+
+        use promkit::{
+            crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers},
+            preset::query_selector::{
+                loader::{LoadFailed, OnLoadError},
+                render, QuerySelector,
+            },
+            Renderer,
+        };
+
+        fn renderer(policy: OnLoadError) -> render::Renderer {
+            QuerySelector::with_try_async_filter(|query: &str| {
+                let query = query.to_string();
+                async move {
+                    match query.as_str() {
+                        "x" => Err(anyhow::anyhow!("connection reset")),
+                        _ => Ok(vec![format!("{} result", query)]),
+                    }
+                }
+            })
+            .debounce(Duration::ZERO)
+            .on_load_error(policy)
+            .renderer()
+        }
+
+        /// Ticks until the renderer redraws or fails.
+        fn tick(renderer: &mut render::Renderer) -> anyhow::Result<()> {
+            let start = Instant::now();
+            while start.elapsed() < Duration::from_secs(5) {
+                if renderer.tick()? && !renderer.loader.as_ref().unwrap().is_loading() {
+                    return Ok(());
+                }
+                std::thread::sleep(Duration::from_millis(5));
+            }
+            panic!("timed out");
+        }
+
+        fn type_char(renderer: &mut render::Renderer, ch: char) {
+            renderer
+                .evaluate(&Event::Key(KeyEvent::new(
+                    KeyCode::Char(ch),
+                    KeyModifiers::NONE,
+                )))
+                .unwrap();
+        }
+
+        #[test]
+        fn test_fail() {
+            let mut renderer = renderer(OnLoadError::Fail);
+            tick(&mut renderer).unwrap();
+            type_char(&mut renderer, 'x');
+            let err = tick(&mut renderer).unwrap_err();
+            assert_eq!(
+                Some(&LoadFailed(String::from("connection reset"))),
+                err.downcast_ref::<LoadFailed>()
+            );
+        }
+
+        #[test]
+        fn test_show() {
+            let mut renderer = renderer(OnLoadError::Show);
+            tick(&mut renderer).unwrap();
+            type_char(&mut renderer, 'x');
+            tick(&mut renderer).unwrap();
+            let rows: Vec<String> = renderer
+                .create_panes(30, 10)
+                .iter()
+                .flat_map(|pane| pane.extract(10))
+                .map(|row| row.to_string())
+                .collect();
+            assert!(rows.contains(&String::from("connection reset")));
+            // The previous items are kept.
+            assert!(rows.iter().any(|row| row.ends_with(" result")));
+        }
+    }
+}
@@ -0,0 +1,1361 @@
+#[cfg(test)]
+mod readline {
+    use promkit::{
+        crossterm::event::{
+            Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+        },
+        preset::{
+            confirm::Confirm,
+            readline::{InterruptAction, Readline},
+        },
+        suggest::Suggest,
+        terminal::TestTerminal,
+        text_editor::WordNavigation,
+        Eof, InterruptBehavior, Interrupted, PromptSignal,
+    };
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn chars(s: &str) -> Vec<Event> {
+        s.chars().map(|ch| key(KeyCode::Char(ch))).collect()
+    }
+
+    #[test]
+    fn test_transpose_and_case_words() {
+        let mut p = Readline::default().prompt().unwrap();
+        let with = |ch, modifiers| Event::Key(KeyEvent::new(KeyCode::Char(ch), modifiers));
+        let ret = p
+            .run_with_events(
+                chars("hello world").into_iter().chain([
+                    with('a', KeyModifiers::CONTROL),
+                    with('c', KeyModifiers::ALT),
+                    with('u', KeyModifiers::ALT),
+                    with('t', KeyModifiers::CONTROL),
+                    key(KeyCode::Enter),
+                ]),
+                &mut TestTerminal::new(20, 10),
+            )
+            .unwrap();
+        assert_eq!("Hello WORDL", ret);
+    }
+
+    #[test]
+    fn test_horizontal_scroll() {
+        let mut p = Readline::default()
+            .horizontal_scroll(true)
+            .prompt()
+            .unwrap();
+        let mut terminal = TestTerminal::new(10, 10);
+        let ret = p
+            .run_with_events(
+                chars("abcdefghij").into_iter().chain([
+                    Event::Key(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::CONTROL)),
+                    key(KeyCode::Enter),
+                ]),
+                &mut terminal,
+            )
+            .unwrap();
+        assert_eq!("abcdefghij", ret);
+        // The cursor is kept in a single row, with the hidden parts marked.
+        assert_eq!(vec!["❯❯ <fghij "], terminal.frame(10));
+        assert_eq!(vec!["❯❯ abcdef>"], terminal.frame(11));
+    }
+
+    #[test]
+    fn test_input_and_submit() {
+        let mut p = Readline::default().title("Name?").prompt().unwrap();
+        let mut terminal = TestTerminal::new(20, 10);
+        let ret = p
+            .run_with_events(
+                chars("ab")
+                    .into_iter()
+                    .chain([key(KeyCode::Left), key(KeyCode::Char('x'))])
+                    .chain([key(KeyCode::Enter)]),
+                &mut terminal,
+            )
+            .unwrap();
+
+        assert_eq!("axb", ret);
+        // The initial frame and a frame per event.
+        assert_eq!(6, terminal.frames().len());
+        assert_eq!(vec!["Name?", "❯❯ axb "], terminal.last_frame());
+    }
+
+    fn click(column: u16, row: u16) -> Event {
+        Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        })
+    }
+
+    #[test]
+    fn test_mouse_click() {
+        let mut p = Readline::default().title("Name?").prompt().unwrap();
+        let mut terminal = TestTerminal::new(20, 10);
+        let ret = p
+            .run_with_events(
+                chars("abcd")
+                    .into_iter()
+                    // On "c", below the title.
+                    .chain([click(5, 1), key(KeyCode::Char('x'))])
+                    // On the title, and below the input line.
+                    .chain([click(5, 0), click(5, 2), key(KeyCode::Char('y'))])
+                    // On the prefix.
+                    .chain([click(1, 1), key(KeyCode::Char('z'))])
+                    .chain([key(KeyCode::Enter)]),
+                &mut terminal,
+            )
+            .unwrap();
+
+        assert_eq!("zabxycd", ret);
+    }
+
+    #[test]
+    fn test_right_prompt() {
+        let mut p = Readline::default().right_prompt("main").prompt().unwrap();
+        let mut terminal = TestTerminal::new(12, 10);
+        let ret = p
+            .run_with_events(
+                chars("ab")
+                    .into_iter()
+                    .chain([Event::Resize(10, 10)])
+                    .chain(chars("cd"))
+                    .chain([key(KeyCode::Enter)]),
+                &mut terminal,
+            )
+            .unwrap();
+
+        assert_eq!("abcd", ret);
+        assert_eq!("❯❯ ab   main", terminal.frames()[2][0].to_string());
+        // Recomputed for the new width, and truncated as the input grows into it.
+        assert_eq!("❯❯ abc  m…", terminal.frames()[4][0].to_string());
+        assert_eq!(vec!["❯❯ abcd  …"], terminal.last_frame());
+    }
+
+    #[test]
+    fn test_suggestion_focus_switching() {
+        let mut p = Readline::default()
+            .suggest(Suggest::from_iter(["apple", "apricot", "banana"]))
+            .prompt()
+            .unwrap();
+        let mut terminal = TestTerminal::new(20, 10);
+        let ret = p
+            .run_with_events(
+                chars("ap")
+                    .into_iter()
+                    .chain([key(KeyCode::Tab), key(KeyCode::Tab)])
+                    // Any other key leaves the suggestions and is not inserted.
+                    .chain(chars("!"))
+                    .chain(chars("s"))
+                    .chain([key(KeyCode::Enter)]),
+                &mut terminal,
+            )
+            .unwrap();
+
+        let frames = terminal.frames();
+        assert_eq!(
+            "❯❯ apple ",
+            frames[3][0].to_string(),
+            "first candidate is filled in"
+        );
+        assert_eq!(3, frames[3].len(), "candidates are listed");
+        assert_eq!("❯❯ apricot ", frames[4][0].to_string());
+        assert_eq!(vec!["❯❯ apricot "], terminal.frame(5));
+        assert_eq!("apricots", ret);
+    }
+
+    #[test]
+    fn test_history_is_kept_across_runs() {
+        let mut p = Readline::default().enable_history().prompt().unwrap();
+        let mut terminal = TestTerminal::new(20, 10);
+
+        let first = p
+            .run_with_events(
+                chars("first").into_iter().chain([key(KeyCode::Enter)]),
+                &mut terminal,
+            )
+            .unwrap();
+        assert_eq!("first", first);
+
+        let second = p
+            .run_with_events([key(KeyCode::Up), key(KeyCode::Enter)], &mut terminal)
+            .unwrap();
+        assert_eq!("first", second);
+    }
+
+    #[test]
+    fn test_key_bindings() {
+        use promkit::{
+            key_bindings::{ctrl, KeyBindings},
+            preset::readline::keymap::Action,
+        };
+
+        let mut p = Readline::default()
+            .enable_history()
+            .key_bindings(
+                KeyBindings::default()
+                    .bind(ctrl('p'), Action::HistoryPrev)
+                    .bind(ctrl('n'), Action::HistoryNext)
+                    .unbind(KeyCode::Up),
+            )
+            .prompt()
+            .unwrap();
+        let mut terminal = TestTerminal::new(20, 10);
+
+        p.run_with_events(
+            chars("first").into_iter().chain([key(KeyCode::Enter)]),
+            &mut terminal,
+        )
+        .unwrap();
+        let ret = p
+            .run_with_events(
+                [
+                    key(KeyCode::Up),
+                    Event::Key(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL)),
+                    key(KeyCode::Enter),
+                ],
+                &mut terminal,
+            )
+            .unwrap();
+        assert_eq!("first", ret);
+        // The unbound key does nothing.
+        assert_eq!(vec!["❯❯  "], terminal.frame(terminal.frames().len() - 3));
+    }
+
+    #[test]
+    fn test_on_submit() {
+        let lines = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let submitted = lines.clone();
+        let mut p = Readline::default()
+            .enable_history()
+            .on_submit(move |line| {
+                submitted.borrow_mut().push(line.to_string());
+                Ok(if line == "exit" {
+                    PromptSignal::Quit
+                } else {
+                    PromptSignal::Continue
+                })
+            })
+            .prompt()
+            .unwrap();
+        let mut terminal = TestTerminal::new(20, 10);
+        let ret = p
+            .run_with_events(
+                chars("a")
+                    .into_iter()
+                    // The history is kept between the lines.
+                    .chain([key(KeyCode::Enter), key(KeyCode::Up), key(KeyCode::Enter)])
+                    .chain(chars("exit"))
+                    .chain([key(KeyCode::Enter)]),
+                &mut terminal,
+            )
+            .unwrap();
+        assert_eq!("exit", ret);
+        assert_eq!(vec!["a", "a", "exit"], *lines.borrow());
+        // The input is cleared after a line is accepted.
+        assert_eq!(vec!["❯❯  "], terminal.frame(2));
+    }
+
+    #[test]
+    fn test_unicode_word_navigation() {
+        let alt = |ch| Event::Key(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::ALT));
+        let ctrl_w = Event::Key(KeyEvent::new(KeyCode::Char('w'), KeyModifiers::CONTROL));
+        let mut p = Readline::default()
+            .word_navigation(WordNavigation::Unicode)
+            .prompt()
+            .unwrap();
+        let ret = p
+            .run_with_events(
+                chars("foo-bar baz")
+                    .into_iter()
+                    .chain([alt('b'), alt('b'), ctrl_w])
+                    .chain([key(KeyCode::Enter)]),
+                &mut TestTerminal::new(20, 10),
+            )
+            .unwrap();
+        assert_eq!("bar baz", ret);
+    }
+
+    #[test]
+    fn test_ctrl_d() {
+        let ctrl_d = Event::Key(KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL));
+        // The empty input ends, unlike Ctrl+C.
+        let mut p = Readline::default().prompt().unwrap();
+        let err = p
+            .run_with_events([ctrl_d.clone()], &mut TestTerminal::new(20, 10))
+            .unwrap_err();
+        assert!(err.is::<Eof>());
+        assert!(!err.is::<Interrupted>());
+
+        // Otherwise, the character under the cursor is deleted, and nothing at the end.
+        let mut p = Readline::default().prompt().unwrap();
+        let ret = p
+            .run_with_events(
+                chars("abc").into_iter().chain([
+                    ctrl_d.clone(),
+                    Event::Key(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::CONTROL)),
+                    key(KeyCode::Right),
+                    ctrl_d,
+                    key(KeyCode::Enter),
+                ]),
+                &mut TestTerminal::new(20, 10),
+            )
+            .unwrap();
+        assert_eq!("ac", ret);
+    }
+
+    #[test]
+    fn test_on_interrupt() {
+        let ctrl_c = Event::Key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL));
+        // The partial input is returned without being validated.
+        let mut p = Readline::default()
+            .validator(|text| text.len() > 3, |_| String::from("too short"))
+            .on_interrupt(InterruptAction::ReturnInput)
+            .prompt()
+            .unwrap();
+        let ret = p
+            .run_with_events(
+                chars("ab").into_iter().chain([ctrl_c.clone()]),
+                &mut TestTerminal::new(20, 10),
+            )
+            .unwrap();
+        assert_eq!("ab", ret);
+
+        // The input and the suggestions are cleared, and the prompt keeps running.
+        let mut p = Readline::default()
+            .suggest(Suggest::from_iter(["apple", "apricot"]))
+            .on_interrupt(InterruptAction::ClearLine)
+            .prompt()
+            .unwrap();
+        let mut terminal = TestTerminal::new(20, 10);
+        let ret = p
+            .run_with_events(
+                chars("ap")
+                    .into_iter()
+                    .chain([key(KeyCode::Tab), ctrl_c])
+                    .chain(chars("b"))
+                    .chain([key(KeyCode::Enter)]),
+                &mut terminal,
+            )
+            .unwrap();
+        assert_eq!("b", ret);
+        assert_eq!(vec!["❯❯  "], terminal.frame(4));
+    }
+
+    #[test]
+    fn test_transient() {
+        let mut p = Readline::default()
+            .title("Command?")
+            .suggest(Suggest::from_iter(["ls", "less"]))
+            .transient(true)
+            .prompt()
+            .unwrap();
+        let mut terminal = TestTerminal::new(20, 10);
+        let ret = p
+            .run_with_events(
+                chars("l").into_iter().chain([
+                    key(KeyCode::Tab),
+                    key(KeyCode::Enter),
+                    key(KeyCode::Enter),
+                ]),
+                &mut terminal,
+            )
+            .unwrap();
+        assert_eq!("less", ret);
+        assert_eq!(
+            vec!["Command?", "❯❯ less ", "❯ less", "  ls"],
+            terminal.frame(2)
+        );
+        assert_eq!(vec!["❯❯ less"], terminal.last_frame());
+
+        let mut p = Readline::default()
+            .mask('*')
+            .transient(true)
+            .prompt()
+            .unwrap();
+        p.run_with_events(
+            chars("pw").into_iter().chain([key(KeyCode::Enter)]),
+            &mut terminal,
+        )
+        .unwrap();
+        assert_eq!(vec!["❯❯ **"], terminal.last_frame());
+
+        let mut p = Readline::default()
+            .transient(true)
+            .transient_summary(|input| format!("$ {}", input.to_uppercase()))
+            .prompt()
+            .unwrap();
+        p.run_with_events(
+            chars("ab").into_iter().chain([key(KeyCode::Enter)]),
+            &mut terminal,
+        )
+        .unwrap();
+        assert_eq!(vec!["$ AB"], terminal.last_frame());
+    }
+
+    #[test]
+    fn test_history_is_suggested() {
+        let mut p = Readline::default()
+            .enable_history()
+            .suggest(Suggest::from_iter(["cargo build"]))
+            .prompt()
+            .unwrap();
+        let mut terminal = TestTerminal::new(20, 10);
+
+        p.run_with_events(
+            chars("cargo test").into_iter().chain([key(KeyCode::Enter)]),
+            &mut terminal,
+        )
+        .unwrap();
+
+        let ret = p
+            .run_with_events(
+                chars("cargo t").into_iter().chain([
+                    key(KeyCode::Tab),
+                    key(KeyCode::Enter),
+                    key(KeyCode::Enter),
+                ]),
+                &mut terminal,
+            )
+            .unwrap();
+        assert_eq!("cargo test", ret);
+    }
+
+    #[test]
+    fn test_events_run_out() {
+        let mut p = Readline::default().prompt().unwrap();
+        let mut terminal = TestTerminal::new(20, 10);
+        assert!(p.run_with_events(chars("a"), &mut terminal).is_err());
+    }
+
+    #[test]
+    fn test_max_rows() {
+        let mut p = Readline::default()
+            .title("A title long enough to wrap onto several rows")
+            .suggest(Suggest::from_iter([
+                "apple", "apricot", "avocado", "banana",
+            ]))
+            .validator(|text| text.len() > 10, |_| String::from("Too short"))
+            .prompt()
+            .unwrap()
+            .max_rows(4);
+        let mut terminal = TestTerminal::new(20, 30);
+        p.run_with_events(
+            chars("a")
+                .into_iter()
+                // Show the suggestions.
+                .chain([key(KeyCode::Tab), key(KeyCode::Down), key(KeyCode::Esc)])
+                // Show the error message.
+                .chain([key(KeyCode::Enter)])
+                .chain([Event::Resize(20, 3)])
+                .chain(chars(" is a fruit"))
+                .chain([Event::Resize(20, 30), key(KeyCode::Enter)]),
+            &mut terminal,
+        )
+        .unwrap();
+
+        for frame in terminal.frames() {
+            assert!(frame.len() <= 4, "{:?}", frame);
+        }
+        // Both the suggestions and the error message appeared within the budget.
+        assert!(terminal.frames().iter().any(|frame| frame.len() == 4));
+        assert!(terminal
+            .frames()
+            .iter()
+            .any(|frame| frame.iter().any(|row| row.to_string() == "Too short")));
+    }
+
+    #[test]
+    fn test_max_rows_keeps_input() {
+        let mut p = Readline::default()
+            .title("A title long enough to wrap onto several rows")
+            .suggest(Suggest::from_iter(["apple", "apricot"]))
+            .prompt()
+            .unwrap()
+            .max_rows(2);
+        let mut terminal = TestTerminal::new(20, 30);
+        p.run_with_events(
+            chars("a")
+                .into_iter()
+                // Show the suggestions.
+                .chain([key(KeyCode::Tab), key(KeyCode::Down)])
+                .chain([Event::Resize(20, 1), key(KeyCode::Esc)])
+                .chain([key(KeyCode::Enter)]),
+            &mut terminal,
+        )
+        .unwrap();
+
+        // The title is cut and the suggestions are hidden before the input line.
+        assert_eq!(
+            vec!["A title long enough ", "❯❯ apricot "],
+            terminal.frame(3)
+        );
+        assert_eq!(vec!["❯❯ apricot "], terminal.frame(4));
+    }
+
+    mod on_interrupt {
+        use super::*;
+
+        fn ctrl_c() -> Event {
+            Event::Key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL))
+        }
+
+        #[test]
+        fn test_error() {
+            let mut p = Readline::default().prompt().unwrap();
+            let err = p
+                .run_with_events([ctrl_c()], &mut TestTerminal::new(20, 10))
+                .unwrap_err();
+            assert!(err.is::<Interrupted>());
+        }
+
+        #[test]
+        fn test_return_default() {
+            let mut p = Confirm::new("Continue?")
+                .prompt()
+                .unwrap()
+                .on_interrupt(InterruptBehavior::ReturnDefault(false));
+            let ret = p
+                .run_with_events(
+                    chars("y").into_iter().chain([ctrl_c()]),
+                    &mut TestTerminal::new(20, 10),
+                )
+                .unwrap();
+            assert!(!ret);
+        }
+
+        #[test]
+        fn test_ignore() {
+            let mut p = Readline::default()
+                .prompt()
+                .unwrap()
+                .on_interrupt(InterruptBehavior::Ignore);
+            let ret = p
+                .run_with_events(
+                    [ctrl_c(), key(KeyCode::Char('a')), key(KeyCode::Enter)],
+                    &mut TestTerminal::new(20, 10),
+                )
+                .unwrap();
+            assert_eq!("a", ret);
+        }
+    }
+
+    mod chord {
+        use super::*;
+
+        use promkit::{chord::KeyCombo, preset::readline::render::Renderer, PromptSignal};
+
+        fn erase_all(renderer: &mut Renderer) -> anyhow::Result<PromptSignal> {
+            renderer
+                .text_editor_snapshot
+                .after_mut()
+                .texteditor
+                .erase_all();
+            Ok(PromptSignal::Continue)
+        }
+
+        fn ctrl(ch: char) -> KeyCombo {
+            KeyCombo::new(KeyCode::Char(ch), KeyModifiers::CONTROL)
+        }
+
+        #[test]
+        fn test_complete_chord() {
+            let mut p = Readline::default()
+                .prompt()
+                .unwrap()
+                .chord([ctrl('x'), ctrl('k')], erase_all);
+            let mut terminal = TestTerminal::new(20, 10);
+            let ret = p
+                .run_with_events(
+                    chars("ab").into_iter().chain([Event::Key(KeyEvent::new(
+                        KeyCode::Char('x'),
+                        KeyModifiers::CONTROL,
+                    ))]),
+                    &mut terminal,
+                )
+                .map(|_| ());
+            assert!(ret.is_err());
+            // The pending prefix is shown below the panes.
+            assert_eq!(vec!["❯❯ ab ", "C-x"], terminal.last_frame());
+
+            let ret = p
+                .run_with_events(
+                    [
+                        Event::Key(KeyEvent::new(KeyCode::Char('k'), KeyModifiers::CONTROL)),
+                        key(KeyCode::Enter),
+                    ],
+                    &mut terminal,
+                )
+                .unwrap();
+            assert_eq!("", ret);
+        }
+
+        #[test]
+        fn test_non_matching_key_is_evaluated() {
+            let mut p = Readline::default()
+                .prompt()
+                .unwrap()
+                .chord(['j'.into(), 'k'.into()], erase_all);
+            let mut terminal = TestTerminal::new(20, 10);
+            let ret = p
+                .run_with_events(
+                    chars("ajx").into_iter().chain([key(KeyCode::Enter)]),
+                    &mut terminal,
+                )
+                .unwrap();
+            assert_eq!("ajx", ret);
+        }
+    }
+
+    mod vi {
+        use super::*;
+
+        use promkit::{
+            preset::readline::{keymap::vi::Mode, EditorMode},
+            text_editor,
+        };
+
+        fn vi() -> Readline {
+            Readline::default().editor_mode(EditorMode::Vi)
+        }
+
+        fn run(readline: Readline, events: Vec<Event>) -> String {
+            let mut p = readline.prompt().unwrap();
+            p.run_with_events(
+                events.into_iter().chain([key(KeyCode::Enter)]),
+                &mut TestTerminal::new(30, 10),
+            )
+            .unwrap()
+        }
+
+        #[test]
+        fn test_starts_in_insert_mode() {
+            assert_eq!("hello", run(vi(), chars("hello")));
+        }
+
+        #[test]
+        fn test_move_and_delete() {
+            let events = chars("hello world")
+                .into_iter()
+                .chain([key(KeyCode::Esc)])
+                // Delete `h` and `e` at the head, and the last `d`.
+                .chain(chars("0xx$x"))
+                .collect();
+            assert_eq!("llo worl", run(vi(), events));
+        }
+
+        #[test]
+        fn test_word_motions() {
+            let events = chars("foo bar baz")
+                .into_iter()
+                .chain([key(KeyCode::Esc)])
+                .chain(chars("0wx"))
+                .chain(chars("bx"))
+                .collect();
+            assert_eq!("oo ar baz", run(vi(), events));
+        }
+
+        #[test]
+        fn test_reenter_insert_mode() {
+            let events = chars("bc")
+                .into_iter()
+                .chain([key(KeyCode::Esc)])
+                .chain(chars("Ia"))
+                .chain([key(KeyCode::Esc)])
+                .chain(chars("Ad"))
+                .chain([key(KeyCode::Esc)])
+                .chain(chars("hhie"))
+                .chain([key(KeyCode::Esc)])
+                .chain(chars("lax"))
+                .collect();
+            assert_eq!("aebxcd", run(vi(), events));
+        }
+
+        #[test]
+        fn test_dd_clears_line() {
+            let events = chars("abc")
+                .into_iter()
+                .chain([key(KeyCode::Esc)])
+                // A `d` followed by another key does not delete the line.
+                .chain(chars("dhdd"))
+                .chain(chars("ix"))
+                .collect();
+            assert_eq!("x", run(vi(), events));
+        }
+
+        #[test]
+        fn test_dd_in_insert_mode() {
+            let mut p = vi().prompt().unwrap();
+            let mut terminal = TestTerminal::new(30, 10);
+            let ret = p
+                .run_with_events(
+                    chars("add").into_iter().chain([key(KeyCode::Enter)]),
+                    &mut terminal,
+                )
+                .unwrap();
+            assert_eq!("add", ret);
+            // The keys are typed without being held as a chord.
+            assert_eq!(vec!["❯❯ ad "], terminal.frame(2));
+        }
+
+        #[test]
+        fn test_mode_indicator() {
+            fn indicator(mode: Mode, state: &mut text_editor::State) {
+                state.prefix = match mode {
+                    Mode::Insert => String::from("[I] "),
+                    Mode::Normal => String::from("[N] "),
+                };
+            }
+            let mut p = vi().mode_indicator(indicator).prompt().unwrap();
+            let mut terminal = TestTerminal::new(20, 10);
+            let ret = p
+                .run_with_events(
+                    chars("a")
+                        .into_iter()
+                        .chain([key(KeyCode::Esc), key(KeyCode::Enter)]),
+                    &mut terminal,
+                )
+                .unwrap();
+            assert_eq!("a", ret);
+            let frames = terminal.frames();
+            assert_eq!("[I] a ", frames[1][0].to_string());
+            assert_eq!("[N] a ", frames[2][0].to_string());
+            assert_eq!(Mode::Insert, p.renderer.vi_mode());
+
+            // The next run starts in the insert mode again.
+            let ret = p
+                .run_with_events(
+                    chars("b").into_iter().chain([key(KeyCode::Enter)]),
+                    &mut terminal,
+                )
+                .unwrap();
+            assert_eq!("b", ret);
+            assert_eq!(vec!["[I] b "], terminal.last_frame());
+        }
+    }
+
+    mod typed {
+        use super::*;
+
+        use std::path::PathBuf;
+
+        fn shows(terminal: &TestTerminal, index: usize, message: &str) -> bool {
+            terminal.frame(index).iter().any(|row| row == message)
+        }
+
+        #[test]
+        fn test_number() {
+            let mut p = Readline::number::<u16>()
+                .range(1..=65535)
+                .title("Port")
+                .prompt()
+                .unwrap();
+            let mut terminal = TestTerminal::new(30, 10);
+            let ret = p
+                .run_with_events(
+                    // Empty input is not submitted.
+                    [key(KeyCode::Enter)]
+                        .into_iter()
+                        .chain(chars("8x"))
+                        .chain([key(KeyCode::Backspace)])
+                        .chain(chars(",080"))
+                        .chain([key(KeyCode::Enter)]),
+                    &mut terminal,
+                )
+                .unwrap();
+            assert_eq!(8080, ret);
+            assert!(shows(&terminal, 1, "expected a number of type u16"));
+            // The error is shown as it is typed, and cleared once fixed.
+            assert!(shows(&terminal, 3, "expected a number of type u16"));
+            assert!(!shows(&terminal, 4, "expected a number of type u16"));
+        }
+
+        #[test]
+        fn test_number_out_of_range() {
+            let mut p = Readline::number::<u16>().range(1..=1024).prompt().unwrap();
+            let mut terminal = TestTerminal::new(30, 10);
+            let ret = p
+                .run_with_events(
+                    chars("2048")
+                        .into_iter()
+                        .chain([key(KeyCode::Enter)])
+                        .chain((0..4).map(|_| key(KeyCode::Backspace)))
+                        .chain(chars("1_000"))
+                        .chain([key(KeyCode::Enter)]),
+                    &mut terminal,
+                )
+                .unwrap();
+            assert_eq!(1000, ret);
+            assert!(shows(&terminal, 5, "must be in 1..=1024"));
+        }
+
+        #[test]
+        fn test_float() {
+            let mut p = Readline::number::<f64>().prompt().unwrap();
+            let ret = p
+                .run_with_events(
+                    chars("1,234.5").into_iter().chain([key(KeyCode::Enter)]),
+                    &mut TestTerminal::new(30, 10),
+                )
+                .unwrap();
+            assert_eq!(1234.5, ret);
+        }
+
+        #[test]
+        fn test_path() {
+            let mut p = Readline::path()
+                .must_exist(true)
+                .extensions([".toml"])
+                .prompt()
+                .unwrap();
+            let mut terminal = TestTerminal::new(60, 10);
+            let ret = p
+                .run_with_events(
+                    chars("src")
+                        .into_iter()
+                        .chain([
+                            key(KeyCode::Enter),
+                            Event::Key(KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL)),
+                        ])
+                        .chain(chars("Cargo.toml"))
+                        .chain([key(KeyCode::Enter)]),
+                    &mut terminal,
+                )
+                .unwrap();
+            assert_eq!(PathBuf::from("Cargo.toml"), ret);
+            assert!(shows(&terminal, 1, "no such file or directory: s"));
+            assert!(shows(&terminal, 4, "expected a file with extension .toml"));
+        }
+
+        #[test]
+        fn test_parse_into_u32() {
+            let mut p = Readline::default().parse_into::<u32>().prompt().unwrap();
+            let mut terminal = TestTerminal::new(40, 10);
+            let ret = p
+                .run_with_events(
+                    chars("-1").into_iter().chain([
+                        key(KeyCode::Enter),
+                        key(KeyCode::Left),
+                        key(KeyCode::Backspace),
+                        key(KeyCode::Enter),
+                    ]),
+                    &mut terminal,
+                )
+                .unwrap();
+            assert_eq!(1, ret);
+            assert!(shows(&terminal, 3, "invalid digit found in string"));
+            // The input is kept to be fixed.
+            assert!(shows(&terminal, 3, "❯❯ -1 "));
+        }
+
+        #[test]
+        fn test_parse_into_f64() {
+            let mut p = Readline::default().parse_into::<f64>().prompt().unwrap();
+            let ret = p
+                .run_with_events(
+                    chars("2.5e3").into_iter().chain([key(KeyCode::Enter)]),
+                    &mut TestTerminal::new(30, 10),
+                )
+                .unwrap();
+            assert_eq!(2500.0, ret);
+        }
+
+        #[derive(Debug, PartialEq)]
+        struct Rgb(u8, u8, u8);
+
+        impl std::str::FromStr for Rgb {
+            type Err = String;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let hex = s
+                    .strip_prefix('#')
+                    .filter(|hex| hex.len() == 6)
+                    .ok_or_else(|| String::from("expected #rrggbb"))?;
+                let channel =
+                    |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|e| e.to_string());
+                Ok(Rgb(channel(0)?, channel(2)?, channel(4)?))
+            }
+        }
+
+        #[test]
+        fn test_parse_into_custom_type() {
+            let mut p = Readline::default().parse_into::<Rgb>().prompt().unwrap();
+            let mut terminal = TestTerminal::new(30, 10);
+            let ret = p
+                .run_with_events(
+                    chars("#ff8800").into_iter().chain([key(KeyCode::Enter)]),
+                    &mut terminal,
+                )
+                .unwrap();
+            assert_eq!(Rgb(255, 136, 0), ret);
+            assert!(shows(&terminal, 1, "expected #rrggbb"));
+        }
+    }
+
+    mod feedback {
+        use std::time::{Duration, Instant};
+
+        use promkit::{
+            crossterm::{
+                event::{Event, KeyCode, KeyEvent, KeyModifiers},
+                style::Attribute,
+            },
+            preset::readline::{render, Feedback, Readline},
+            Renderer,
+        };
+
+        fn renderer(feedback: Feedback) -> render::Renderer {
+            Readline::default().feedback(feedback).renderer()
+        }
+
+        fn press(renderer: &mut render::Renderer, code: KeyCode) {
+            renderer
+                .evaluate(&Event::Key(KeyEvent::new(code, KeyModifiers::NONE)))
+                .unwrap();
+        }
+
+        #[test]
+        fn test_none() {
+            let mut renderer = renderer(Feedback::None);
+            press(&mut renderer, KeyCode::Backspace);
+            assert_eq!(None, renderer.feedback_until);
+            assert_eq!(None, renderer.take_output());
+        }
+
+        #[test]
+        fn test_bell() {
+            let mut renderer = renderer(Feedback::Bell);
+            press(&mut renderer, KeyCode::Char('a'));
+            assert_eq!(None, renderer.take_output());
+            // Past the tail, and the history is disabled.
+            press(&mut renderer, KeyCode::Right);
+            press(&mut renderer, KeyCode::Up);
+            assert_eq!(Some(String::from("\x07\x07")), renderer.take_output());
+        }
+
+        #[test]
+        fn test_flash() {
+            let mut renderer = renderer(Feedback::Flash);
+            press(&mut renderer, KeyCode::Left);
+            let prefix_reversed = |renderer: &render::Renderer| {
+                renderer.create_panes(20, 10)[2].extract(1)[0]
+                    .iter()
+                    .next()
+                    .unwrap()
+                    .style()
+                    .attributes
+                    .has(Attribute::Reverse)
+            };
+            assert!(prefix_reversed(&renderer));
+
+            renderer.feedback_until = Some(Instant::now());
+            assert!(renderer.tick().unwrap());
+            assert!(!prefix_reversed(&renderer));
+            assert_eq!(None, renderer.tick_interval());
+        }
+
+        #[test]
+        fn test_notice() {
+            let mut renderer = renderer(Feedback::Notice);
+            // No suggestions to complete with.
+            press(&mut renderer, KeyCode::Tab);
+            assert_eq!("rejected", renderer.error_message_snapshot.after().text);
+            assert!(renderer.tick_interval().unwrap() > Duration::from_millis(500));
+
+            renderer.feedback_until = Some(Instant::now());
+            assert!(renderer.tick().unwrap());
+            assert_eq!("", renderer.error_message_snapshot.after().text);
+        }
+    }
+
+    mod inactivity_timeout {
+        use std::time::Duration;
+
+        use promkit::{
+            clock::Clock,
+            crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers},
+            preset::readline::{password::Password, Readline},
+            terminal::TestTerminal,
+            Renderer, TimedOut,
+        };
+
+        fn key(code: KeyCode) -> Event {
+            Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+        }
+
+        #[test]
+        fn test_countdown() {
+            let clock = Clock::manual();
+            let mut p = Readline::default()
+                .inactivity_timeout(Duration::from_secs(30))
+                .inactivity_countdown(true)
+                .prompt()
+                .unwrap();
+            p.renderer.clock = clock.clone();
+            let mut terminal = TestTerminal::new(20, 10);
+            let advancing = clock.clone();
+            let ret = p
+                .run_with_events(
+                    [key(KeyCode::Char('1')), key(KeyCode::Char('2'))]
+                        .into_iter()
+                        .chain([key(KeyCode::Enter)])
+                        .inspect(move |_| advancing.advance(Duration::from_secs(10))),
+                    &mut terminal,
+                )
+                .unwrap();
+            assert_eq!("12", ret);
+            // Every keystroke restarts the countdown.
+            assert_eq!(vec!["❯❯               30s"], terminal.frame(0));
+            assert_eq!(vec!["❯❯ 1             30s"], terminal.frame(1));
+        }
+
+        #[test]
+        fn test_timed_out() {
+            let clock = Clock::manual();
+            let mut renderer = Password::default()
+                .inactivity_timeout(Duration::from_secs(30))
+                .renderer();
+            renderer.clock = clock.clone();
+            // The countdown starts when the prompt is first drawn.
+            renderer.create_panes(20, 10);
+            assert_eq!(Some(Duration::from_secs(30)), renderer.tick_interval());
+
+            clock.advance(Duration::from_secs(20));
+            renderer.evaluate(&key(KeyCode::Char('1'))).unwrap();
+            clock.advance(Duration::from_secs(20));
+            assert!(!renderer.tick().unwrap());
+            assert_eq!(Some(Duration::from_secs(10)), renderer.tick_interval());
+
+            clock.advance(Duration::from_secs(10));
+            assert!(renderer.tick().unwrap_err().is::<TimedOut>());
+        }
+    }
+
+    #[cfg(feature = "async")]
+    mod async_validator {
+        use std::time::{Duration, Instant};
+
+        use promkit::{
+            crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers},
+            preset::readline::{render, Readline},
+            Finalizer, Interrupted, PromptSignal, Renderer,
+        };
+
+        fn key(code: KeyCode) -> Event {
+            Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+        }
+
+        fn ctrl(ch: char) -> Event {
+            Event::Key(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::CONTROL))
+        }
+
+        fn renderer(validate_on_idle: Option<Duration>) -> render::Renderer {
+            let mut readline = Readline::default().async_validator(|input: &str| {
+                let input = input.to_string();
+                async move {
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    match input.as_str() {
+                        "taken" => Err(format!("{} is taken", input)),
+                        _ => Ok(()),
+                    }
+                }
+            });
+            if let Some(debounce) = validate_on_idle {
+                readline = readline.validate_on_idle(debounce);
+            }
+            readline.renderer()
+        }
+
+        fn type_str(renderer: &mut render::Renderer, s: &str) {
+            for ch in s.chars() {
+                assert!(matches!(
+                    renderer.evaluate(&key(KeyCode::Char(ch))),
+                    Ok(PromptSignal::Continue)
+                ));
+            }
+        }
+
+        /// Ticks the renderer as the prompt does until the check is over.
+        fn settle(renderer: &mut render::Renderer) {
+            let start = Instant::now();
+            while renderer.tick_interval().is_some() && start.elapsed() < Duration::from_secs(5) {
+                std::thread::sleep(Duration::from_millis(5));
+                renderer.tick().unwrap();
+            }
+        }
+
+        fn error_message(renderer: &render::Renderer) -> String {
+            renderer.error_message_snapshot.after().text.clone()
+        }
+
+        #[test]
+        fn test_submit() {
+            let mut renderer = renderer(None);
+            type_str(&mut renderer, "alice");
+            // Enter waits for the check.
+            assert!(matches!(
+                renderer.evaluate(&key(KeyCode::Enter)),
+                Ok(PromptSignal::Continue)
+            ));
+            assert!(renderer.take_signal().is_none());
+            settle(&mut renderer);
+            assert!(matches!(renderer.take_signal(), Some(PromptSignal::Quit)));
+            assert_eq!("alice", renderer.finalize().unwrap());
+        }
+
+        #[test]
+        fn test_invalid() {
+            let mut renderer = renderer(None);
+            type_str(&mut renderer, "taken");
+            renderer.evaluate(&key(KeyCode::Enter)).unwrap();
+            settle(&mut renderer);
+            assert!(renderer.take_signal().is_none());
+            assert_eq!("taken is taken", error_message(&renderer));
+
+            // Editing the input clears the error.
+            renderer.evaluate(&key(KeyCode::Backspace)).unwrap();
+            assert_eq!("", error_message(&renderer));
+        }
+
+        #[test]
+        fn test_validate_on_idle() {
+            let mut renderer = renderer(Some(Duration::from_millis(10)));
+            type_str(&mut renderer, "take");
+            // The check of "take" is superseded before it arrives.
+            type_str(&mut renderer, "n");
+            settle(&mut renderer);
+            assert_eq!("taken is taken", error_message(&renderer));
+            assert!(renderer.take_signal().is_none());
+
+            type_str(&mut renderer, "!");
+            settle(&mut renderer);
+            assert_eq!("", error_message(&renderer));
+            // The input already checked is accepted at once.
+            assert!(matches!(
+                renderer.evaluate(&key(KeyCode::Enter)),
+                Ok(PromptSignal::Quit)
+            ));
+        }
+
+        #[test]
+        fn test_interrupt_while_checking() {
+            let mut renderer = renderer(None);
+            type_str(&mut renderer, "alice");
+            renderer.evaluate(&key(KeyCode::Enter)).unwrap();
+            assert!(matches!(
+                renderer.evaluate(&ctrl('c')),
+                Err(e) if e.is::<Interrupted>()
+            ));
+            assert_eq!(None, renderer.tick_interval());
+            std::thread::sleep(Duration::from_millis(50));
+            renderer.tick().unwrap();
+            assert!(renderer.take_signal().is_none());
+        }
+
+        #[test]
+        fn test_within_runtime() {
+            // The prompt blocks the caller even within a runtime,
+            // running the checks on a runtime of its own.
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            let ret = runtime.block_on(async {
+                let mut renderer = renderer(None);
+                type_str(&mut renderer, "alice");
+                renderer.evaluate(&key(KeyCode::Enter)).unwrap();
+                settle(&mut renderer);
+                assert!(matches!(renderer.take_signal(), Some(PromptSignal::Quit)));
+                // Dropping the renderer shuts its runtime down without panicking.
+                renderer.finalize().unwrap()
+            });
+            assert_eq!("alice", ret);
+        }
+    }
+
+    mod confirm {
+        use super::*;
+
+        fn run(confirm: Confirm, events: Vec<Event>) -> anyhow::Result<bool> {
+            let mut p = confirm.prompt().unwrap();
+            p.run_with_events(events, &mut TestTerminal::new(30, 10))
+        }
+
+        fn enter(events: &str) -> Vec<Event> {
+            chars(events)
+                .into_iter()
+                .chain([key(KeyCode::Enter)])
+                .collect()
+        }
+
+        #[test]
+        fn test() {
+            assert!(run(Confirm::new("Continue?"), enter("y")).unwrap());
+            assert!(!run(Confirm::new("Continue?"), enter("n")).unwrap());
+            assert!(run(Confirm::new("Continue?"), enter("Y")).unwrap());
+            assert!(!run(Confirm::new("Continue?"), enter("N")).unwrap());
+            // The last answer typed wins.
+            assert!(!run(Confirm::new("Continue?"), enter("yn")).unwrap());
+        }
+
+        #[test]
+        fn test_default_yes() {
+            assert!(run(Confirm::new("Continue?").default_yes(true), enter("")).unwrap());
+            assert!(!run(Confirm::new("Continue?").default_yes(true), enter("n")).unwrap());
+            assert!(!run(Confirm::new("Continue?").default_yes(false), enter("")).unwrap());
+            assert!(run(Confirm::new("Continue?").default_yes(false), enter("y")).unwrap());
+        }
+
+        #[test]
+        fn test_default_is_capitalized() {
+            let mut p = Confirm::new("Continue?")
+                .default_yes(false)
+                .prompt()
+                .unwrap();
+            let mut terminal = TestTerminal::new(30, 10);
+            p.run_with_events([key(KeyCode::Enter)], &mut terminal)
+                .unwrap();
+            assert_eq!(vec!["Continue? (y/N)  "], terminal.frame(0));
+            // The default answer is typed on submission.
+            assert_eq!(vec!["Continue? (y/N) n "], terminal.last_frame());
+        }
+
+        #[test]
+        fn test_unrecognized_keys_are_ignored() {
+            // Without a default, Enter waits for an answer.
+            assert!(run(Confirm::new("Continue?"), enter("")).is_err());
+            assert!(run(Confirm::new("Continue?"), enter("x")).is_err());
+            assert!(run(Confirm::new("Continue?"), enter("xy")).unwrap());
+            assert!(run(Confirm::new("Continue?").default_yes(true), enter("x")).unwrap());
+        }
+    }
+
+    mod read_only {
+        use promkit::{crossterm::style::Attribute, read_only::ReadOnly, Finalizer, Renderer};
+
+        use super::*;
+
+        fn ctrl_c() -> Event {
+            Event::Key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL))
+        }
+
+        #[test]
+        fn test() {
+            let read_only = ReadOnly::default();
+            let mut p = Readline::default()
+                .read_only(read_only.clone())
+                .prompt()
+                .unwrap();
+            read_only.set(true);
+            let mut terminal = TestTerminal::new(20, 10);
+            let err = p
+                .run_with_events(
+                    chars("ab")
+                        .into_iter()
+                        .chain([key(KeyCode::Enter), ctrl_c()]),
+                    &mut terminal,
+                )
+                .unwrap_err();
+            assert!(err.is::<Interrupted>());
+            assert_eq!("", p.renderer.finalize().unwrap());
+
+            // The input line is dimmed without the cursor.
+            let frame = &terminal.frames()[0][0];
+            assert!(frame
+                .iter()
+                .all(|g| g.style().attributes.has(Attribute::Dim)
+                    && g.style().background_color.is_none()));
+        }
+
+        #[test]
+        fn test_set_read_only() {
+            let mut p = Readline::default().prompt().unwrap();
+            p.renderer.set_read_only(true);
+            p.renderer.evaluate(&key(KeyCode::Char('a'))).unwrap();
+            assert!(p.renderer.is_read_only());
+            p.renderer.set_read_only(false);
+            let ret = p
+                .run_with_events(
+                    chars("b").into_iter().chain([key(KeyCode::Enter)]),
+                    &mut TestTerminal::new(20, 10),
+                )
+                .unwrap();
+            assert_eq!("b", ret);
+        }
+
+        #[test]
+        fn test_tick_redraws_on_change() {
+            let read_only = ReadOnly::default();
+            let mut p = Readline::default()
+                .read_only(read_only.clone())
+                .prompt()
+                .unwrap();
+            assert!(p.renderer.tick_interval().is_some());
+            p.renderer.create_panes(20, 10);
+            assert!(!p.renderer.tick().unwrap());
+            read_only.set(true);
+            assert!(p.renderer.tick().unwrap());
+        }
+    }
+
+    #[cfg(feature = "async")]
+    mod submit_sender {
+        use promkit::{
+            crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers},
+            preset::readline::Readline,
+            terminal::TestTerminal,
+            Interrupted,
+        };
+
+        fn key(code: KeyCode) -> Event {
+            Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+        }
+
+        fn chars(s: &str) -> Vec<Event> {
+            s.chars().map(|ch| key(KeyCode::Char(ch))).collect()
+        }
+
+        fn lines(lines: &[&str]) -> Vec<Event> {
+            lines
+                .iter()
+                .flat_map(|line| chars(line).into_iter().chain([key(KeyCode::Enter)]))
+                .chain([Event::Key(KeyEvent::new(
+                    KeyCode::Char('c'),
+                    KeyModifiers::CONTROL,
+                ))])
+                .collect()
+        }
+
+        #[test]
+        fn test_sends_lines_while_running() {
+            let (sender, mut receiver) = tokio::sync::mpsc::channel(2);
+            let mut p = Readline::default().submit_sender(sender).prompt().unwrap();
+            let mut terminal = TestTerminal::new(20, 10);
+            let err = p
+                .run_with_events(lines(&["error", "warn", "info"]), &mut terminal)
+                .unwrap_err();
+            assert!(err.is::<Interrupted>());
+            assert_eq!("error", receiver.try_recv().unwrap());
+            assert_eq!("warn", receiver.try_recv().unwrap());
+            // Dropped as the channel is full.
+            assert!(receiver.try_recv().is_err());
+            // The input is cleared after each line.
+            assert_eq!(vec!["❯❯  "], terminal.frame(6));
+        }
+
+        #[test]
+        fn test_keeps_running_without_receiver() {
+            let (sender, receiver) = tokio::sync::mpsc::channel(1);
+            drop(receiver);
+            let mut p = Readline::default().submit_sender(sender).prompt().unwrap();
+            let err = p
+                .run_with_events(lines(&["a", "b"]), &mut TestTerminal::new(20, 10))
+                .unwrap_err();
+            assert!(err.is::<Interrupted>());
+        }
+    }
+}
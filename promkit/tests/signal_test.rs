@@ -0,0 +1,106 @@
+#[cfg(unix)]
+mod signal {
+    use std::{
+        mem::MaybeUninit,
+        os::{fd::FromRawFd, unix::process::CommandExt},
+        process::{Command, Stdio},
+        time::{Duration, Instant},
+    };
+
+    use promkit::preset::readline::Readline;
+
+    const CHILD_ENV: &str = "PROMKIT_SIGNAL_TEST_CHILD";
+
+    fn termios(fd: libc::c_int) -> libc::termios {
+        unsafe {
+            let mut termios = MaybeUninit::<libc::termios>::uninit();
+            assert_eq!(0, libc::tcgetattr(fd, termios.as_mut_ptr()));
+            termios.assume_init()
+        }
+    }
+
+    fn is_raw(termios: &libc::termios) -> bool {
+        termios.c_lflag & (libc::ICANON | libc::ECHO) == 0
+    }
+
+    #[test]
+    fn test_termios_restored_on_sigterm() {
+        if std::env::var_os(CHILD_ENV).is_some() {
+            // In the child, run a prompt on the pseudo terminal until killed.
+            let mut p = Readline::default().prompt().unwrap();
+            p.run().ok();
+            return;
+        }
+
+        let (mut master, mut slave) = (0, 0);
+        let winsize = libc::winsize {
+            ws_row: 24,
+            ws_col: 80,
+            ws_xpixel: 0,
+            ws_ypixel: 0,
+        };
+        unsafe {
+            assert_eq!(
+                0,
+                libc::openpty(
+                    &mut master,
+                    &mut slave,
+                    std::ptr::null_mut(),
+                    std::ptr::null(),
+                    &winsize,
+                )
+            );
+        }
+        let before = termios(slave);
+        assert!(!is_raw(&before));
+
+        let mut child = unsafe {
+            Command::new(std::env::current_exe().unwrap())
+                .args([
+                    "--exact",
+                    "signal::test_termios_restored_on_sigterm",
+                    "--test-threads=1",
+                ])
+                .env(CHILD_ENV, "1")
+                .stdin(Stdio::from_raw_fd(libc::dup(slave)))
+                // Not a terminal, so that the cursor position is not queried.
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .pre_exec(|| {
+                    // Make the pseudo terminal the controlling terminal, i.e. `/dev/tty`.
+                    if libc::setsid() < 0 || libc::ioctl(0, libc::TIOCSCTTY, 0) < 0 {
+                        return Err(std::io::Error::last_os_error());
+                    }
+                    Ok(())
+                })
+                .spawn()
+                .unwrap()
+        };
+
+        let start = Instant::now();
+        while !is_raw(&termios(slave)) {
+            assert!(
+                start.elapsed() < Duration::from_secs(30),
+                "the prompt did not start"
+            );
+            std::thread::sleep(Duration::from_millis(20));
+        }
+
+        unsafe {
+            libc::kill(child.id() as libc::pid_t, libc::SIGTERM);
+        }
+        let status = child.wait().unwrap();
+
+        let after = termios(slave);
+        assert!(!status.success());
+        assert!(!is_raw(&after));
+        assert_eq!(before.c_lflag, after.c_lflag);
+        assert_eq!(before.c_iflag, after.c_iflag);
+        assert_eq!(before.c_oflag, after.c_oflag);
+
+        unsafe {
+            libc::close(master);
+            libc::close(slave);
+        }
+    }
+}
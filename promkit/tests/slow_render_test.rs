@@ -0,0 +1,110 @@
+#[cfg(test)]
+mod slow_render {
+    use std::{cell::RefCell, sync::mpsc, thread, time::Duration};
+
+    use promkit::{
+        crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers},
+        pane::Pane,
+        preset::readline::{render, Readline},
+        terminal::TestTerminal,
+        Finalizer, Prompt, PromptSignal, Renderer,
+    };
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    /// A readline renderer creating its panes artificially slowly,
+    /// and recording the events it evaluates.
+    struct Slow {
+        inner: render::Renderer,
+        evaluated: RefCell<Vec<Event>>,
+    }
+
+    impl Slow {
+        fn new() -> Self {
+            Self {
+                inner: Readline::default().renderer(),
+                evaluated: Default::default(),
+            }
+        }
+    }
+
+    impl Finalizer for Slow {
+        type Return = String;
+
+        fn finalize(&mut self) -> anyhow::Result<Self::Return> {
+            self.inner.finalize()
+        }
+    }
+
+    impl Renderer for Slow {
+        fn create_panes(&self, width: u16, height: u16) -> Vec<Pane> {
+            thread::sleep(Duration::from_millis(10));
+            self.inner.create_panes(width, height)
+        }
+
+        fn evaluate(&mut self, event: &Event) -> anyhow::Result<PromptSignal> {
+            self.evaluated.borrow_mut().push(event.clone());
+            self.inner.evaluate(event)
+        }
+    }
+
+    fn digits(n: usize) -> String {
+        (0..n).map(|i| char::from(b'0' + (i % 10) as u8)).collect()
+    }
+
+    #[test]
+    fn test_no_events_lost_or_reordered() {
+        let input = digits(50);
+
+        // The events arrive in bursts, much faster than the panes are rendered.
+        let (tx, rx) = mpsc::channel();
+        let chars = input.clone();
+        let sender = thread::spawn(move || {
+            for burst in chars.chars().collect::<Vec<_>>().chunks(10) {
+                for ch in burst {
+                    tx.send(key(KeyCode::Char(*ch))).unwrap();
+                }
+                thread::sleep(Duration::from_millis(15));
+            }
+            tx.send(key(KeyCode::Enter)).unwrap();
+        });
+
+        let mut p = Prompt::new(Slow::new());
+        let mut terminal = TestTerminal::new(80, 10);
+        let result = p.run_with_receiver(rx, &mut terminal).unwrap();
+        sender.join().unwrap();
+
+        assert_eq!(input, result);
+        // The events queued behind a render are evaluated together before the next one,
+        // instead of a render per event.
+        assert!(terminal.frames().len() < 30, "{}", terminal.frames().len());
+    }
+
+    #[test]
+    fn test_drops_stale_movement_events() {
+        // Every event has already arrived, and waits behind the first render.
+        let (tx, rx) = mpsc::channel();
+        let input = digits(20);
+        for ch in input.chars() {
+            tx.send(key(KeyCode::Char(ch))).unwrap();
+            tx.send(key(KeyCode::Left)).unwrap();
+        }
+        tx.send(key(KeyCode::Enter)).unwrap();
+
+        let mut p = Prompt::new(Slow::new()).stale_event_threshold(Duration::from_millis(1));
+        let result = p
+            .run_with_receiver(rx, &mut TestTerminal::new(80, 10))
+            .unwrap();
+
+        // The stale cursor movements are dropped, while the chars and Enter are kept in order.
+        assert_eq!(input, result);
+        let expected: Vec<Event> = input
+            .chars()
+            .map(|ch| key(KeyCode::Char(ch)))
+            .chain([key(KeyCode::Enter)])
+            .collect();
+        assert_eq!(expected, *p.renderer.evaluated.borrow());
+    }
+}
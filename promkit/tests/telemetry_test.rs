@@ -0,0 +1,191 @@
+#[cfg(all(test, feature = "tracing"))]
+mod telemetry {
+    use std::{
+        collections::BTreeMap,
+        fmt,
+        sync::{Arc, Mutex},
+    };
+
+    use promkit::{
+        crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers},
+        preset::readline::Readline,
+        terminal::TestTerminal,
+    };
+    use tracing::{
+        field::{Field, Visit},
+        span::{Attributes, Id, Record},
+        Subscriber,
+    };
+    use tracing_subscriber::{layer::Context, prelude::*, registry::LookupSpan, Layer};
+
+    #[derive(Clone, Debug, Default, PartialEq)]
+    struct CapturedSpan {
+        name: &'static str,
+        parent: Option<&'static str>,
+        fields: BTreeMap<&'static str, String>,
+    }
+
+    /// Captures the spans with their parents and fields, in the order of creation.
+    #[derive(Clone, Default)]
+    struct Capture {
+        spans: Arc<Mutex<Vec<(Id, CapturedSpan)>>>,
+    }
+
+    impl Capture {
+        fn spans(&self) -> Vec<CapturedSpan> {
+            self.spans
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|(_, span)| span.clone())
+                .collect()
+        }
+    }
+
+    struct FieldVisitor<'a>(&'a mut BTreeMap<&'static str, String>);
+
+    impl Visit for FieldVisitor<'_> {
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            self.0.insert(field.name(), format!("{:?}", value));
+        }
+
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.insert(field.name(), value.to_string());
+        }
+    }
+
+    impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for Capture {
+        fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+            let mut span = CapturedSpan {
+                name: attrs.metadata().name(),
+                parent: ctx
+                    .span(id)
+                    .and_then(|span| span.parent())
+                    .map(|parent| parent.name()),
+                fields: Default::default(),
+            };
+            attrs.record(&mut FieldVisitor(&mut span.fields));
+            self.spans.lock().unwrap().push((id.clone(), span));
+        }
+
+        fn on_record(&self, id: &Id, values: &Record<'_>, _ctx: Context<'_, S>) {
+            let mut spans = self.spans.lock().unwrap();
+            if let Some((_, span)) = spans.iter_mut().find(|(captured, _)| captured == id) {
+                values.record(&mut FieldVisitor(&mut span.fields));
+            }
+        }
+    }
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn test_prompt_span() {
+        let capture = Capture::default();
+        let subscriber = tracing_subscriber::registry().with(capture.clone());
+
+        let ret = tracing::subscriber::with_default(subscriber, || {
+            let parent = tracing::info_span!("cli.command", cli.name = "deploy");
+            let mut p = Readline::default()
+                .validator(|text| !text.is_empty(), |_| String::from("empty"))
+                .prompt()
+                .unwrap()
+                .parent_span(parent);
+            p.run_with_events(
+                [
+                    key(KeyCode::Enter),
+                    key(KeyCode::Char('a')),
+                    key(KeyCode::Enter),
+                ],
+                &mut TestTerminal::new(20, 10),
+            )
+        });
+        assert_eq!("a", ret.unwrap());
+
+        let spans = capture.spans();
+        assert_eq!(
+            vec![
+                "cli.command",
+                "promkit.prompt",
+                "promkit.validate",
+                "promkit.validate"
+            ],
+            spans.iter().map(|span| span.name).collect::<Vec<_>>()
+        );
+
+        let prompt = &spans[1];
+        assert_eq!(Some("cli.command"), prompt.parent);
+        assert_eq!("readline", prompt.fields["promkit.preset"]);
+        assert_eq!("submitted", prompt.fields["promkit.outcome"]);
+        assert_eq!("3", prompt.fields["promkit.keystrokes"]);
+        assert!(prompt.fields.contains_key("promkit.duration_ms"));
+        // The input is never recorded.
+        assert!(prompt.fields.values().all(|value| value != "a"));
+
+        assert_eq!(Some("promkit.prompt"), spans[2].parent);
+        assert_eq!("false", spans[2].fields["promkit.valid"]);
+        assert_eq!("true", spans[3].fields["promkit.valid"]);
+    }
+
+    #[test]
+    fn test_interrupted() {
+        let capture = Capture::default();
+        let subscriber = tracing_subscriber::registry().with(capture.clone());
+
+        let ret = tracing::subscriber::with_default(subscriber, || {
+            let mut p = Readline::default().prompt().unwrap();
+            p.run_with_events(
+                [Event::Key(KeyEvent::new(
+                    KeyCode::Char('c'),
+                    KeyModifiers::CONTROL,
+                ))],
+                &mut TestTerminal::new(20, 10),
+            )
+        });
+        assert!(ret.is_err());
+
+        let spans = capture.spans();
+        assert_eq!(1, spans.len());
+        assert_eq!(None, spans[0].parent);
+        assert_eq!("interrupted", spans[0].fields["promkit.outcome"]);
+        assert_eq!("1", spans[0].fields["promkit.keystrokes"]);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_load_span() {
+        use std::time::{Duration, Instant};
+
+        use promkit::preset::query_selector::loader::Loader;
+
+        let capture = Capture::default();
+        let subscriber = tracing_subscriber::registry().with(capture.clone());
+
+        let items = tracing::subscriber::with_default(subscriber, || {
+            let _prompt = tracing::info_span!("promkit.prompt").entered();
+            let mut loader = Loader::new(
+                Arc::new(|query: &str| {
+                    let query = query.to_string();
+                    Box::pin(async move { Ok(vec![query.clone(), query]) })
+                }),
+                Duration::ZERO,
+            );
+            loader.request("a").unwrap();
+            let start = Instant::now();
+            while start.elapsed() < Duration::from_secs(5) {
+                if let Some(items) = loader.poll().unwrap() {
+                    return items;
+                }
+                std::thread::sleep(Duration::from_millis(5));
+            }
+            vec![]
+        });
+        assert_eq!(2, items.len());
+
+        let spans = capture.spans();
+        assert_eq!("promkit.load", spans[1].name);
+        assert_eq!(Some("promkit.prompt"), spans[1].parent);
+        assert_eq!("2", spans[1].fields["promkit.items"]);
+    }
+}
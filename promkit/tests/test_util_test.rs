@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod test_util {
+    use promkit::{
+        checkbox::{self, Checkbox},
+        crossterm::style::{Attribute, ContentStyle},
+        listbox::{self, Listbox},
+        style::StyleBuilder,
+        test_util::{render_styled, render_to_strings},
+        Overflow,
+    };
+
+    fn listbox_state(items: &[&str], cursor: &str) -> listbox::State {
+        listbox::State {
+            listbox: Listbox::from_displayable(items),
+            cursor: String::from(cursor),
+            active_item_style: Some(StyleBuilder::new().attrs(Attribute::Bold.into()).build()),
+            inactive_item_style: None,
+            description_style: ContentStyle::default(),
+            overflow: Overflow::Wrap,
+            item_lines: None,
+            partial_item: Default::default(),
+            lines: None,
+        }
+    }
+
+    #[test]
+    fn test_emoji_cursor_keeps_items_aligned() {
+        let state = listbox_state(&["a", "b"], "👨‍👩‍👧 ");
+        assert_eq!(vec!["👨‍👩‍👧 a", "   b"], render_to_strings(&state, 20, 10));
+        let rows = render_styled(&state, 20, 10);
+        // The family emoji is a single grapheme.
+        assert_eq!("👨‍👩‍👧", rows[0][0].0);
+        assert!(rows[0]
+            .iter()
+            .all(|(_, style)| style.attributes.has(Attribute::Bold)));
+        assert!(rows[1]
+            .iter()
+            .all(|(_, style)| *style == ContentStyle::default()));
+    }
+
+    #[test]
+    fn test_height_is_clamped_by_lines_and_terminal() {
+        let mut state = listbox_state(&["one", "two", "three", "four"], "❯ ");
+        assert_eq!(
+            vec!["❯ one", "  two", "  three"],
+            render_to_strings(&state, 20, 3)
+        );
+        state.lines = Some(2);
+        assert_eq!(vec!["❯ one", "  two"], render_to_strings(&state, 20, 10));
+    }
+
+    #[test]
+    fn test_narrow_width_wraps() {
+        let state = listbox_state(&["a long item"], "❯ ");
+        assert_eq!(
+            vec!["❯ a lo", "  ng i", "  tem"],
+            render_to_strings(&state, 6, 10)
+        );
+    }
+
+    #[test]
+    fn test_checkbox_marks_of_different_widths() {
+        let mut state = checkbox::State {
+            checkbox: Checkbox::from_displayable(["a", "b"]),
+            cursor: String::from("❯ "),
+            active_mark: '✅',
+            inactive_mark: '-',
+            active_item_style: ContentStyle::default(),
+            inactive_item_style: ContentStyle::default(),
+            overflow: Overflow::Wrap,
+            item_lines: None,
+            partial_item: Default::default(),
+            lines: None,
+        };
+        state.checkbox.toggle();
+        assert_eq!(vec!["❯ ✅ a", "  -  b"], render_to_strings(&state, 20, 10));
+    }
+}
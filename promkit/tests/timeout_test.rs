@@ -0,0 +1,95 @@
+#[cfg(test)]
+mod timeout {
+    use std::time::Duration;
+
+    use promkit::{
+        clock::Clock,
+        crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers},
+        preset::readline::Readline,
+        terminal::TestTerminal,
+        Prompt, TimedOut, TimeoutMode,
+    };
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn prompt(clock: &Clock) -> Prompt<promkit::preset::readline::render::Renderer> {
+        Readline::default()
+            .prompt()
+            .unwrap()
+            .timeout(Duration::from_secs(10))
+            .clock(clock.clone())
+    }
+
+    /// Yields each event after advancing the clock by the seconds paired with it.
+    fn after(clock: &Clock, events: Vec<(u64, Event)>) -> impl Iterator<Item = Event> {
+        let clock = clock.clone();
+        events.into_iter().map(move |(secs, ev)| {
+            clock.advance(Duration::from_secs(secs));
+            ev
+        })
+    }
+
+    #[test]
+    fn test_idle() {
+        let clock = Clock::manual();
+        let mut p = prompt(&clock);
+        let mut terminal = TestTerminal::new(20, 10);
+        // Every event restarts the countdown.
+        let events = vec![
+            (8, key(KeyCode::Char('a'))),
+            (8, key(KeyCode::Char('b'))),
+            (8, key(KeyCode::Enter)),
+        ];
+        let ret = p.run_with_events(after(&clock, events), &mut terminal);
+        assert_eq!("ab", ret.unwrap());
+
+        let events = vec![(8, key(KeyCode::Char('a'))), (10, key(KeyCode::Enter))];
+        let ret = p.run_with_events(after(&clock, events), &mut terminal);
+        assert!(ret.unwrap_err().is::<TimedOut>());
+    }
+
+    #[test]
+    fn test_absolute() {
+        let clock = Clock::manual();
+        let mut p = prompt(&clock).timeout_mode(TimeoutMode::Absolute);
+        let mut terminal = TestTerminal::new(20, 10);
+        let events = vec![
+            (8, key(KeyCode::Char('a'))),
+            (8, key(KeyCode::Char('b'))),
+            (0, key(KeyCode::Enter)),
+        ];
+        let ret = p.run_with_events(after(&clock, events), &mut terminal);
+        assert!(ret.unwrap_err().is::<TimedOut>());
+    }
+
+    #[test]
+    fn test_timeout_default() {
+        let clock = Clock::manual();
+        let mut p = prompt(&clock).timeout_default(String::from("anonymous"));
+        let mut terminal = TestTerminal::new(20, 10);
+        let events = vec![(10, key(KeyCode::Char('a')))];
+        let ret = p.run_with_events(after(&clock, events), &mut terminal);
+        assert_eq!("anonymous", ret.unwrap());
+    }
+
+    #[test]
+    fn test_countdown() {
+        let clock = Clock::manual();
+        let mut p = prompt(&clock)
+            .timeout_mode(TimeoutMode::Absolute)
+            .show_countdown(true);
+        let mut terminal = TestTerminal::new(20, 10);
+        let events = vec![
+            (0, key(KeyCode::Char('a'))),
+            (3, key(KeyCode::Char('b'))),
+            (0, key(KeyCode::Enter)),
+        ];
+        let ret = p.run_with_events(after(&clock, events), &mut terminal);
+        assert_eq!("ab", ret.unwrap());
+        assert_eq!(vec!["Timeout in 10s", "❯❯  "], terminal.frame(0));
+        assert_eq!(vec!["Timeout in 10s", "❯❯ a "], terminal.frame(1));
+        assert_eq!(vec!["Timeout in 7s", "❯❯ ab "], terminal.frame(2));
+    }
+}
@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod tree {
+    use promkit::{
+        crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers},
+        preset::tree::Tree,
+        terminal::TestTerminal,
+        tree::{Loader, Node},
+    };
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn root() -> Node {
+        Node::NonLeaf {
+            id: "root".into(),
+            children: vec![
+                Node::NonLeaf {
+                    id: "src".into(),
+                    children: vec![Node::Leaf("lib.rs".into()), Node::Leaf("main.rs".into())],
+                    children_visible: true,
+                },
+                Node::Leaf("Cargo.toml".into()),
+            ],
+            children_visible: true,
+        }
+    }
+
+    #[test]
+    fn test_full_path() {
+        let mut p = Tree::new(root()).prompt().unwrap();
+        let ret = p
+            .run_with_events(
+                [
+                    key(KeyCode::Down),
+                    key(KeyCode::Down),
+                    key(KeyCode::Down),
+                    key(KeyCode::Enter),
+                ],
+                &mut TestTerminal::new(30, 10),
+            )
+            .unwrap();
+        assert_eq!(vec!["root", "src", "main.rs"], ret);
+    }
+
+    #[test]
+    fn test_multi_select() {
+        let mut p = Tree::new(root()).indent(0).prompt_multi_select().unwrap();
+        let mut terminal = TestTerminal::new(30, 10);
+        let ret = p
+            .run_with_events(
+                [
+                    // Mark `Cargo.toml` and `lib.rs`.
+                    key(KeyCode::Down),
+                    key(KeyCode::Down),
+                    key(KeyCode::Char(' ')),
+                    key(KeyCode::Down),
+                    key(KeyCode::Down),
+                    key(KeyCode::Char(' ')),
+                    // Fold and unfold `src`.
+                    key(KeyCode::Up),
+                    key(KeyCode::Up),
+                    key(KeyCode::Up),
+                    key(KeyCode::Char(' ')),
+                    key(KeyCode::Char(' ')),
+                    key(KeyCode::Enter),
+                ],
+                &mut terminal,
+            )
+            .unwrap();
+
+        assert_eq!(
+            vec![vec!["root", "src", "lib.rs"], vec!["root", "Cargo.toml"]],
+            ret
+        );
+        assert_eq!(
+            vec!["▼ src", "  ☒ lib.rs", "  ☐ main.rs", "  ☒ Cargo.toml"],
+            terminal.last_frame()
+        );
+    }
+
+    #[test]
+    fn test_lazy() {
+        let root = Node::NonLeaf {
+            id: "root".into(),
+            children: vec![Node::Lazy {
+                id: "src".into(),
+                loader: Loader::new(|ids| vec![Node::Leaf(ids.join("/"))]),
+            }],
+            children_visible: true,
+        };
+        let mut p = Tree::new(root).indent(0).prompt().unwrap();
+        let mut terminal = TestTerminal::new(30, 10);
+        let ret = p
+            .run_with_events(
+                [
+                    key(KeyCode::Down),
+                    key(KeyCode::Char(' ')),
+                    key(KeyCode::Down),
+                    key(KeyCode::Enter),
+                ],
+                &mut terminal,
+            )
+            .unwrap();
+
+        assert_eq!(vec!["root", "src", "root/src"], ret);
+        // The placeholder is drawn before the children are loaded.
+        assert_eq!(vec!["▼ src", "  loading…"], terminal.frame(2));
+        assert_eq!(vec!["▼ src", "  root/src"], terminal.frame(3));
+    }
+}